@@ -1,33 +1,120 @@
 use anyhow::{Context, Result};
 use base64::Engine;
-use reqwest::{Client, RequestBuilder};
+use reqwest::{Client, RequestBuilder, Response, Url};
 use serde_json::Value;
 use std::fmt;
-use tracing::{debug, error};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+use tracing::{debug, error, info};
 
-use crate::config::AtlassianConfig;
+use crate::config::{redact_proxy_url, AtlassianConfig, Deployment};
+
+/// Attachment content is served behind at least one redirect to a media host;
+/// anything beyond a short chain is treated as a loop.
+pub const MAX_ATTACHMENT_REDIRECTS: usize = 5;
+
+/// Boards and sprints live under the Agile API, which is versioned apart from
+/// the core JIRA API and is the same on Cloud and Server.
+pub const JIRA_AGILE_API_BASE: &str = "/rest/agile/1.0";
+
+/// Returned when a request exceeds the configured timeout, so a slow or
+/// unreachable instance can be told apart from an API error.
+#[derive(Debug)]
+pub struct RequestTimedOut {
+    pub timeout: Duration,
+}
+
+impl fmt::Display for RequestTimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Atlassian request timed out after {}s; raise ATLASSIAN_TIMEOUT_SECS if the instance is slow",
+            self.timeout.as_secs()
+        )
+    }
+}
+
+impl std::error::Error for RequestTimedOut {}
+
+trait SendTimed {
+    async fn send_timed(self, timeout: Duration) -> Result<Response>;
+}
+
+impl SendTimed for RequestBuilder {
+    async fn send_timed(self, timeout: Duration) -> Result<Response> {
+        self.send().await.map_err(|e| {
+            if e.is_timeout() {
+                anyhow::Error::new(RequestTimedOut { timeout })
+            } else {
+                e.into()
+            }
+        })
+    }
+}
 
 #[derive(Clone)]
 pub struct AtlassianClient {
     client: Client,
+    download_client: Client,
     config: AtlassianConfig,
     auth_header: String,
+    deployment: Arc<OnceCell<Deployment>>,
+    current_user: Arc<OnceCell<String>>,
 }
 
 impl AtlassianClient {
-    pub fn new(config: AtlassianConfig) -> Self {
-        let client = Client::new();
+    pub fn new(config: AtlassianConfig) -> Result<Self> {
+        let client = http_client_builder(&config)?
+            .timeout(config.timeout())
+            .build()
+            .with_context(|| "Failed to build HTTP client")?;
+        // Redirects are followed by hand so credentials never leave the instance.
+        // A large attachment may legitimately take longer than the request
+        // timeout, so downloads are only cut off when the connection stalls.
+        let download_client = http_client_builder(&config)?
+            .connect_timeout(config.timeout())
+            .read_timeout(config.timeout())
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .with_context(|| "Failed to build HTTP client")?;
         let credentials = format!("{}:{}", config.email, config.token);
         let auth_header = format!(
             "Basic {}",
             base64::engine::general_purpose::STANDARD.encode(credentials.as_bytes())
         );
 
-        Self {
+        let deployment = Arc::new(OnceCell::new_with(config.deployment));
+
+        Ok(Self {
             client,
+            download_client,
             config,
             auth_header,
-        }
+            deployment,
+            current_user: Arc::new(OnceCell::new()),
+        })
+    }
+
+    /// Returns the configured deployment, probing the instance if none was set.
+    /// Only a conclusive answer is cached; a failed probe is retried on the next call.
+    async fn deployment(&self) -> Result<Deployment> {
+        self.deployment
+            .get_or_try_init(|| async {
+                let response = self
+                    .request(reqwest::Method::GET, "/rest/api/3/serverInfo")
+                    .send_timed(self.config.timeout())
+                    .await
+                    .with_context(|| "Failed to detect the Atlassian deployment")?;
+                let status = response.status();
+                let info: Option<Value> = response.json().await.ok();
+                let deployment = deployment_from_server_info(status, info.as_ref())?;
+                info!("Using {:?} JIRA REST API", deployment);
+                Ok(deployment)
+            })
+            .await
+            .copied()
     }
 
     fn request(&self, method: reqwest::Method, path: &str) -> RequestBuilder {
@@ -41,13 +128,112 @@ impl AtlassianClient {
             .header("Content-Type", "application/json")
     }
 
+    /// Returns the authenticated user as JQL identifies them (accountId on Cloud,
+    /// username on Server), looking it up via `/myself` only on first use.
+    pub async fn current_user_id(&self) -> Result<String> {
+        let deployment = self.deployment().await?;
+        self.current_user
+            .get_or_try_init(|| async {
+                let response = self
+                    .request(reqwest::Method::GET, &format!("{}/myself", jira_api_base(deployment)))
+                    .send_timed(self.config.timeout())
+                    .await
+                    .with_context(|| "Failed to get the current JIRA user")?;
+
+                if !response.status().is_success() {
+                    let status = response.status();
+                    let text = response.text().await.unwrap_or_default();
+                    error!("JIRA API error: {} - {}", status, text);
+                    anyhow::bail!("JIRA API error: {} - {}", status, text);
+                }
+
+                let myself: Value = response
+                    .json()
+                    .await
+                    .with_context(|| "Failed to parse JIRA current user response")?;
+
+                let user_id = user_id_from_myself(deployment, &myself)
+                    .ok_or_else(|| anyhow::anyhow!("JIRA /myself response has no user id"))?;
+                debug!("Resolved current JIRA user to {}", user_id);
+                Ok(user_id)
+            })
+            .await
+            .cloned()
+    }
+
+    fn agile_request(&self, method: reqwest::Method, path: &str) -> RequestBuilder {
+        self.request(method, &format!("{}{}", JIRA_AGILE_API_BASE, path))
+    }
+
+    async fn agile_get(&self, path: &str, query: &[(&str, String)], what: &str) -> Result<Value> {
+        let response = self
+            .agile_request(reqwest::Method::GET, path)
+            .query(query)
+            .send_timed(self.config.timeout())
+            .await
+            .with_context(|| format!("Failed to get JIRA {}", what))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            error!("JIRA Agile API error: {} - {}", status, text);
+            anyhow::bail!("JIRA Agile API error: {} - {}", status, text);
+        }
+
+        response
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse JIRA {} response", what))
+    }
+
+    pub async fn list_jira_boards(&self, project_key: Option<&str>, start_at: u32, max_results: u32) -> Result<Value> {
+        let mut query = vec![
+            ("startAt", start_at.to_string()),
+            ("maxResults", max_results.to_string()),
+        ];
+        if let Some(project_key) = project_key {
+            query.push(("projectKeyOrId", project_key.to_string()));
+        }
+
+        let page = self.agile_get("/board", &query, "boards").await?;
+        Ok(compact_jira_boards(&page))
+    }
+
+    pub async fn list_jira_sprints(&self, board_id: u64, state: Option<&str>, start_at: u32, max_results: u32) -> Result<Value> {
+        let mut query = vec![
+            ("startAt", start_at.to_string()),
+            ("maxResults", max_results.to_string()),
+        ];
+        if let Some(state) = state {
+            query.push(("state", state.to_string()));
+        }
+
+        let page = self
+            .agile_get(&format!("/board/{}/sprint", board_id), &query, "sprints")
+            .await?;
+        Ok(compact_jira_sprints(&page))
+    }
+
+    /// Returns the sprint's issues in the same shape as a JQL search.
+    pub async fn get_sprint_issues(&self, sprint_id: u64, start_at: u32, max_results: u32) -> Result<Value> {
+        let query = [
+            ("startAt", start_at.to_string()),
+            ("maxResults", max_results.to_string()),
+            ("fields", "summary,status,assignee,created,updated".to_string()),
+        ];
+
+        self.agile_get(&format!("/sprint/{}/issue", sprint_id), &query, "sprint issues")
+            .await
+    }
+
     pub async fn get_jira_ticket(&self, ticket_key: &str) -> Result<Value> {
+        let api = jira_api_base(self.deployment().await?);
         let response = self
-            .request(reqwest::Method::GET, &format!("/rest/api/3/issue/{}", ticket_key))
+            .request(reqwest::Method::GET, &format!("{}/issue/{}", api, ticket_key))
             .query(&[
                 ("fields", "summary,description,status,created,updated,assignee,reporter,priority,issuetype")
             ])
-            .send()
+            .send_timed(self.config.timeout())
             .await
             .with_context(|| format!("Failed to get JIRA ticket {}", ticket_key))?;
 
@@ -66,17 +252,60 @@ impl AtlassianClient {
         Ok(ticket)
     }
 
+    /// Returns one page of an issue's change history. Cloud has a dedicated
+    /// paginated endpoint; Server only exposes the changelog as an issue expansion.
+    pub async fn get_jira_changelog(&self, ticket_key: &str, start_at: u32, max_results: u32) -> Result<Value> {
+        let deployment = self.deployment().await?;
+        let api = jira_api_base(deployment);
+
+        let request = match deployment {
+            Deployment::Cloud => self
+                .request(reqwest::Method::GET, &format!("{}/issue/{}/changelog", api, ticket_key))
+                .query(&[("startAt", start_at.to_string()), ("maxResults", max_results.to_string())]),
+            Deployment::Server => self
+                .request(reqwest::Method::GET, &format!("{}/issue/{}", api, ticket_key))
+                .query(&[("expand", "changelog"), ("fields", "summary")]),
+        };
+
+        let response = request
+            .send_timed(self.config.timeout())
+            .await
+            .with_context(|| format!("Failed to get changelog for JIRA ticket {}", ticket_key))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            error!("JIRA API error: {} - {}", status, text);
+            anyhow::bail!("JIRA API error: {} - {}", status, text);
+        }
+
+        let changelog: Value = response
+            .json()
+            .await
+            .with_context(|| "Failed to parse JIRA changelog response")?;
+
+        Ok(match deployment {
+            Deployment::Cloud => compact_jira_changelog(&changelog),
+            Deployment::Server => compact_jira_changelog(&page_server_changelog(
+                changelog.get("changelog").unwrap_or(&Value::Null),
+                start_at,
+                max_results,
+            )),
+        })
+    }
+
     pub async fn search_jira_tickets(&self, jql: &str, max_results: Option<u32>) -> Result<Value> {
         let max_results = max_results.unwrap_or(10);
+        let api = jira_api_base(self.deployment().await?);
         
         let response = self
-            .request(reqwest::Method::GET, "/rest/api/3/search")
+            .request(reqwest::Method::GET, &format!("{}/search", api))
             .query(&[
                 ("jql", jql),
                 ("maxResults", &max_results.to_string()),
-                ("fields", "summary,status,created,updated"),
+                ("fields", "summary,status,assignee,created,updated"),
             ])
-            .send()
+            .send_timed(self.config.timeout())
             .await
             .with_context(|| "Failed to search JIRA tickets")?;
 
@@ -95,6 +324,58 @@ impl AtlassianClient {
         Ok(results)
     }
 
+    /// Checks a JQL query for syntax and field errors without running it. Cloud
+    /// has a dedicated parse endpoint; Server only reports problems through a
+    /// search, so a zero-result search stands in for it there.
+    pub async fn validate_jql(&self, jql: &str) -> Result<Value> {
+        match self.deployment().await? {
+            Deployment::Cloud => {
+                let response = self
+                    .request(reqwest::Method::POST, "/rest/api/3/jql/parse")
+                    .query(&[("validation", "strict")])
+                    .json(&serde_json::json!({ "queries": [jql] }))
+                    .send_timed(self.config.timeout())
+                    .await
+                    .with_context(|| "Failed to validate JQL")?;
+
+                if !response.status().is_success() {
+                    let status = response.status();
+                    let text = response.text().await.unwrap_or_default();
+                    error!("JIRA API error: {} - {}", status, text);
+                    anyhow::bail!("JIRA API error: {} - {}", status, text);
+                }
+
+                let parsed: Value = response
+                    .json()
+                    .await
+                    .with_context(|| "Failed to parse JQL parse response")?;
+
+                Ok(jql_validation_from_parse(jql, &parsed))
+            }
+            Deployment::Server => {
+                let response = self
+                    .request(reqwest::Method::GET, "/rest/api/2/search")
+                    .query(&[("jql", jql), ("maxResults", "0"), ("validateQuery", "strict")])
+                    .send_timed(self.config.timeout())
+                    .await
+                    .with_context(|| "Failed to validate JQL")?;
+
+                let status = response.status();
+                if status == reqwest::StatusCode::BAD_REQUEST {
+                    let body: Value = response.json().await.unwrap_or(Value::Null);
+                    return Ok(jql_validation_from_error(jql, &body));
+                }
+                if !status.is_success() {
+                    let text = response.text().await.unwrap_or_default();
+                    error!("JIRA API error: {} - {}", status, text);
+                    anyhow::bail!("JIRA API error: {} - {}", status, text);
+                }
+
+                Ok(jql_validation(jql, Vec::new(), Vec::new()))
+            }
+        }
+    }
+
     pub async fn create_jira_ticket(
         &self,
         project_key: &str,
@@ -103,6 +384,7 @@ impl AtlassianClient {
         issue_type: Option<&str>,
     ) -> Result<Value> {
         let issue_type = issue_type.unwrap_or("Task");
+        let deployment = self.deployment().await?;
         
         let payload = serde_json::json!({
             "fields": {
@@ -110,21 +392,7 @@ impl AtlassianClient {
                     "key": project_key
                 },
                 "summary": summary,
-                "description": {
-                    "type": "doc",
-                    "version": 1,
-                    "content": [
-                        {
-                            "type": "paragraph",
-                            "content": [
-                                {
-                                    "type": "text",
-                                    "text": description
-                                }
-                            ]
-                        }
-                    ]
-                },
+                "description": jira_text_body(deployment, description),
                 "issuetype": {
                     "name": issue_type
                 }
@@ -132,9 +400,9 @@ impl AtlassianClient {
         });
 
         let response = self
-            .request(reqwest::Method::POST, "/rest/api/3/issue")
+            .request(reqwest::Method::POST, &format!("{}/issue", jira_api_base(deployment)))
             .json(&payload)
-            .send()
+            .send_timed(self.config.timeout())
             .await
             .with_context(|| "Failed to create JIRA ticket")?;
 
@@ -154,28 +422,15 @@ impl AtlassianClient {
     }
 
     pub async fn add_comment_to_jira_ticket(&self, ticket_key: &str, comment: &str) -> Result<Value> {
+        let deployment = self.deployment().await?;
         let payload = serde_json::json!({
-            "body": {
-                "type": "doc",
-                "version": 1,
-                "content": [
-                    {
-                        "type": "paragraph",
-                        "content": [
-                            {
-                                "type": "text",
-                                "text": comment
-                            }
-                        ]
-                    }
-                ]
-            }
+            "body": jira_text_body(deployment, comment)
         });
 
         let response = self
-            .request(reqwest::Method::POST, &format!("/rest/api/3/issue/{}/comment", ticket_key))
+            .request(reqwest::Method::POST, &format!("{}/issue/{}/comment", jira_api_base(deployment), ticket_key))
             .json(&payload)
-            .send()
+            .send_timed(self.config.timeout())
             .await
             .with_context(|| format!("Failed to add comment to JIRA ticket {}", ticket_key))?;
 
@@ -194,11 +449,57 @@ impl AtlassianClient {
         Ok(comment_response)
     }
 
+    pub async fn list_jira_priorities(&self) -> Result<Value> {
+        let api = jira_api_base(self.deployment().await?);
+        let response = self
+            .request(reqwest::Method::GET, &format!("{}/priority", api))
+            .send_timed(self.config.timeout())
+            .await
+            .with_context(|| "Failed to list JIRA priorities")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            error!("JIRA API error: {} - {}", status, text);
+            anyhow::bail!("JIRA API error: {} - {}", status, text);
+        }
+
+        let priorities: Value = response
+            .json()
+            .await
+            .with_context(|| "Failed to parse JIRA priorities response")?;
+
+        Ok(id_name_pairs(&priorities))
+    }
+
+    pub async fn list_jira_statuses(&self) -> Result<Value> {
+        let api = jira_api_base(self.deployment().await?);
+        let response = self
+            .request(reqwest::Method::GET, &format!("{}/status", api))
+            .send_timed(self.config.timeout())
+            .await
+            .with_context(|| "Failed to list JIRA statuses")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            error!("JIRA API error: {} - {}", status, text);
+            anyhow::bail!("JIRA API error: {} - {}", status, text);
+        }
+
+        let statuses: Value = response
+            .json()
+            .await
+            .with_context(|| "Failed to parse JIRA statuses response")?;
+
+        Ok(id_name_pairs(&statuses))
+    }
+
     pub async fn get_confluence_page(&self, page_id: &str) -> Result<Value> {
         let response = self
             .request(reqwest::Method::GET, &format!("/wiki/rest/api/content/{}", page_id))
             .query(&[("expand", "body.storage,version,space")])
-            .send()
+            .send_timed(self.config.timeout())
             .await
             .with_context(|| format!("Failed to get Confluence page {}", page_id))?;
 
@@ -228,7 +529,7 @@ impl AtlassianClient {
                 ("limit", &limit.to_string()),
                 ("expand", &"space".to_string()),
             ])
-            .send()
+            .send_timed(self.config.timeout())
             .await
             .with_context(|| "Failed to search Confluence")?;
 
@@ -247,11 +548,41 @@ impl AtlassianClient {
         Ok(results)
     }
 
+    pub async fn add_confluence_comment(
+        &self,
+        page_id: &str,
+        body: &str,
+        parent_comment_id: Option<&str>,
+    ) -> Result<Value> {
+        let payload = confluence_comment_payload(page_id, body, parent_comment_id);
+
+        let response = self
+            .request(reqwest::Method::POST, "/wiki/rest/api/content")
+            .json(&payload)
+            .send_timed(self.config.timeout())
+            .await
+            .with_context(|| format!("Failed to add comment to Confluence page {}", page_id))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            error!("Confluence API error: {} - {}", status, text);
+            anyhow::bail!("Confluence API error: {} - {}", status, text);
+        }
+
+        let comment: Value = response
+            .json()
+            .await
+            .with_context(|| "Failed to parse Confluence comment response")?;
+
+        Ok(comment)
+    }
+
     pub async fn get_confluence_spaces(&self) -> Result<Value> {
         let response = self
             .request(reqwest::Method::GET, "/wiki/rest/api/space")
             .query(&[("limit", "25")])
-            .send()
+            .send_timed(self.config.timeout())
             .await
             .with_context(|| "Failed to get Confluence spaces")?;
 
@@ -270,16 +601,1058 @@ impl AtlassianClient {
         Ok(spaces)
     }
 
+    /// Downloads an attachment, given its id or `content` URL, into the configured
+    /// downloads directory as `filename`, or the server-provided name when unset.
+    pub async fn download_jira_attachment(&self, attachment: &str, filename: Option<&str>) -> Result<Value> {
+        let api = jira_api_base(self.deployment().await?);
+        let base = Url::parse(&self.config.base_url)
+            .with_context(|| format!("Invalid base URL: {}", self.config.base_url))?;
+        let mut url = attachment_content_url(&base, api, attachment)?;
+        let mut send_auth = same_origin(&url, &base);
+        let mut redirects = 0;
+
+        let response = loop {
+            debug!("Downloading attachment from: {}", url);
+            let mut request = self.download_client.get(url.clone());
+            if send_auth {
+                request = request.header("Authorization", &self.auth_header);
+            }
+
+            let response = request
+                .send_timed(self.config.timeout())
+                .await
+                .with_context(|| format!("Failed to download JIRA attachment {}", attachment))?;
+
+            if !response.status().is_redirection() {
+                break response;
+            }
+
+            redirects += 1;
+            if redirects > MAX_ATTACHMENT_REDIRECTS {
+                anyhow::bail!("Too many redirects downloading JIRA attachment {}", attachment);
+            }
+
+            let location = response
+                .headers()
+                .get(reqwest::header::LOCATION)
+                .and_then(|v| v.to_str().ok())
+                .context("Attachment redirect is missing a Location header")?;
+            (url, send_auth) = next_download_hop(&url, location, &base)?;
+        };
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            error!("JIRA API error: {} - {}", status, text);
+            anyhow::bail!("JIRA API error: {} - {}", status, text);
+        }
+
+        let requested_filename = filename;
+        let filename = response
+            .headers()
+            .get(reqwest::header::CONTENT_DISPOSITION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(content_disposition_filename);
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string());
+
+        let bytes = response
+            .bytes()
+            .await
+            .with_context(|| "Failed to read JIRA attachment content")?;
+
+        let dir = self.config.downloads_path()?;
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .with_context(|| format!("Failed to create downloads directory {}", dir.display()))?;
+        let fallback = format!("attachment-{}", attachment_id_from(attachment));
+        let path = attachment_save_path(&dir, requested_filename.or(filename.as_deref()), &fallback);
+        let size = write_attachment(&path, &bytes).await?;
+
+        Ok(serde_json::json!({
+            "path": path.display().to_string(),
+            "size": size,
+            "content_type": content_type,
+        }))
+    }
+
     pub async fn get_recent_jira_tickets(&self) -> Result<Value> {
         self.search_jira_tickets("ORDER BY updated DESC", Some(10)).await
     }
 }
 
+/// Reduces a JIRA search response to `{key, summary, status, assignee, updated}` per issue.
+pub fn compact_jira_search_results(results: &Value) -> Value {
+    let issues: Vec<Value> = results
+        .get("issues")
+        .and_then(|v| v.as_array())
+        .map(|issues| {
+            issues
+                .iter()
+                .map(|issue| {
+                    let fields = issue.get("fields");
+                    serde_json::json!({
+                        "key": issue.get("key").cloned().unwrap_or(Value::Null),
+                        "summary": fields.and_then(|f| f.get("summary")).cloned().unwrap_or(Value::Null),
+                        "status": fields.and_then(|f| f.pointer("/status/name")).cloned().unwrap_or(Value::Null),
+                        "assignee": fields.and_then(|f| f.pointer("/assignee/displayName")).cloned().unwrap_or(Value::Null),
+                        "updated": fields.and_then(|f| f.get("updated")).cloned().unwrap_or(Value::Null),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    serde_json::json!({
+        "total": results.get("total").cloned().unwrap_or(Value::Null),
+        "issues": issues,
+    })
+}
+
+fn jql_validation(jql: &str, errors: Vec<Value>, warnings: Vec<Value>) -> Value {
+    serde_json::json!({
+        "query": jql,
+        "valid": errors.is_empty(),
+        "errors": errors,
+        "warnings": warnings,
+    })
+}
+
+/// Turns a Cloud `/jql/parse` response into `{query, valid, errors, warnings}`.
+pub fn jql_validation_from_parse(jql: &str, parsed: &Value) -> Value {
+    let query = parsed.pointer("/queries/0");
+    let messages = |key: &str| {
+        query
+            .and_then(|q| q.get(key))
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default()
+    };
+    jql_validation(jql, messages("errors"), messages("warnings"))
+}
+
+/// Turns a 400 search response into `{query, valid, errors, warnings}`, merging
+/// `errorMessages` with the per-field `errors` map.
+pub fn jql_validation_from_error(jql: &str, body: &Value) -> Value {
+    let mut errors: Vec<Value> = body
+        .get("errorMessages")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    if let Some(fields) = body.get("errors").and_then(|v| v.as_object()) {
+        errors.extend(fields.values().cloned());
+    }
+    let warnings = body
+        .get("warningMessages")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    jql_validation(jql, errors, warnings)
+}
+
+/// Reduces an Agile API board page to `{id, name, type, project_key}` per board.
+pub fn compact_jira_boards(page: &Value) -> Value {
+    compact_agile_page(page, "boards", |board| {
+        serde_json::json!({
+            "id": board.get("id").cloned().unwrap_or(Value::Null),
+            "name": board.get("name").cloned().unwrap_or(Value::Null),
+            "type": board.get("type").cloned().unwrap_or(Value::Null),
+            "project_key": board.pointer("/location/projectKey").cloned().unwrap_or(Value::Null),
+        })
+    })
+}
+
+/// Reduces an Agile API sprint page to `{id, name, state, start_date, end_date, goal}` per sprint.
+pub fn compact_jira_sprints(page: &Value) -> Value {
+    compact_agile_page(page, "sprints", |sprint| {
+        serde_json::json!({
+            "id": sprint.get("id").cloned().unwrap_or(Value::Null),
+            "name": sprint.get("name").cloned().unwrap_or(Value::Null),
+            "state": sprint.get("state").cloned().unwrap_or(Value::Null),
+            "start_date": sprint.get("startDate").cloned().unwrap_or(Value::Null),
+            "end_date": sprint.get("endDate").cloned().unwrap_or(Value::Null),
+            "goal": sprint.get("goal").cloned().unwrap_or(Value::Null),
+        })
+    })
+}
+
+/// Agile API list pages carry `values` plus `startAt`/`maxResults`/`isLast`.
+fn compact_agile_page(page: &Value, key: &str, compact: impl Fn(&Value) -> Value) -> Value {
+    let values: Vec<Value> = page
+        .get("values")
+        .and_then(|v| v.as_array())
+        .map(|values| values.iter().map(&compact).collect())
+        .unwrap_or_default();
+
+    let mut compacted = serde_json::json!({
+        "start_at": page.get("startAt").and_then(|v| v.as_u64()).unwrap_or(0),
+        "max_results": page.get("maxResults").cloned().unwrap_or(Value::Null),
+        "is_last": page.get("isLast").and_then(|v| v.as_bool()).unwrap_or(true),
+    });
+    compacted[key] = Value::Array(values);
+    compacted
+}
+
+/// Resolves an attachment id or absolute `content` URL to the URL to fetch.
+pub fn attachment_content_url(base: &Url, api: &str, attachment: &str) -> Result<Url> {
+    if attachment.starts_with("http://") || attachment.starts_with("https://") {
+        return Url::parse(attachment).with_context(|| format!("Invalid attachment URL: {}", attachment));
+    }
+    if attachment.is_empty() || !attachment.chars().all(|c| c.is_ascii_digit()) {
+        anyhow::bail!("Attachment must be a numeric id or a content URL, got '{}'", attachment);
+    }
+    let url = format!("{}{}/attachment/content/{}", base.as_str().trim_end_matches('/'), api, attachment);
+    Url::parse(&url).with_context(|| format!("Invalid attachment URL: {}", url))
+}
+
+/// Resolves a redirect `Location` against the current URL. Credentials are only
+/// forwarded while the chain stays on the Atlassian instance; the media host
+/// JIRA redirects to authenticates through a signed query string instead.
+pub fn next_download_hop(current: &Url, location: &str, base: &Url) -> Result<(Url, bool)> {
+    let next = current
+        .join(location)
+        .with_context(|| format!("Invalid redirect location: {}", location))?;
+    let send_auth = same_origin(&next, base);
+    Ok((next, send_auth))
+}
+
+fn same_origin(url: &Url, base: &Url) -> bool {
+    url.origin() == base.origin()
+}
+
+fn attachment_id_from(attachment: &str) -> &str {
+    attachment
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or(attachment)
+}
+
+/// Pulls `filename` out of a `Content-Disposition` header, dropping any directory
+/// components so a hostile name can't escape the output directory.
+pub fn content_disposition_filename(header: &str) -> Option<String> {
+    let value = header
+        .split(';')
+        .map(str::trim)
+        .find_map(|part| part.strip_prefix("filename="))?;
+    let name = value.trim_matches('"');
+    let name = name.rsplit(['/', '\\']).next().unwrap_or(name);
+    (!name.is_empty() && name != "." && name != "..").then(|| name.to_string())
+}
+
+/// Where to save an attachment inside `dir`. Only the final path component of
+/// `filename` is used, and an existing file is never overwritten: `report.pdf`
+/// becomes `report (1).pdf`, `report (2).pdf`, ...
+pub fn attachment_save_path(dir: &Path, filename: Option<&str>, fallback: &str) -> PathBuf {
+    let name = filename
+        .and_then(|name| name.rsplit(['/', '\\']).next())
+        .filter(|name| !name.is_empty() && *name != "." && *name != "..")
+        .unwrap_or(fallback);
+
+    let candidate = dir.join(name);
+    if !candidate.exists() {
+        return candidate;
+    }
+
+    let path = Path::new(name);
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or(name);
+    let extension = path.extension().and_then(|s| s.to_str());
+    (1..)
+        .map(|n| match extension {
+            Some(ext) => dir.join(format!("{} ({}).{}", stem, n, ext)),
+            None => dir.join(format!("{} ({})", stem, n)),
+        })
+        .find(|candidate| !candidate.exists())
+        .expect("unbounded counter always finds a free name")
+}
+
+/// Writes the attachment to a new file and returns the byte count. Fails rather
+/// than overwrite a file that appeared after the name was picked.
+pub async fn write_attachment(path: &Path, bytes: &[u8]) -> Result<u64> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .await
+        .with_context(|| format!("Failed to create attachment file {}", path.display()))?;
+    file.write_all(bytes)
+        .await
+        .with_context(|| format!("Failed to write attachment to {}", path.display()))?;
+    file.flush()
+        .await
+        .with_context(|| format!("Failed to write attachment to {}", path.display()))?;
+    Ok(bytes.len() as u64)
+}
+
+/// Reduces a changelog page to `{start_at, max_results, total, is_last, histories}`,
+/// where each history has its author, timestamp and changed fields with from/to
+/// display values. Accepts both the Cloud page (`values`) and the Server
+/// `expand=changelog` object (`histories`).
+pub fn compact_jira_changelog(page: &Value) -> Value {
+    let histories: Vec<Value> = page
+        .get("values")
+        .or_else(|| page.get("histories"))
+        .and_then(|v| v.as_array())
+        .map(|histories| {
+            histories
+                .iter()
+                .map(|history| {
+                    let items: Vec<Value> = history
+                        .get("items")
+                        .and_then(|v| v.as_array())
+                        .map(|items| {
+                            items
+                                .iter()
+                                .map(|item| {
+                                    // Prefer display strings; raw ids are the fallback
+                                    let display = |text: &str, raw: &str| {
+                                        item.get(text)
+                                            .filter(|v| !v.is_null())
+                                            .or_else(|| item.get(raw))
+                                            .cloned()
+                                            .unwrap_or(Value::Null)
+                                    };
+                                    serde_json::json!({
+                                        "field": item.get("field").cloned().unwrap_or(Value::Null),
+                                        "from": display("fromString", "from"),
+                                        "to": display("toString", "to"),
+                                    })
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default();
+
+                    serde_json::json!({
+                        "id": history.get("id").cloned().unwrap_or(Value::Null),
+                        "author": history.pointer("/author/displayName").cloned().unwrap_or(Value::Null),
+                        "created": history.get("created").cloned().unwrap_or(Value::Null),
+                        "items": items,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let start_at = page.get("startAt").and_then(|v| v.as_u64()).unwrap_or(0);
+    let total = page.get("total").and_then(|v| v.as_u64());
+    let is_last = page
+        .get("isLast")
+        .and_then(|v| v.as_bool())
+        .unwrap_or_else(|| total.is_none_or(|total| start_at + histories.len() as u64 >= total));
+
+    serde_json::json!({
+        "start_at": start_at,
+        "max_results": page.get("maxResults").cloned().unwrap_or(Value::Null),
+        "total": total,
+        "is_last": is_last,
+        "histories": histories,
+    })
+}
+
+/// Server returns the whole changelog in one `expand=changelog` object; slice it
+/// into the same page shape Cloud returns.
+pub fn page_server_changelog(changelog: &Value, start_at: u32, max_results: u32) -> Value {
+    let histories = changelog
+        .get("histories")
+        .and_then(|v| v.as_array())
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let start = (start_at as usize).min(histories.len());
+    let end = start.saturating_add(max_results as usize).min(histories.len());
+
+    serde_json::json!({
+        "startAt": start_at,
+        "maxResults": max_results,
+        "total": histories.len(),
+        "isLast": end == histories.len(),
+        "values": &histories[start..end],
+    })
+}
+
+/// Cloud serves the v3 API; Server and Data Center only have v2.
+pub fn jira_api_base(deployment: Deployment) -> &'static str {
+    match deployment {
+        Deployment::Cloud => "/rest/api/3",
+        Deployment::Server => "/rest/api/2",
+    }
+}
+
+/// Formats a description or comment: an Atlassian Document Format doc on Cloud,
+/// plain wiki-markup text on Server.
+pub fn jira_text_body(deployment: Deployment, text: &str) -> Value {
+    match deployment {
+        Deployment::Cloud => serde_json::json!({
+            "type": "doc",
+            "version": 1,
+            "content": [
+                {
+                    "type": "paragraph",
+                    "content": [
+                        {
+                            "type": "text",
+                            "text": text
+                        }
+                    ]
+                }
+            ]
+        }),
+        Deployment::Server => Value::String(text.to_string()),
+    }
+}
+
+/// Interprets the `/rest/api/3/serverInfo` probe. Server and Data Center don't serve
+/// v3 at all, so they answer 404; Cloud reports `deploymentType: "Cloud"`. Any other
+/// failure (bad credentials, rate limiting, an outage) says nothing about the deployment.
+pub fn deployment_from_server_info(status: reqwest::StatusCode, server_info: Option<&Value>) -> Result<Deployment> {
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Ok(Deployment::Server);
+    }
+    if !status.is_success() {
+        anyhow::bail!("Failed to detect the Atlassian deployment: serverInfo returned {}", status);
+    }
+
+    Ok(match server_info.and_then(|info| info.get("deploymentType")).and_then(|v| v.as_str()) {
+        Some("Server") | Some("DataCenter") => Deployment::Server,
+        _ => Deployment::Cloud,
+    })
+}
+
+/// Builds a footer comment on `page_id`; `parent_comment_id` makes it a reply to that comment.
+pub fn confluence_comment_payload(page_id: &str, body: &str, parent_comment_id: Option<&str>) -> Value {
+    let mut payload = serde_json::json!({
+        "type": "comment",
+        "container": {
+            "id": page_id,
+            "type": "page"
+        },
+        "body": {
+            "storage": {
+                "value": body,
+                "representation": "storage"
+            }
+        }
+    });
+
+    if let Some(parent_id) = parent_comment_id {
+        payload["ancestors"] = serde_json::json!([{ "id": parent_id }]);
+    }
+
+    payload
+}
+
+/// Cloud JQL only matches users by accountId; Server and Data Center use the username.
+pub fn user_id_from_myself(deployment: Deployment, myself: &Value) -> Option<String> {
+    let field = match deployment {
+        Deployment::Cloud => "accountId",
+        Deployment::Server => "name",
+    };
+
+    myself.get(field).and_then(|v| v.as_str()).map(str::to_string)
+}
+
+/// Prepends `field = "user_id"` for each of `fields` to `jql`, keeping any
+/// trailing `ORDER BY` at the end. An empty `jql` yields just the clauses.
+pub fn scope_jql_to_user(jql: &str, fields: &[&str], user_id: &str) -> String {
+    let quoted = user_id.replace('\\', "\\\\").replace('"', "\\\"");
+    let mut clauses: Vec<String> = fields
+        .iter()
+        .map(|field| format!("{} = \"{}\"", field, quoted))
+        .collect();
+
+    let (filter, order_by) = split_order_by(jql);
+    if !filter.is_empty() {
+        clauses.push(format!("({})", filter));
+    }
+
+    let mut scoped = clauses.join(" AND ");
+    if let Some(order_by) = order_by {
+        if !scoped.is_empty() {
+            scoped.push(' ');
+        }
+        scoped.push_str(order_by);
+    }
+    scoped
+}
+
+/// Splits off a trailing `ORDER BY` that isn't inside a quoted string.
+fn split_order_by(jql: &str) -> (&str, Option<&str>) {
+    let lower = jql.to_ascii_lowercase();
+    let order_by = lower.rmatch_indices("order by").map(|(i, _)| i).find(|&i| {
+        let at_word_start = i == 0 || lower.as_bytes()[i - 1].is_ascii_whitespace();
+        let outside_quotes = jql[..i].matches('"').count().is_multiple_of(2);
+        at_word_start && outside_quotes
+    });
+
+    match order_by {
+        Some(i) => (jql[..i].trim(), Some(jql[i..].trim())),
+        None => (jql.trim(), None),
+    }
+}
+
+/// Reduces a JIRA list response (priorities, statuses) to `{id, name}` per entry.
+pub fn id_name_pairs(values: &Value) -> Value {
+    let pairs: Vec<Value> = values
+        .as_array()
+        .map(|entries| {
+            entries
+                .iter()
+                .map(|entry| {
+                    serde_json::json!({
+                        "id": entry.get("id").cloned().unwrap_or(Value::Null),
+                        "name": entry.get("name").cloned().unwrap_or(Value::Null),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Value::Array(pairs)
+}
+
+/// Applies the configured proxy shared by every client; each client sets its own timeouts.
+fn http_client_builder(config: &AtlassianConfig) -> Result<reqwest::ClientBuilder> {
+    let mut builder = Client::builder();
+    if let Some(proxy) = &config.proxy {
+        let proxy = reqwest::Proxy::all(proxy)
+            .with_context(|| format!("Invalid Atlassian proxy URL: {}", redact_proxy_url(proxy)))?;
+        builder = builder.proxy(proxy);
+    }
+    Ok(builder)
+}
+
 impl fmt::Debug for AtlassianClient {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("AtlassianClient")
             .field("base_url", &self.config.base_url)
             .field("email", &self.config.email)
+            .field("deployment", &self.deployment.get())
+            .field("timeout", &self.config.timeout())
             .finish()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compact_jira_search_results() {
+        let results = serde_json::json!({
+            "expand": "names,schema",
+            "startAt": 0,
+            "maxResults": 10,
+            "total": 1,
+            "issues": [{
+                "id": "10001",
+                "self": "https://example.atlassian.net/rest/api/3/issue/10001",
+                "key": "PROJ-1",
+                "fields": {
+                    "summary": "Fix login",
+                    "status": {"name": "In Progress", "statusCategory": {"key": "indeterminate"}},
+                    "assignee": {"displayName": "Ada Lovelace", "avatarUrls": {"48x48": "https://example.com/a.png"}},
+                    "created": "2024-01-01T10:00:00.000+0000",
+                    "updated": "2024-01-02T10:00:00.000+0000"
+                }
+            }]
+        });
+
+        let compact = compact_jira_search_results(&results);
+        let issue = &compact["issues"][0];
+        assert_eq!(compact["total"], 1);
+        assert_eq!(issue["key"], "PROJ-1");
+        assert_eq!(issue["summary"], "Fix login");
+        assert_eq!(issue["status"], "In Progress");
+        assert_eq!(issue["assignee"], "Ada Lovelace");
+
+        let text = compact.to_string();
+        assert!(!text.contains("avatarUrls"));
+        assert!(!text.contains("statusCategory"));
+        assert!(!text.contains("\"self\""));
+    }
+
+    #[test]
+    fn test_jql_validation_from_parse_errors() {
+        let parsed = serde_json::json!({
+            "queries": [{
+                "query": "project = PROJ AND sttaus = Open",
+                "errors": [
+                    "Field 'sttaus' does not exist or you do not have permission to view it."
+                ]
+            }]
+        });
+
+        let result = jql_validation_from_parse("project = PROJ AND sttaus = Open", &parsed);
+        assert_eq!(result["valid"], false);
+        assert_eq!(result["query"], "project = PROJ AND sttaus = Open");
+        assert_eq!(result["errors"][0], "Field 'sttaus' does not exist or you do not have permission to view it.");
+        assert_eq!(result["warnings"], serde_json::json!([]));
+
+        let ok = serde_json::json!({
+            "queries": [{
+                "query": "project = PROJ",
+                "structure": {"where": {"field": {"name": "project"}, "operator": "=", "operand": {"value": "PROJ"}}}
+            }]
+        });
+        assert_eq!(jql_validation_from_parse("project = PROJ", &ok)["valid"], true);
+
+        let server_error = serde_json::json!({
+            "errorMessages": ["Error in the JQL Query: Expecting operator but got 'Open'. (line 1, character 16)"],
+            "errors": {"sttaus": "Field 'sttaus' does not exist."}
+        });
+        let result = jql_validation_from_error("status Open", &server_error);
+        assert_eq!(result["valid"], false);
+        assert_eq!(result["errors"].as_array().unwrap().len(), 2);
+        assert_eq!(result["errors"][1], "Field 'sttaus' does not exist.");
+    }
+
+    #[test]
+    fn test_scope_jql_to_user() {
+        let account_id = "557058:f58131cb-b67d-43c7-b30d-6b58d40bd077";
+
+        assert_eq!(
+            scope_jql_to_user("project = PROJ ORDER BY updated DESC", &["assignee"], account_id),
+            "assignee = \"557058:f58131cb-b67d-43c7-b30d-6b58d40bd077\" AND (project = PROJ) ORDER BY updated DESC"
+        );
+        assert_eq!(
+            scope_jql_to_user("", &["assignee", "reporter"], "jsmith"),
+            "assignee = \"jsmith\" AND reporter = \"jsmith\""
+        );
+        assert_eq!(
+            scope_jql_to_user("order by created", &["assignee"], "jsmith"),
+            "assignee = \"jsmith\" order by created"
+        );
+        assert_eq!(
+            scope_jql_to_user("summary ~ \"sort order by date\" OR status = Open", &["assignee"], "jsmith"),
+            "assignee = \"jsmith\" AND (summary ~ \"sort order by date\" OR status = Open)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_timeout_surfaces_distinct_error() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/issue/PROJ-1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"key": "PROJ-1"}))
+                    .set_delay(Duration::from_secs(5)),
+            )
+            .mount(&server)
+            .await;
+
+        let client = AtlassianClient::new(AtlassianConfig {
+            base_url: server.uri(),
+            email: "mia@example.com".to_string(),
+            token: "token".to_string(),
+            deployment: Some(Deployment::Cloud),
+            timeout_secs: Some(1),
+            proxy: None,
+            downloads_dir: None,
+        })
+        .unwrap();
+
+        let err = client.get_jira_ticket("PROJ-1").await.unwrap_err();
+        let timed_out = err.downcast_ref::<RequestTimedOut>().expect("timeout error");
+        assert_eq!(timed_out.timeout, Duration::from_secs(1));
+        assert!(format!("{:#}", err).contains("timed out after 1s"));
+
+        let proxied = AtlassianClient::new(AtlassianConfig {
+            base_url: server.uri(),
+            email: "mia@example.com".to_string(),
+            token: "token".to_string(),
+            deployment: Some(Deployment::Cloud),
+            timeout_secs: None,
+            proxy: Some("http://proxy.corp.example:3128".to_string()),
+            downloads_dir: None,
+        });
+        assert!(proxied.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_assigned_to_me_uses_cached_account_id() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/myself"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "accountId": "5b10a2844c20165700ede21g",
+                "displayName": "Mia Krystof",
+                "name": "ignored-on-cloud"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = AtlassianClient::new(AtlassianConfig {
+            base_url: server.uri(),
+            email: "mia@example.com".to_string(),
+            token: "token".to_string(),
+            deployment: Some(Deployment::Cloud),
+            timeout_secs: None,
+            proxy: None,
+            downloads_dir: None,
+        })
+        .unwrap();
+
+        let first = client.current_user_id().await.unwrap();
+        let second = client.current_user_id().await.unwrap();
+        assert_eq!(first, "5b10a2844c20165700ede21g");
+        assert_eq!(second, first);
+        assert_eq!(
+            scope_jql_to_user("status = Open", &["assignee"], &second),
+            "assignee = \"5b10a2844c20165700ede21g\" AND (status = Open)"
+        );
+
+        assert_eq!(
+            user_id_from_myself(Deployment::Server, &serde_json::json!({"name": "mkrystof", "key": "JIRAUSER10100"})),
+            Some("mkrystof".to_string())
+        );
+    }
+
+    #[test]
+    fn test_compact_jira_boards() {
+        let page = serde_json::json!({
+            "maxResults": 50,
+            "startAt": 0,
+            "total": 2,
+            "isLast": true,
+            "values": [
+                {
+                    "id": 84,
+                    "self": "https://example.atlassian.net/rest/agile/1.0/board/84",
+                    "name": "PROJ board",
+                    "type": "scrum",
+                    "location": {"projectId": 10000, "projectKey": "PROJ", "displayName": "Project (PROJ)"}
+                },
+                {
+                    "id": 92,
+                    "self": "https://example.atlassian.net/rest/agile/1.0/board/92",
+                    "name": "Ops kanban",
+                    "type": "kanban"
+                }
+            ]
+        });
+
+        let boards = compact_jira_boards(&page);
+        assert_eq!(boards["is_last"], true);
+        assert_eq!(
+            boards["boards"],
+            serde_json::json!([
+                {"id": 84, "name": "PROJ board", "type": "scrum", "project_key": "PROJ"},
+                {"id": 92, "name": "Ops kanban", "type": "kanban", "project_key": null}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_compact_jira_sprints() {
+        let page = serde_json::json!({
+            "maxResults": 2,
+            "startAt": 0,
+            "isLast": false,
+            "values": [
+                {
+                    "id": 37,
+                    "self": "https://example.atlassian.net/rest/agile/1.0/sprint/37",
+                    "state": "closed",
+                    "name": "Sprint 1",
+                    "startDate": "2024-04-01T09:00:00.000Z",
+                    "endDate": "2024-04-15T09:00:00.000Z",
+                    "completeDate": "2024-04-15T10:12:00.000Z",
+                    "originBoardId": 84,
+                    "goal": "Ship login"
+                },
+                {
+                    "id": 38,
+                    "self": "https://example.atlassian.net/rest/agile/1.0/sprint/38",
+                    "state": "future",
+                    "name": "Sprint 2",
+                    "originBoardId": 84
+                }
+            ]
+        });
+
+        let sprints = compact_jira_sprints(&page);
+        assert_eq!(sprints["start_at"], 0);
+        assert_eq!(sprints["max_results"], 2);
+        assert_eq!(sprints["is_last"], false);
+        assert_eq!(sprints["sprints"][0]["state"], "closed");
+        assert_eq!(sprints["sprints"][0]["goal"], "Ship login");
+        assert_eq!(
+            sprints["sprints"][1],
+            serde_json::json!({
+                "id": 38, "name": "Sprint 2", "state": "future",
+                "start_date": null, "end_date": null, "goal": null
+            })
+        );
+        assert_eq!(JIRA_AGILE_API_BASE, "/rest/agile/1.0");
+    }
+
+    #[test]
+    fn test_id_name_pairs_for_priorities_and_statuses() {
+        let priorities = serde_json::json!([
+            {
+                "self": "https://example.atlassian.net/rest/api/3/priority/1",
+                "statusColor": "#d04437",
+                "description": "This problem will block progress.",
+                "iconUrl": "https://example.atlassian.net/images/icons/priorities/highest.svg",
+                "name": "Highest",
+                "id": "1"
+            },
+            {
+                "self": "https://example.atlassian.net/rest/api/3/priority/3",
+                "statusColor": "#f79232",
+                "description": "Has the potential to affect progress.",
+                "iconUrl": "https://example.atlassian.net/images/icons/priorities/medium.svg",
+                "name": "Medium",
+                "id": "3"
+            }
+        ]);
+        let statuses = serde_json::json!([
+            {
+                "self": "https://example.atlassian.net/rest/api/3/status/10000",
+                "description": "",
+                "iconUrl": "https://example.atlassian.net/",
+                "name": "To Do",
+                "untranslatedName": "To Do",
+                "id": "10000",
+                "statusCategory": {"id": 2, "key": "new", "name": "To Do"}
+            }
+        ]);
+
+        assert_eq!(
+            id_name_pairs(&priorities),
+            serde_json::json!([{"id": "1", "name": "Highest"}, {"id": "3", "name": "Medium"}])
+        );
+        assert_eq!(
+            id_name_pairs(&statuses),
+            serde_json::json!([{"id": "10000", "name": "To Do"}])
+        );
+    }
+
+    #[test]
+    fn test_confluence_comment_payload() {
+        let payload = confluence_comment_payload("123456", "<p>Looks good</p>", None);
+        assert_eq!(
+            payload,
+            serde_json::json!({
+                "type": "comment",
+                "container": {"id": "123456", "type": "page"},
+                "body": {"storage": {"value": "<p>Looks good</p>", "representation": "storage"}}
+            })
+        );
+
+        let reply = confluence_comment_payload("123456", "<p>Agreed</p>", Some("789"));
+        assert_eq!(reply["container"]["id"], "123456");
+        assert_eq!(reply["ancestors"], serde_json::json!([{"id": "789"}]));
+    }
+
+    #[test]
+    fn test_jira_format_follows_deployment() {
+        assert_eq!(jira_api_base(Deployment::Cloud), "/rest/api/3");
+        assert_eq!(jira_api_base(Deployment::Server), "/rest/api/2");
+
+        let cloud = jira_text_body(Deployment::Cloud, "Steps to reproduce");
+        assert_eq!(cloud["type"], "doc");
+        assert_eq!(cloud["content"][0]["content"][0]["text"], "Steps to reproduce");
+
+        let server = jira_text_body(Deployment::Server, "Steps to reproduce");
+        assert_eq!(server, Value::String("Steps to reproduce".to_string()));
+    }
+
+    #[test]
+    fn test_deployment_from_server_info() {
+        let cloud = serde_json::json!({"baseUrl": "https://example.atlassian.net", "deploymentType": "Cloud"});
+        let data_center = serde_json::json!({"baseUrl": "https://jira.example.com", "deploymentType": "DataCenter"});
+
+        use reqwest::StatusCode;
+
+        assert_eq!(deployment_from_server_info(StatusCode::OK, Some(&cloud)).unwrap(), Deployment::Cloud);
+        assert_eq!(deployment_from_server_info(StatusCode::OK, Some(&data_center)).unwrap(), Deployment::Server);
+        assert_eq!(deployment_from_server_info(StatusCode::NOT_FOUND, None).unwrap(), Deployment::Server);
+        assert_eq!(deployment_from_server_info(StatusCode::OK, None).unwrap(), Deployment::Cloud);
+        assert!(deployment_from_server_info(StatusCode::UNAUTHORIZED, None).is_err());
+        assert!(deployment_from_server_info(StatusCode::FORBIDDEN, None).is_err());
+        assert!(deployment_from_server_info(StatusCode::TOO_MANY_REQUESTS, None).is_err());
+        assert!(deployment_from_server_info(StatusCode::SERVICE_UNAVAILABLE, None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_failed_deployment_probe_is_not_cached() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/serverInfo"))
+            .respond_with(ResponseTemplate::new(401))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/serverInfo"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/serverInfo"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"deploymentType": "Cloud"})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = AtlassianClient::new(AtlassianConfig {
+            base_url: server.uri(),
+            email: "mia@example.com".to_string(),
+            token: "token".to_string(),
+            deployment: None,
+            timeout_secs: None,
+            proxy: None,
+            downloads_dir: None,
+        })
+        .unwrap();
+
+        assert!(format!("{:#}", client.deployment().await.unwrap_err()).contains("401"));
+        assert!(format!("{:#}", client.deployment().await.unwrap_err()).contains("503"));
+        assert_eq!(client.deployment().await.unwrap(), Deployment::Cloud);
+        assert_eq!(client.deployment().await.unwrap(), Deployment::Cloud);
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_deployment_probe_is_not_cached() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let client = AtlassianClient::new(AtlassianConfig {
+            base_url,
+            email: "mia@example.com".to_string(),
+            token: "token".to_string(),
+            deployment: None,
+            timeout_secs: Some(5),
+            proxy: None,
+            downloads_dir: None,
+        })
+        .unwrap();
+
+        assert!(client.deployment().await.is_err());
+        assert!(client.deployment.get().is_none());
+    }
+
+    #[test]
+    fn test_attachment_download_redirects() {
+        let base = Url::parse("https://example.atlassian.net").unwrap();
+
+        let url = attachment_content_url(&base, "/rest/api/3", "10001").unwrap();
+        assert_eq!(url.as_str(), "https://example.atlassian.net/rest/api/3/attachment/content/10001");
+        let direct = "https://example.atlassian.net/rest/api/3/attachment/content/10002";
+        assert_eq!(attachment_content_url(&base, "/rest/api/3", direct).unwrap().as_str(), direct);
+        assert!(attachment_content_url(&base, "/rest/api/3", "../secret").is_err());
+
+        // Same-instance relative redirect keeps credentials
+        let (next, send_auth) = next_download_hop(&url, "/secure/attachment/10001/report.pdf", &base).unwrap();
+        assert_eq!(next.as_str(), "https://example.atlassian.net/secure/attachment/10001/report.pdf");
+        assert!(send_auth);
+
+        // The media host gets a signed URL but never the Basic auth header
+        let (next, send_auth) = next_download_hop(&next, "https://api.media.atlassian.com/file/abc/binary?token=t", &base).unwrap();
+        assert_eq!(next.host_str(), Some("api.media.atlassian.com"));
+        assert!(!send_auth);
+
+        assert_eq!(attachment_id_from(direct), "10002");
+        assert_eq!(
+            content_disposition_filename("attachment; filename=\"../../etc/report.pdf\""),
+            Some("report.pdf".to_string())
+        );
+        assert_eq!(content_disposition_filename("inline"), None);
+    }
+
+    #[tokio::test]
+    async fn test_write_attachment_paths() {
+        let dir = std::env::temp_dir().join(format!("atlassian-attachment-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir(&dir).unwrap();
+
+        let path = attachment_save_path(&dir, Some("report.pdf"), "attachment-1");
+        assert_eq!(path, dir.join("report.pdf"));
+        assert_eq!(write_attachment(&path, b"%PDF-1.7").await.unwrap(), 8);
+        assert_eq!(std::fs::read(&path).unwrap(), b"%PDF-1.7");
+
+        // An existing file is never overwritten
+        assert!(write_attachment(&path, b"other").await.is_err());
+        assert_eq!(std::fs::read(&path).unwrap(), b"%PDF-1.7");
+        assert_eq!(attachment_save_path(&dir, Some("report.pdf"), "attachment-1"), dir.join("report (1).pdf"));
+
+        // Names can't leave the directory
+        assert_eq!(attachment_save_path(&dir, Some("../../etc/passwd"), "attachment-1"), dir.join("passwd"));
+        assert_eq!(attachment_save_path(&dir, Some("/tmp/.."), "attachment-1"), dir.join("attachment-1"));
+        assert_eq!(attachment_save_path(&dir, None, "attachment-1"), dir.join("attachment-1"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_compact_jira_changelog() {
+        let page = serde_json::json!({
+            "self": "https://example.atlassian.net/rest/api/3/issue/PROJ-1/changelog?startAt=0&maxResults=2",
+            "startAt": 0,
+            "maxResults": 2,
+            "total": 3,
+            "isLast": false,
+            "values": [
+                {
+                    "id": "10001",
+                    "author": {"accountId": "abc", "displayName": "Ada Lovelace"},
+                    "created": "2024-03-01T10:00:00.000+0000",
+                    "items": [
+                        {"field": "status", "fieldtype": "jira", "from": "1", "fromString": "To Do", "to": "3", "toString": "In Progress"},
+                        {"field": "assignee", "fieldtype": "jira", "from": null, "fromString": null, "to": "abc", "toString": "Ada Lovelace"}
+                    ]
+                },
+                {
+                    "id": "10002",
+                    "author": {"accountId": "def", "displayName": "Alan Turing"},
+                    "created": "2024-03-02T09:30:00.000+0000",
+                    "items": [
+                        {"field": "labels", "fieldtype": "jira", "from": null, "fromString": "", "to": null, "toString": "urgent"}
+                    ]
+                }
+            ]
+        });
+
+        let changelog = compact_jira_changelog(&page);
+        assert_eq!(changelog["total"], 3);
+        assert_eq!(changelog["is_last"], false);
+        assert_eq!(changelog["histories"].as_array().unwrap().len(), 2);
+
+        let first = &changelog["histories"][0];
+        assert_eq!(first["author"], "Ada Lovelace");
+        assert_eq!(first["created"], "2024-03-01T10:00:00.000+0000");
+        assert_eq!(first["items"][0], serde_json::json!({"field": "status", "from": "To Do", "to": "In Progress"}));
+        assert_eq!(first["items"][1], serde_json::json!({"field": "assignee", "from": null, "to": "Ada Lovelace"}));
+
+        // Server returns every history at once; it is paged locally
+        let server = serde_json::json!({"startAt": 0, "maxResults": 2, "total": 2, "histories": page["values"].clone()});
+        let changelog = compact_jira_changelog(&page_server_changelog(&server, 1, 10));
+        assert_eq!(changelog["start_at"], 1);
+        assert_eq!(changelog["total"], 2);
+        assert_eq!(changelog["is_last"], true);
+        assert_eq!(changelog["histories"].as_array().unwrap().len(), 1);
+        assert_eq!(changelog["histories"][0]["items"][0]["to"], "urgent");
+
+        // Without isLast the flag is derived from total
+        let open_ended = serde_json::json!({"startAt": 0, "total": 5, "values": page["values"].clone()});
+        assert_eq!(compact_jira_changelog(&open_ended)["is_last"], false);
+    }
+}
@@ -2,7 +2,10 @@ use anyhow::Result;
 use clap::Parser;
 use serde_json::Value;
 use std::collections::HashMap;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use std::sync::Arc;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::Mutex;
+use tokio::task::JoinSet;
 use tracing::{error, info};
 use tracing_subscriber;
 
@@ -10,7 +13,7 @@ mod atlassian;
 mod config;
 mod mcp_types;
 
-use atlassian::AtlassianClient;
+use atlassian::{compact_jira_search_results, scope_jql_to_user, AtlassianClient};
 use config::Config;
 use mcp_types::*;
 
@@ -26,9 +29,9 @@ pub struct AtlassianMcpServer {
 }
 
 impl AtlassianMcpServer {
-    fn new(config: Config) -> Self {
-        let client = AtlassianClient::new(config.atlassian.clone());
-        Self { client, config }
+    fn new(config: Config) -> Result<Self> {
+        let client = AtlassianClient::new(config.atlassian.clone())?;
+        Ok(Self { client, config })
     }
 
     fn get_tools(&self) -> Vec<Tool> {
@@ -55,12 +58,40 @@ impl AtlassianMcpServer {
                     "properties": {
                         "jql": {
                             "type": "string",
-                            "description": "JQL query string"
+                            "description": "JQL query string; may be omitted when assigned_to_me or reported_by_me is set"
+                        },
+                        "assigned_to_me": {
+                            "type": "boolean",
+                            "description": "Only tickets assigned to the authenticated user",
+                            "default": false
+                        },
+                        "reported_by_me": {
+                            "type": "boolean",
+                            "description": "Only tickets reported by the authenticated user",
+                            "default": false
                         },
                         "max_results": {
                             "type": "integer",
                             "description": "Maximum number of results to return",
                             "default": 10
+                        },
+                        "detailed": {
+                            "type": "boolean",
+                            "description": "Return the full JIRA response instead of compact key/summary/status/assignee/updated summaries",
+                            "default": false
+                        }
+                    }
+                }),
+            },
+            Tool {
+                name: "validate_jql".to_string(),
+                description: "Check a JQL query for syntax and field errors without running it".to_string(),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "jql": {
+                            "type": "string",
+                            "description": "JQL query string to validate"
                         }
                     },
                     "required": ["jql"]
@@ -111,6 +142,144 @@ impl AtlassianMcpServer {
                     "required": ["ticket_key", "comment"]
                 }),
             },
+            Tool {
+                name: "get_jira_changelog".to_string(),
+                description: "Get the history of field changes on a JIRA ticket, with author and timestamp".to_string(),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "ticket_key": {
+                            "type": "string",
+                            "description": "The JIRA ticket key (e.g., PROJ-123)"
+                        },
+                        "start_at": {
+                            "type": "integer",
+                            "description": "Index of the first history entry to return",
+                            "default": 0
+                        },
+                        "max_results": {
+                            "type": "integer",
+                            "description": "Maximum number of history entries to return",
+                            "default": 50
+                        }
+                    },
+                    "required": ["ticket_key"]
+                }),
+            },
+            Tool {
+                name: "list_jira_priorities".to_string(),
+                description: "List the JIRA priorities available on this instance as id/name pairs".to_string(),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {}
+                }),
+            },
+            Tool {
+                name: "list_jira_statuses".to_string(),
+                description: "List the JIRA statuses available on this instance as id/name pairs".to_string(),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {}
+                }),
+            },
+            Tool {
+                name: "list_boards".to_string(),
+                description: "List JIRA Agile boards with their id, name and type".to_string(),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_key": {
+                            "type": "string",
+                            "description": "Only list boards for this project key or ID"
+                        },
+                        "start_at": {
+                            "type": "integer",
+                            "description": "Index of the first board to return",
+                            "default": 0
+                        },
+                        "max_results": {
+                            "type": "integer",
+                            "description": "Maximum number of boards to return",
+                            "default": 50
+                        }
+                    }
+                }),
+            },
+            Tool {
+                name: "list_sprints".to_string(),
+                description: "List the sprints of a JIRA board with their id, name and state".to_string(),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "board_id": {
+                            "type": "integer",
+                            "description": "The board ID (from list_boards)"
+                        },
+                        "state": {
+                            "type": "string",
+                            "description": "Comma-separated sprint states to include: future, active, closed"
+                        },
+                        "start_at": {
+                            "type": "integer",
+                            "description": "Index of the first sprint to return",
+                            "default": 0
+                        },
+                        "max_results": {
+                            "type": "integer",
+                            "description": "Maximum number of sprints to return",
+                            "default": 50
+                        }
+                    },
+                    "required": ["board_id"]
+                }),
+            },
+            Tool {
+                name: "get_sprint_issues".to_string(),
+                description: "Get the issues in a JIRA sprint".to_string(),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "sprint_id": {
+                            "type": "integer",
+                            "description": "The sprint ID (from list_sprints)"
+                        },
+                        "start_at": {
+                            "type": "integer",
+                            "description": "Index of the first issue to return",
+                            "default": 0
+                        },
+                        "max_results": {
+                            "type": "integer",
+                            "description": "Maximum number of issues to return",
+                            "default": 50
+                        },
+                        "detailed": {
+                            "type": "boolean",
+                            "description": "Return the full JIRA response instead of compact issue summaries",
+                            "default": false
+                        }
+                    },
+                    "required": ["sprint_id"]
+                }),
+            },
+            Tool {
+                name: "download_jira_attachment".to_string(),
+                description: "Download a JIRA attachment into the server's downloads directory and return its path and size".to_string(),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "attachment": {
+                            "type": "string",
+                            "description": "Attachment ID or its content URL"
+                        },
+                        "filename": {
+                            "type": "string",
+                            "description": "Name to save the file as inside the downloads directory (defaults to the attachment's original name). An existing file is never overwritten; a numeric suffix is added instead."
+                        }
+                    },
+                    "required": ["attachment"]
+                }),
+            },
             Tool {
                 name: "get_confluence_page".to_string(),
                 description: "Get a Confluence page by ID".to_string(),
@@ -125,6 +294,28 @@ impl AtlassianMcpServer {
                     "required": ["page_id"]
                 }),
             },
+            Tool {
+                name: "add_confluence_comment".to_string(),
+                description: "Add a comment to a Confluence page".to_string(),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "page_id": {
+                            "type": "string",
+                            "description": "The Confluence page ID"
+                        },
+                        "body": {
+                            "type": "string",
+                            "description": "Comment body in Confluence storage format (e.g. <p>text</p>)"
+                        },
+                        "parent_comment_id": {
+                            "type": "string",
+                            "description": "ID of an existing comment to reply to"
+                        }
+                    },
+                    "required": ["page_id", "body"]
+                }),
+            },
             Tool {
                 name: "search_confluence".to_string(),
                 description: "Search for content in Confluence".to_string(),
@@ -224,9 +415,18 @@ impl AtlassianMcpServer {
         let result = match params.name.as_str() {
             "get_jira_ticket" => self.call_get_jira_ticket(args).await,
             "search_jira_tickets" => self.call_search_jira_tickets(args).await,
+            "validate_jql" => self.call_validate_jql(args).await,
             "create_jira_ticket" => self.call_create_jira_ticket(args).await,
             "add_comment_to_jira_ticket" => self.call_add_comment_to_jira_ticket(args).await,
+            "list_jira_priorities" => self.call_list_jira_priorities().await,
+            "list_jira_statuses" => self.call_list_jira_statuses().await,
+            "get_jira_changelog" => self.call_get_jira_changelog(args).await,
+            "list_boards" => self.call_list_boards(args).await,
+            "list_sprints" => self.call_list_sprints(args).await,
+            "get_sprint_issues" => self.call_get_sprint_issues(args).await,
+            "download_jira_attachment" => self.call_download_jira_attachment(args).await,
             "get_confluence_page" => self.call_get_confluence_page(args).await,
+            "add_confluence_comment" => self.call_add_confluence_comment(args).await,
             "search_confluence" => self.call_search_confluence(args).await,
             _ => {
                 return error_response(
@@ -267,17 +467,56 @@ impl AtlassianMcpServer {
         
         match self.client.get_jira_ticket(&ticket_key).await {
             Ok(ticket) => Ok(serde_json::to_string_pretty(&ticket).unwrap_or_else(|_| ticket.to_string())),
-            Err(e) => Err(format!("Error getting JIRA ticket: {}", e)),
+            Err(e) => Err(format!("Error getting JIRA ticket: {:#}", e)),
         }
     }
 
     async fn call_search_jira_tickets(&self, args: HashMap<String, Value>) -> Result<String, String> {
-        let jql: String = required_param(&args, "jql")?;
+        let jql: Option<String> = optional_param(&args, "jql")?;
         let max_results: Option<u32> = optional_param(&args, "max_results")?;
+        let detailed: bool = optional_param(&args, "detailed")?.unwrap_or(false);
+
+        let mut user_fields = Vec::new();
+        if optional_param(&args, "assigned_to_me")?.unwrap_or(false) {
+            user_fields.push("assignee");
+        }
+        if optional_param(&args, "reported_by_me")?.unwrap_or(false) {
+            user_fields.push("reporter");
+        }
+
+        let jql = match (jql, user_fields.is_empty()) {
+            (Some(jql), true) => jql,
+            (None, true) => return Err("Missing required parameter: jql (or set assigned_to_me/reported_by_me)".to_string()),
+            (jql, false) => {
+                // Cloud rejects usernames in JQL, so pin the clause to the cached accountId
+                let user_id = self
+                    .client
+                    .current_user_id()
+                    .await
+                    .map_err(|e| format!("Error resolving the current JIRA user: {:#}", e))?;
+                scope_jql_to_user(jql.as_deref().unwrap_or_default(), &user_fields, &user_id)
+            }
+        };
 
         match self.client.search_jira_tickets(&jql, max_results).await {
-            Ok(results) => Ok(serde_json::to_string_pretty(&results).unwrap_or_else(|_| results.to_string())),
-            Err(e) => Err(format!("Error searching JIRA tickets: {}", e)),
+            Ok(results) => {
+                let results = if detailed {
+                    results
+                } else {
+                    compact_jira_search_results(&results)
+                };
+                Ok(serde_json::to_string_pretty(&results).unwrap_or_else(|_| results.to_string()))
+            }
+            Err(e) => Err(format!("Error searching JIRA tickets: {:#}", e)),
+        }
+    }
+
+    async fn call_validate_jql(&self, args: HashMap<String, Value>) -> Result<String, String> {
+        let jql: String = required_param(&args, "jql")?;
+
+        match self.client.validate_jql(&jql).await {
+            Ok(result) => Ok(serde_json::to_string_pretty(&result).unwrap_or_else(|_| result.to_string())),
+            Err(e) => Err(format!("Error validating JQL: {:#}", e)),
         }
     }
 
@@ -304,7 +543,7 @@ impl AtlassianMcpServer {
                     .unwrap_or("Unknown");
                 Ok(format!("Created JIRA ticket: {}\n\n{}", ticket_key, serde_json::to_string_pretty(&ticket).unwrap_or_else(|_| ticket.to_string())))
             }
-            Err(e) => Err(format!("Error creating JIRA ticket: {}", e)),
+            Err(e) => Err(format!("Error creating JIRA ticket: {:#}", e)),
         }
     }
 
@@ -318,7 +557,84 @@ impl AtlassianMcpServer {
             .await
         {
             Ok(_) => Ok(format!("Added comment to {}", ticket_key)),
-            Err(e) => Err(format!("Error adding comment to JIRA ticket: {}", e)),
+            Err(e) => Err(format!("Error adding comment to JIRA ticket: {:#}", e)),
+        }
+    }
+
+    async fn call_list_jira_priorities(&self) -> Result<String, String> {
+        match self.client.list_jira_priorities().await {
+            Ok(priorities) => Ok(serde_json::to_string_pretty(&priorities).unwrap_or_else(|_| priorities.to_string())),
+            Err(e) => Err(format!("Error listing JIRA priorities: {:#}", e)),
+        }
+    }
+
+    async fn call_list_jira_statuses(&self) -> Result<String, String> {
+        match self.client.list_jira_statuses().await {
+            Ok(statuses) => Ok(serde_json::to_string_pretty(&statuses).unwrap_or_else(|_| statuses.to_string())),
+            Err(e) => Err(format!("Error listing JIRA statuses: {:#}", e)),
+        }
+    }
+
+    async fn call_get_jira_changelog(&self, args: HashMap<String, Value>) -> Result<String, String> {
+        let ticket_key: String = required_param(&args, "ticket_key")?;
+        let start_at: u32 = optional_param(&args, "start_at")?.unwrap_or(0);
+        let max_results: u32 = optional_param(&args, "max_results")?.unwrap_or(50);
+
+        match self.client.get_jira_changelog(&ticket_key, start_at, max_results).await {
+            Ok(changelog) => Ok(serde_json::to_string_pretty(&changelog).unwrap_or_else(|_| changelog.to_string())),
+            Err(e) => Err(format!("Error getting JIRA changelog: {:#}", e)),
+        }
+    }
+
+    async fn call_list_boards(&self, args: HashMap<String, Value>) -> Result<String, String> {
+        let project_key: Option<String> = optional_param(&args, "project_key")?;
+        let start_at: u32 = optional_param(&args, "start_at")?.unwrap_or(0);
+        let max_results: u32 = optional_param(&args, "max_results")?.unwrap_or(50);
+
+        match self.client.list_jira_boards(project_key.as_deref(), start_at, max_results).await {
+            Ok(boards) => Ok(serde_json::to_string_pretty(&boards).unwrap_or_else(|_| boards.to_string())),
+            Err(e) => Err(format!("Error listing JIRA boards: {:#}", e)),
+        }
+    }
+
+    async fn call_list_sprints(&self, args: HashMap<String, Value>) -> Result<String, String> {
+        let board_id: u64 = required_param(&args, "board_id")?;
+        let state: Option<String> = optional_param(&args, "state")?;
+        let start_at: u32 = optional_param(&args, "start_at")?.unwrap_or(0);
+        let max_results: u32 = optional_param(&args, "max_results")?.unwrap_or(50);
+
+        match self.client.list_jira_sprints(board_id, state.as_deref(), start_at, max_results).await {
+            Ok(sprints) => Ok(serde_json::to_string_pretty(&sprints).unwrap_or_else(|_| sprints.to_string())),
+            Err(e) => Err(format!("Error listing JIRA sprints: {:#}", e)),
+        }
+    }
+
+    async fn call_get_sprint_issues(&self, args: HashMap<String, Value>) -> Result<String, String> {
+        let sprint_id: u64 = required_param(&args, "sprint_id")?;
+        let start_at: u32 = optional_param(&args, "start_at")?.unwrap_or(0);
+        let max_results: u32 = optional_param(&args, "max_results")?.unwrap_or(50);
+        let detailed: bool = optional_param(&args, "detailed")?.unwrap_or(false);
+
+        match self.client.get_sprint_issues(sprint_id, start_at, max_results).await {
+            Ok(issues) => {
+                let issues = if detailed {
+                    issues
+                } else {
+                    compact_jira_search_results(&issues)
+                };
+                Ok(serde_json::to_string_pretty(&issues).unwrap_or_else(|_| issues.to_string()))
+            }
+            Err(e) => Err(format!("Error getting JIRA sprint issues: {:#}", e)),
+        }
+    }
+
+    async fn call_download_jira_attachment(&self, args: HashMap<String, Value>) -> Result<String, String> {
+        let attachment: String = required_param(&args, "attachment")?;
+        let filename: Option<String> = optional_param(&args, "filename")?;
+
+        match self.client.download_jira_attachment(&attachment, filename.as_deref()).await {
+            Ok(download) => Ok(serde_json::to_string_pretty(&download).unwrap_or_else(|_| download.to_string())),
+            Err(e) => Err(format!("Error downloading JIRA attachment: {:#}", e)),
         }
     }
 
@@ -327,7 +643,28 @@ impl AtlassianMcpServer {
 
         match self.client.get_confluence_page(&page_id).await {
             Ok(page) => Ok(serde_json::to_string_pretty(&page).unwrap_or_else(|_| page.to_string())),
-            Err(e) => Err(format!("Error getting Confluence page: {}", e)),
+            Err(e) => Err(format!("Error getting Confluence page: {:#}", e)),
+        }
+    }
+
+    async fn call_add_confluence_comment(&self, args: HashMap<String, Value>) -> Result<String, String> {
+        let page_id: String = required_param(&args, "page_id")?;
+        let body: String = required_param(&args, "body")?;
+        let parent_comment_id: Option<String> = optional_param(&args, "parent_comment_id")?;
+
+        match self
+            .client
+            .add_confluence_comment(&page_id, &body, parent_comment_id.as_deref())
+            .await
+        {
+            Ok(comment) => {
+                let comment_id = comment
+                    .get("id")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("Unknown");
+                Ok(format!("Added comment {} to Confluence page {}", comment_id, page_id))
+            }
+            Err(e) => Err(format!("Error adding comment to Confluence page: {:#}", e)),
         }
     }
 
@@ -337,21 +674,33 @@ impl AtlassianMcpServer {
 
         match self.client.search_confluence(&query, limit).await {
             Ok(results) => Ok(serde_json::to_string_pretty(&results).unwrap_or_else(|_| results.to_string())),
-            Err(e) => Err(format!("Error searching Confluence: {}", e)),
+            Err(e) => Err(format!("Error searching Confluence: {:#}", e)),
         }
     }
 
-    pub async fn run_stdio(&mut self) -> Result<()> {
-        let stdin = tokio::io::stdin();
-        let mut stdout = tokio::io::stdout();
-        let mut reader = BufReader::new(stdin);
-        let mut line = String::new();
-
+    pub async fn run_stdio(&self) -> Result<()> {
         eprintln!(
             "Atlassian MCP server running on stdio (connected to {})",
             self.config.atlassian.base_url
         );
-        eprintln!("Available tools: get_jira_ticket, search_jira_tickets, create_jira_ticket, add_comment_to_jira_ticket, get_confluence_page, search_confluence");
+        let tool_names: Vec<String> = self.get_tools().into_iter().map(|tool| tool.name).collect();
+        eprintln!("Available tools: {}", tool_names.join(", "));
+
+        self.serve(BufReader::new(tokio::io::stdin()), tokio::io::stdout()).await
+    }
+
+    /// Spawns a task per JSON-RPC line, so a slow JQL search doesn't delay a
+    /// `get_confluence_page` queued behind it. Unparseable lines are answered
+    /// with -32700 straight away; a read error on the input ends the session
+    /// once the handlers already running have replied.
+    async fn serve<R, W>(&self, mut reader: R, writer: W) -> Result<()>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin + Send + 'static,
+    {
+        let writer = Arc::new(Mutex::new(writer));
+        let mut in_flight = JoinSet::new();
+        let mut line = String::new();
 
         loop {
             line.clear();
@@ -373,30 +722,58 @@ impl AtlassianMcpServer {
                                 "Parse error",
                                 Some(serde_json::json!({"error": e.to_string()})),
                             );
-                            let response_json = serde_json::to_string(&error_resp)?;
-                            stdout.write_all(response_json.as_bytes()).await?;
-                            stdout.write_all(b"\n").await?;
-                            stdout.flush().await?;
+                            write_response(&writer, &error_resp).await?;
                             continue;
                         }
                     };
 
-                    let response = self.handle_request(request).await;
-                    let response_json = serde_json::to_string(&response)?;
-                    stdout.write_all(response_json.as_bytes()).await?;
-                    stdout.write_all(b"\n").await?;
-                    stdout.flush().await?;
+                    let server = self.clone();
+                    let writer = writer.clone();
+                    in_flight.spawn(async move {
+                        let response = server.handle_request(request).await;
+                        write_response(&writer, &response).await
+                    });
                 }
                 Err(e) => {
                     error!("Error reading from stdin: {}", e);
                     break;
                 }
             }
+
+            // Reap finished requests so the set does not grow with the session
+            while let Some(done) = in_flight.try_join_next() {
+                report_task_result(done);
+            }
+        }
+
+        while let Some(done) = in_flight.join_next().await {
+            report_task_result(done);
         }
         Ok(())
     }
 }
 
+/// Appends one newline-terminated response. Handlers finishing at the same
+/// moment queue on the mutex instead of splicing their JSON together.
+async fn write_response<W: AsyncWrite + Unpin>(writer: &Mutex<W>, response: &JsonRpcResponse) -> Result<()> {
+    let response_json = serde_json::to_string(response)?;
+    let mut writer = writer.lock().await;
+    writer.write_all(response_json.as_bytes()).await?;
+    writer.write_all(b"\n").await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Nothing awaits a handler task's outcome except the serve loop, so write
+/// failures and panics are reported here rather than dropped.
+fn report_task_result(result: std::result::Result<Result<()>, tokio::task::JoinError>) {
+    match result {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => error!("Failed to write response: {}", e),
+        Err(e) => error!("Request handler panicked: {}", e),
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     tracing_subscriber::fmt::init();
@@ -414,12 +791,90 @@ async fn main() -> Result<()> {
             eprintln!("  ATLASSIAN_BASE_URL=https://your-instance.atlassian.net");
             eprintln!("  ATLASSIAN_EMAIL=your-email@example.com");
             eprintln!("  ATLASSIAN_TOKEN=your-api-token");
+            eprintln!("or provide them in ~/.atlassian-mcp/config.toml");
             std::process::exit(1);
         }
     };
 
-    let mut atlassian_server = AtlassianMcpServer::new(config);
+    let atlassian_server = AtlassianMcpServer::new(config)?;
     atlassian_server.run_stdio().await?;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use config::{AtlassianConfig, Deployment, ServerConfig};
+    use std::time::{Duration, Instant};
+    use tokio::io::AsyncReadExt;
+
+    #[tokio::test]
+    async fn test_concurrent_requests_overlap() {
+        use wiremock::matchers::{method, path_regex};
+        use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+        const REQUESTS: u64 = 5;
+        const DELAY: Duration = Duration::from_millis(200);
+
+        let jira = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/rest/api/3/issue/PROJ-\d+$"))
+            .respond_with(|request: &Request| {
+                let key = request.url.path().rsplit('/').next().unwrap_or_default();
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"key": key}))
+                    .set_delay(DELAY)
+            })
+            .expect(REQUESTS)
+            .mount(&jira)
+            .await;
+
+        let server = AtlassianMcpServer::new(Config {
+            atlassian: AtlassianConfig {
+                base_url: jira.uri(),
+                email: "mia@example.com".to_string(),
+                token: "token".to_string(),
+                deployment: Some(Deployment::Cloud),
+                timeout_secs: None,
+                proxy: None,
+                downloads_dir: None,
+            },
+            server: ServerConfig {
+                name: "atlassian-server".to_string(),
+                version: "0.1.0".to_string(),
+            },
+        })
+        .unwrap();
+
+        let input = (1..=REQUESTS)
+            .map(|id| {
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "method": "tools/call",
+                    "params": {"name": "get_jira_ticket", "arguments": {"ticket_key": format!("PROJ-{}", id)}}
+                })
+                .to_string()
+                    + "\n"
+            })
+            .collect::<String>();
+        let (writer, mut output) = tokio::io::duplex(64 * 1024);
+
+        let started = Instant::now();
+        server.serve(input.as_bytes(), writer).await.unwrap();
+        let elapsed = started.elapsed();
+        assert!(elapsed < DELAY * 3, "{} requests took {:?}; they ran one after another", REQUESTS, elapsed);
+
+        let mut text = String::new();
+        output.read_to_string(&mut text).await.unwrap();
+        let mut responses: Vec<Value> = text.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        responses.sort_by_key(|r| r["id"].as_u64());
+        assert_eq!(responses.len(), REQUESTS as usize);
+        for (response, id) in responses.iter().zip(1..=REQUESTS) {
+            let key = format!("PROJ-{}", id);
+            let text = response["result"]["content"][0]["text"].as_str().unwrap();
+            assert!(text.contains(&key), "{} missing from {}", key, text);
+        }
+    }
+}
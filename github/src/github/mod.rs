@@ -1,5 +1,7 @@
+pub mod app_auth;
 pub mod simple_client;
 pub mod simple_types;
 
+pub use app_auth::GitHubAppCredentials;
 pub use simple_client::*;
 pub use simple_types::*;
\ No newline at end of file
@@ -1,23 +1,312 @@
 use anyhow::{anyhow, Result};
+use futures::StreamExt;
 use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT, AUTHORIZATION};
-use serde_json::Value;
+use serde_json::{Map, Value};
+use std::collections::hash_map::RandomState;
+use std::collections::BTreeMap;
+use std::hash::{BuildHasher, Hasher};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tracing::{debug, error, info};
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
 use url::Url;
 
+use super::app_auth::{GitHubAppCredentials, InstallationTokenSource};
 use super::simple_types::*;
 
+const TOPICS_MEDIA_TYPE: &str = "application/vnd.github.mercy-preview+json";
+const REACTIONS_MEDIA_TYPE: &str = "application/vnd.github.squirrel-girl-preview+json";
+const DIFF_MEDIA_TYPE: &str = "application/vnd.github.diff";
+
+/// How a GraphQL response carrying both `data` and `errors` is treated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphQLErrorPolicy {
+    /// Any entry in `errors` fails the request.
+    Strict,
+    /// Usable `data` is returned and the errors are only logged.
+    AllowPartial,
+}
+
 #[derive(Debug, Clone)]
 pub struct GitHubConfig {
+    /// Personal access token; ignored when `app_auth` is set.
     pub token: String,
     pub host: Option<String>,
     pub user_agent: String,
+    pub backoff: BackoffPolicy,
+    /// Retries after a rate limit or 5xx before the response is returned.
+    pub max_retries: u32,
+    /// Sleep until a rate limit resets and retry; when false, rate-limited
+    /// responses fail straight away (5xx retries are unaffected).
+    pub respect_rate_limit: bool,
+    /// Longest rate-limit reset worth sleeping for. A limit that resets later
+    /// fails at once with [`RateLimitExceeded`]; keep this below the tool timeout.
+    pub max_rate_limit_wait: Duration,
+    /// Authenticate as a GitHub App installation instead of with `token`.
+    pub app_auth: Option<GitHubAppCredentials>,
+    /// Directory release assets may be uploaded from; uploads are refused when unset.
+    pub uploads_dir: Option<PathBuf>,
+}
+
+/// Retries allowed by default after a rate limit or 5xx.
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// Rate limits resetting further away than this fail instead of being waited out.
+pub const DEFAULT_MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+/// Delays between retries when GitHub doesn't say how long to wait. The n-th
+/// retry waits `base_delay * multiplier^n`, capped at `max_delay` and spread by
+/// up to `jitter` (a fraction) either way.
+#[derive(Debug, Clone)]
+pub struct BackoffPolicy {
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub multiplier: f64,
+    pub jitter: f64,
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: 0.2,
+        }
+    }
+}
+
+impl BackoffPolicy {
+    /// Delay before retry number `attempt` (starting at 0). `sample` in `[0, 1]`
+    /// picks a point within the jitter range; 0.5 is the un-jittered delay.
+    pub fn delay(&self, attempt: u32, sample: f64) -> Duration {
+        let max = self.max_delay.as_secs_f64();
+        let nominal = (self.base_delay.as_secs_f64() * self.multiplier.powi(attempt as i32)).min(max);
+        let spread = nominal * self.jitter.clamp(0.0, 1.0);
+        let jittered = nominal - spread + 2.0 * spread * sample.clamp(0.0, 1.0);
+
+        Duration::from_secs_f64(jittered.clamp(0.0, max))
+    }
+
+    fn next_delay(&self, attempt: u32) -> Duration {
+        // A freshly keyed hasher is a cheap source of randomness for jitter
+        let random = RandomState::new().build_hasher().finish();
+        self.delay(attempt, (random >> 11) as f64 / (1u64 << 53) as f64)
+    }
+}
+
+/// A rate-limited response (429, or 403 with an exhausted quota or a
+/// `retry-after`) and how long until it lifts, when GitHub said.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub reset_in: Option<Duration>,
+}
+
+/// Classifies a response as rate limited, reading `retry-after` or
+/// `x-ratelimit-reset` (seconds since the epoch, given as `now`).
+pub fn rate_limit(status: reqwest::StatusCode, headers: &HeaderMap, now: u64) -> Option<RateLimit> {
+    let header = |name: &str| {
+        headers.get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok())
+    };
+    let retry_after = header("retry-after").map(Duration::from_secs);
+    let quota_exhausted = header("x-ratelimit-remaining") == Some(0);
+    let rate_limited = status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        || (status == reqwest::StatusCode::FORBIDDEN && (quota_exhausted || retry_after.is_some()));
+    if !rate_limited {
+        return None;
+    }
+
+    let reset_wait = header("x-ratelimit-reset")
+        .filter(|_| quota_exhausted)
+        .map(|reset| Duration::from_secs(reset.saturating_sub(now)));
+    Some(RateLimit { reset_in: retry_after.or(reset_wait) })
+}
+
+/// Returned once a rate-limited request is out of retries, saying how long
+/// until the limit resets so the caller can explain the delay.
+#[derive(Debug)]
+pub struct RateLimitExceeded {
+    pub status: reqwest::StatusCode,
+    /// Times the request was sent, counting the first try.
+    pub attempts: u32,
+    pub reset_in: Option<Duration>,
+}
+
+impl std::fmt::Display for RateLimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let plural = if self.attempts == 1 { "" } else { "s" };
+        write!(f, "GitHub rate limit exceeded ({}) after {} attempt{}", self.status, self.attempts, plural)?;
+        match self.reset_in {
+            Some(wait) => write!(f, "; the limit resets in {}", format_wait(wait)),
+            None => write!(f, "; try again later"),
+        }
+    }
+}
+
+impl std::error::Error for RateLimitExceeded {}
+
+/// Renders a wait as e.g. `42s`, `3m 5s` or `1h 2m`.
+pub fn format_wait(wait: Duration) -> String {
+    let secs = wait.as_secs();
+    match (secs / 3600, secs % 3600 / 60, secs % 60) {
+        (0, 0, s) => format!("{}s", s),
+        (0, m, s) => format!("{}m {}s", m, s),
+        (h, m, _) => format!("{}h {}m", h, m),
+    }
+}
+
+/// Decides whether a response is retried and after how long, given the
+/// `max_retries`, `respect_rate_limit` and backoff from `config`. Rate limits
+/// wait out the reset time from [`rate_limit`], even past the backoff's
+/// `max_delay`, unless `respect_rate_limit` is off or the reset is further away
+/// than `max_rate_limit_wait`. A 5xx is only retried for idempotent requests,
+/// so a POST is never sent twice.
+pub fn retry_delay(
+    status: reqwest::StatusCode,
+    headers: &HeaderMap,
+    idempotent: bool,
+    config: &GitHubConfig,
+    attempt: u32,
+    now: u64,
+) -> Option<Duration> {
+    if attempt >= config.max_retries {
+        return None;
+    }
+
+    if let Some(limit) = rate_limit(status, headers, now) {
+        if !config.respect_rate_limit {
+            return None;
+        }
+        let wait = limit.reset_in.unwrap_or_else(|| config.backoff.next_delay(attempt));
+        return (wait <= config.max_rate_limit_wait).then_some(wait);
+    }
+
+    if status.is_server_error() && idempotent {
+        return Some(config.backoff.next_delay(attempt));
+    }
+
+    None
+}
+
+/// Sends a request with the client's credentials, retrying it as the
+/// client's [`GitHubConfig`] allows. A rate limit that outlasts the retries is returned
+/// as a [`RateLimitExceeded`] error rather than a response.
+trait SendWith {
+    async fn send_with(self, github: &GitHubClient) -> Result<reqwest::Response>;
+}
+
+impl SendWith for reqwest::RequestBuilder {
+    async fn send_with(self, github: &GitHubClient) -> Result<reqwest::Response> {
+        let config = &github.config;
+        let builder = match &github.app_tokens {
+            Some(tokens) => self.bearer_auth(tokens.token(&github.client).await?),
+            None => self,
+        };
+        let (client, request) = builder.build_split();
+        let mut request = request?;
+        let idempotent = matches!(
+            *request.method(),
+            reqwest::Method::GET | reqwest::Method::HEAD | reqwest::Method::PUT | reqwest::Method::DELETE
+        );
+        let mut attempt = 0;
+
+        loop {
+            // Streaming bodies cannot be cloned, so such requests are sent once
+            let retry = request.try_clone();
+            let url = request.url().clone();
+            let response = client.execute(request).await?;
+
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default();
+            let delay = retry_delay(response.status(), response.headers(), idempotent, config, attempt, now);
+
+            match (retry, delay) {
+                (Some(next), Some(delay)) => {
+                    warn!(
+                        "{} returned {}; retrying in {:?} (retry {}/{})",
+                        url, response.status(), delay, attempt + 1, config.max_retries
+                    );
+                    tokio::time::sleep(delay).await;
+                    request = next;
+                    attempt += 1;
+                }
+                _ => {
+                    if let Some(limit) = rate_limit(response.status(), response.headers(), now) {
+                        warn!("{} is rate limited; giving up after {} attempt(s)", url, attempt + 1);
+                        return Err(RateLimitExceeded {
+                            status: response.status(),
+                            attempts: attempt + 1,
+                            reset_in: limit.reset_in,
+                        }.into());
+                    }
+                    return Ok(response);
+                }
+            }
+        }
+    }
+}
+
+/// Asset upload endpoint under the host's upload base (`uploads.github.com`,
+/// `uploads.<tenant>.ghe.com`, or `/api/uploads` on GHES).
+pub fn release_asset_upload_url(upload: &Url, owner: &str, repo: &str, release_id: u64, name: &str) -> Result<Url> {
+    let mut url = upload.join(&format!(
+        "repos/{}/{}/releases/{}/assets",
+        urlencoding::encode(owner), urlencoding::encode(repo), release_id
+    ))?;
+    url.query_pairs_mut().append_pair("name", name);
+    Ok(url)
+}
+
+/// Resolves a file to upload inside `uploads_dir`, refusing anything that
+/// lands outside it (absolute paths, `..`, symlinks pointing elsewhere).
+pub fn resolve_upload_path(uploads_dir: Option<&Path>, file_path: &Path) -> Result<PathBuf> {
+    let dir = uploads_dir.ok_or_else(|| {
+        anyhow!("Uploads are disabled; start the server with --uploads-dir (or GITHUB_UPLOADS_DIR) to allow them")
+    })?;
+    let root = dir.canonicalize()
+        .map_err(|e| anyhow!("Uploads directory {} is not usable: {}", dir.display(), e))?;
+    let path = root.join(file_path).canonicalize()
+        .map_err(|e| anyhow!("Failed to read {}: {}", file_path.display(), e))?;
+
+    if !path.starts_with(&root) {
+        return Err(anyhow!("{} is outside the uploads directory", file_path.display()));
+    }
+    Ok(path)
+}
+
+/// Content type for an asset or file, guessed from its file extension.
+pub fn asset_content_type(name: &str) -> &'static str {
+    let extension = name.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase());
+    match extension.as_deref() {
+        Some("zip") => "application/zip",
+        Some("gz") | Some("tgz") => "application/gzip",
+        Some("tar") => "application/x-tar",
+        Some("xz") => "application/x-xz",
+        Some("bz2") => "application/x-bzip2",
+        Some("7z") => "application/x-7z-compressed",
+        Some("json") => "application/json",
+        Some("pdf") => "application/pdf",
+        Some("txt") | Some("md") | Some("sha256") | Some("asc") => "text/plain",
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("ico") => "image/x-icon",
+        Some("deb") => "application/vnd.debian.binary-package",
+        Some("rpm") => "application/x-rpm",
+        _ => "application/octet-stream",
+    }
 }
 
 pub struct GitHubClient {
     client: reqwest::Client,
     config: GitHubConfig,
     api_urls: ApiUrls,
+    app_tokens: Option<InstallationTokenSource>,
 }
 
 #[derive(Debug, Clone)]
@@ -31,11 +320,18 @@ impl GitHubClient {
     pub async fn new(config: GitHubConfig) -> Result<Self> {
         let api_urls = Self::parse_api_host(&config.host)?;
         
+        // App installations get a short-lived token per request instead of a fixed header
+        let app_tokens = config.app_auth.clone()
+            .map(|credentials| InstallationTokenSource::new(credentials, &api_urls.rest_base))
+            .transpose()?;
+
         let mut headers = HeaderMap::new();
-        headers.insert(
-            AUTHORIZATION,
-            HeaderValue::from_str(&format!("Bearer {}", config.token))?,
-        );
+        if app_tokens.is_none() {
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {}", config.token))?,
+            );
+        }
         headers.insert(
             USER_AGENT,
             HeaderValue::from_str(&config.user_agent)?,
@@ -45,12 +341,17 @@ impl GitHubClient {
             .default_headers(headers)
             .build()?;
 
-        info!("GitHub client initialized for host: {:?}", config.host);
+        info!(
+            "GitHub client initialized for host: {:?} ({})",
+            config.host,
+            if app_tokens.is_some() { "GitHub App installation" } else { "personal access token" }
+        );
 
         Ok(Self {
             client,
             config,
             api_urls,
+            app_tokens,
         })
     }
 
@@ -96,6 +397,10 @@ impl GitHubClient {
         let scheme = url.scheme();
         let hostname = url.host_str()
             .ok_or_else(|| anyhow!("Invalid hostname in GHES URL"))?;
+        let hostname = match url.port() {
+            Some(port) => format!("{}:{}", hostname, port),
+            None => hostname.to_string(),
+        };
 
         Ok(ApiUrls {
             rest_base: Url::parse(&format!("{}://{}/api/v3/", scheme, hostname))?,
@@ -105,6 +410,27 @@ impl GitHubClient {
     }
 
     pub async fn graphql_query(&self, query: &str, variables: Option<Value>) -> Result<Value> {
+        self.graphql_request(query, variables, GraphQLErrorPolicy::Strict).await
+    }
+
+    /// Runs a mutation and returns its single top-level payload
+    /// (e.g. the object under `addSubIssue`).
+    pub async fn graphql_mutate(
+        &self,
+        mutation: &str,
+        variables: Value,
+        policy: GraphQLErrorPolicy,
+    ) -> Result<Value> {
+        let data = self.graphql_request(mutation, Some(variables), policy).await?;
+        mutation_payload(&data)
+    }
+
+    pub async fn graphql_request(
+        &self,
+        query: &str,
+        variables: Option<Value>,
+        policy: GraphQLErrorPolicy,
+    ) -> Result<Value> {
         let request_body = serde_json::json!({
             "query": query,
             "variables": variables.unwrap_or(Value::Null)
@@ -115,7 +441,7 @@ impl GitHubClient {
         let response = self.client
             .post(self.api_urls.graphql.clone())
             .json(&request_body)
-            .send()
+            .send_with(self)
             .await?;
 
         if !response.status().is_success() {
@@ -125,21 +451,13 @@ impl GitHubClient {
         }
 
         let response_data: Value = response.json().await?;
-        
-        if let Some(errors) = response_data.get("errors") {
-            error!("GraphQL errors: {}", errors);
-            return Err(anyhow!("GraphQL errors: {}", errors));
-        }
-
-        response_data.get("data")
-            .cloned()
-            .ok_or_else(|| anyhow!("No data in GraphQL response"))
+        graphql_response_data(response_data, policy)
     }
 
     // Repository operations
     pub async fn get_repository(&self, owner: &str, repo: &str) -> Result<Repository> {
         let url = format!("{}repos/{}/{}", self.api_urls.rest_base, owner, repo);
-        let response = self.client.get(&url).send().await?;
+        let response = self.client.get(&url).send_with(self).await?;
         
         if !response.status().is_success() {
             return Err(anyhow!("Failed to get repository: {}", response.status()));
@@ -149,14 +467,202 @@ impl GitHubClient {
         self.parse_repository(repo_data)
     }
 
+    /// Returns the languages used in the repository, mapped to bytes of code.
+    pub async fn get_repo_languages(&self, owner: &str, repo: &str) -> Result<BTreeMap<String, u64>> {
+        let url = format!("{}repos/{}/{}/languages", self.api_urls.rest_base, owner, repo);
+        let response = self.client.get(&url).send_with(self).await?;
+        
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to get repository languages: {}", response.status()));
+        }
+        
+        let languages_data: Value = response.json().await?;
+        parse_repo_languages(languages_data)
+    }
+
+    /// Returns per-contributor commit activity, waiting while GitHub computes it.
+    pub async fn get_repo_contributors_stats(&self, owner: &str, repo: &str) -> Result<Vec<ContributorStats>> {
+        let url = format!("{}repos/{}/{}/stats/contributors", self.api_urls.rest_base, owner, repo);
+
+        poll_computed_stats(
+            || async {
+                let response = self.client.get(&url).send_with(self).await?;
+                
+                if response.status() == reqwest::StatusCode::ACCEPTED {
+                    return Ok(None);
+                }
+                if !response.status().is_success() {
+                    return Err(anyhow!("Failed to get contributor statistics: {}", response.status()));
+                }
+                
+                let stats_data: Value = response.json().await?;
+                let stats: Vec<ContributorStats> = serde_json::from_value(stats_data)
+                    .map_err(|e| anyhow!("Failed to parse contributor statistics: {}", e))?;
+                Ok(Some(stats))
+            },
+            STATS_MAX_ATTEMPTS,
+            STATS_RETRY_DELAY,
+        )
+        .await
+    }
+
+    /// Returns the default branch together with the SHA it currently points at.
+    pub async fn get_repo_default_branch(&self, owner: &str, repo: &str) -> Result<DefaultBranch> {
+        let repository = self.get_repository(owner, repo).await?;
+        self.get_branch_head(owner, repo, &repository.default_branch).await
+    }
+
+    /// Returns `branch` together with the SHA it currently points at.
+    async fn get_branch_head(&self, owner: &str, repo: &str, branch: &str) -> Result<DefaultBranch> {
+        let url = format!(
+            "{}repos/{}/{}/git/ref/heads/{}",
+            self.api_urls.rest_base, owner, repo, urlencoding::encode(branch)
+        );
+        let response = self.client.get(&url).send_with(self).await?;
+        
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to get default branch ref: {}", response.status()));
+        }
+        
+        let ref_data: Value = response.json().await?;
+        parse_default_branch(branch, ref_data)
+    }
+
+    /// Returns the most recent published release, or `None` if there is none yet.
+    pub async fn get_latest_release(&self, owner: &str, repo: &str) -> Result<Option<Release>> {
+        let url = format!("{}repos/{}/{}/releases/latest", self.api_urls.rest_base, owner, repo);
+        let response = self.client.get(&url).send_with(self).await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to get latest release: {}", response.status()));
+        }
+
+        let release_data: Value = response.json().await?;
+        serde_json::from_value(release_data)
+            .map(Some)
+            .map_err(|e| anyhow!("Failed to parse release: {}", e))
+    }
+
+    /// Uploads a local file as an asset of release `release_id`. The asset is
+    /// named after the file unless `name` is given, and its content type is
+    /// guessed from the extension unless `content_type` is given.
+    pub async fn upload_release_asset(
+        &self,
+        owner: &str,
+        repo: &str,
+        release_id: u64,
+        file_path: &Path,
+        name: Option<&str>,
+        content_type: Option<&str>,
+    ) -> Result<ReleaseAsset> {
+        let file_path = resolve_upload_path(self.config.uploads_dir.as_deref(), file_path)?;
+        let name = match name {
+            Some(name) => name.to_string(),
+            None => file_path.file_name()
+                .and_then(|n| n.to_str())
+                .map(str::to_string)
+                .ok_or_else(|| anyhow!("Cannot derive an asset name from {}", file_path.display()))?,
+        };
+        let content_type = content_type
+            .map(str::to_string)
+            .unwrap_or_else(|| asset_content_type(&name).to_string());
+        let data = tokio::fs::read(&file_path).await
+            .map_err(|e| anyhow!("Failed to read {}: {}", file_path.display(), e))?;
+
+        let url = release_asset_upload_url(&self.api_urls.upload, owner, repo, release_id, &name)?;
+        debug!("Uploading {} ({} bytes) to {}", name, data.len(), url);
+        let response = self.client.post(url)
+            .header(reqwest::header::CONTENT_TYPE, content_type)
+            .header(reqwest::header::CONTENT_LENGTH, data.len())
+            .body(data)
+            .send_with(self)
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Failed to upload release asset: {} - {}", status, text));
+        }
+
+        let asset_data: Value = response.json().await?;
+        serde_json::from_value(asset_data)
+            .map_err(|e| anyhow!("Failed to parse release asset: {}", e))
+    }
+
+    /// Counts open issues (`kind` = `issue`) or pull requests (`kind` = `pr`) via
+    /// the search API, since `open_issues_count` lumps the two together.
+    pub async fn count_open_items(&self, owner: &str, repo: &str, kind: &str) -> Result<u64> {
+        let query = format!("repo:{}/{} is:{} is:open", owner, repo, kind);
+        let url = format!("{}search/issues?q={}&per_page=1", self.api_urls.rest_base, urlencoding::encode(&query));
+        let response = self.client.get(&url).send_with(self).await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to count open {}s: {}", kind, response.status()));
+        }
+
+        let search_data: Value = response.json().await?;
+        search_data.get("total_count")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| anyhow!("Search response has no total_count"))
+    }
+
+    /// Fetches metadata, open issue/PR counts, default branch, latest release and
+    /// top languages concurrently. Pieces that fail are left out and reported in
+    /// `errors`; only a failure of every piece is an error.
+    pub async fn get_repository_overview(&self, owner: &str, repo: &str) -> Result<RepositoryOverview> {
+        // The default branch name comes from the repository itself, so only its head is fetched
+        let repository_and_branch = async {
+            let repository = self.get_repository(owner, repo).await;
+            let default_branch = match &repository {
+                Ok(repository) => self.get_branch_head(owner, repo, &repository.default_branch).await,
+                Err(e) => Err(anyhow!("Repository lookup failed: {}", e)),
+            };
+            (repository, default_branch)
+        };
+        let ((repository, default_branch), open_issues, open_pull_requests, latest_release, languages) = tokio::join!(
+            repository_and_branch,
+            self.count_open_items(owner, repo, "issue"),
+            self.count_open_items(owner, repo, "pr"),
+            self.get_latest_release(owner, repo),
+            self.get_repo_languages(owner, repo),
+        );
+
+        let mut errors = BTreeMap::new();
+        let overview = RepositoryOverview {
+            repository: overview_piece(&mut errors, "repository", repository),
+            open_issues: overview_piece(&mut errors, "open_issues", open_issues),
+            open_pull_requests: overview_piece(&mut errors, "open_pull_requests", open_pull_requests),
+            default_branch: overview_piece(&mut errors, "default_branch", default_branch),
+            latest_release: overview_piece(&mut errors, "latest_release", latest_release).flatten(),
+            top_languages: overview_piece(&mut errors, "top_languages", languages)
+                .map(|languages| top_languages(&languages, OVERVIEW_TOP_LANGUAGES)),
+            errors,
+        };
+
+        if overview.errors.len() == OVERVIEW_PIECES {
+            let failures: Vec<String> = overview.errors.iter()
+                .map(|(field, error)| format!("{}: {}", field, error))
+                .collect();
+            return Err(anyhow!("Failed to get repository overview for {}/{}: {}", owner, repo, failures.join("; ")));
+        }
+        if !overview.errors.is_empty() {
+            warn!("Repository overview for {}/{} is partial: {:?}", owner, repo, overview.errors);
+        }
+
+        Ok(overview)
+    }
+
     pub async fn get_file_contents(&self, owner: &str, repo: &str, path: &str, reference: Option<&str>) -> Result<FileContent> {
-        let mut url = format!("{}repos/{}/{}/contents/{}", self.api_urls.rest_base, owner, repo, path);
+        let mut url = format!("{}repos/{}/{}/contents/{}", self.api_urls.rest_base, owner, repo, encode_path_segments(path));
         
         if let Some(ref_name) = reference {
-            url = format!("{}?ref={}", url, ref_name);
+            url = format!("{}?ref={}", url, urlencoding::encode(ref_name));
         }
         
-        let response = self.client.get(&url).send().await?;
+        let response = self.client.get(&url).send_with(self).await?;
         
         if !response.status().is_success() {
             return Err(anyhow!("Failed to get file contents: {}", response.status()));
@@ -166,6 +672,44 @@ impl GitHubClient {
         self.parse_file_content(content_data)
     }
 
+    /// Blames `path` at `reference` (defaults to `HEAD`) through the GraphQL `blame` field.
+    pub async fn get_file_blame(&self, owner: &str, repo: &str, path: &str, reference: Option<&str>) -> Result<FileBlame> {
+        let query = r#"
+            query($owner: String!, $repo: String!, $ref: String!, $path: String!) {
+                repository(owner: $owner, name: $repo) {
+                    object(expression: $ref) {
+                        ... on Commit {
+                            blame(path: $path) {
+                                ranges {
+                                    startingLine
+                                    endingLine
+                                    age
+                                    commit {
+                                        oid
+                                        messageHeadline
+                                        committedDate
+                                        author { name user { login } }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let git_ref = reference.unwrap_or("HEAD");
+        let variables = serde_json::json!({
+            "owner": owner,
+            "repo": repo,
+            "ref": git_ref,
+            "path": path
+        });
+
+        let data = self.graphql_query(query, Some(variables)).await?;
+        parse_blame_response(&data, path, git_ref)
+    }
+
     pub async fn create_or_update_file(
         &self,
         owner: &str,
@@ -191,7 +735,7 @@ impl GitHubClient {
             body["branch"] = Value::String(branch.to_string());
         }
         
-        let response = self.client.put(&url).json(&body).send().await?;
+        let response = self.client.put(&url).json(&body).send_with(self).await?;
         
         if !response.status().is_success() {
             return Err(anyhow!("Failed to create/update file: {}", response.status()));
@@ -201,6 +745,235 @@ impl GitHubClient {
         self.parse_file_commit(commit_data)
     }
 
+    /// Deletes the file at `path`, whose current blob `sha` must be given so a
+    /// concurrent change is not silently discarded.
+    pub async fn delete_file(
+        &self,
+        owner: &str,
+        repo: &str,
+        path: &str,
+        message: &str,
+        sha: &str,
+        branch: Option<&str>,
+    ) -> Result<FileDeletion> {
+        let url = format!("{}repos/{}/{}/contents/{}", self.api_urls.rest_base, owner, repo, encode_path_segments(path));
+
+        let mut body = serde_json::json!({
+            "message": message,
+            "sha": sha
+        });
+
+        if let Some(branch) = branch {
+            body["branch"] = Value::String(branch.to_string());
+        }
+
+        let response = self.client.delete(&url).json(&body).send_with(self).await?;
+
+        match response.status() {
+            reqwest::StatusCode::NOT_FOUND => {
+                return Err(anyhow!("File {} not found in {}/{}", path, owner, repo));
+            }
+            reqwest::StatusCode::CONFLICT => {
+                return Err(anyhow!("File {} has changed since blob {}; fetch its current sha and retry", path, sha));
+            }
+            status if !status.is_success() => {
+                let text = response.text().await.unwrap_or_default();
+                return Err(anyhow!("Failed to delete file: {} {}", status, text));
+            }
+            _ => {}
+        }
+
+        let deletion_data: Value = response.json().await?;
+        let commit = deletion_data.get("commit")
+            .cloned()
+            .ok_or_else(|| anyhow!("Delete file response has no commit"))?;
+        Ok(FileDeletion {
+            path: path.to_string(),
+            commit: serde_json::from_value(commit).map_err(|e| anyhow!("Failed to parse file commit: {}", e))?,
+        })
+    }
+
+    /// Commits several files to `branch` in a single commit using the git data
+    /// API: one blob per file, a tree on top of the branch head's tree, a commit
+    /// with the head as parent, then a non-forced ref update. Files are only
+    /// visible on the branch once the final ref update succeeds.
+    pub async fn create_commit_with_files(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+        message: &str,
+        files: &[FileChange],
+    ) -> Result<MultiFileCommit> {
+        if files.is_empty() {
+            return Err(anyhow!("At least one file is required"));
+        }
+
+        let git_base = format!("{}repos/{}/{}/git", self.api_urls.rest_base, owner, repo);
+        let branch_path = encode_path_segments(branch);
+        let ref_url = format!("{}/refs/heads/{}", git_base, branch_path);
+
+        let head = self.git_data_request(
+            self.client.get(format!("{}/ref/heads/{}", git_base, branch_path)),
+            "get branch ref",
+        ).await?;
+        let parent_sha = string_field(&head, "/object/sha", "branch ref")?;
+
+        let parent = self.git_data_request(
+            self.client.get(format!("{}/commits/{}", git_base, parent_sha)),
+            "get head commit",
+        ).await?;
+        let base_tree = string_field(&parent, "/tree/sha", "head commit")?;
+
+        let mut entries = Vec::with_capacity(files.len());
+        for file in files {
+            let blob = self.git_data_request(
+                self.client.post(format!("{}/blobs", git_base)).json(&serde_json::json!({
+                    "content": file.content,
+                    "encoding": "utf-8"
+                })),
+                "create blob",
+            ).await?;
+            entries.push(serde_json::json!({
+                "path": file.path,
+                "mode": "100644",
+                "type": "blob",
+                "sha": string_field(&blob, "/sha", "blob")?
+            }));
+        }
+
+        let tree = self.git_data_request(
+            self.client.post(format!("{}/trees", git_base)).json(&serde_json::json!({
+                "base_tree": base_tree,
+                "tree": entries
+            })),
+            "create tree",
+        ).await?;
+        let tree_sha = string_field(&tree, "/sha", "tree")?;
+
+        let commit = self.git_data_request(
+            self.client.post(format!("{}/commits", git_base)).json(&serde_json::json!({
+                "message": message,
+                "tree": tree_sha,
+                "parents": [parent_sha]
+            })),
+            "create commit",
+        ).await?;
+        let sha = string_field(&commit, "/sha", "commit")?;
+
+        self.git_data_request(
+            self.client.patch(&ref_url).json(&serde_json::json!({
+                "sha": sha,
+                "force": false
+            })),
+            "update branch ref",
+        ).await?;
+
+        Ok(MultiFileCommit {
+            sha,
+            html_url: commit.get("html_url").and_then(|v| v.as_str()).map(str::to_string),
+            tree_sha,
+            parent_sha,
+            branch: branch.to_string(),
+            files: files.iter().map(|f| f.path.clone()).collect(),
+        })
+    }
+
+    /// Creates an annotated tag: a tag object pointing at `object`, then the
+    /// `refs/tags/{tag}` ref pointing at the tag object. If the ref already
+    /// exists the second step fails and the tag object is left unreferenced.
+    pub async fn create_tag(&self, owner: &str, repo: &str, new_tag: &NewTag) -> Result<CreatedTag> {
+        let body = build_tag_object_body(new_tag)?;
+        let git_base = format!("{}repos/{}/{}/git", self.api_urls.rest_base, owner, repo);
+
+        let tag_object = self.git_data_request(
+            self.client.post(format!("{}/tags", git_base)).json(&body),
+            "create tag object",
+        ).await?;
+        let sha = string_field(&tag_object, "/sha", "tag object")?;
+
+        let ref_name = format!("refs/tags/{}", new_tag.tag);
+        self.git_data_request(
+            self.client.post(format!("{}/refs", git_base)).json(&serde_json::json!({
+                "ref": ref_name,
+                "sha": sha
+            })),
+            "create tag ref",
+        ).await?;
+
+        Ok(CreatedTag {
+            tag: new_tag.tag.clone(),
+            sha,
+            object_sha: new_tag.object.clone(),
+            object_type: new_tag.object_type.clone(),
+            ref_name,
+            message: new_tag.message.clone(),
+        })
+    }
+
+    /// Reads a ref such as `heads/main` or `tags/v1.0.0` (without the `refs/` prefix).
+    pub async fn get_ref(&self, owner: &str, repo: &str, ref_path: &str) -> Result<GitReference> {
+        let url = format!("{}repos/{}/{}/git/ref/{}", self.api_urls.rest_base, owner, repo, encode_path_segments(ref_path));
+        let response = self.client.get(&url).send_with(self).await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(anyhow!("Ref {} not found in {}/{}", ref_path, owner, repo));
+        }
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to get ref {}: {}", ref_path, response.status()));
+        }
+
+        let ref_data: Value = response.json().await?;
+        serde_json::from_value(ref_data).map_err(|e| anyhow!("Failed to parse git reference: {}", e))
+    }
+
+    /// Creates the fully qualified ref `ref_name` (e.g. `refs/heads/feature`) at `sha`.
+    pub async fn create_ref(&self, owner: &str, repo: &str, ref_name: &str, sha: &str) -> Result<GitReference> {
+        let url = format!("{}repos/{}/{}/git/refs", self.api_urls.rest_base, owner, repo);
+        let response = self.client.post(&url)
+            .json(&serde_json::json!({ "ref": ref_name, "sha": sha }))
+            .send_with(self)
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(create_ref_error(ref_name, status, &text));
+        }
+
+        let ref_data: Value = response.json().await?;
+        serde_json::from_value(ref_data).map_err(|e| anyhow!("Failed to parse git reference: {}", e))
+    }
+
+    /// Creates `branch` at the head of `from_branch`, or of the default branch when unset.
+    pub async fn create_branch(&self, owner: &str, repo: &str, branch: &str, from_branch: Option<&str>) -> Result<CreatedBranch> {
+        let from_branch = match from_branch {
+            Some(from_branch) => from_branch.to_string(),
+            None => self.get_repository(owner, repo).await?.default_branch,
+        };
+
+        let source = self.get_ref(owner, repo, &format!("heads/{}", from_branch)).await?;
+        let created = self.create_ref(owner, repo, &format!("refs/heads/{}", branch), &source.object.sha).await?;
+
+        Ok(CreatedBranch {
+            ref_name: created.r#ref,
+            sha: created.object.sha,
+            from_branch,
+        })
+    }
+
+    async fn git_data_request(&self, request: reqwest::RequestBuilder, action: &str) -> Result<Value> {
+        let response = request.send_with(self).await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Failed to {}: {} {}", action, status, error_text));
+        }
+
+        Ok(response.json().await?)
+    }
+
     pub async fn search_repositories(&self, query: &str, sort: Option<&str>, order: Option<&str>, per_page: Option<u8>, page: Option<u32>) -> Result<SearchResults<Repository>> {
         let mut url = format!("{}search/repositories?q={}", self.api_urls.rest_base, urlencoding::encode(query));
         
@@ -220,71 +993,233 @@ impl GitHubClient {
             url = format!("{}&page={}", url, page);
         }
         
-        let response = self.client.get(&url).send().await?;
+        let response = self.client.get(&url).send_with(self).await?;
         
         if !response.status().is_success() {
             return Err(anyhow!("Failed to search repositories: {}", response.status()));
         }
         
+        let pagination = page_info(&response, per_page, page);
         let search_data: Value = response.json().await?;
-        self.parse_repository_search_results(search_data)
+        let mut results = self.parse_repository_search_results(search_data)?;
+        results.pagination = Some(pagination);
+        Ok(results)
     }
 
-    // Issue operations
-    pub async fn get_issue(&self, owner: &str, repo: &str, number: u64) -> Result<Issue> {
-        let url = format!("{}repos/{}/{}/issues/{}", self.api_urls.rest_base, owner, repo, number);
-        let response = self.client.get(&url).send().await?;
+    pub async fn get_repo_topics(&self, owner: &str, repo: &str) -> Result<RepositoryTopics> {
+        let url = format!("{}repos/{}/{}/topics", self.api_urls.rest_base, owner, repo);
+        let response = self.client
+            .get(&url)
+            .header("Accept", TOPICS_MEDIA_TYPE)
+            .send_with(self)
+            .await?;
         
         if !response.status().is_success() {
-            return Err(anyhow!("Failed to get issue: {}", response.status()));
+            return Err(anyhow!("Failed to get repository topics: {}", response.status()));
         }
         
-        let issue_data: Value = response.json().await?;
-        self.parse_issue(issue_data)
+        let topics_data: Value = response.json().await?;
+        self.parse_repository_topics(topics_data)
     }
 
-    pub async fn list_issues(&self, owner: &str, repo: &str, state: Option<&str>, labels: Option<Vec<String>>, assignee: Option<&str>, creator: Option<&str>, mentioned: Option<&str>, milestone: Option<&str>, sort: Option<&str>, direction: Option<&str>, since: Option<&str>, per_page: Option<u8>, page: Option<u32>) -> Result<Vec<Issue>> {
-        let mut url = format!("{}repos/{}/{}/issues", self.api_urls.rest_base, owner, repo);
-        let mut params = Vec::new();
-        
-        if let Some(state) = state {
-            params.push(format!("state={}", state));
-        }
-        
-        if let Some(labels) = labels {
-            if !labels.is_empty() {
-                params.push(format!("labels={}", labels.join(",")));
-            }
-        }
-        
-        if let Some(assignee) = assignee {
-            params.push(format!("assignee={}", assignee));
-        }
-        
-        if let Some(creator) = creator {
-            params.push(format!("creator={}", creator));
-        }
+    pub async fn replace_repo_topics(&self, owner: &str, repo: &str, names: &[String]) -> Result<RepositoryTopics> {
+        let url = format!("{}repos/{}/{}/topics", self.api_urls.rest_base, owner, repo);
+        let body = build_replace_topics_body(names)?;
         
-        if let Some(per_page) = per_page {
-            params.push(format!("per_page={}", per_page));
-        }
+        let response = self.client
+            .put(&url)
+            .header("Accept", TOPICS_MEDIA_TYPE)
+            .json(&body)
+            .send_with(self)
+            .await?;
         
-        if let Some(page) = page {
-            params.push(format!("page={}", page));
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to replace repository topics: {}", response.status()));
         }
         
-        if !params.is_empty() {
-            url = format!("{}?{}", url, params.join("&"));
+        let topics_data: Value = response.json().await?;
+        self.parse_repository_topics(topics_data)
+    }
+
+    /// Returns the authenticated user's watch settings; GitHub answers 404 when
+    /// the repository is not watched.
+    pub async fn get_repo_subscription(&self, owner: &str, repo: &str) -> Result<RepositorySubscription> {
+        let url = format!("{}repos/{}/{}/subscription", self.api_urls.rest_base, owner, repo);
+        let response = self.client.get(&url).send_with(self).await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(RepositorySubscription::default());
         }
-        
-        let response = self.client.get(&url).send().await?;
-        
         if !response.status().is_success() {
-            return Err(anyhow!("Failed to list issues: {}", response.status()));
+            return Err(anyhow!("Failed to get repository subscription: {}", response.status()));
         }
-        
-        let issues_data: Value = response.json().await?;
-        self.parse_issues_list(issues_data)
+
+        let subscription_data: Value = response.json().await?;
+        serde_json::from_value(subscription_data)
+            .map_err(|e| anyhow!("Failed to parse repository subscription: {}", e))
+    }
+
+    pub async fn set_repo_subscription(&self, owner: &str, repo: &str, subscribed: bool, ignored: bool) -> Result<RepositorySubscription> {
+        let url = format!("{}repos/{}/{}/subscription", self.api_urls.rest_base, owner, repo);
+        let body = build_subscription_body(subscribed, ignored);
+
+        let response = self.client.put(&url).json(&body).send_with(self).await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to set repository subscription: {}", response.status()));
+        }
+
+        let subscription_data: Value = response.json().await?;
+        serde_json::from_value(subscription_data)
+            .map_err(|e| anyhow!("Failed to parse repository subscription: {}", e))
+    }
+
+    /// Stops watching the repository and returns the resulting (unwatched) state.
+    pub async fn delete_repo_subscription(&self, owner: &str, repo: &str) -> Result<RepositorySubscription> {
+        let url = format!("{}repos/{}/{}/subscription", self.api_urls.rest_base, owner, repo);
+        let response = self.client.delete(&url).send_with(self).await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to delete repository subscription: {}", response.status()));
+        }
+
+        Ok(RepositorySubscription::default())
+    }
+
+    pub async fn list_commits(&self, owner: &str, repo: &str, filters: &CommitFilters, per_page: Option<u8>, page: Option<u32>) -> Result<Vec<CommitSummary>> {
+        let mut url = format!("{}repos/{}/{}/commits", self.api_urls.rest_base, owner, repo);
+        let query = build_list_commits_query(filters, per_page, page);
+        
+        if !query.is_empty() {
+            url = format!("{}?{}", url, query);
+        }
+        
+        let response = self.client.get(&url).send_with(self).await?;
+        
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to list commits: {}", response.status()));
+        }
+        
+        let commits_data: Value = response.json().await?;
+        parse_commit_summaries(&commits_data)
+    }
+
+    /// Gets one commit by SHA, branch or tag. Per-file patches and the raw
+    /// `diff` are only kept when `include_diff` is set, as they can be large.
+    pub async fn get_commit(&self, owner: &str, repo: &str, reference: &str, include_diff: bool) -> Result<CommitDetail> {
+        let url = format!("{}repos/{}/{}/commits/{}", self.api_urls.rest_base, owner, repo, urlencoding::encode(reference));
+        let response = self.client.get(&url).send_with(self).await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND || response.status() == reqwest::StatusCode::UNPROCESSABLE_ENTITY {
+            return Err(anyhow!("No commit found for '{}' in {}/{}", reference, owner, repo));
+        }
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to get commit: {}", response.status()));
+        }
+
+        let commit_data: Value = response.json().await?;
+        let mut commit = parse_commit_detail(&commit_data)?;
+
+        if include_diff {
+            let response = self.client.get(&url)
+                .header(reqwest::header::ACCEPT, DIFF_MEDIA_TYPE)
+                .send_with(self)
+                .await?;
+            if !response.status().is_success() {
+                return Err(anyhow!("Failed to get commit diff: {}", response.status()));
+            }
+            commit.diff = Some(response.text().await?);
+        } else {
+            for file in &mut commit.files {
+                file.patch = None;
+            }
+        }
+
+        Ok(commit)
+    }
+
+    // Issue operations
+    pub async fn get_issue(&self, owner: &str, repo: &str, number: u64, include_reactions: bool) -> Result<Issue> {
+        let url = format!("{}repos/{}/{}/issues/{}", self.api_urls.rest_base, owner, repo, number);
+        let mut request = self.client.get(&url);
+        
+        if include_reactions {
+            request = request.header("Accept", REACTIONS_MEDIA_TYPE);
+        }
+        
+        let response = request.send_with(self).await?;
+        
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to get issue: {}", response.status()));
+        }
+        
+        let issue_data: Value = response.json().await?;
+        let mut issue = self.parse_issue(issue_data)?;
+        
+        if !include_reactions {
+            issue.reactions = None;
+        }
+        
+        Ok(issue)
+    }
+
+    pub async fn list_issues(&self, owner: &str, repo: &str, filters: &IssueFilters, per_page: Option<u8>, page: Option<u32>) -> Result<Paginated<Issue>> {
+        let mut url = format!("{}repos/{}/{}/issues", self.api_urls.rest_base, owner, repo);
+        let query = build_list_issues_query(filters, per_page, page);
+        
+        if !query.is_empty() {
+            url = format!("{}?{}", url, query);
+        }
+        
+        let response = self.client.get(&url).send_with(self).await?;
+        
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to list issues: {}", response.status()));
+        }
+        
+        let pagination = page_info(&response, per_page, page);
+        let issues_data: Value = response.json().await?;
+        let items = self.parse_issues_list(issues_data)?;
+        Ok(Paginated { items, pagination })
+    }
+
+    /// Lists issues across every repository in `org` the token can see, via
+    /// the search API, which unlike `/orgs/{org}/issues` filters by assignee.
+    pub async fn list_org_issues(&self, org: &str, filters: &IssueFilters, per_page: Option<u8>, page: Option<u32>) -> Result<SearchResults<Issue>> {
+        let query = build_org_issues_search_query(org, filters);
+        let mut url = format!("{}search/issues?q={}", self.api_urls.rest_base, urlencoding::encode(&query));
+
+        if let Some(sort) = &filters.sort {
+            url = format!("{}&sort={}", url, urlencoding::encode(sort));
+        }
+
+        if let Some(direction) = &filters.direction {
+            url = format!("{}&order={}", url, urlencoding::encode(direction));
+        }
+
+        if let Some(per_page) = per_page {
+            url = format!("{}&per_page={}", url, per_page);
+        }
+
+        if let Some(page) = page {
+            url = format!("{}&page={}", url, page);
+        }
+
+        let response = self.client.get(&url).send_with(self).await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Failed to list issues for organization {}: {} - {}", org, status, text));
+        }
+
+        let pagination = page_info(&response, per_page, page);
+        let search_data: Value = response.json().await?;
+        let mut results: SearchResults<Issue> = serde_json::from_value(search_data)
+            .map_err(|e| anyhow!("Failed to parse organization issues: {}", e))?;
+        results.items = results.items.into_iter().map(mark_pull_request).collect();
+        results.pagination = Some(pagination);
+        Ok(results)
     }
 
     pub async fn create_issue(&self, owner: &str, repo: &str, title: &str, body: Option<&str>, assignees: Option<Vec<String>>, milestone: Option<u64>, labels: Option<Vec<String>>) -> Result<Issue> {
@@ -310,7 +1245,7 @@ impl GitHubClient {
             body_json["labels"] = serde_json::to_value(labels)?;
         }
         
-        let response = self.client.post(&url).json(&body_json).send().await?;
+        let response = self.client.post(&url).json(&body_json).send_with(self).await?;
         
         if !response.status().is_success() {
             return Err(anyhow!("Failed to create issue: {}", response.status()));
@@ -320,10 +1255,261 @@ impl GitHubClient {
         self.parse_issue(issue_data)
     }
 
+    // Sub-issue operations (GraphQL only)
+    pub async fn list_sub_issues(&self, owner: &str, repo: &str, number: u64, first: u32) -> Result<SubIssueList> {
+        let query = r#"
+            query($owner: String!, $repo: String!, $number: Int!, $first: Int!) {
+                repository(owner: $owner, name: $repo) {
+                    issue(number: $number) {
+                        id
+                        number
+                        title
+                        state
+                        url
+                        subIssuesSummary { total completed percentCompleted }
+                        subIssues(first: $first) {
+                            nodes { id number title state url }
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({
+            "owner": owner,
+            "repo": repo,
+            "number": number,
+            "first": first
+        });
+
+        let data = self.graphql_query(query, Some(variables))
+            .await
+            .map_err(|e| sub_issues_error(owner, repo, e))?;
+        parse_sub_issues_response(&data)
+    }
+
+    // Project (Projects v2) operations
+    pub async fn list_project_items(&self, org: &str, project_number: u64, first: u32, after: Option<&str>) -> Result<ProjectItemList> {
+        let query = r#"
+            query($org: String!, $number: Int!, $first: Int!, $after: String) {
+                organization(login: $org) {
+                    projectV2(number: $number) {
+                        title
+                        items(first: $first, after: $after) {
+                            pageInfo { hasNextPage endCursor }
+                            nodes {
+                                id
+                                type
+                                content {
+                                    ... on Issue { title number url state repository { nameWithOwner } }
+                                    ... on PullRequest { title number url state repository { nameWithOwner } }
+                                    ... on DraftIssue { title }
+                                }
+                                fieldValues(first: 50) {
+                                    nodes {
+                                        ... on ProjectV2ItemFieldTextValue { text field { ... on ProjectV2FieldCommon { name } } }
+                                        ... on ProjectV2ItemFieldNumberValue { number field { ... on ProjectV2FieldCommon { name } } }
+                                        ... on ProjectV2ItemFieldDateValue { date field { ... on ProjectV2FieldCommon { name } } }
+                                        ... on ProjectV2ItemFieldSingleSelectValue { name field { ... on ProjectV2FieldCommon { name } } }
+                                        ... on ProjectV2ItemFieldIterationValue { title field { ... on ProjectV2FieldCommon { name } } }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({
+            "org": org,
+            "number": project_number,
+            "first": first,
+            "after": after
+        });
+
+        let data = self.graphql_query(query, Some(variables)).await?;
+        let project = project_node(&data, org, project_number)?;
+        parse_project_items(project)
+    }
+
+    pub async fn get_project_fields(&self, org: &str, project_number: u64, first: u32, after: Option<&str>) -> Result<ProjectFieldList> {
+        let query = r#"
+            query($org: String!, $number: Int!, $first: Int!, $after: String) {
+                organization(login: $org) {
+                    projectV2(number: $number) {
+                        title
+                        fields(first: $first, after: $after) {
+                            pageInfo { hasNextPage endCursor }
+                            nodes {
+                                ... on ProjectV2Field { id name dataType }
+                                ... on ProjectV2SingleSelectField { id name dataType options { id name } }
+                                ... on ProjectV2IterationField {
+                                    id name dataType
+                                    configuration { iterations { id title startDate duration } }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({
+            "org": org,
+            "number": project_number,
+            "first": first,
+            "after": after
+        });
+
+        let data = self.graphql_query(query, Some(variables)).await?;
+        let project = project_node(&data, org, project_number)?;
+        parse_project_fields(project)
+    }
+
+    pub async fn list_issue_comments(&self, owner: &str, repo: &str, issue_number: u64, per_page: Option<u8>, page: Option<u32>) -> Result<Paginated<IssueComment>> {
+        let url = format!(
+            "{}repos/{}/{}/issues/{}/comments?per_page={}&page={}",
+            self.api_urls.rest_base, owner, repo, issue_number,
+            per_page.map(u32::from).unwrap_or(DEFAULT_PER_PAGE), page.unwrap_or(1)
+        );
+        let response = self.client.get(&url).send_with(self).await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to list issue comments: {}", response.status()));
+        }
+
+        let pagination = page_info(&response, per_page, page);
+        let comments_data: Value = response.json().await?;
+        let items = comments_data.as_array()
+            .ok_or_else(|| anyhow!("Expected an array of issue comments"))?
+            .iter()
+            .map(compact_issue_comment)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Paginated { items, pagination })
+    }
+
+    pub async fn create_issue_comment(&self, owner: &str, repo: &str, issue_number: u64, body: &str) -> Result<IssueComment> {
+        let url = format!("{}repos/{}/{}/issues/{}/comments", self.api_urls.rest_base, owner, repo, issue_number);
+        let response = self.client.post(&url)
+            .json(&serde_json::json!({ "body": body }))
+            .send_with(self)
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to create issue comment: {}", response.status()));
+        }
+
+        let comment_data: Value = response.json().await?;
+        compact_issue_comment(&comment_data)
+    }
+
+    pub async fn update_issue_comment(&self, owner: &str, repo: &str, comment_id: u64, body: &str) -> Result<IssueComment> {
+        let url = format!("{}repos/{}/{}/issues/comments/{}", self.api_urls.rest_base, owner, repo, comment_id);
+        let response = self.client.patch(&url)
+            .json(&serde_json::json!({ "body": body }))
+            .send_with(self)
+            .await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(anyhow!("Issue comment {} not found in {}/{}", comment_id, owner, repo));
+        }
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to update issue comment: {}", response.status()));
+        }
+
+        let comment_data: Value = response.json().await?;
+        compact_issue_comment(&comment_data)
+    }
+
+    pub async fn delete_issue_comment(&self, owner: &str, repo: &str, comment_id: u64) -> Result<()> {
+        let url = format!("{}repos/{}/{}/issues/comments/{}", self.api_urls.rest_base, owner, repo, comment_id);
+        let response = self.client.delete(&url).send_with(self).await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(anyhow!("Issue comment {} not found in {}/{}", comment_id, owner, repo));
+        }
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to delete issue comment: {}", response.status()));
+        }
+
+        Ok(())
+    }
+
+    /// Reacts to an issue or pull request, or to one of their comments when `comment_id` is set.
+    pub async fn add_reaction(&self, owner: &str, repo: &str, issue_number: Option<u64>, comment_id: Option<u64>, content: &str) -> Result<Reaction> {
+        let url = match (issue_number, comment_id) {
+            (_, Some(comment_id)) => format!("{}repos/{}/{}/issues/comments/{}/reactions", self.api_urls.rest_base, owner, repo, comment_id),
+            (Some(issue_number), None) => format!("{}repos/{}/{}/issues/{}/reactions", self.api_urls.rest_base, owner, repo, issue_number),
+            (None, None) => return Err(anyhow!("Either an issue number or a comment id is required")),
+        };
+        
+        let response = self.client
+            .post(&url)
+            .header("Accept", REACTIONS_MEDIA_TYPE)
+            .json(&serde_json::json!({ "content": content }))
+            .send_with(self)
+            .await?;
+        
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to add reaction: {}", response.status()));
+        }
+        
+        let reaction_data: Value = response.json().await?;
+        serde_json::from_value(reaction_data).map_err(|e| anyhow!("Failed to parse reaction: {}", e))
+    }
+
+    pub async fn add_sub_issue(&self, owner: &str, repo: &str, parent_number: u64, child_number: u64) -> Result<SubIssueLink> {
+        let lookup = r#"
+            query($owner: String!, $repo: String!, $parent: Int!, $child: Int!) {
+                repository(owner: $owner, name: $repo) {
+                    parent: issue(number: $parent) { id }
+                    child: issue(number: $child) { id }
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({
+            "owner": owner,
+            "repo": repo,
+            "parent": parent_number,
+            "child": child_number
+        });
+
+        let ids = self.graphql_query(lookup, Some(variables))
+            .await
+            .map_err(|e| sub_issues_error(owner, repo, e))?;
+        let parent_id = ids.pointer("/repository/parent/id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("Issue #{} not found in {}/{}", parent_number, owner, repo))?;
+        let child_id = ids.pointer("/repository/child/id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("Issue #{} not found in {}/{}", child_number, owner, repo))?;
+
+        let mutation = r#"
+            mutation($issueId: ID!, $subIssueId: ID!) {
+                addSubIssue(input: { issueId: $issueId, subIssueId: $subIssueId }) {
+                    issue { id number title state url }
+                    subIssue { id number title state url }
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({
+            "issueId": parent_id,
+            "subIssueId": child_id
+        });
+
+        let payload = self.graphql_mutate(mutation, variables, GraphQLErrorPolicy::Strict)
+            .await
+            .map_err(|e| sub_issues_error(owner, repo, e))?;
+        parse_sub_issue_link(&payload)
+    }
+
     // Pull request operations
     pub async fn get_pull_request(&self, owner: &str, repo: &str, number: u64) -> Result<PullRequest> {
         let url = format!("{}repos/{}/{}/pulls/{}", self.api_urls.rest_base, owner, repo, number);
-        let response = self.client.get(&url).send().await?;
+        let response = self.client.get(&url).send_with(self).await?;
         
         if !response.status().is_success() {
             return Err(anyhow!("Failed to get pull request: {}", response.status()));
@@ -333,7 +1519,20 @@ impl GitHubClient {
         self.parse_pull_request(pr_data)
     }
 
-    pub async fn list_pull_requests(&self, owner: &str, repo: &str, state: Option<&str>, head: Option<&str>, base: Option<&str>, sort: Option<&str>, direction: Option<&str>, per_page: Option<u8>, page: Option<u32>) -> Result<Vec<PullRequest>> {
+    /// Re-fetches the pull request until GitHub has computed `mergeable`.
+    pub async fn check_mergeable(&self, owner: &str, repo: &str, number: u64) -> Result<MergeableStatus> {
+        poll_mergeable(
+            || async {
+                let pr = self.get_pull_request(owner, repo, number).await?;
+                Ok((pr.mergeable, pr.mergeable_state))
+            },
+            MERGEABLE_MAX_ATTEMPTS,
+            MERGEABLE_RETRY_DELAY,
+        )
+        .await
+    }
+
+    pub async fn list_pull_requests(&self, owner: &str, repo: &str, state: Option<&str>, head: Option<&str>, base: Option<&str>, sort: Option<&str>, direction: Option<&str>, per_page: Option<u8>, page: Option<u32>) -> Result<Paginated<PullRequest>> {
         let mut url = format!("{}repos/{}/{}/pulls", self.api_urls.rest_base, owner, repo);
         let mut params = Vec::new();
         
@@ -361,14 +1560,44 @@ impl GitHubClient {
             url = format!("{}?{}", url, params.join("&"));
         }
         
-        let response = self.client.get(&url).send().await?;
+        let response = self.client.get(&url).send_with(self).await?;
         
         if !response.status().is_success() {
             return Err(anyhow!("Failed to list pull requests: {}", response.status()));
         }
         
+        let pagination = page_info(&response, per_page, page);
         let prs_data: Value = response.json().await?;
-        self.parse_pull_requests_list(prs_data)
+        let items = self.parse_pull_requests_list(prs_data)?;
+        Ok(Paginated { items, pagination })
+    }
+
+    pub async fn list_pull_request_files(&self, owner: &str, repo: &str, number: u64, per_page: Option<u8>, page: Option<u32>) -> Result<Paginated<PullRequestFile>> {
+        let mut url = format!("{}repos/{}/{}/pulls/{}/files", self.api_urls.rest_base, owner, repo, number);
+        let mut params = Vec::new();
+
+        if let Some(per_page) = per_page {
+            params.push(format!("per_page={}", per_page));
+        }
+
+        if let Some(page) = page {
+            params.push(format!("page={}", page));
+        }
+
+        if !params.is_empty() {
+            url = format!("{}?{}", url, params.join("&"));
+        }
+
+        let response = self.client.get(&url).send_with(self).await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to list pull request files: {}", response.status()));
+        }
+
+        let pagination = page_info(&response, per_page, page);
+        let items: Vec<PullRequestFile> = response.json().await
+            .map_err(|e| anyhow!("Failed to parse pull request files: {}", e))?;
+        Ok(Paginated { items, pagination })
     }
 
     pub async fn create_pull_request(&self, owner: &str, repo: &str, title: &str, head: &str, base: &str, body: Option<&str>, draft: Option<bool>) -> Result<PullRequest> {
@@ -388,7 +1617,7 @@ impl GitHubClient {
             body_json["draft"] = Value::Bool(draft);
         }
         
-        let response = self.client.post(&url).json(&body_json).send().await?;
+        let response = self.client.post(&url).json(&body_json).send_with(self).await?;
         
         if !response.status().is_success() {
             return Err(anyhow!("Failed to create pull request: {}", response.status()));
@@ -398,86 +1627,2819 @@ impl GitHubClient {
         self.parse_pull_request(pr_data)
     }
 
-    // User operations
-    pub async fn get_authenticated_user(&self) -> Result<User> {
-        let url = format!("{}user", self.api_urls.rest_base);
-        let response = self.client.get(&url).send().await?;
-        
+    /// Merges a pull request with `PUT /pulls/{number}/merge`.
+    pub async fn merge_pull_request(&self, owner: &str, repo: &str, number: u64, options: &MergeOptions) -> Result<MergeResult> {
+        if let Some(method) = &options.merge_method {
+            if !MERGE_METHODS.contains(&method.as_str()) {
+                return Err(anyhow!("Invalid merge_method '{}', expected one of: {}", method, MERGE_METHODS.join(", ")));
+            }
+        }
+
+        let url = format!("{}repos/{}/{}/pulls/{}/merge", self.api_urls.rest_base, owner, repo, number);
+        let response = self.client.put(&url).json(options).send_with(self).await?;
+
         if !response.status().is_success() {
-            return Err(anyhow!("Failed to get authenticated user: {}", response.status()));
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(merge_error(number, status, &text));
         }
-        
-        let user_data: Value = response.json().await?;
-        self.parse_user(user_data)
+
+        let merge_data: Value = response.json().await?;
+        serde_json::from_value(merge_data)
+            .map_err(|e| anyhow!("Failed to parse merge result: {}", e))
     }
 
-    pub async fn search_users(&self, query: &str, sort: Option<&str>, order: Option<&str>, per_page: Option<u8>, page: Option<u32>) -> Result<SearchResults<User>> {
-        let mut url = format!("{}search/users?q={}", self.api_urls.rest_base, urlencoding::encode(query));
+    // Actions operations
+    pub async fn list_workflow_runs(&self, owner: &str, repo: &str, filters: &WorkflowRunFilters, per_page: Option<u8>, page: Option<u32>) -> Result<WorkflowRunList> {
+        let mut url = match &filters.workflow_id {
+            Some(workflow_id) => format!("{}repos/{}/{}/actions/workflows/{}/runs", self.api_urls.rest_base, owner, repo, urlencoding::encode(workflow_id)),
+            None => format!("{}repos/{}/{}/actions/runs", self.api_urls.rest_base, owner, repo),
+        };
+        let query = build_list_workflow_runs_query(filters, per_page, page);
         
-        if let Some(sort) = sort {
-            url = format!("{}&sort={}", url, sort);
+        if !query.is_empty() {
+            url = format!("{}?{}", url, query);
         }
         
-        if let Some(order) = order {
-            url = format!("{}&order={}", url, order);
+        let response = self.client.get(&url).send_with(self).await?;
+        
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to list workflow runs: {}", response.status()));
         }
         
+        let runs_data: Value = response.json().await?;
+        self.parse_workflow_runs(runs_data)
+    }
+
+    /// Lists deployments newest first, each with its most recent status.
+    pub async fn list_deployments(&self, owner: &str, repo: &str, environment: Option<&str>, git_ref: Option<&str>, per_page: Option<u8>, page: Option<u32>) -> Result<Vec<Deployment>> {
+        /// Deployment status lookups kept in flight at once.
+        const DEPLOYMENT_STATUS_CONCURRENCY: usize = 8;
+
+        let mut url = format!("{}repos/{}/{}/deployments", self.api_urls.rest_base, owner, repo);
+        let mut params = Vec::new();
+
+        if let Some(environment) = environment {
+            params.push(format!("environment={}", urlencoding::encode(environment)));
+        }
+
+        if let Some(git_ref) = git_ref {
+            params.push(format!("ref={}", urlencoding::encode(git_ref)));
+        }
+
         if let Some(per_page) = per_page {
-            url = format!("{}&per_page={}", url, per_page);
+            params.push(format!("per_page={}", per_page));
         }
-        
+
         if let Some(page) = page {
-            url = format!("{}&page={}", url, page);
+            params.push(format!("page={}", page));
         }
-        
-        let response = self.client.get(&url).send().await?;
-        
-        if !response.status().is_success() {
-            return Err(anyhow!("Failed to search users: {}", response.status()));
+
+        if !params.is_empty() {
+            url = format!("{}?{}", url, params.join("&"));
         }
-        
-        let search_data: Value = response.json().await?;
-        self.parse_user_search_results(search_data)
-    }
 
-    // Helper parsing methods - simplified implementations
-    fn parse_user(&self, data: Value) -> Result<User> {
-        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse user: {}", e))
-    }
+        let response = self.client.get(&url).send_with(self).await?;
 
-    fn parse_repository(&self, data: Value) -> Result<Repository> {
-        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse repository: {}", e))
-    }
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to list deployments: {}", response.status()));
+        }
 
-    fn parse_issue(&self, data: Value) -> Result<Issue> {
-        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse issue: {}", e))
-    }
+        let deployments_data: Value = response.json().await?;
+        let mut deployments = self.parse_deployments(deployments_data)?;
 
-    fn parse_pull_request(&self, data: Value) -> Result<PullRequest> {
-        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse pull request: {}", e))
-    }
+        // The list endpoint carries no state; it lives on the statuses. A page can
+        // hold 100 deployments, so only a few lookups run at once, and one that
+        // fails leaves its deployment without a status instead of failing the list.
+        let lookups: Vec<_> = deployments
+            .iter()
+            .enumerate()
+            .map(|(index, deployment)| {
+                let id = deployment.id;
+                async move {
+                    match self.get_latest_deployment_status(owner, repo, id).await {
+                        Ok(status) => (index, status),
+                        Err(e) => {
+                            warn!("Could not get status of deployment {}: {}", id, e);
+                            (index, None)
+                        }
+                    }
+                }
+            })
+            .collect();
+        let statuses: Vec<(usize, Option<DeploymentStatus>)> = futures::stream::iter(lookups)
+            .buffer_unordered(DEPLOYMENT_STATUS_CONCURRENCY)
+            .collect()
+            .await;
+        for (index, status) in statuses {
+            deployments[index].latest_status = status;
+        }
 
-    fn parse_file_content(&self, data: Value) -> Result<FileContent> {
-        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse file content: {}", e))
+        Ok(deployments)
     }
 
-    fn parse_file_commit(&self, data: Value) -> Result<FileCommit> {
-        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse file commit: {}", e))
-    }
+    async fn get_latest_deployment_status(&self, owner: &str, repo: &str, deployment_id: u64) -> Result<Option<DeploymentStatus>> {
+        let url = format!("{}repos/{}/{}/deployments/{}/statuses?per_page=1", self.api_urls.rest_base, owner, repo, deployment_id);
+        let response = self.client.get(&url).send_with(self).await?;
 
-    fn parse_repository_search_results(&self, data: Value) -> Result<SearchResults<Repository>> {
-        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse repository search results: {}", e))
-    }
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to get deployment statuses: {}", response.status()));
+        }
 
-    fn parse_user_search_results(&self, data: Value) -> Result<SearchResults<User>> {
-        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse user search results: {}", e))
+        let statuses: Vec<DeploymentStatus> = response.json().await
+            .map_err(|e| anyhow!("Failed to parse deployment statuses: {}", e))?;
+        Ok(statuses.into_iter().next())
     }
 
-    fn parse_issues_list(&self, data: Value) -> Result<Vec<Issue>> {
-        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse issues list: {}", e))
-    }
+    pub async fn list_environments(&self, owner: &str, repo: &str, per_page: Option<u8>, page: Option<u32>) -> Result<EnvironmentList> {
+        let mut url = format!("{}repos/{}/{}/environments", self.api_urls.rest_base, owner, repo);
+        let mut params = Vec::new();
 
-    fn parse_pull_requests_list(&self, data: Value) -> Result<Vec<PullRequest>> {
-        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse pull requests list: {}", e))
+        if let Some(per_page) = per_page {
+            params.push(format!("per_page={}", per_page));
+        }
+
+        if let Some(page) = page {
+            params.push(format!("page={}", page));
+        }
+
+        if !params.is_empty() {
+            url = format!("{}?{}", url, params.join("&"));
+        }
+
+        let response = self.client.get(&url).send_with(self).await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to list environments: {}", response.status()));
+        }
+
+        let environments_data: Value = response.json().await?;
+        self.parse_environments(environments_data)
+    }
+
+    /// Triggers a `workflow_dispatch` event. GitHub answers with 204 No Content,
+    /// so there is no run id to return; callers should list runs to find it.
+    pub async fn dispatch_workflow(&self, owner: &str, repo: &str, workflow_id: &str, git_ref: &str, inputs: Option<&Map<String, Value>>) -> Result<()> {
+        let url = format!("{}repos/{}/{}/actions/workflows/{}/dispatches", self.api_urls.rest_base, owner, repo, urlencoding::encode(workflow_id));
+        let body = build_dispatch_body(git_ref, inputs);
+        
+        let response = self.client.post(&url).json(&body).send_with(self).await?;
+        
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to dispatch workflow: {}", response.status()));
+        }
+        
+        Ok(())
+    }
+
+    // Security alert operations
+    pub async fn list_secret_scanning_alerts(&self, owner: &str, repo: &str, state: Option<&str>, secret_type: Option<&str>, per_page: Option<u8>, page: Option<u32>) -> Result<Vec<SecretScanningAlert>> {
+        let mut params = Vec::new();
+        if let Some(state) = state {
+            params.push(format!("state={}", state));
+        }
+        if let Some(secret_type) = secret_type {
+            params.push(format!("secret_type={}", urlencoding::encode(secret_type)));
+        }
+        if let Some(per_page) = per_page {
+            params.push(format!("per_page={}", per_page));
+        }
+        if let Some(page) = page {
+            params.push(format!("page={}", page));
+        }
+
+        let data = self.security_alerts_request(owner, repo, "secret-scanning/alerts", &params, "secret scanning").await?;
+        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse secret scanning alerts: {}", e))
+    }
+
+    pub async fn list_dependabot_alerts(&self, owner: &str, repo: &str, state: Option<&str>, severity: Option<&str>, ecosystem: Option<&str>, per_page: Option<u8>) -> Result<Vec<DependabotAlert>> {
+        let mut params = Vec::new();
+        if let Some(state) = state {
+            params.push(format!("state={}", state));
+        }
+        if let Some(severity) = severity {
+            params.push(format!("severity={}", severity));
+        }
+        if let Some(ecosystem) = ecosystem {
+            params.push(format!("ecosystem={}", ecosystem));
+        }
+        if let Some(per_page) = per_page {
+            params.push(format!("per_page={}", per_page));
+        }
+
+        let data = self.security_alerts_request(owner, repo, "dependabot/alerts", &params, "Dependabot").await?;
+        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse Dependabot alerts: {}", e))
+    }
+
+    async fn security_alerts_request(&self, owner: &str, repo: &str, endpoint: &str, params: &[String], kind: &str) -> Result<Value> {
+        let mut url = format!("{}repos/{}/{}/{}", self.api_urls.rest_base, owner, repo, endpoint);
+        if !params.is_empty() {
+            url = format!("{}?{}", url, params.join("&"));
+        }
+
+        let response = self.client.get(&url).send_with(self).await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(security_alerts_error(status, &error_text, kind, owner, repo));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    // User operations
+    pub async fn get_authenticated_user(&self) -> Result<User> {
+        let url = format!("{}user", self.api_urls.rest_base);
+        let response = self.client.get(&url).send_with(self).await?;
+        
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to get authenticated user: {}", response.status()));
+        }
+        
+        let user_data: Value = response.json().await?;
+        self.parse_user(user_data)
+    }
+
+    pub async fn search_users(&self, query: &str, sort: Option<&str>, order: Option<&str>, per_page: Option<u8>, page: Option<u32>) -> Result<SearchResults<User>> {
+        let mut url = format!("{}search/users?q={}", self.api_urls.rest_base, urlencoding::encode(query));
+        
+        if let Some(sort) = sort {
+            url = format!("{}&sort={}", url, sort);
+        }
+        
+        if let Some(order) = order {
+            url = format!("{}&order={}", url, order);
+        }
+        
+        if let Some(per_page) = per_page {
+            url = format!("{}&per_page={}", url, per_page);
+        }
+        
+        if let Some(page) = page {
+            url = format!("{}&page={}", url, page);
+        }
+        
+        let response = self.client.get(&url).send_with(self).await?;
+        
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to search users: {}", response.status()));
+        }
+        
+        let pagination = page_info(&response, per_page, page);
+        let search_data: Value = response.json().await?;
+        let mut results = self.parse_user_search_results(search_data)?;
+        results.pagination = Some(pagination);
+        Ok(results)
+    }
+
+    // Helper parsing methods - simplified implementations
+    fn parse_user(&self, data: Value) -> Result<User> {
+        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse user: {}", e))
+    }
+
+    fn parse_repository(&self, data: Value) -> Result<Repository> {
+        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse repository: {}", e))
+    }
+
+    fn parse_issue(&self, data: Value) -> Result<Issue> {
+        parse_issue_value(data)
+    }
+
+    fn parse_pull_request(&self, data: Value) -> Result<PullRequest> {
+        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse pull request: {}", e))
+    }
+
+    fn parse_file_content(&self, data: Value) -> Result<FileContent> {
+        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse file content: {}", e))
+    }
+
+    fn parse_file_commit(&self, data: Value) -> Result<FileCommit> {
+        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse file commit: {}", e))
+    }
+
+    fn parse_repository_search_results(&self, data: Value) -> Result<SearchResults<Repository>> {
+        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse repository search results: {}", e))
+    }
+
+    fn parse_repository_topics(&self, data: Value) -> Result<RepositoryTopics> {
+        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse repository topics: {}", e))
+    }
+
+    fn parse_workflow_runs(&self, data: Value) -> Result<WorkflowRunList> {
+        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse workflow runs: {}", e))
+    }
+
+    fn parse_deployments(&self, data: Value) -> Result<Vec<Deployment>> {
+        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse deployments: {}", e))
+    }
+
+    fn parse_environments(&self, data: Value) -> Result<EnvironmentList> {
+        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse environments: {}", e))
+    }
+
+    fn parse_user_search_results(&self, data: Value) -> Result<SearchResults<User>> {
+        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse user search results: {}", e))
+    }
+
+    fn parse_issues_list(&self, data: Value) -> Result<Vec<Issue>> {
+        let issues: Vec<Issue> = serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse issues list: {}", e))?;
+        Ok(issues.into_iter().map(mark_pull_request).collect())
+    }
+
+    fn parse_pull_requests_list(&self, data: Value) -> Result<Vec<PullRequest>> {
+        serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse pull requests list: {}", e))
+    }
+}
+
+/// Config for a client talking to `host` with a dummy token and the default
+/// retry settings; tests override single fields with struct update syntax.
+#[cfg(test)]
+pub(crate) fn test_config(host: &str) -> GitHubConfig {
+    GitHubConfig {
+        token: "test".to_string(),
+        host: Some(host.to_string()),
+        user_agent: "test".to_string(),
+        backoff: BackoffPolicy::default(),
+        max_retries: DEFAULT_MAX_RETRIES,
+        respect_rate_limit: true,
+        max_rate_limit_wait: DEFAULT_MAX_RATE_LIMIT_WAIT,
+        app_auth: None,
+        uploads_dir: None,
+    }
+}
+
+/// A client built from [`test_config`].
+#[cfg(test)]
+pub(crate) async fn test_client(host: &str) -> GitHubClient {
+    GitHubClient::new(test_config(host)).await.unwrap()
+}
+
+/// GitHub's default page size when `per_page` is not sent.
+const DEFAULT_PER_PAGE: u32 = 30;
+
+fn page_info(response: &reqwest::Response, per_page: Option<u8>, page: Option<u32>) -> PageInfo {
+    let link = response
+        .headers()
+        .get(reqwest::header::LINK)
+        .and_then(|value| value.to_str().ok());
+
+    PageInfo {
+        page: page.unwrap_or(1),
+        per_page: per_page.map(u32::from).unwrap_or(DEFAULT_PER_PAGE),
+        has_next: has_next_page(link),
+    }
+}
+
+/// Whether a `Link` header advertises a `rel="next"` page.
+pub fn has_next_page(link_header: Option<&str>) -> bool {
+    link_header.is_some_and(|link| {
+        link.split(',').any(|entry| {
+            entry
+                .split(';')
+                .skip(1)
+                .filter_map(|param| param.trim().strip_prefix("rel="))
+                .any(|rel| rel.trim_matches('"').split_whitespace().any(|r| r == "next"))
+        })
+    })
+}
+
+/// Reduces an issue comment to its id, author login, creation time and body.
+/// Comments by deleted accounts have no user and are attributed to `ghost`.
+pub fn compact_issue_comment(comment: &Value) -> Result<IssueComment> {
+    let text = |field: &str| comment.get(field).and_then(|v| v.as_str()).map(str::to_string);
+
+    Ok(IssueComment {
+        id: comment.get("id")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| anyhow!("Issue comment has no id"))?,
+        user: comment.pointer("/user/login")
+            .and_then(|v| v.as_str())
+            .unwrap_or("ghost")
+            .to_string(),
+        created_at: text("created_at").unwrap_or_default(),
+        body: text("body").unwrap_or_default(),
+    })
+}
+
+/// Encodes each segment of a ref or file path, keeping the `/` separators.
+fn encode_path_segments(ref_path: &str) -> String {
+    ref_path.split('/').map(|segment| urlencoding::encode(segment)).collect::<Vec<_>>().join("/")
+}
+
+/// GitHub answers 422 "Reference already exists" when the ref is taken, which
+/// is worth telling apart from other validation failures such as a bad SHA.
+pub fn create_ref_error(ref_name: &str, status: reqwest::StatusCode, body: &str) -> anyhow::Error {
+    let message = serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|v| v.get("message").and_then(|m| m.as_str()).map(str::to_string))
+        .unwrap_or_else(|| body.to_string());
+
+    if status == reqwest::StatusCode::UNPROCESSABLE_ENTITY && message.contains("already exists") {
+        let name = ref_name.strip_prefix("refs/heads/").unwrap_or(ref_name);
+        return anyhow!("Branch {} already exists", name);
+    }
+
+    anyhow!("Failed to create ref {}: {} {}", ref_name, status, message)
+}
+
+pub const MERGE_METHODS: [&str; 3] = ["merge", "squash", "rebase"];
+
+/// Explains a failed merge. GitHub answers 405 when the pull request cannot be
+/// merged (conflicts, failing required checks, a disallowed merge method) and
+/// 409 when the head moved while merging; both carry a `message` worth showing.
+pub fn merge_error(number: u64, status: reqwest::StatusCode, body: &str) -> anyhow::Error {
+    let message = serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|v| v.get("message").and_then(|m| m.as_str()).map(str::to_string))
+        .filter(|m| !m.is_empty());
+
+    match (status.as_u16(), message) {
+        (405, Some(message)) => anyhow!("Pull request #{} is not mergeable: {}", number, message),
+        (405, None) => anyhow!("Pull request #{} is not mergeable", number),
+        (409, Some(message)) => anyhow!("Pull request #{} changed while merging: {}", number, message),
+        (409, None) => anyhow!("Pull request #{} changed while merging; fetch it and try again", number),
+        (_, Some(message)) => anyhow!("Failed to merge pull request #{}: {} - {}", number, status, message),
+        (_, None) => anyhow!("Failed to merge pull request #{}: {}", number, status),
+    }
+}
+
+pub const MERGEABLE_MAX_ATTEMPTS: u32 = 5;
+pub const MERGEABLE_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Calls `fetch` until `mergeable` is non-null or `max_attempts` is reached.
+///
+/// GitHub computes mergeability in the background, so the first reads often return `null`.
+pub async fn poll_mergeable<F, Fut>(fetch: F, max_attempts: u32, delay: std::time::Duration) -> Result<MergeableStatus>
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = Result<(Option<bool>, Option<String>)>>,
+{
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let (mergeable, mergeable_state) = fetch().await?;
+
+        if mergeable.is_some() || attempts >= max_attempts {
+            return Ok(MergeableStatus { mergeable, mergeable_state, attempts });
+        }
+
+        debug!("Mergeability not computed yet (attempt {}/{}), retrying", attempts, max_attempts);
+        tokio::time::sleep(delay).await;
+    }
+}
+
+pub const STATS_MAX_ATTEMPTS: u32 = 5;
+pub const STATS_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Calls `fetch` until it returns data, treating `None` as GitHub's 202 "still computing".
+pub async fn poll_computed_stats<T, F, Fut>(fetch: F, max_attempts: u32, delay: std::time::Duration) -> Result<T>
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = Result<Option<T>>>,
+{
+    for attempt in 1..=max_attempts {
+        if let Some(stats) = fetch().await? {
+            return Ok(stats);
+        }
+
+        debug!("Statistics still being computed (attempt {}/{}), retrying", attempt, max_attempts);
+        if attempt < max_attempts {
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    Err(anyhow!(
+        "GitHub is still computing these statistics after {} attempts; try again shortly",
+        max_attempts
+    ))
+}
+
+/// Number of concurrent fetches behind `get_repository_overview`.
+const OVERVIEW_PIECES: usize = 6;
+
+/// Languages kept in a repository overview.
+const OVERVIEW_TOP_LANGUAGES: usize = 5;
+
+fn overview_piece<T>(errors: &mut BTreeMap<String, String>, name: &str, result: Result<T>) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(e) => {
+            errors.insert(name.to_string(), e.to_string());
+            None
+        }
+    }
+}
+
+/// The `limit` largest languages by bytes, with their share of the total in percent.
+pub fn top_languages(languages: &BTreeMap<String, u64>, limit: usize) -> Vec<LanguageShare> {
+    let total: u64 = languages.values().sum();
+    let mut shares: Vec<LanguageShare> = languages
+        .iter()
+        .map(|(name, bytes)| LanguageShare {
+            name: name.clone(),
+            bytes: *bytes,
+            percent: if total == 0 { 0.0 } else { (*bytes as f64 * 1000.0 / total as f64).round() / 10.0 },
+        })
+        .collect();
+    shares.sort_by_key(|share| std::cmp::Reverse(share.bytes));
+    shares.truncate(limit);
+    shares
+}
+
+pub fn parse_repo_languages(languages_data: Value) -> Result<BTreeMap<String, u64>> {
+    serde_json::from_value(languages_data)
+        .map_err(|e| anyhow!("Failed to parse repository languages: {}", e))
+}
+
+pub fn parse_default_branch(default_branch: &str, ref_data: Value) -> Result<DefaultBranch> {
+    let reference: GitReference = serde_json::from_value(ref_data)
+        .map_err(|e| anyhow!("Failed to parse git reference: {}", e))?;
+
+    let expected = format!("refs/heads/{}", default_branch);
+    if reference.r#ref != expected {
+        return Err(anyhow!("Expected ref {} but got {}", expected, reference.r#ref));
+    }
+
+    Ok(DefaultBranch {
+        default_branch: default_branch.to_string(),
+        sha: reference.object.sha,
+    })
+}
+
+pub fn parse_issue_value(data: Value) -> Result<Issue> {
+    let issue: Issue = serde_json::from_value(data).map_err(|e| anyhow!("Failed to parse issue: {}", e))?;
+    Ok(mark_pull_request(issue))
+}
+
+fn mark_pull_request(mut issue: Issue) -> Issue {
+    issue.is_pull_request = issue.pull_request.is_some();
+    issue
+}
+
+fn sub_issues_error(owner: &str, repo: &str, err: anyhow::Error) -> anyhow::Error {
+    let message = err.to_string();
+    if message.contains("subIssue") || message.contains("addSubIssue") || message.contains("sub-issue") {
+        anyhow!("Sub-issues are not available for {}/{}: {}", owner, repo, message)
+    } else {
+        err
+    }
+}
+
+/// Reads `pageInfo` from a GraphQL connection; a missing block means no more pages.
+pub fn graphql_page_info(connection: &Value) -> GraphQLPageInfo {
+    connection.get("pageInfo")
+        .and_then(|info| serde_json::from_value(info.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Nodes of a GraphQL connection, skipping nulls (e.g. items hidden by permissions).
+fn connection_nodes(connection: &Value) -> impl Iterator<Item = &Value> {
+    connection.get("nodes")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|node| !node.is_null())
+}
+
+fn project_node<'a>(data: &'a Value, org: &str, project_number: u64) -> Result<&'a Value> {
+    let organization = data.get("organization")
+        .filter(|v| !v.is_null())
+        .ok_or_else(|| anyhow!("Organization '{}' not found", org))?;
+    organization.get("projectV2")
+        .filter(|v| !v.is_null())
+        .ok_or_else(|| anyhow!("Project #{} not found in organization '{}'", project_number, org))
+}
+
+fn project_title(project: &Value) -> String {
+    project.get("title").and_then(|v| v.as_str()).unwrap_or_default().to_string()
+}
+
+/// Parses the `projectV2` node of a `list_project_items` query.
+pub fn parse_project_items(project: &Value) -> Result<ProjectItemList> {
+    let items = project.get("items")
+        .ok_or_else(|| anyhow!("No items in project response"))?;
+
+    let items_list = connection_nodes(items)
+        .map(|node| {
+            let id = node.get("id").and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow!("Project item without id"))?;
+
+            let content = node.get("content")
+                .filter(|v| v.as_object().is_some_and(|o| !o.is_empty()))
+                .map(|content| ProjectItemContent {
+                    title: content.get("title").and_then(|v| v.as_str()).map(str::to_string),
+                    number: content.get("number").and_then(|v| v.as_u64()),
+                    url: content.get("url").and_then(|v| v.as_str()).map(str::to_string),
+                    state: content.get("state").and_then(|v| v.as_str()).map(str::to_string),
+                    repository: content.pointer("/repository/nameWithOwner")
+                        .and_then(|v| v.as_str())
+                        .map(str::to_string),
+                });
+
+            // Values of field types the query does not select come back as `{}`
+            let field_values = node.get("fieldValues")
+                .map(|values| connection_nodes(values)
+                    .filter_map(|value| {
+                        let field = value.pointer("/field/name")?.as_str()?.to_string();
+                        let value = ["text", "number", "date", "name", "title"]
+                            .iter()
+                            .find_map(|key| value.get(*key).filter(|v| !v.is_null()))?
+                            .clone();
+                        Some(ProjectFieldValue { field, value })
+                    })
+                    .collect())
+                .unwrap_or_default();
+
+            Ok(ProjectItem {
+                id: id.to_string(),
+                item_type: node.get("type").and_then(|v| v.as_str()).unwrap_or("UNKNOWN").to_string(),
+                content,
+                field_values,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(ProjectItemList {
+        project_title: project_title(project),
+        items: items_list,
+        page_info: graphql_page_info(items),
+    })
+}
+
+/// Parses the `projectV2` node of a `get_project_fields` query.
+pub fn parse_project_fields(project: &Value) -> Result<ProjectFieldList> {
+    let fields = project.get("fields")
+        .ok_or_else(|| anyhow!("No fields in project response"))?;
+
+    let fields_list = connection_nodes(fields)
+        .filter(|node| node.get("id").is_some())
+        .map(|node| {
+            let text = |key: &str| node.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+            let options = match node.get("options") {
+                Some(options) => Some(serde_json::from_value(options.clone())
+                    .map_err(|e| anyhow!("Failed to parse field options: {}", e))?),
+                None => None,
+            };
+            let iterations = node.pointer("/configuration/iterations").map(|iterations| {
+                iterations.as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|iteration| ProjectIteration {
+                        id: iteration.get("id").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                        title: iteration.get("title").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                        start_date: iteration.get("startDate").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                        duration: iteration.get("duration").and_then(|v| v.as_u64()).unwrap_or(0) as u32,
+                    })
+                    .collect()
+            });
+
+            Ok(ProjectField {
+                id: text("id"),
+                name: text("name"),
+                data_type: text("dataType"),
+                options,
+                iterations,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(ProjectFieldList {
+        project_title: project_title(project),
+        fields: fields_list,
+        page_info: graphql_page_info(fields),
+    })
+}
+
+pub fn parse_sub_issues_response(data: &Value) -> Result<SubIssueList> {
+    let issue = data.pointer("/repository/issue")
+        .filter(|v| !v.is_null())
+        .ok_or_else(|| anyhow!("Issue not found in sub-issues response"))?;
+
+    let parent: SubIssue = serde_json::from_value(issue.clone())
+        .map_err(|e| anyhow!("Failed to parse parent issue: {}", e))?;
+
+    let summary = match issue.get("subIssuesSummary") {
+        Some(v) if !v.is_null() => Some(serde_json::from_value(v.clone())
+            .map_err(|e| anyhow!("Failed to parse sub-issues summary: {}", e))?),
+        _ => None,
+    };
+
+    let sub_issues = match issue.pointer("/subIssues/nodes") {
+        Some(nodes) => serde_json::from_value(nodes.clone())
+            .map_err(|e| anyhow!("Failed to parse sub-issues: {}", e))?,
+        None => Vec::new(),
+    };
+
+    Ok(SubIssueList { parent, summary, sub_issues })
+}
+
+pub fn parse_blame_response(data: &Value, path: &str, git_ref: &str) -> Result<FileBlame> {
+    let object = data.pointer("/repository/object")
+        .filter(|v| !v.is_null())
+        .ok_or_else(|| anyhow!("Ref '{}' not found", git_ref))?;
+
+    let ranges = object.pointer("/blame/ranges")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| anyhow!("No blame available for '{}' at '{}'", path, git_ref))?;
+
+    let ranges = ranges.iter().map(|range| {
+        let commit = range.get("commit").unwrap_or(&Value::Null);
+        let line = |field: &str| range.get(field)
+            .and_then(|v| v.as_u64())
+            .map(|n| n as u32)
+            .ok_or_else(|| anyhow!("Blame range is missing {}", field));
+        let text = |pointer: &str| commit.pointer(pointer)
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        Ok(BlameRange {
+            start_line: line("startingLine")?,
+            end_line: line("endingLine")?,
+            age: line("age")?,
+            commit_sha: text("/oid").ok_or_else(|| anyhow!("Blame range is missing commit oid"))?,
+            commit_message: text("/messageHeadline").unwrap_or_default(),
+            committed_date: text("/committedDate"),
+            author_name: text("/author/name"),
+            author_login: text("/author/user/login"),
+        })
+    }).collect::<Result<Vec<_>>>()?;
+
+    Ok(FileBlame {
+        path: path.to_string(),
+        git_ref: git_ref.to_string(),
+        ranges,
+    })
+}
+
+/// Extracts `data` from a GraphQL response body. GraphQL may return partial
+/// `data` alongside `errors`; under `AllowPartial` that data is kept as long
+/// as it is not null.
+pub fn graphql_response_data(mut response: Value, policy: GraphQLErrorPolicy) -> Result<Value> {
+    let data = response.get_mut("data")
+        .map(Value::take)
+        .filter(|v| !v.is_null());
+    let errors = response.get("errors")
+        .filter(|v| v.as_array().is_none_or(|a| !a.is_empty()));
+
+    match (data, errors) {
+        (Some(data), None) => Ok(data),
+        (Some(data), Some(errors)) if policy == GraphQLErrorPolicy::AllowPartial => {
+            warn!("GraphQL returned partial data with errors: {}", errors);
+            Ok(data)
+        }
+        (_, Some(errors)) => {
+            error!("GraphQL errors: {}", errors);
+            Err(anyhow!("GraphQL errors: {}", errors))
+        }
+        (None, None) => Err(anyhow!("No data in GraphQL response")),
+    }
+}
+
+/// Returns the payload of a mutation, which GraphQL nests under a single
+/// top-level field named after the mutation.
+pub fn mutation_payload(data: &Value) -> Result<Value> {
+    let fields = data.as_object()
+        .ok_or_else(|| anyhow!("GraphQL mutation data is not an object"))?;
+    let mut entries = fields.iter();
+    match (entries.next(), entries.next()) {
+        (Some((name, payload)), None) => {
+            if payload.is_null() {
+                Err(anyhow!("No {} payload in response", name))
+            } else {
+                Ok(payload.clone())
+            }
+        }
+        (None, _) => Err(anyhow!("GraphQL mutation returned no payload")),
+        (Some(_), Some(_)) => Err(anyhow!(
+            "Expected a single mutation payload, got {} fields",
+            fields.len()
+        )),
+    }
+}
+
+fn string_field(data: &Value, pointer: &str, what: &str) -> Result<String> {
+    data.pointer(pointer)
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("No {} in {} response", pointer.trim_start_matches('/'), what))
+}
+
+/// Parses the `addSubIssue` mutation payload.
+pub fn parse_sub_issue_link(payload: &Value) -> Result<SubIssueLink> {
+    let parent = payload.get("issue").cloned().unwrap_or(Value::Null);
+    let sub_issue = payload.get("subIssue").cloned().unwrap_or(Value::Null);
+
+    Ok(SubIssueLink {
+        parent: serde_json::from_value(parent)
+            .map_err(|e| anyhow!("Failed to parse parent issue: {}", e))?,
+        sub_issue: serde_json::from_value(sub_issue)
+            .map_err(|e| anyhow!("Failed to parse sub-issue: {}", e))?,
+    })
+}
+
+/// GitHub topics must be lowercase, start with a letter or number, contain only
+/// letters, numbers and hyphens, and be at most 50 characters long.
+pub fn validate_topic_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.len() > 50 {
+        return Err(anyhow!("Topic '{}' must be between 1 and 50 characters", name));
+    }
+
+    if !name.chars().next().map(|c| c.is_ascii_lowercase() || c.is_ascii_digit()).unwrap_or(false) {
+        return Err(anyhow!("Topic '{}' must start with a lowercase letter or number", name));
+    }
+
+    if !name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-') {
+        return Err(anyhow!("Topic '{}' may only contain lowercase letters, numbers and hyphens", name));
+    }
+
+    Ok(())
+}
+
+pub fn build_replace_topics_body(names: &[String]) -> Result<Value> {
+    for name in names {
+        validate_topic_name(name)?;
+    }
+
+    Ok(serde_json::json!({ "names": names }))
+}
+
+pub fn build_tag_object_body(new_tag: &NewTag) -> Result<Value> {
+    if new_tag.tag.is_empty() {
+        return Err(anyhow!("Tag name must not be empty"));
+    }
+    if !matches!(new_tag.object_type.as_str(), "commit" | "tree" | "blob") {
+        return Err(anyhow!("Invalid object type '{}': expected commit, tree or blob", new_tag.object_type));
+    }
+
+    Ok(serde_json::to_value(new_tag)?)
+}
+
+/// `subscribed` receives notifications; `ignored` blocks all notifications.
+pub fn build_subscription_body(subscribed: bool, ignored: bool) -> Value {
+    serde_json::json!({ "subscribed": subscribed, "ignored": ignored })
+}
+
+pub fn build_list_issues_query(filters: &IssueFilters, per_page: Option<u8>, page: Option<u32>) -> String {
+    let mut params = Vec::new();
+
+    let labels = filters.labels.as_ref()
+        .filter(|labels| !labels.is_empty())
+        .map(|labels| labels.join(","));
+
+    let fields = [
+        ("state", filters.state.as_ref()),
+        ("labels", labels.as_ref()),
+        ("assignee", filters.assignee.as_ref()),
+        ("creator", filters.creator.as_ref()),
+        ("mentioned", filters.mentioned.as_ref()),
+        ("milestone", filters.milestone.as_ref()),
+        ("sort", filters.sort.as_ref()),
+        ("direction", filters.direction.as_ref()),
+        ("since", filters.since.as_ref()),
+    ];
+    for (name, value) in fields {
+        if let Some(value) = value {
+            params.push(format!("{}={}", name, urlencoding::encode(value)));
+        }
+    }
+
+    if let Some(per_page) = per_page {
+        params.push(format!("per_page={}", per_page));
+    }
+
+    if let Some(page) = page {
+        params.push(format!("page={}", page));
+    }
+
+    params.join("&")
+}
+
+/// Search query for open or closed issues in `org`. A `state` of `all` adds no
+/// state qualifier, and several labels must all match, as with `list_issues`.
+pub fn build_org_issues_search_query(org: &str, filters: &IssueFilters) -> String {
+    let mut qualifiers = vec![format!("org:{}", org), "is:issue".to_string()];
+
+    if let Some(state @ ("open" | "closed")) = filters.state.as_deref() {
+        qualifiers.push(format!("state:{}", state));
+    }
+
+    for label in filters.labels.iter().flatten() {
+        qualifiers.push(format!("label:{}", quote_search_value(label)));
+    }
+
+    let users = [
+        ("assignee", &filters.assignee),
+        ("author", &filters.creator),
+        ("mentions", &filters.mentioned),
+    ];
+    for (qualifier, user) in users {
+        if let Some(user) = user {
+            qualifiers.push(format!("{}:{}", qualifier, quote_search_value(user)));
+        }
+    }
+
+    qualifiers.join(" ")
+}
+
+/// Quotes a qualifier value containing spaces, like `label:"good first issue"`.
+fn quote_search_value(value: &str) -> String {
+    if value.contains(char::is_whitespace) {
+        format!("\"{}\"", value.replace('"', ""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn build_list_commits_query(filters: &CommitFilters, per_page: Option<u8>, page: Option<u32>) -> String {
+    let mut params = Vec::new();
+
+    let fields = [
+        ("sha", &filters.sha),
+        ("path", &filters.path),
+        ("author", &filters.author),
+        ("committer", &filters.committer),
+        ("since", &filters.since),
+        ("until", &filters.until),
+    ];
+    for (name, value) in fields {
+        if let Some(value) = value {
+            params.push(format!("{}={}", name, urlencoding::encode(value)));
+        }
+    }
+
+    if let Some(per_page) = per_page {
+        params.push(format!("per_page={}", per_page));
+    }
+
+    if let Some(page) = page {
+        params.push(format!("page={}", page));
+    }
+
+    params.join("&")
+}
+
+/// Query string for `list_workflow_runs`; the workflow id is part of the path instead.
+pub fn build_list_workflow_runs_query(filters: &WorkflowRunFilters, per_page: Option<u8>, page: Option<u32>) -> String {
+    let mut params = Vec::new();
+
+    for (name, value) in [("status", &filters.status), ("branch", &filters.branch)] {
+        if let Some(value) = value {
+            params.push(format!("{}={}", name, urlencoding::encode(value)));
+        }
+    }
+
+    if let Some(per_page) = per_page {
+        params.push(format!("per_page={}", per_page));
+    }
+
+    if let Some(page) = page {
+        params.push(format!("page={}", page));
+    }
+
+    params.join("&")
+}
+
+/// Reduces the commits list to sha, message subject, author, author date and
+/// signature verification.
+pub fn parse_commit_summaries(data: &Value) -> Result<Vec<CommitSummary>> {
+    let commits = data.as_array()
+        .ok_or_else(|| anyhow!("Failed to parse commits list: expected an array"))?;
+
+    commits.iter().map(parse_commit_summary).collect()
+}
+
+fn parse_commit_summary(commit: &Value) -> Result<CommitSummary> {
+    let sha = commit.get("sha")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow!("Failed to parse commit: missing sha"))?;
+    let message = commit.pointer("/commit/message").and_then(|v| v.as_str()).unwrap_or("");
+    let verification = match commit.pointer("/commit/verification") {
+        Some(v) if !v.is_null() => Some(serde_json::from_value(v.clone())
+            .map_err(|e| anyhow!("Failed to parse commit verification: {}", e))?),
+        _ => None,
+    };
+
+    Ok(CommitSummary {
+        sha: sha.to_string(),
+        subject: message.lines().next().unwrap_or("").to_string(),
+        author: commit.pointer("/commit/author/name").and_then(|v| v.as_str()).map(String::from),
+        author_login: commit.pointer("/author/login").and_then(|v| v.as_str()).map(String::from),
+        date: commit.pointer("/commit/author/date").and_then(|v| v.as_str()).map(String::from),
+        html_url: commit.get("html_url").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+        verification,
+    })
+}
+
+/// Parses a single-commit response: the summary plus full message, parents,
+/// stats and changed files.
+pub fn parse_commit_detail(commit: &Value) -> Result<CommitDetail> {
+    let parents = commit.get("parents")
+        .and_then(|v| v.as_array())
+        .map(|parents| parents.iter()
+            .filter_map(|p| p.get("sha").and_then(|v| v.as_str()).map(String::from))
+            .collect())
+        .unwrap_or_default();
+    let stats = match commit.get("stats") {
+        Some(v) if !v.is_null() => Some(serde_json::from_value(v.clone())
+            .map_err(|e| anyhow!("Failed to parse commit stats: {}", e))?),
+        _ => None,
+    };
+    let files = match commit.get("files") {
+        Some(v) if !v.is_null() => serde_json::from_value(v.clone())
+            .map_err(|e| anyhow!("Failed to parse commit files: {}", e))?,
+        _ => Vec::new(),
+    };
+
+    Ok(CommitDetail {
+        summary: parse_commit_summary(commit)?,
+        message: commit.pointer("/commit/message").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+        parents,
+        stats,
+        files,
+        diff: None,
+    })
+}
+
+/// GitHub answers 403 when the token can't read security alerts, which is
+/// almost always a missing scope rather than a missing repository.
+pub fn security_alerts_error(status: reqwest::StatusCode, body: &str, kind: &str, owner: &str, repo: &str) -> anyhow::Error {
+    let message = serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|v| v.get("message").and_then(|m| m.as_str()).map(str::to_string))
+        .unwrap_or_else(|| body.to_string());
+
+    if status == reqwest::StatusCode::FORBIDDEN {
+        return anyhow!(
+            "Not permitted to read {} alerts for {}/{}: the token needs the `security_events` scope (classic) or read access to {} alerts (fine-grained), and the feature must be enabled for the repository. GitHub said: {}",
+            kind, owner, repo, kind, message
+        );
+    }
+
+    anyhow!("Failed to list {} alerts: {} {}", kind, status, message)
+}
+
+/// Workflow dispatch inputs are always strings on the GitHub side, so scalar
+/// values are stringified rather than rejected.
+pub fn build_dispatch_body(git_ref: &str, inputs: Option<&Map<String, Value>>) -> Value {
+    let mut body = serde_json::json!({ "ref": git_ref });
+
+    if let Some(inputs) = inputs {
+        let inputs: Map<String, Value> = inputs.iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    Value::String(s) => Value::String(s.clone()),
+                    other => Value::String(other.to_string()),
+                };
+                (key.clone(), value)
+            })
+            .collect();
+        body["inputs"] = Value::Object(inputs);
+    }
+
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_project_items_response() {
+        let data = serde_json::json!({
+            "organization": {
+                "projectV2": {
+                    "title": "Roadmap",
+                    "items": {
+                        "pageInfo": {"hasNextPage": true, "endCursor": "Y3Vyc29yOjI="},
+                        "nodes": [
+                            {
+                                "id": "PVTI_1",
+                                "type": "ISSUE",
+                                "content": {
+                                    "title": "Ship projects support",
+                                    "number": 42,
+                                    "url": "https://github.com/octo/repo/issues/42",
+                                    "state": "OPEN",
+                                    "repository": {"nameWithOwner": "octo/repo"}
+                                },
+                                "fieldValues": {
+                                    "nodes": [
+                                        {},
+                                        {"text": "Ship projects support", "field": {"name": "Title"}},
+                                        {"name": "In Progress", "field": {"name": "Status"}},
+                                        {"number": 3.0, "field": {"name": "Estimate"}},
+                                        {"title": "Sprint 7", "field": {"name": "Iteration"}}
+                                    ]
+                                }
+                            },
+                            {
+                                "id": "PVTI_2",
+                                "type": "DRAFT_ISSUE",
+                                "content": {"title": "Write docs"},
+                                "fieldValues": {"nodes": [{"date": "2024-05-01", "field": {"name": "Due"}}]}
+                            },
+                            null
+                        ]
+                    }
+                }
+            }
+        });
+
+        let project = project_node(&data, "octo", 1).unwrap();
+        let list = parse_project_items(project).unwrap();
+        assert_eq!(list.project_title, "Roadmap");
+        assert_eq!(list.page_info, GraphQLPageInfo { has_next_page: true, end_cursor: Some("Y3Vyc29yOjI=".to_string()) });
+        assert_eq!(list.items.len(), 2);
+
+        let issue = &list.items[0];
+        assert_eq!(issue.item_type, "ISSUE");
+        let content = issue.content.as_ref().unwrap();
+        assert_eq!(content.number, Some(42));
+        assert_eq!(content.repository.as_deref(), Some("octo/repo"));
+        assert_eq!(issue.field_values.len(), 4);
+        assert_eq!(issue.field_values[1], ProjectFieldValue { field: "Status".to_string(), value: serde_json::json!("In Progress") });
+        assert_eq!(issue.field_values[2].value, serde_json::json!(3.0));
+        assert_eq!(issue.field_values[3].value, serde_json::json!("Sprint 7"));
+
+        let draft = &list.items[1];
+        assert_eq!(draft.content.as_ref().unwrap().number, None);
+        assert_eq!(draft.field_values[0].value, serde_json::json!("2024-05-01"));
+
+        let missing = serde_json::json!({"organization": {"projectV2": null}});
+        assert!(project_node(&missing, "octo", 9).unwrap_err().to_string().contains("Project #9"));
+    }
+
+    #[test]
+    fn test_parse_project_fields_response() {
+        let project = serde_json::json!({
+            "title": "Roadmap",
+            "fields": {
+                "pageInfo": {"hasNextPage": false, "endCursor": null},
+                "nodes": [
+                    {"id": "PVTF_1", "name": "Title", "dataType": "TITLE"},
+                    {"id": "PVTSSF_2", "name": "Status", "dataType": "SINGLE_SELECT",
+                     "options": [{"id": "f75ad846", "name": "Todo"}, {"id": "47fc9ee4", "name": "Done"}]},
+                    {"id": "PVTIF_3", "name": "Iteration", "dataType": "ITERATION",
+                     "configuration": {"iterations": [{"id": "c1", "title": "Sprint 7", "startDate": "2024-05-06", "duration": 14}]}}
+                ]
+            }
+        });
+
+        let list = parse_project_fields(&project).unwrap();
+        assert!(!list.page_info.has_next_page);
+        assert_eq!(list.fields.len(), 3);
+        assert!(list.fields[0].options.is_none());
+        assert_eq!(list.fields[1].options.as_ref().unwrap()[1].name, "Done");
+        let iteration = &list.fields[2].iterations.as_ref().unwrap()[0];
+        assert_eq!((iteration.title.as_str(), iteration.duration), ("Sprint 7", 14));
+    }
+
+    #[test]
+    fn test_graphql_response_data_with_partial_errors() {
+        let response = serde_json::json!({
+            "data": {
+                "addSubIssue": {
+                    "issue": { "id": "I_parent", "number": 1 },
+                    "subIssue": null
+                }
+            },
+            "errors": [
+                { "message": "Resource not accessible", "path": ["addSubIssue", "subIssue"] }
+            ]
+        });
+
+        let err = graphql_response_data(response.clone(), GraphQLErrorPolicy::Strict).unwrap_err();
+        assert!(err.to_string().contains("Resource not accessible"));
+
+        let data = graphql_response_data(response, GraphQLErrorPolicy::AllowPartial).unwrap();
+        let payload = mutation_payload(&data).unwrap();
+        assert_eq!(payload["issue"]["number"], 1);
+        assert!(payload["subIssue"].is_null());
+
+        let null_data = serde_json::json!({
+            "data": null,
+            "errors": [{ "message": "Could not resolve to a node" }]
+        });
+        assert!(graphql_response_data(null_data, GraphQLErrorPolicy::AllowPartial).is_err());
+
+        let no_payload = serde_json::json!({ "addSubIssue": null });
+        assert!(mutation_payload(&no_payload).unwrap_err().to_string().contains("addSubIssue"));
+    }
+
+    #[tokio::test]
+    async fn test_add_sub_issue_returns_mutation_payload() {
+        use wiremock::matchers::{body_string_contains, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST")).and(path("/api/graphql"))
+            .and(body_string_contains("repository(owner"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"repository": {"parent": {"id": "I_parent"}, "child": {"id": "I_child"}}}
+            })))
+            .mount(&server).await;
+        Mock::given(method("POST")).and(path("/api/graphql"))
+            .and(body_string_contains("addSubIssue"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"addSubIssue": {
+                    "issue": {"id": "I_parent", "number": 1, "title": "Epic", "state": "OPEN", "url": "https://github.com/octo/repo/issues/1"},
+                    "subIssue": {"id": "I_child", "number": 2, "title": "Task", "state": "OPEN", "url": "https://github.com/octo/repo/issues/2"}
+                }}
+            })))
+            .mount(&server).await;
+
+        let client = GitHubClient::new(GitHubConfig { max_retries: 0, ..test_config(&server.uri()) }).await.unwrap();
+
+        let link = client.add_sub_issue("octo", "repo", 1, 2).await.unwrap();
+        assert_eq!(link.parent.number, 1);
+        assert_eq!(link.sub_issue.number, 2);
+    }
+
+    #[test]
+    fn test_parse_sub_issues_response() {
+        let data = serde_json::json!({
+            "repository": {
+                "issue": {
+                    "id": "I_parent",
+                    "number": 1,
+                    "title": "Epic",
+                    "state": "OPEN",
+                    "url": "https://github.com/octo/repo/issues/1",
+                    "subIssuesSummary": {"total": 2, "completed": 1, "percentCompleted": 50},
+                    "subIssues": {
+                        "nodes": [
+                            {"id": "I_a", "number": 2, "title": "Task A", "state": "CLOSED", "url": "https://github.com/octo/repo/issues/2"},
+                            {"id": "I_b", "number": 3, "title": "Task B", "state": "OPEN", "url": "https://github.com/octo/repo/issues/3"}
+                        ]
+                    }
+                }
+            }
+        });
+
+        let list = parse_sub_issues_response(&data).unwrap();
+        assert_eq!(list.parent.number, 1);
+        assert_eq!(list.sub_issues.len(), 2);
+        assert_eq!(list.sub_issues[1].title, "Task B");
+
+        let summary = list.summary.unwrap();
+        assert_eq!(summary.total, 2);
+        assert_eq!(summary.percent_completed, 50);
+    }
+
+    #[test]
+    fn test_parse_blame_response() {
+        let data = serde_json::json!({
+            "repository": {
+                "object": {
+                    "blame": {
+                        "ranges": [
+                            {
+                                "startingLine": 1, "endingLine": 12, "age": 10,
+                                "commit": {
+                                    "oid": "aaa111", "messageHeadline": "Initial commit",
+                                    "committedDate": "2023-01-02T03:04:05Z",
+                                    "author": {"name": "Mona", "user": {"login": "octocat"}}
+                                }
+                            },
+                            {
+                                "startingLine": 13, "endingLine": 13, "age": 1,
+                                "commit": {
+                                    "oid": "bbb222", "messageHeadline": "Fix off-by-one",
+                                    "committedDate": "2024-06-01T00:00:00Z",
+                                    "author": {"name": "Former Contributor", "user": null}
+                                }
+                            }
+                        ]
+                    }
+                }
+            }
+        });
+
+        let blame = parse_blame_response(&data, "src/lib.rs", "main").unwrap();
+        assert_eq!(blame.git_ref, "main");
+        assert_eq!(blame.ranges.len(), 2);
+        assert_eq!((blame.ranges[0].start_line, blame.ranges[0].end_line), (1, 12));
+        assert_eq!(blame.ranges[0].author_login.as_deref(), Some("octocat"));
+        assert_eq!(blame.ranges[1].commit_sha, "bbb222");
+        assert_eq!(blame.ranges[1].author_name.as_deref(), Some("Former Contributor"));
+        assert_eq!(blame.ranges[1].author_login, None);
+
+        let missing = serde_json::json!({"repository": {"object": null}});
+        assert!(parse_blame_response(&missing, "src/lib.rs", "nope").is_err());
+    }
+
+    #[test]
+    fn test_parse_sub_issues_missing_issue() {
+        let data = serde_json::json!({"repository": {"issue": null}});
+        assert!(parse_sub_issues_response(&data).is_err());
+    }
+
+    #[test]
+    fn test_validate_topic_name() {
+        assert!(validate_topic_name("rust").is_ok());
+        assert!(validate_topic_name("mcp-server").is_ok());
+        assert!(validate_topic_name("web3").is_ok());
+        assert!(validate_topic_name("Rust").is_err());
+        assert!(validate_topic_name("-leading").is_err());
+        assert!(validate_topic_name("under_score").is_err());
+        assert!(validate_topic_name("").is_err());
+        assert!(validate_topic_name(&"a".repeat(51)).is_err());
+    }
+
+    #[test]
+    fn test_build_replace_topics_body() {
+        let names = vec!["rust".to_string(), "mcp-server".to_string()];
+        let body = build_replace_topics_body(&names).unwrap();
+        assert_eq!(body, serde_json::json!({"names": ["rust", "mcp-server"]}));
+
+        let empty = build_replace_topics_body(&[]).unwrap();
+        assert_eq!(empty, serde_json::json!({"names": []}));
+
+        assert!(build_replace_topics_body(&["Bad Topic".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_parse_workflow_runs() {
+        let data = serde_json::json!({
+            "total_count": 1,
+            "workflow_runs": [{
+                "id": 30433642,
+                "name": "Build",
+                "workflow_id": 159038,
+                "run_number": 562,
+                "event": "push",
+                "status": "completed",
+                "conclusion": "success",
+                "head_branch": "main",
+                "head_sha": "acb5820ced9479c074f688cc328bf03f341a511d",
+                "html_url": "https://github.com/octo/repo/actions/runs/30433642",
+                "created_at": "2020-01-22T19:33:08Z",
+                "updated_at": "2020-01-22T19:33:08Z",
+                "actor": {"login": "octocat"}
+            }]
+        });
+
+        let runs: WorkflowRunList = serde_json::from_value(data).unwrap();
+        assert_eq!(runs.total_count, 1);
+        assert_eq!(runs.workflow_runs[0].id, 30433642);
+        assert_eq!(runs.workflow_runs[0].status.as_deref(), Some("completed"));
+        assert_eq!(runs.workflow_runs[0].conclusion.as_deref(), Some("success"));
+    }
+
+    #[test]
+    fn test_parse_deployments() {
+        let data = serde_json::json!([{
+            "url": "https://api.github.com/repos/octo/repo/deployments/1",
+            "id": 1,
+            "node_id": "MDEwOkRlcGxveW1lbnQx",
+            "sha": "a84d88e7554fc1fa21bcbc4efae3c782a70d2b9d",
+            "ref": "topic-branch",
+            "task": "deploy",
+            "payload": {},
+            "original_environment": "staging",
+            "environment": "production",
+            "description": "Deploy request from hubot",
+            "creator": {"login": "octocat", "id": 1},
+            "created_at": "2012-07-20T01:19:13Z",
+            "updated_at": "2012-07-20T01:19:13Z",
+            "statuses_url": "https://api.github.com/repos/octo/repo/deployments/1/statuses",
+            "repository_url": "https://api.github.com/repos/octo/repo",
+            "transient_environment": false,
+            "production_environment": true
+        }]);
+
+        let deployments: Vec<Deployment> = serde_json::from_value(data).unwrap();
+        assert_eq!(deployments[0].id, 1);
+        assert_eq!(deployments[0].r#ref, "topic-branch");
+        assert_eq!(deployments[0].environment, "production");
+        assert_eq!(deployments[0].production_environment, Some(true));
+        assert!(deployments[0].latest_status.is_none());
+
+        let statuses: Vec<DeploymentStatus> = serde_json::from_value(serde_json::json!([{
+            "id": 1,
+            "state": "success",
+            "description": "Deployment finished successfully.",
+            "environment": "production",
+            "environment_url": "https://example.com",
+            "log_url": "https://example.com/deployment/42/output",
+            "created_at": "2012-07-20T01:19:13Z",
+            "updated_at": "2012-07-20T01:19:13Z"
+        }])).unwrap();
+        assert_eq!(statuses[0].state, "success");
+        assert_eq!(statuses[0].environment_url.as_deref(), Some("https://example.com"));
+    }
+
+    #[tokio::test]
+    async fn test_list_deployments_keeps_deployments_whose_status_fails() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let deployment = |id: u64| serde_json::json!({
+            "id": id,
+            "sha": "a84d88e7554fc1fa21bcbc4efae3c782a70d2b9d",
+            "ref": "main",
+            "task": "deploy",
+            "environment": "production",
+            "creator": {"login": "octocat", "id": 1},
+            "created_at": "2012-07-20T01:19:13Z",
+            "updated_at": "2012-07-20T01:19:13Z"
+        });
+        Mock::given(method("GET")).and(path("/api/v3/repos/octo/repo/deployments"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([deployment(3), deployment(2), deployment(1)])))
+            .mount(&server).await;
+        for id in [3, 1] {
+            Mock::given(method("GET")).and(path(format!("/api/v3/repos/octo/repo/deployments/{}/statuses", id)))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                    "id": id * 10,
+                    "state": "success",
+                    "created_at": "2012-07-20T01:19:13Z",
+                    "updated_at": "2012-07-20T01:19:13Z"
+                }])))
+                .mount(&server).await;
+        }
+        Mock::given(method("GET")).and(path("/api/v3/repos/octo/repo/deployments/2/statuses"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server).await;
+
+        let client = test_client(&server.uri()).await;
+
+        let deployments = client.list_deployments("octo", "repo", None, None, None, None).await.unwrap();
+        let summary: Vec<(u64, Option<u64>)> = deployments
+            .iter()
+            .map(|d| (d.id, d.latest_status.as_ref().map(|s| s.id)))
+            .collect();
+        assert_eq!(summary, vec![(3, Some(30)), (2, None), (1, Some(10))]);
+    }
+
+    #[test]
+    fn test_parse_environments() {
+        let data = serde_json::json!({
+            "total_count": 1,
+            "environments": [{
+                "id": 161088068,
+                "node_id": "MDExOkVudmlyb25tZW50MTYxMDg4MDY4",
+                "name": "staging",
+                "url": "https://api.github.com/repos/octo/repo/environments/staging",
+                "html_url": "https://github.com/octo/repo/deployments/activity_log?environments_filter=staging",
+                "created_at": "2020-11-23T22:00:40Z",
+                "updated_at": "2020-11-23T22:00:40Z",
+                "protection_rules": [
+                    {"id": 3736, "node_id": "MDQ6R2F0ZTM3MzY=", "type": "wait_timer", "wait_timer": 30},
+                    {
+                        "id": 3755,
+                        "node_id": "MDQ6R2F0ZTM3NTU=",
+                        "prevent_self_review": false,
+                        "type": "required_reviewers",
+                        "reviewers": [
+                            {"type": "User", "reviewer": {"login": "octocat", "id": 1}},
+                            {"type": "Team", "reviewer": {"id": 1, "name": "Justice League", "slug": "justice-league"}}
+                        ]
+                    },
+                    {"id": 3756, "node_id": "MDQ6R2F0ZTM3NTY=", "type": "branch_policy"}
+                ],
+                "deployment_branch_policy": {"protected_branches": false, "custom_branch_policies": true}
+            }]
+        });
+
+        let list: EnvironmentList = serde_json::from_value(data).unwrap();
+        assert_eq!(list.total_count, 1);
+        let staging = &list.environments[0];
+        assert_eq!(staging.name, "staging");
+        assert_eq!(staging.protection_rules.len(), 3);
+        assert_eq!(staging.protection_rules[0].wait_timer, Some(30));
+        let reviewers = &staging.protection_rules[1].reviewers;
+        assert_eq!(reviewers[0].reviewer.login.as_deref(), Some("octocat"));
+        assert_eq!(reviewers[1].reviewer.slug.as_deref(), Some("justice-league"));
+        assert!(staging.protection_rules[2].reviewers.is_empty());
+        assert!(staging.deployment_branch_policy.as_ref().unwrap().custom_branch_policies);
+    }
+
+    #[test]
+    fn test_build_dispatch_body() {
+        let inputs = serde_json::json!({"environment": "staging", "debug": true, "retries": 3});
+        let body = build_dispatch_body("main", inputs.as_object());
+        assert_eq!(body, serde_json::json!({
+            "ref": "main",
+            "inputs": {"environment": "staging", "debug": "true", "retries": "3"}
+        }));
+
+        assert_eq!(build_dispatch_body("v1.0.0", None), serde_json::json!({"ref": "v1.0.0"}));
+    }
+
+    #[test]
+    fn test_build_list_issues_query_includes_all_filters() {
+        let filters = IssueFilters {
+            state: Some("open".to_string()),
+            labels: Some(vec!["bug".to_string(), "good first issue".to_string()]),
+            assignee: Some("octocat".to_string()),
+            creator: Some("hubot".to_string()),
+            mentioned: Some("monalisa".to_string()),
+            milestone: Some("*".to_string()),
+            sort: Some("updated".to_string()),
+            direction: Some("asc".to_string()),
+            since: Some("2024-01-01T00:00:00Z".to_string()),
+        };
+
+        assert_eq!(
+            build_list_issues_query(&filters, Some(50), Some(3)),
+            "state=open&labels=bug%2Cgood%20first%20issue&assignee=octocat&creator=hubot&mentioned=monalisa\
+             &milestone=%2A&sort=updated&direction=asc&since=2024-01-01T00%3A00%3A00Z&per_page=50&page=3"
+        );
+
+        let none = IssueFilters { milestone: Some("none".to_string()), labels: Some(vec![]), ..Default::default() };
+        assert_eq!(build_list_issues_query(&none, None, None), "milestone=none");
+    }
+
+    #[test]
+    fn test_build_list_commits_query_encodes_path() {
+        let filters = CommitFilters {
+            path: Some("src/my file.rs".to_string()),
+            author: Some("octocat".to_string()),
+            since: Some("2024-01-01T00:00:00Z".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            build_list_commits_query(&filters, Some(10), Some(2)),
+            "path=src%2Fmy%20file.rs&author=octocat&since=2024-01-01T00%3A00%3A00Z&per_page=10&page=2"
+        );
+    }
+
+    #[test]
+    fn test_build_list_workflow_runs_query_encodes_filters() {
+        let filters = WorkflowRunFilters {
+            workflow_id: Some("ci.yml".to_string()),
+            status: Some("in_progress".to_string()),
+            branch: Some("release/2.0 & hotfix".to_string()),
+        };
+
+        assert_eq!(
+            build_list_workflow_runs_query(&filters, Some(20), None),
+            "status=in_progress&branch=release%2F2.0%20%26%20hotfix&per_page=20"
+        );
+        assert_eq!(build_list_workflow_runs_query(&WorkflowRunFilters::default(), None, None), "");
+    }
+
+    #[tokio::test]
+    async fn test_delete_file_sends_sha_and_branch() {
+        use wiremock::matchers::{body_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE")).and(path("/api/v3/repos/octo/repo/contents/docs/old%20notes.md"))
+            .and(body_json(serde_json::json!({
+                "message": "Remove old notes",
+                "sha": "329688480d39049927147c162b9d2deaf885005f",
+                "branch": "cleanup"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "content": null,
+                "commit": {
+                    "sha": "7638417db6d59f3c431d3e1f261cc637155684cd",
+                    "url": "https://api.github.com/repos/octo/repo/git/commits/7638417",
+                    "html_url": "https://github.com/octo/repo/commit/7638417",
+                    "author": {"name": "Monalisa Octocat", "email": "mona@github.com", "date": "2024-05-01T09:00:00Z"},
+                    "committer": {"name": "Monalisa Octocat", "email": "mona@github.com", "date": "2024-05-01T09:00:00Z"},
+                    "message": "Remove old notes",
+                    "tree": {"sha": "691272480426f78a0138979dd3ce63b77f706feb", "url": "https://api.github.com/repos/octo/repo/git/trees/6912"},
+                    "parents": [{"sha": "1acc419d4d6a9ce985db7be48c6349a0475975b5", "url": "https://api.github.com/repos/octo/repo/git/commits/1acc419", "html_url": "https://github.com/octo/repo/commit/1acc419"}]
+                }
+            })))
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("DELETE")).and(path("/api/v3/repos/octo/repo/contents/README.md"))
+            .respond_with(ResponseTemplate::new(409).set_body_json(serde_json::json!({
+                "message": "README.md does not match 1111111111111111111111111111111111111111"
+            })))
+            .mount(&server).await;
+
+        let client = test_client(&server.uri()).await;
+
+        let deletion = client.delete_file(
+            "octo", "repo", "docs/old notes.md", "Remove old notes",
+            "329688480d39049927147c162b9d2deaf885005f", Some("cleanup"),
+        ).await.unwrap();
+        assert_eq!(deletion.path, "docs/old notes.md");
+        assert_eq!(deletion.commit.sha, "7638417db6d59f3c431d3e1f261cc637155684cd");
+
+        let err = client.delete_file(
+            "octo", "repo", "README.md", "Remove readme", "1111111111111111111111111111111111111111", None,
+        ).await.unwrap_err();
+        assert!(err.to_string().contains("has changed since blob 1111111111111111111111111111111111111111"));
+    }
+
+    #[tokio::test]
+    async fn test_create_branch_from_source_branch() {
+        use wiremock::matchers::{body_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET")).and(path("/api/v3/repos/octo/repo/git/ref/heads/release/1.x"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ref": "refs/heads/release/1.x",
+                "object": {"sha": "aa218f56b14c9653891f9e74264a383fa43fefbd", "type": "commit"}
+            })))
+            .mount(&server).await;
+        Mock::given(method("POST")).and(path("/api/v3/repos/octo/repo/git/refs"))
+            .and(body_json(serde_json::json!({
+                "ref": "refs/heads/fix-login",
+                "sha": "aa218f56b14c9653891f9e74264a383fa43fefbd"
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "ref": "refs/heads/fix-login",
+                "object": {"sha": "aa218f56b14c9653891f9e74264a383fa43fefbd", "type": "commit"}
+            })))
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("POST")).and(path("/api/v3/repos/octo/repo/git/refs"))
+            .respond_with(ResponseTemplate::new(422).set_body_json(serde_json::json!({
+                "message": "Reference already exists",
+                "documentation_url": "https://docs.github.com/rest/git/refs#create-a-reference"
+            })))
+            .mount(&server).await;
+
+        let client = test_client(&server.uri()).await;
+
+        let branch = client.create_branch("octo", "repo", "fix-login", Some("release/1.x")).await.unwrap();
+        assert_eq!(branch.ref_name, "refs/heads/fix-login");
+        assert_eq!(branch.sha, "aa218f56b14c9653891f9e74264a383fa43fefbd");
+        assert_eq!(branch.from_branch, "release/1.x");
+
+        let err = client.create_branch("octo", "repo", "main", Some("release/1.x")).await.unwrap_err();
+        assert_eq!(err.to_string(), "Branch main already exists");
+
+        let err = client.create_branch("octo", "repo", "x", Some("missing")).await.unwrap_err();
+        assert!(err.to_string().contains("Ref heads/missing not found"));
+
+        assert_eq!(
+            create_ref_error("refs/heads/x", reqwest::StatusCode::UNPROCESSABLE_ENTITY, r#"{"message": "Object does not exist"}"#).to_string(),
+            "Failed to create ref refs/heads/x: 422 Unprocessable Entity Object does not exist"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_commit_with_and_without_diff() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let diff = "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1 @@\n-old\n+new\n";
+        let server = MockServer::start().await;
+        Mock::given(method("GET")).and(path("/api/v3/repos/octo/repo/commits/v1.0.0"))
+            .and(header("accept", "application/vnd.github.diff"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(diff))
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("GET")).and(path("/api/v3/repos/octo/repo/commits/v1.0.0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sha": "6dcb09b5b57875f334f61aebed695e2e4193db5e",
+                "html_url": "https://github.com/octo/repo/commit/6dcb09b",
+                "commit": {
+                    "message": "Swap old for new\n\nCloses #3",
+                    "author": {"name": "Monalisa Octocat", "date": "2024-05-01T09:00:00Z"}
+                },
+                "author": {"login": "octocat"},
+                "parents": [{"sha": "7638417db6d59f3c431d3e1f261cc637155684cd"}],
+                "stats": {"additions": 1, "deletions": 1, "total": 2},
+                "files": [{
+                    "sha": "bbcd538c8e72b8c175046e27cc8f907076331401",
+                    "filename": "src/lib.rs",
+                    "status": "modified",
+                    "additions": 1,
+                    "deletions": 1,
+                    "changes": 2,
+                    "patch": "@@ -1 +1 @@\n-old\n+new"
+                }]
+            })))
+            .expect(2)
+            .mount(&server).await;
+
+        let client = test_client(&server.uri()).await;
+
+        let commit = client.get_commit("octo", "repo", "v1.0.0", false).await.unwrap();
+        assert_eq!(commit.summary.subject, "Swap old for new");
+        assert_eq!(commit.message, "Swap old for new\n\nCloses #3");
+        assert_eq!(commit.parents, vec!["7638417db6d59f3c431d3e1f261cc637155684cd"]);
+        assert_eq!(commit.stats.as_ref().unwrap().total, 2);
+        assert_eq!(commit.files[0].filename, "src/lib.rs");
+        assert!(commit.files[0].patch.is_none());
+        assert!(commit.diff.is_none());
+
+        let value = serde_json::to_value(&commit).unwrap();
+        assert_eq!(value["sha"], "6dcb09b5b57875f334f61aebed695e2e4193db5e");
+        assert!(value.get("diff").is_none());
+
+        let commit = client.get_commit("octo", "repo", "v1.0.0", true).await.unwrap();
+        assert_eq!(commit.diff.as_deref(), Some(diff));
+        assert_eq!(commit.files[0].patch.as_deref(), Some("@@ -1 +1 @@\n-old\n+new"));
+    }
+
+    #[test]
+    fn test_parse_commit_summaries() {
+        let data = serde_json::json!([{
+            "sha": "6dcb09b5b57875f334f61aebed695e2e4193db5e",
+            "html_url": "https://github.com/octo/repo/commit/6dcb09b",
+            "commit": {
+                "message": "Fix all the bugs\n\nLonger description",
+                "author": {"name": "Monalisa Octocat", "date": "2011-04-14T16:00:49Z"}
+            },
+            "author": {"login": "octocat"}
+        }]);
+
+        let commits = parse_commit_summaries(&data).unwrap();
+        assert_eq!(commits[0].subject, "Fix all the bugs");
+        assert_eq!(commits[0].author_login.as_deref(), Some("octocat"));
+        assert_eq!(commits[0].date.as_deref(), Some("2011-04-14T16:00:49Z"));
+        assert!(commits[0].verification.is_none());
+    }
+
+    #[test]
+    fn test_parse_commit_verification() {
+        let data = serde_json::json!([{
+            "sha": "7638417db6d59f3c431d3e1f261cc637155684cd",
+            "html_url": "https://github.com/octo/repo/commit/7638417",
+            "commit": {
+                "message": "Signed release",
+                "author": {"name": "Monalisa Octocat", "date": "2024-05-01T12:00:00Z"},
+                "verification": {
+                    "verified": true,
+                    "reason": "valid",
+                    "signature": "-----BEGIN PGP SIGNATURE-----\n...\n-----END PGP SIGNATURE-----",
+                    "payload": "tree 6913...",
+                    "verified_at": "2024-05-01T12:00:05Z"
+                }
+            },
+            "author": null
+        }]);
+
+        let commits = parse_commit_summaries(&data).unwrap();
+        let verification = commits[0].verification.as_ref().unwrap();
+        assert!(verification.verified);
+        assert_eq!(verification.reason, "valid");
+        assert!(verification.signature.as_deref().unwrap().starts_with("-----BEGIN PGP SIGNATURE-----"));
+
+        let commit: CommitInfo = serde_json::from_value(serde_json::json!({
+            "sha": "7638417db6d59f3c431d3e1f261cc637155684cd",
+            "url": "https://api.github.com/repos/octo/repo/git/commits/7638417",
+            "html_url": "https://github.com/octo/repo/commit/7638417",
+            "author": {"name": "Mona", "email": "mona@example.com", "date": "2024-05-01T12:00:00Z"},
+            "committer": {"name": "Mona", "email": "mona@example.com", "date": "2024-05-01T12:00:00Z"},
+            "message": "Update file",
+            "tree": {"sha": "691272480426f78a0138979dd3ce63b77f706feb", "url": "https://api.github.com/repos/octo/repo/git/trees/6912"},
+            "parents": [],
+            "verification": {"verified": false, "reason": "unsigned", "signature": null, "payload": null}
+        })).unwrap();
+        let verification = commit.verification.unwrap();
+        assert!(!verification.verified);
+        assert_eq!(verification.reason, "unsigned");
+        assert_eq!(verification.signature, None);
+    }
+
+    fn issue_json(pull_request: Option<Value>) -> Value {
+        let mut issue = serde_json::json!({
+            "id": 1,
+            "number": 42,
+            "title": "Add feature",
+            "body": null,
+            "user": {
+                "login": "octocat",
+                "id": 1,
+                "avatar_url": "https://github.com/images/error/octocat_happy.gif",
+                "html_url": "https://github.com/octocat",
+                "type": "User",
+                "site_admin": false
+            },
+            "labels": [],
+            "state": "open",
+            "locked": false,
+            "assignee": null,
+            "assignees": [],
+            "milestone": null,
+            "comments": 0,
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z",
+            "closed_at": null,
+            "html_url": "https://github.com/octo/repo/pull/42",
+            "reactions": {
+                "total_count": 3, "+1": 2, "-1": 0, "laugh": 0, "hooray": 1,
+                "confused": 0, "heart": 0, "rocket": 0, "eyes": 0
+            }
+        });
+        if let Some(link) = pull_request {
+            issue["pull_request"] = link;
+        }
+        issue
+    }
+
+    #[test]
+    fn test_parse_issue_marks_pull_requests() {
+        let pr = parse_issue_value(issue_json(Some(serde_json::json!({
+            "url": "https://api.github.com/repos/octo/repo/pulls/42",
+            "html_url": "https://github.com/octo/repo/pull/42",
+            "diff_url": "https://github.com/octo/repo/pull/42.diff",
+            "patch_url": "https://github.com/octo/repo/pull/42.patch"
+        })))).unwrap();
+        assert!(pr.is_pull_request);
+        assert_eq!(pr.reactions.as_ref().unwrap().plus_one, 2);
+
+        let issue = parse_issue_value(issue_json(None)).unwrap();
+        assert!(!issue.is_pull_request);
+    }
+
+    #[test]
+    fn test_parse_default_branch() {
+        let ref_data = serde_json::json!({
+            "ref": "refs/heads/main",
+            "node_id": "MDM6UmVmcmVmcy9oZWFkcy9tYWlu",
+            "url": "https://api.github.com/repos/octo/repo/git/refs/heads/main",
+            "object": {
+                "type": "commit",
+                "sha": "aa218f56b14c9653891f9e74264a383fa43fefbd",
+                "url": "https://api.github.com/repos/octo/repo/git/commits/aa218f56b14c9653891f9e74264a383fa43fefbd"
+            }
+        });
+
+        let branch = parse_default_branch("main", ref_data.clone()).unwrap();
+        assert_eq!(branch.default_branch, "main");
+        assert_eq!(branch.sha, "aa218f56b14c9653891f9e74264a383fa43fefbd");
+
+        assert!(parse_default_branch("develop", ref_data).is_err());
+    }
+
+    #[test]
+    fn test_top_languages() {
+        let languages = BTreeMap::from([
+            ("C".to_string(), 100),
+            ("Rust".to_string(), 700),
+            ("Shell".to_string(), 200),
+        ]);
+
+        let top = top_languages(&languages, 2);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].name, "Rust");
+        assert_eq!(top[0].percent, 70.0);
+        assert_eq!(top[1].name, "Shell");
+        assert!(top_languages(&BTreeMap::new(), 5).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_repository_overview_tolerates_partial_failure() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let repo = "/api/v3/repos/octo/repo";
+        let owner = serde_json::json!({
+            "id": 1,
+            "login": "octo",
+            "avatar_url": "https://github.com/images/error/octo.gif",
+            "html_url": "https://github.com/octo",
+            "type": "Organization",
+            "site_admin": false
+        });
+
+        Mock::given(method("GET")).and(path(repo))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": 1296269,
+                "name": "repo",
+                "full_name": "octo/repo",
+                "owner": owner,
+                "private": false,
+                "html_url": "https://github.com/octo/repo",
+                "description": "This your first repo!",
+                "fork": false,
+                "url": "https://api.github.com/repos/octo/repo",
+                "created_at": "2011-01-26T19:01:12Z",
+                "updated_at": "2011-01-26T19:14:43Z",
+                "pushed_at": "2011-01-26T19:06:43Z",
+                "clone_url": "https://github.com/octo/repo.git",
+                "ssh_url": "git@github.com:octo/repo.git",
+                "size": 108,
+                "stargazers_count": 80,
+                "watchers_count": 80,
+                "language": "Rust",
+                "forks_count": 9,
+                "archived": false,
+                "disabled": false,
+                "open_issues_count": 12,
+                "license": null,
+                "topics": ["mcp"],
+                "visibility": "public",
+                "default_branch": "main"
+            })))
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("GET")).and(path(format!("{}/git/ref/heads/main", repo)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ref": "refs/heads/main",
+                "object": {"sha": "aa218f56b14c9653891f9e74264a383fa43fefbd", "type": "commit"}
+            })))
+            .mount(&server).await;
+        Mock::given(method("GET")).and(path("/api/v3/search/issues"))
+            .and(query_param("q", "repo:octo/repo is:issue is:open"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"total_count": 9, "items": []})))
+            .mount(&server).await;
+        Mock::given(method("GET")).and(path("/api/v3/search/issues"))
+            .and(query_param("q", "repo:octo/repo is:pr is:open"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"total_count": 3, "items": []})))
+            .mount(&server).await;
+        Mock::given(method("GET")).and(path(format!("{}/releases/latest", repo)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": 1,
+                "tag_name": "v1.0.0",
+                "name": "v1.0.0",
+                "draft": false,
+                "prerelease": false,
+                "html_url": "https://github.com/octo/repo/releases/v1.0.0",
+                "created_at": "2013-02-27T19:35:32Z",
+                "published_at": "2013-02-27T19:35:32Z"
+            })))
+            .mount(&server).await;
+        Mock::given(method("GET")).and(path(format!("{}/languages", repo)))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server).await;
+
+        let client = GitHubClient::new(GitHubConfig { max_retries: 0, ..test_config(&server.uri()) }).await.unwrap();
+
+        let overview = client.get_repository_overview("octo", "repo").await.unwrap();
+        assert_eq!(overview.repository.as_ref().unwrap().full_name, "octo/repo");
+        assert_eq!(overview.open_issues, Some(9));
+        assert_eq!(overview.open_pull_requests, Some(3));
+        assert_eq!(overview.default_branch.as_ref().unwrap().sha, "aa218f56b14c9653891f9e74264a383fa43fefbd");
+        assert_eq!(overview.latest_release.as_ref().unwrap().tag_name, "v1.0.0");
+        assert!(overview.top_languages.is_none());
+        assert_eq!(overview.errors.len(), 1);
+        assert!(overview.errors["top_languages"].contains("500"));
+    }
+
+    #[tokio::test]
+    async fn test_repository_overview_reports_every_failure() {
+        use wiremock::matchers::any;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server).await;
+
+        let client = GitHubClient::new(GitHubConfig { max_retries: 0, ..test_config(&server.uri()) }).await.unwrap();
+
+        let error = client.get_repository_overview("octo", "repo").await.unwrap_err().to_string();
+        for field in ["repository", "open_issues", "open_pull_requests", "default_branch", "latest_release", "top_languages"] {
+            assert!(error.contains(&format!("{}: ", field)), "{} missing from {}", field, error);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_security_alerts() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET")).and(path("/api/v3/repos/octo/repo/secret-scanning/alerts"))
+            .and(query_param("state", "open"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                "number": 2,
+                "created_at": "2024-05-01T10:00:00Z",
+                "url": "https://api.github.com/repos/octo/repo/secret-scanning/alerts/2",
+                "html_url": "https://github.com/octo/repo/security/secret-scanning/2",
+                "state": "open",
+                "resolution": null,
+                "resolved_at": null,
+                "secret_type": "github_personal_access_token",
+                "secret_type_display_name": "GitHub Personal Access Token",
+                "validity": "active"
+            }])))
+            .expect(1).mount(&server).await;
+        Mock::given(method("GET")).and(path("/api/v3/repos/octo/repo/dependabot/alerts"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                "number": 7,
+                "state": "open",
+                "dependency": {
+                    "package": {"ecosystem": "npm", "name": "lodash"},
+                    "manifest_path": "package-lock.json",
+                    "scope": "runtime"
+                },
+                "security_advisory": {
+                    "ghsa_id": "GHSA-jf85-cpcp-j695",
+                    "cve_id": "CVE-2019-10744",
+                    "summary": "Prototype Pollution in lodash",
+                    "severity": "critical"
+                },
+                "security_vulnerability": {
+                    "package": {"ecosystem": "npm", "name": "lodash"},
+                    "severity": "critical",
+                    "vulnerable_version_range": "< 4.17.12",
+                    "first_patched_version": {"identifier": "4.17.12"}
+                },
+                "html_url": "https://github.com/octo/repo/security/dependabot/7",
+                "created_at": "2024-05-02T10:00:00Z",
+                "dismissed_reason": null,
+                "fixed_at": null
+            }])))
+            .expect(1).mount(&server).await;
+        Mock::given(method("GET")).and(path("/api/v3/repos/octo/private/dependabot/alerts"))
+            .respond_with(ResponseTemplate::new(403).set_body_json(serde_json::json!({
+                "message": "Resource not accessible by personal access token"
+            })))
+            .expect(1).mount(&server).await;
+
+        let client = test_client(&server.uri()).await;
+
+        let secrets = client.list_secret_scanning_alerts("octo", "repo", Some("open"), None, None, None).await.unwrap();
+        assert_eq!(secrets.len(), 1);
+        assert_eq!(secrets[0].secret_type, "github_personal_access_token");
+        assert_eq!(secrets[0].validity.as_deref(), Some("active"));
+
+        let alerts = client.list_dependabot_alerts("octo", "repo", None, None, None, None).await.unwrap();
+        assert_eq!(alerts[0].dependency.package.name, "lodash");
+        assert_eq!(alerts[0].security_advisory.severity, "critical");
+        let patched = alerts[0].security_vulnerability.as_ref().unwrap().first_patched_version.as_ref().unwrap();
+        assert_eq!(patched.identifier, "4.17.12");
+
+        let err = client.list_dependabot_alerts("octo", "private", None, None, None, None).await.unwrap_err().to_string();
+        assert!(err.starts_with("Not permitted to read Dependabot alerts for octo/private"), "{}", err);
+        assert!(err.contains("security_events"));
+        assert!(err.contains("Resource not accessible by personal access token"));
+    }
+
+    #[tokio::test]
+    async fn test_create_commit_with_files_sequence() {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let git = "/api/v3/repos/octo/repo/git";
+
+        Mock::given(method("GET")).and(path(format!("{}/ref/heads/main", git)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ref": "refs/heads/main", "object": {"sha": "head1", "type": "commit"}
+            })))
+            .expect(1).mount(&server).await;
+        Mock::given(method("GET")).and(path(format!("{}/commits/head1", git)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sha": "head1", "tree": {"sha": "basetree"}
+            })))
+            .expect(1).mount(&server).await;
+        Mock::given(method("POST")).and(path(format!("{}/blobs", git)))
+            .and(body_partial_json(serde_json::json!({"content": "fn main() {}\n"})))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({"sha": "blob-a"})))
+            .expect(1).mount(&server).await;
+        Mock::given(method("POST")).and(path(format!("{}/blobs", git)))
+            .and(body_partial_json(serde_json::json!({"content": "# Readme\n"})))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({"sha": "blob-b"})))
+            .expect(1).mount(&server).await;
+        Mock::given(method("POST")).and(path(format!("{}/trees", git)))
+            .and(body_partial_json(serde_json::json!({
+                "base_tree": "basetree",
+                "tree": [
+                    {"path": "src/main.rs", "sha": "blob-a", "mode": "100644", "type": "blob"},
+                    {"path": "README.md", "sha": "blob-b", "mode": "100644", "type": "blob"}
+                ]
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({"sha": "newtree"})))
+            .expect(1).mount(&server).await;
+        Mock::given(method("POST")).and(path(format!("{}/commits", git)))
+            .and(body_partial_json(serde_json::json!({
+                "message": "Add files", "tree": "newtree", "parents": ["head1"]
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "sha": "commit2", "html_url": "https://github.example/octo/repo/commit/commit2"
+            })))
+            .expect(1).mount(&server).await;
+        Mock::given(method("PATCH")).and(path(format!("{}/refs/heads/main", git)))
+            .and(body_partial_json(serde_json::json!({"sha": "commit2", "force": false})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ref": "refs/heads/main", "object": {"sha": "commit2"}
+            })))
+            .expect(1).mount(&server).await;
+
+        let client = test_client(&server.uri()).await;
+
+        let files = vec![
+            FileChange { path: "src/main.rs".to_string(), content: "fn main() {}\n".to_string() },
+            FileChange { path: "README.md".to_string(), content: "# Readme\n".to_string() },
+        ];
+        let commit = client
+            .create_commit_with_files("octo", "repo", "main", "Add files", &files)
+            .await
+            .unwrap();
+        assert_eq!(commit.sha, "commit2");
+        assert_eq!(commit.tree_sha, "newtree");
+        assert_eq!(commit.parent_sha, "head1");
+        assert_eq!(commit.files, vec!["src/main.rs", "README.md"]);
+
+        let order: Vec<String> = server.received_requests().await.unwrap()
+            .iter()
+            .map(|r| format!("{} {}", r.method, r.url.path().trim_start_matches(git)))
+            .collect();
+        assert_eq!(order, vec![
+            "GET /ref/heads/main",
+            "GET /commits/head1",
+            "POST /blobs",
+            "POST /blobs",
+            "POST /trees",
+            "POST /commits",
+            "PATCH /refs/heads/main",
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_create_commit_with_files_encodes_branch() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let git = "/api/v3/repos/octo/repo/git";
+
+        Mock::given(method("GET")).and(path(format!("{}/ref/heads/fix/%2312", git)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ref": "refs/heads/fix/#12", "object": {"sha": "head1", "type": "commit"}
+            })))
+            .expect(1).mount(&server).await;
+        Mock::given(method("GET")).and(path(format!("{}/commits/head1", git)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sha": "head1", "tree": {"sha": "basetree"}
+            })))
+            .mount(&server).await;
+        Mock::given(method("POST")).and(path(format!("{}/blobs", git)))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({"sha": "blob-a"})))
+            .mount(&server).await;
+        Mock::given(method("POST")).and(path(format!("{}/trees", git)))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({"sha": "newtree"})))
+            .mount(&server).await;
+        Mock::given(method("POST")).and(path(format!("{}/commits", git)))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "sha": "commit2", "html_url": "https://github.example/octo/repo/commit/commit2"
+            })))
+            .mount(&server).await;
+        Mock::given(method("PATCH")).and(path(format!("{}/refs/heads/fix/%2312", git)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ref": "refs/heads/fix/#12", "object": {"sha": "commit2"}
+            })))
+            .expect(1).mount(&server).await;
+
+        let client = test_client(&server.uri()).await;
+
+        let files = vec![FileChange { path: "src/main.rs".to_string(), content: "fn main() {}\n".to_string() }];
+        let commit = client
+            .create_commit_with_files("octo", "repo", "fix/#12", "Fix #12", &files)
+            .await
+            .unwrap();
+        assert_eq!(commit.sha, "commit2");
+    }
+
+    #[test]
+    fn test_release_asset_upload_url_per_host() {
+        let upload_url = |host: Option<&str>| {
+            let urls = GitHubClient::parse_api_host(&host.map(str::to_string)).unwrap();
+            release_asset_upload_url(&urls.upload, "octo", "repo", 42, "app v1+linux.tar.gz")
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(
+            upload_url(None),
+            "https://uploads.github.com/repos/octo/repo/releases/42/assets?name=app+v1%2Blinux.tar.gz"
+        );
+        assert_eq!(
+            upload_url(Some("https://octocorp.ghe.com")),
+            "https://uploads.octocorp.ghe.com/repos/octo/repo/releases/42/assets?name=app+v1%2Blinux.tar.gz"
+        );
+        assert_eq!(
+            upload_url(Some("https://github.example.com:8443")),
+            "https://github.example.com:8443/api/uploads/repos/octo/repo/releases/42/assets?name=app+v1%2Blinux.tar.gz"
+        );
+
+        assert_eq!(asset_content_type("app-linux.tar.gz"), "application/gzip");
+        assert_eq!(asset_content_type("checksums.SHA256"), "text/plain");
+        assert_eq!(asset_content_type("app"), "application/octet-stream");
+    }
+
+    #[tokio::test]
+    async fn test_upload_release_asset_posts_file() {
+        use wiremock::matchers::{body_bytes, header, method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("app.zip");
+        std::fs::write(&file, b"PK\x03\x04zipdata").unwrap();
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST")).and(path("/api/uploads/repos/octo/repo/releases/7/assets"))
+            .and(query_param("name", "app.zip"))
+            .and(header("content-type", "application/zip"))
+            .and(header("content-length", "11"))
+            .and(body_bytes(b"PK\x03\x04zipdata".to_vec()))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": 1,
+                "name": "app.zip",
+                "label": null,
+                "content_type": "application/zip",
+                "size": 11,
+                "state": "uploaded",
+                "browser_download_url": "https://github.example.com/octo/repo/releases/download/v1.0.0/app.zip"
+            })))
+            .expect(1)
+            .mount(&server).await;
+
+        let client = GitHubClient::new(GitHubConfig {
+            uploads_dir: Some(dir.path().to_path_buf()),
+            ..test_config(&server.uri())
+        }).await.unwrap();
+
+        let asset = client.upload_release_asset("octo", "repo", 7, Path::new("app.zip"), None, None).await.unwrap();
+        assert_eq!(asset.state, "uploaded");
+        assert_eq!(asset.browser_download_url, "https://github.example.com/octo/repo/releases/download/v1.0.0/app.zip");
+    }
+
+    #[test]
+    fn test_resolve_upload_path_stays_inside_uploads_dir() {
+        let root = tempfile::tempdir().unwrap();
+        let uploads = root.path().join("uploads");
+        std::fs::create_dir(&uploads).unwrap();
+        std::fs::write(uploads.join("app.zip"), b"zip").unwrap();
+        std::fs::write(root.path().join("id_rsa"), b"secret").unwrap();
+
+        let resolved = resolve_upload_path(Some(&uploads), Path::new("app.zip")).unwrap();
+        assert_eq!(resolved, uploads.canonicalize().unwrap().join("app.zip"));
+
+        assert!(resolve_upload_path(Some(&uploads), Path::new("../id_rsa")).is_err());
+        assert!(resolve_upload_path(Some(&uploads), &root.path().join("id_rsa")).is_err());
+        assert!(resolve_upload_path(None, Path::new("app.zip")).is_err());
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(root.path().join("id_rsa"), uploads.join("key")).unwrap();
+            assert!(resolve_upload_path(Some(&uploads), Path::new("key")).is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_tag_creates_object_then_ref() {
+        use wiremock::matchers::{body_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let git = "/api/v3/repos/octo/repo/git";
+
+        Mock::given(method("POST")).and(path(format!("{}/tags", git)))
+            .and(body_json(serde_json::json!({
+                "tag": "v1.2.0",
+                "message": "Release 1.2.0",
+                "object": "c3d0be41ecbe669545ee3e94d31ed9a4bc91ee3c",
+                "type": "commit",
+                "tagger": {"name": "Monalisa Octocat", "email": "octocat@github.com"}
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "sha": "940bd336248efae0f9ee5bc7b2d5c985887b16ac", "tag": "v1.2.0"
+            })))
+            .expect(1).mount(&server).await;
+        Mock::given(method("POST")).and(path(format!("{}/refs", git)))
+            .and(body_json(serde_json::json!({
+                "ref": "refs/tags/v1.2.0", "sha": "940bd336248efae0f9ee5bc7b2d5c985887b16ac"
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "ref": "refs/tags/v1.2.0", "object": {"sha": "940bd336248efae0f9ee5bc7b2d5c985887b16ac", "type": "tag"}
+            })))
+            .expect(1).mount(&server).await;
+
+        let client = test_client(&server.uri()).await;
+
+        let new_tag = NewTag {
+            tag: "v1.2.0".to_string(),
+            message: "Release 1.2.0".to_string(),
+            object: "c3d0be41ecbe669545ee3e94d31ed9a4bc91ee3c".to_string(),
+            object_type: "commit".to_string(),
+            tagger: Some(Tagger { name: "Monalisa Octocat".to_string(), email: "octocat@github.com".to_string(), date: None }),
+        };
+        let tag = client.create_tag("octo", "repo", &new_tag).await.unwrap();
+        assert_eq!(tag.sha, "940bd336248efae0f9ee5bc7b2d5c985887b16ac");
+        assert_eq!(tag.ref_name, "refs/tags/v1.2.0");
+        assert_eq!(tag.object_sha, "c3d0be41ecbe669545ee3e94d31ed9a4bc91ee3c");
+
+        let order: Vec<String> = server.received_requests().await.unwrap()
+            .iter()
+            .map(|r| format!("{} {}", r.method, r.url.path().trim_start_matches(git)))
+            .collect();
+        assert_eq!(order, vec!["POST /tags", "POST /refs"]);
+
+        let untagged = NewTag { tagger: None, object_type: "tree".to_string(), ..new_tag.clone() };
+        assert!(build_tag_object_body(&untagged).unwrap().get("tagger").is_none());
+        assert!(build_tag_object_body(&NewTag { object_type: "branch".to_string(), ..new_tag.clone() }).is_err());
+        assert!(build_tag_object_body(&NewTag { tag: String::new(), ..new_tag }).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_merge_pull_request() {
+        use wiremock::matchers::{body_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("PUT")).and(path("/api/v3/repos/octo/repo/pulls/7/merge"))
+            .and(body_json(serde_json::json!({"commit_title": "Ship it (#7)", "merge_method": "squash"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sha": "6dcb09b5b57875f334f61aebed695e2e4193db5e",
+                "merged": true,
+                "message": "Pull Request successfully merged"
+            })))
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("PUT")).and(path("/api/v3/repos/octo/repo/pulls/8/merge"))
+            .respond_with(ResponseTemplate::new(405).set_body_json(serde_json::json!({
+                "message": "Pull Request is not mergeable",
+                "documentation_url": "https://docs.github.com/rest/pulls/pulls#merge-a-pull-request"
+            })))
+            .mount(&server).await;
+
+        let client = test_client(&server.uri()).await;
+
+        let options = MergeOptions {
+            commit_title: Some("Ship it (#7)".to_string()),
+            merge_method: Some("squash".to_string()),
+            ..MergeOptions::default()
+        };
+        let merged = client.merge_pull_request("octo", "repo", 7, &options).await.unwrap();
+        assert!(merged.merged);
+        assert_eq!(merged.sha, "6dcb09b5b57875f334f61aebed695e2e4193db5e");
+
+        let err = client.merge_pull_request("octo", "repo", 8, &MergeOptions::default()).await.unwrap_err();
+        assert_eq!(err.to_string(), "Pull request #8 is not mergeable: Pull Request is not mergeable");
+
+        let bad_method = MergeOptions { merge_method: Some("fast-forward".to_string()), ..MergeOptions::default() };
+        let err = client.merge_pull_request("octo", "repo", 7, &bad_method).await.unwrap_err();
+        assert!(err.to_string().contains("expected one of: merge, squash, rebase"));
+
+        assert_eq!(
+            merge_error(9, reqwest::StatusCode::CONFLICT, "").to_string(),
+            "Pull request #9 changed while merging; fetch it and try again"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_org_issues_parses_search_results() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let filters = IssueFilters {
+            state: Some("open".to_string()),
+            labels: Some(vec!["bug".to_string(), "good first issue".to_string()]),
+            assignee: Some("octocat".to_string()),
+            sort: Some("updated".to_string()),
+            direction: Some("desc".to_string()),
+            ..Default::default()
+        };
+        let query = "org:octo is:issue state:open label:bug label:\"good first issue\" assignee:octocat";
+        assert_eq!(build_org_issues_search_query("octo", &filters), query);
+
+        let all = IssueFilters { state: Some("all".to_string()), creator: Some("hubot".to_string()), ..Default::default() };
+        assert_eq!(build_org_issues_search_query("octo", &all), "org:octo is:issue author:hubot");
+
+        // A space can't smuggle in extra qualifiers
+        let sneaky = IssueFilters { mentioned: Some("me repo:other/private".to_string()), ..Default::default() };
+        assert_eq!(
+            build_org_issues_search_query("octo", &sneaky),
+            "org:octo is:issue mentions:\"me repo:other/private\""
+        );
+
+        let user = serde_json::json!({
+            "login": "octocat",
+            "id": 1,
+            "avatar_url": "https://github.com/images/error/octocat_happy.gif",
+            "html_url": "https://github.com/octocat",
+            "type": "User",
+            "site_admin": false
+        });
+        let issue = |number: u64, repo: &str| serde_json::json!({
+            "id": 1000 + number,
+            "number": number,
+            "title": format!("Crash in {}", repo),
+            "body": null,
+            "user": user,
+            "labels": [{"id": 208045946, "name": "bug", "color": "f29513", "description": null, "default": true}],
+            "state": "open",
+            "locked": false,
+            "assignee": user,
+            "assignees": [user],
+            "milestone": null,
+            "comments": 2,
+            "created_at": "2024-03-01T10:00:00Z",
+            "updated_at": "2024-03-02T10:00:00Z",
+            "closed_at": null,
+            "html_url": format!("https://github.com/octo/{}/issues/{}", repo, number),
+            "repository_url": format!("https://api.github.com/repos/octo/{}", repo),
+            "score": 1.0
+        });
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET")).and(path("/api/v3/search/issues"))
+            .and(query_param("q", query))
+            .and(query_param("sort", "updated"))
+            .and(query_param("order", "desc"))
+            .and(query_param("per_page", "2"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200)
+                .insert_header("link", "<https://api.github.com/search/issues?page=2>; rel=\"next\"")
+                .set_body_json(serde_json::json!({
+                    "total_count": 3,
+                    "incomplete_results": false,
+                    "items": [issue(12, "api"), issue(7, "web")]
+                })))
+            .expect(1)
+            .mount(&server).await;
+
+        let client = test_client(&server.uri()).await;
+
+        let results = client.list_org_issues("octo", &filters, Some(2), Some(1)).await.unwrap();
+        assert_eq!(results.total_count, 3);
+        assert_eq!(results.items.len(), 2);
+        assert_eq!(results.items[0].number, 12);
+        assert_eq!(results.items[0].repository_url.as_deref(), Some("https://api.github.com/repos/octo/api"));
+        assert_eq!(results.items[1].repository_url.as_deref(), Some("https://api.github.com/repos/octo/web"));
+        assert!(!results.items[1].is_pull_request);
+        assert!(results.pagination.unwrap().has_next);
+    }
+
+    #[tokio::test]
+    async fn test_poll_mergeable_waits_for_computed_value() {
+        let responses = std::sync::Mutex::new(vec![
+            (Some(true), Some("clean".to_string())),
+            (None, Some("unknown".to_string())),
+        ]);
+
+        let status = poll_mergeable(
+            || {
+                let next = responses.lock().unwrap().pop().unwrap();
+                async move { Ok(next) }
+            },
+            MERGEABLE_MAX_ATTEMPTS,
+            std::time::Duration::ZERO,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(status.mergeable, Some(true));
+        assert_eq!(status.mergeable_state.as_deref(), Some("clean"));
+        assert_eq!(status.attempts, 2);
+    }
+
+    #[test]
+    fn test_parse_repo_languages() {
+        let languages = parse_repo_languages(serde_json::json!({
+            "Rust": 184320,
+            "Shell": 2048,
+            "Dockerfile": 512
+        }))
+        .unwrap();
+
+        assert_eq!(languages.len(), 3);
+        assert_eq!(languages["Rust"], 184320);
+        assert_eq!(languages["Dockerfile"], 512);
+    }
+
+    #[tokio::test]
+    async fn test_poll_computed_stats_retries_on_accepted() {
+        let responses = std::sync::Mutex::new(vec![Some(vec![42u64]), None]);
+
+        let stats = poll_computed_stats(
+            || {
+                let next = responses.lock().unwrap().pop().unwrap();
+                async move { Ok(next) }
+            },
+            STATS_MAX_ATTEMPTS,
+            std::time::Duration::ZERO,
+        )
+        .await
+        .unwrap();
+        assert_eq!(stats, vec![42]);
+
+        let still_computing: Result<Vec<u64>> =
+            poll_computed_stats(|| async { Ok(None) }, 3, std::time::Duration::ZERO).await;
+        assert!(still_computing.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_issue_comment_crud() {
+        use wiremock::matchers::{body_json, method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let comment = |id: u64, login: Option<&str>, body: &str| serde_json::json!({
+            "id": id,
+            "node_id": "IC_kwDOA",
+            "html_url": format!("https://github.com/octo/repo/issues/5#issuecomment-{}", id),
+            "user": login.map(|login| serde_json::json!({"login": login, "id": 1, "type": "User"})),
+            "created_at": "2024-03-01T12:00:00Z",
+            "updated_at": "2024-03-01T12:30:00Z",
+            "author_association": "MEMBER",
+            "body": body
+        });
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET")).and(path("/api/v3/repos/octo/repo/issues/5/comments"))
+            .and(query_param("per_page", "2"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200)
+                .insert_header("link", "<https://api.github.com/repositories/1/issues/5/comments?per_page=2&page=2>; rel=\"next\"")
+                .set_body_json(serde_json::json!([
+                    comment(11, Some("octocat"), "Can reproduce"),
+                    comment(12, None, "Same here")
+                ])))
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("POST")).and(path("/api/v3/repos/octo/repo/issues/5/comments"))
+            .and(body_json(serde_json::json!({"body": "Triaged"})))
+            .respond_with(ResponseTemplate::new(201).set_body_json(comment(13, Some("triage-bot"), "Triaged")))
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("PATCH")).and(path("/api/v3/repos/octo/repo/issues/comments/13"))
+            .and(body_json(serde_json::json!({"body": "Triaged: needs repro"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(comment(13, Some("triage-bot"), "Triaged: needs repro")))
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("DELETE")).and(path("/api/v3/repos/octo/repo/issues/comments/13"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("DELETE")).and(path("/api/v3/repos/octo/repo/issues/comments/99"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server).await;
+
+        let client = test_client(&server.uri()).await;
+
+        let page = client.list_issue_comments("octo", "repo", 5, Some(2), Some(1)).await.unwrap();
+        assert!(page.pagination.has_next);
+        assert_eq!(page.items.len(), 2);
+        assert_eq!(page.items[0].user, "octocat");
+        assert_eq!(page.items[1].user, "ghost");
+        assert_eq!(
+            serde_json::to_value(&page.items[0]).unwrap(),
+            serde_json::json!({"id": 11, "user": "octocat", "created_at": "2024-03-01T12:00:00Z", "body": "Can reproduce"})
+        );
+
+        let created = client.create_issue_comment("octo", "repo", 5, "Triaged").await.unwrap();
+        assert_eq!(created.id, 13);
+        let updated = client.update_issue_comment("octo", "repo", 13, "Triaged: needs repro").await.unwrap();
+        assert_eq!(updated.body, "Triaged: needs repro");
+        client.delete_issue_comment("octo", "repo", 13).await.unwrap();
+
+        let err = client.delete_issue_comment("octo", "repo", 99).await.unwrap_err();
+        assert!(err.to_string().contains("Issue comment 99 not found"));
+    }
+
+    #[test]
+    fn test_has_next_page_from_link_header() {
+        let link = r#"<https://api.github.com/repositories/1/issues?page=3>; rel="next", <https://api.github.com/repositories/1/issues?page=5>; rel="last""#;
+        assert!(has_next_page(Some(link)));
+
+        let last_page = r#"<https://api.github.com/repositories/1/issues?page=1>; rel="first", <https://api.github.com/repositories/1/issues?page=4>; rel="prev""#;
+        assert!(!has_next_page(Some(last_page)));
+        assert!(!has_next_page(None));
+    }
+
+    #[test]
+    fn test_backoff_delays_follow_policy() {
+        let policy = BackoffPolicy {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            multiplier: 3.0,
+            jitter: 0.25,
+        };
+
+        // The midpoint sample is the un-jittered exponential delay, capped at max_delay
+        assert_eq!(policy.delay(0, 0.5), Duration::from_millis(100));
+        assert_eq!(policy.delay(1, 0.5), Duration::from_millis(300));
+        assert_eq!(policy.delay(2, 0.5), Duration::from_millis(900));
+        assert_eq!(policy.delay(3, 0.5), Duration::from_secs(1));
+
+        // Jitter spreads by at most the configured fraction and never exceeds max_delay
+        for attempt in 0..5 {
+            let nominal = policy.delay(attempt, 0.5).as_secs_f64();
+            for sample in [0.0, 0.1, 0.5, 0.9, 1.0] {
+                let delay = policy.delay(attempt, sample).as_secs_f64();
+                assert!(delay >= nominal * 0.75 - 1e-9, "attempt {} sample {}: {}", attempt, sample, delay);
+                assert!(delay <= (nominal * 1.25).min(1.0) + 1e-9, "attempt {} sample {}: {}", attempt, sample, delay);
+            }
+            for _ in 0..50 {
+                let delay = policy.next_delay(attempt).as_secs_f64();
+                assert!(delay >= nominal * 0.75 - 1e-9 && delay <= (nominal * 1.25).min(1.0) + 1e-9);
+            }
+        }
+        assert_eq!(policy.delay(0, 0.0), Duration::from_millis(75));
+        assert_eq!(policy.delay(0, 1.0), Duration::from_millis(125));
+
+        let steady = BackoffPolicy { jitter: 0.0, ..policy.clone() };
+        assert_eq!(steady.next_delay(1), Duration::from_millis(300));
+    }
+
+    #[test]
+    fn test_retry_delay_classifies_responses() {
+        use reqwest::StatusCode;
+
+        let config = GitHubConfig {
+            backoff: BackoffPolicy { jitter: 0.0, ..BackoffPolicy::default() },
+            ..test_config("http://127.0.0.1")
+        };
+        let none = HeaderMap::new();
+        let mut exhausted = HeaderMap::new();
+        exhausted.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        exhausted.insert("x-ratelimit-reset", HeaderValue::from_static("1010"));
+        let mut retry_after = HeaderMap::new();
+        retry_after.insert("retry-after", HeaderValue::from_static("60"));
+
+        assert_eq!(retry_delay(StatusCode::SERVICE_UNAVAILABLE, &none, true, &config, 1, 0), Some(Duration::from_secs(2)));
+        assert_eq!(retry_delay(StatusCode::SERVICE_UNAVAILABLE, &none, false, &config, 0, 0), None);
+        assert_eq!(retry_delay(StatusCode::TOO_MANY_REQUESTS, &none, false, &config, 0, 0), Some(Duration::from_secs(1)));
+        assert_eq!(retry_delay(StatusCode::FORBIDDEN, &exhausted, false, &config, 0, 1000), Some(Duration::from_secs(10)));
+        assert_eq!(retry_delay(StatusCode::FORBIDDEN, &none, true, &config, 0, 0), None);
+        assert_eq!(retry_delay(StatusCode::BAD_GATEWAY, &none, true, &config, 3, 0), None);
+
+        // A rate limit is waited out in full, even past the backoff's 30s max_delay,
+        // but not when it resets further away than max_rate_limit_wait
+        assert_eq!(retry_delay(StatusCode::FORBIDDEN, &retry_after, true, &config, 0, 0), Some(Duration::from_secs(60)));
+        assert_eq!(retry_delay(StatusCode::FORBIDDEN, &exhausted, true, &config, 0, 0), None);
+        let patient = GitHubConfig { max_rate_limit_wait: Duration::from_secs(3600), ..config.clone() };
+        assert_eq!(retry_delay(StatusCode::FORBIDDEN, &exhausted, true, &patient, 0, 0), Some(Duration::from_secs(1010)));
+
+        let impatient = GitHubConfig { respect_rate_limit: false, ..config.clone() };
+        assert_eq!(retry_delay(StatusCode::FORBIDDEN, &exhausted, false, &impatient, 0, 1000), None);
+        assert_eq!(retry_delay(StatusCode::TOO_MANY_REQUESTS, &none, true, &impatient, 0, 0), None);
+        assert_eq!(retry_delay(StatusCode::BAD_GATEWAY, &none, true, &impatient, 0, 0), Some(Duration::from_secs(1)));
+
+        assert_eq!(rate_limit(StatusCode::FORBIDDEN, &exhausted, 1000), Some(RateLimit { reset_in: Some(Duration::from_secs(10)) }));
+        assert_eq!(rate_limit(StatusCode::FORBIDDEN, &retry_after, 0), Some(RateLimit { reset_in: Some(Duration::from_secs(60)) }));
+        assert_eq!(rate_limit(StatusCode::TOO_MANY_REQUESTS, &none, 0), Some(RateLimit { reset_in: None }));
+        assert_eq!(rate_limit(StatusCode::FORBIDDEN, &none, 0), None);
+
+        assert_eq!(format_wait(Duration::from_secs(42)), "42s");
+        assert_eq!(format_wait(Duration::from_secs(185)), "3m 5s");
+        assert_eq!(format_wait(Duration::from_secs(3720)), "1h 2m");
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_rate_limit_reports_reset() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let reset = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() + 600;
+        let server = MockServer::start().await;
+        Mock::given(method("GET")).and(path("/api/v3/repos/octo/repo/topics"))
+            .respond_with(ResponseTemplate::new(403)
+                .insert_header("x-ratelimit-remaining", "0")
+                .insert_header("x-ratelimit-reset", reset.to_string().as_str())
+                .set_body_json(serde_json::json!({"message": "API rate limit exceeded"})))
+            .expect(1)
+            .mount(&server).await;
+
+        let client = GitHubClient::new(GitHubConfig { respect_rate_limit: false, ..test_config(&server.uri()) }).await.unwrap();
+
+        // Not waiting for the reset, so the first response is the answer
+        let err = client.get_repo_topics("octo", "repo").await.unwrap_err();
+        let exceeded = err.downcast_ref::<RateLimitExceeded>().expect("rate limit error");
+        assert_eq!(exceeded.attempts, 1);
+        assert!(exceeded.reset_in.unwrap() > Duration::from_secs(590));
+        assert!(err.to_string().contains("after 1 attempt;"));
+        assert!(err.to_string().contains("resets in 9m") || err.to_string().contains("resets in 10m 0s"));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_wait_may_exceed_max_delay() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let limited = || ResponseTemplate::new(429).insert_header("retry-after", "1");
+        Mock::given(method("GET")).and(path("/api/v3/repos/octo/repo/topics"))
+            .respond_with(limited())
+            .up_to_n_times(1)
+            .mount(&server).await;
+        Mock::given(method("GET")).and(path("/api/v3/repos/octo/repo/topics"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"names": ["rust"]})))
+            .up_to_n_times(1)
+            .mount(&server).await;
+        Mock::given(method("GET")).and(path("/api/v3/repos/octo/repo/languages"))
+            .respond_with(limited())
+            .mount(&server).await;
+
+        let client = GitHubClient::new(GitHubConfig {
+            backoff: BackoffPolicy { max_delay: Duration::from_millis(10), ..BackoffPolicy::default() },
+            max_retries: 1,
+            ..test_config(&server.uri())
+        }).await.unwrap();
+
+        // retry-after is 1s against a 10ms max_delay, and is still waited out
+        let topics = client.get_repo_topics("octo", "repo").await.unwrap();
+        assert_eq!(topics.names, vec!["rust"]);
+
+        let err = client.get_repo_languages("octo", "repo").await.unwrap_err();
+        let exceeded = err.downcast_ref::<RateLimitExceeded>().expect("rate limit error");
+        assert_eq!(exceeded.attempts, 2);
+        assert!(err.to_string().contains("after 2 attempts;"));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_past_max_wait_fails_at_once() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET")).and(path("/api/v3/repos/octo/repo/languages"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "725"))
+            .expect(1)
+            .mount(&server).await;
+
+        let client = test_client(&server.uri()).await;
+
+        // Sleeping 12 minutes would outlast the tool timeout, so the reset is reported instead
+        let started = std::time::Instant::now();
+        let err = client.get_repo_languages("octo", "repo").await.unwrap_err();
+        assert!(started.elapsed() < Duration::from_secs(5));
+        let exceeded = err.downcast_ref::<RateLimitExceeded>().expect("rate limit error");
+        assert_eq!(exceeded.attempts, 1);
+        assert_eq!(exceeded.reset_in, Some(Duration::from_secs(725)));
+        assert!(err.to_string().ends_with("after 1 attempt; the limit resets in 12m 5s"));
+    }
+
+    #[tokio::test]
+    async fn test_requests_retry_server_errors() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET")).and(path("/api/v3/repos/octo/repo/topics"))
+            .respond_with(ResponseTemplate::new(502))
+            .up_to_n_times(2)
+            .mount(&server).await;
+        Mock::given(method("GET")).and(path("/api/v3/repos/octo/repo/topics"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"names": ["rust"]})))
+            .mount(&server).await;
+
+        let client = GitHubClient::new(GitHubConfig {
+            backoff: BackoffPolicy {
+                base_delay: Duration::from_millis(1),
+                max_delay: Duration::from_millis(10),
+                ..BackoffPolicy::default()
+            },
+            ..test_config(&server.uri())
+        }).await.unwrap();
+
+        let topics = client.get_repo_topics("octo", "repo").await.unwrap();
+        assert_eq!(topics.names, vec!["rust"]);
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_set_repo_subscription_sends_flags() {
+        use wiremock::matchers::{body_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("PUT")).and(path("/api/v3/repos/octo/repo/subscription"))
+            .and(body_json(serde_json::json!({"subscribed": false, "ignored": true})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "subscribed": false,
+                "ignored": true,
+                "reason": null,
+                "created_at": "2012-10-06T21:34:12Z",
+                "url": "https://api.github.com/repos/octo/repo/subscription",
+                "repository_url": "https://api.github.com/repos/octo/repo"
+            })))
+            .expect(1)
+            .mount(&server).await;
+
+        let client = test_client(&server.uri()).await;
+
+        let subscription = client.set_repo_subscription("octo", "repo", false, true).await.unwrap();
+        assert!(!subscription.subscribed);
+        assert!(subscription.ignored);
+        assert_eq!(subscription.created_at.as_deref(), Some("2012-10-06T21:34:12Z"));
+    }
+
+    #[tokio::test]
+    async fn test_delete_repo_subscription_and_unwatched_state() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE")).and(path("/api/v3/repos/octo/repo/subscription"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server).await;
+        Mock::given(method("GET")).and(path("/api/v3/repos/octo/repo/subscription"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server).await;
+
+        let client = test_client(&server.uri()).await;
+
+        let subscription = client.delete_repo_subscription("octo", "repo").await.unwrap();
+        assert!(!subscription.subscribed && !subscription.ignored);
+
+        let subscription = client.get_repo_subscription("octo", "repo").await.unwrap();
+        assert!(!subscription.subscribed && !subscription.ignored);
+        assert!(subscription.created_at.is_none());
     }
-}
\ No newline at end of file
+}
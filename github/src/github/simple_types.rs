@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
@@ -56,6 +58,20 @@ pub struct Repository {
     pub default_branch: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositoryTopics {
+    pub names: Vec<String>,
+}
+
+/// Watch settings for a repository. Not watching is reported as both flags `false`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RepositorySubscription {
+    pub subscribed: bool,
+    pub ignored: bool,
+    pub reason: Option<String>,
+    pub created_at: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct License {
     pub key: String,
@@ -82,7 +98,30 @@ pub struct Issue {
     pub updated_at: DateTime<Utc>,
     pub closed_at: Option<DateTime<Utc>>,
     pub html_url: String,
+    /// API URL of the issue's repository; tells org-wide results apart.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository_url: Option<String>,
     pub pull_request: Option<PullRequestLink>,
+    /// Derived from `pull_request`; GitHub's issues API returns pull requests too.
+    #[serde(default)]
+    pub is_pull_request: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reactions: Option<Reactions>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reactions {
+    pub total_count: u32,
+    #[serde(rename = "+1")]
+    pub plus_one: u32,
+    #[serde(rename = "-1")]
+    pub minus_one: u32,
+    pub laugh: u32,
+    pub hooray: u32,
+    pub confused: u32,
+    pub heart: u32,
+    pub rocket: u32,
+    pub eyes: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -154,6 +193,19 @@ pub struct PullRequestLink {
     pub patch_url: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullRequestFile {
+    pub sha: Option<String>,
+    pub filename: String,
+    pub status: String,
+    pub additions: u32,
+    pub deletions: u32,
+    pub changes: u32,
+    pub previous_filename: Option<String>,
+    /// Absent for binary files and for diffs GitHub considers too large.
+    pub patch: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitRef {
     pub label: String,
@@ -180,6 +232,12 @@ pub struct FileContent {
     pub submodule_git_url: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileDeletion {
+    pub path: String,
+    pub commit: CommitInfo,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileCommit {
     pub content: FileContent,
@@ -196,6 +254,17 @@ pub struct CommitInfo {
     pub message: String,
     pub tree: GitTree,
     pub parents: Vec<GitParent>,
+    #[serde(default)]
+    pub verification: Option<CommitVerification>,
+}
+
+/// GitHub's signature check for a commit. `reason` is e.g. `valid`, `unsigned`
+/// or `bad_email`; `signature` is the raw armored signature when signed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitVerification {
+    pub verified: bool,
+    pub reason: String,
+    pub signature: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -223,4 +292,552 @@ pub struct SearchResults<T> {
     pub total_count: u32,
     pub incomplete_results: bool,
     pub items: Vec<T>,
-}
\ No newline at end of file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<PageInfo>,
+}
+
+/// Where a list result sits in the full listing, so callers know to request more.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageInfo {
+    pub page: u32,
+    pub per_page: u32,
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Paginated<T> {
+    pub items: Vec<T>,
+    pub pagination: PageInfo,
+}
+
+/// Compact issue comment: `user` is the author's login.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueComment {
+    pub id: u64,
+    pub user: String,
+    pub created_at: String,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubIssue {
+    pub id: String,
+    pub number: u64,
+    pub title: String,
+    pub state: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubIssuesSummary {
+    pub total: u32,
+    pub completed: u32,
+    pub percent_completed: u32,
+}
+
+/// Cursor pagination state of a GraphQL connection.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQLPageInfo {
+    pub has_next_page: bool,
+    pub end_cursor: Option<String>,
+}
+
+/// The issue, pull request, or draft issue behind a project item.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectItemContent {
+    pub title: Option<String>,
+    pub number: Option<u64>,
+    pub url: Option<String>,
+    pub state: Option<String>,
+    pub repository: Option<String>,
+}
+
+/// A field value set on a project item, keyed by the field's name.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProjectFieldValue {
+    pub field: String,
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectItem {
+    pub id: String,
+    /// `ISSUE`, `PULL_REQUEST`, `DRAFT_ISSUE`, or `REDACTED`.
+    pub item_type: String,
+    pub content: Option<ProjectItemContent>,
+    pub field_values: Vec<ProjectFieldValue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectItemList {
+    pub project_title: String,
+    pub items: Vec<ProjectItem>,
+    pub page_info: GraphQLPageInfo,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectFieldOption {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectIteration {
+    pub id: String,
+    pub title: String,
+    pub start_date: String,
+    pub duration: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectField {
+    pub id: String,
+    pub name: String,
+    /// GraphQL `ProjectV2FieldType`, e.g. `TEXT`, `SINGLE_SELECT`, `ITERATION`.
+    pub data_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<Vec<ProjectFieldOption>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iterations: Option<Vec<ProjectIteration>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectFieldList {
+    pub project_title: String,
+    pub fields: Vec<ProjectField>,
+    pub page_info: GraphQLPageInfo,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubIssueList {
+    pub parent: SubIssue,
+    pub summary: Option<SubIssuesSummary>,
+    pub sub_issues: Vec<SubIssue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubIssueLink {
+    pub parent: SubIssue,
+    pub sub_issue: SubIssue,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowRun {
+    pub id: u64,
+    pub name: Option<String>,
+    pub workflow_id: u64,
+    pub run_number: u64,
+    pub event: String,
+    pub status: Option<String>,
+    pub conclusion: Option<String>,
+    pub head_branch: Option<String>,
+    pub head_sha: String,
+    pub html_url: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Narrows `list_workflow_runs`; `workflow_id` (numeric id or file name such
+/// as `ci.yml`) limits it to one workflow.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkflowRunFilters {
+    pub workflow_id: Option<String>,
+    pub status: Option<String>,
+    pub branch: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowRunList {
+    pub total_count: u32,
+    pub workflow_runs: Vec<WorkflowRun>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeploymentStatus {
+    pub id: u64,
+    pub state: String,
+    pub description: Option<String>,
+    pub environment: Option<String>,
+    pub environment_url: Option<String>,
+    pub log_url: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Deployment {
+    pub id: u64,
+    pub sha: String,
+    pub r#ref: String,
+    pub task: String,
+    pub environment: String,
+    pub description: Option<String>,
+    pub production_environment: Option<bool>,
+    pub transient_environment: Option<bool>,
+    pub created_at: String,
+    pub updated_at: String,
+    /// Newest entry from the deployment's statuses; not part of GitHub's payload.
+    #[serde(default)]
+    pub latest_status: Option<DeploymentStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentReviewerRef {
+    pub id: u64,
+    pub login: Option<String>,
+    pub slug: Option<String>,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentReviewer {
+    pub r#type: String,
+    pub reviewer: EnvironmentReviewerRef,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentProtectionRule {
+    pub id: u64,
+    pub r#type: String,
+    pub wait_timer: Option<u32>,
+    pub prevent_self_review: Option<bool>,
+    #[serde(default)]
+    pub reviewers: Vec<EnvironmentReviewer>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeploymentBranchPolicy {
+    pub protected_branches: bool,
+    pub custom_branch_policies: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Environment {
+    pub id: u64,
+    pub name: String,
+    pub html_url: String,
+    pub created_at: String,
+    pub updated_at: String,
+    #[serde(default)]
+    pub protection_rules: Vec<EnvironmentProtectionRule>,
+    pub deployment_branch_policy: Option<DeploymentBranchPolicy>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentList {
+    pub total_count: u32,
+    pub environments: Vec<Environment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecretScanningAlert {
+    pub number: u64,
+    pub state: String,
+    pub secret_type: String,
+    pub secret_type_display_name: Option<String>,
+    pub resolution: Option<String>,
+    pub validity: Option<String>,
+    pub html_url: String,
+    pub created_at: String,
+    pub resolved_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependabotPackage {
+    pub ecosystem: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependabotDependency {
+    pub package: DependabotPackage,
+    pub manifest_path: Option<String>,
+    pub scope: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependabotAdvisory {
+    pub ghsa_id: String,
+    pub cve_id: Option<String>,
+    pub summary: String,
+    pub severity: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependabotPatchedVersion {
+    pub identifier: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependabotVulnerability {
+    pub vulnerable_version_range: String,
+    pub first_patched_version: Option<DependabotPatchedVersion>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependabotAlert {
+    pub number: u64,
+    pub state: String,
+    pub dependency: DependabotDependency,
+    pub security_advisory: DependabotAdvisory,
+    pub security_vulnerability: Option<DependabotVulnerability>,
+    pub html_url: String,
+    pub created_at: String,
+    pub dismissed_reason: Option<String>,
+    pub fixed_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IssueFilters {
+    pub state: Option<String>,
+    pub labels: Option<Vec<String>>,
+    pub assignee: Option<String>,
+    pub creator: Option<String>,
+    pub mentioned: Option<String>,
+    /// A milestone number, `none` for issues without one, or `*` for any milestone.
+    pub milestone: Option<String>,
+    pub sort: Option<String>,
+    pub direction: Option<String>,
+    pub since: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CommitFilters {
+    pub sha: Option<String>,
+    pub path: Option<String>,
+    pub author: Option<String>,
+    pub committer: Option<String>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitSummary {
+    pub sha: String,
+    pub subject: String,
+    pub author: Option<String>,
+    pub author_login: Option<String>,
+    pub date: Option<String>,
+    pub html_url: String,
+    pub verification: Option<CommitVerification>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitStats {
+    pub additions: u32,
+    pub deletions: u32,
+    pub total: u32,
+}
+
+/// A single commit with its full message and changed files. `diff` holds the
+/// raw unified diff and is only fetched on request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitDetail {
+    #[serde(flatten)]
+    pub summary: CommitSummary,
+    pub message: String,
+    pub parents: Vec<String>,
+    pub stats: Option<CommitStats>,
+    pub files: Vec<PullRequestFile>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub diff: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatedBranch {
+    #[serde(rename = "ref")]
+    pub ref_name: String,
+    pub sha: String,
+    pub from_branch: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitReference {
+    pub r#ref: String,
+    pub object: GitObject,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitObject {
+    pub sha: String,
+    pub r#type: String,
+}
+
+/// Blame for a file at a ref, one entry per contiguous range of lines last
+/// touched by the same commit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileBlame {
+    pub path: String,
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    pub ranges: Vec<BlameRange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlameRange {
+    pub start_line: u32,
+    pub end_line: u32,
+    /// GitHub's recency bucket, 1 (newest) to 10 (oldest).
+    pub age: u32,
+    pub commit_sha: String,
+    pub commit_message: String,
+    pub committed_date: Option<String>,
+    pub author_name: Option<String>,
+    pub author_login: Option<String>,
+}
+
+/// A file to write as part of a multi-file commit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileChange {
+    pub path: String,
+    pub content: String,
+}
+
+/// Result of committing several files at once through the git data API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiFileCommit {
+    pub sha: String,
+    pub html_url: Option<String>,
+    pub tree_sha: String,
+    pub parent_sha: String,
+    pub branch: String,
+    pub files: Vec<String>,
+}
+
+/// Author of an annotated tag; GitHub uses the authenticated user when omitted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tagger {
+    pub name: String,
+    pub email: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+}
+
+/// An annotated tag to create through the git data API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewTag {
+    pub tag: String,
+    pub message: String,
+    /// SHA of the tagged object.
+    pub object: String,
+    /// `commit`, `tree` or `blob`.
+    #[serde(rename = "type")]
+    pub object_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tagger: Option<Tagger>,
+}
+
+/// Result of creating an annotated tag object and its `refs/tags/*` ref.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatedTag {
+    pub tag: String,
+    pub sha: String,
+    pub object_sha: String,
+    pub object_type: String,
+    #[serde(rename = "ref")]
+    pub ref_name: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DefaultBranch {
+    pub default_branch: String,
+    pub sha: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Release {
+    pub id: u64,
+    pub tag_name: String,
+    pub name: Option<String>,
+    pub draft: bool,
+    pub prerelease: bool,
+    pub html_url: String,
+    pub created_at: String,
+    pub published_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseAsset {
+    pub id: u64,
+    pub name: String,
+    pub label: Option<String>,
+    pub content_type: String,
+    pub size: u64,
+    pub state: String,
+    pub browser_download_url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageShare {
+    pub name: String,
+    pub bytes: u64,
+    pub percent: f64,
+}
+
+/// Everything `get_repository_overview` could fetch. A piece that failed is
+/// `None` and its error is recorded under the same name in `errors`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositoryOverview {
+    pub repository: Option<Repository>,
+    pub open_issues: Option<u64>,
+    pub open_pull_requests: Option<u64>,
+    pub default_branch: Option<DefaultBranch>,
+    pub latest_release: Option<Release>,
+    pub top_languages: Option<Vec<LanguageShare>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub errors: BTreeMap<String, String>,
+}
+
+/// Options for merging a pull request; unset fields use the repository defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MergeOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merge_method: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeResult {
+    pub sha: String,
+    pub merged: bool,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeableStatus {
+    pub mergeable: Option<bool>,
+    pub mergeable_state: Option<String>,
+    pub attempts: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContributorStats {
+    pub author: Option<User>,
+    pub total: u64,
+    pub weeks: Vec<ContributorWeek>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContributorWeek {
+    /// Start of the week as a Unix timestamp.
+    pub w: i64,
+    /// Lines added.
+    pub a: u64,
+    /// Lines deleted.
+    pub d: u64,
+    /// Commits.
+    pub c: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reaction {
+    pub id: u64,
+    pub content: String,
+    pub created_at: Option<DateTime<Utc>>,
+}
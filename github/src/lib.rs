@@ -5,4 +5,4 @@ pub mod resources;
 pub mod mcp_core;
 
 pub use server::{GitHubMcpServer, GitHubServerConfig};
-pub use github::{GitHubClient, GitHubConfig};
\ No newline at end of file
+pub use github::{BackoffPolicy, GitHubAppCredentials, GitHubClient, GitHubConfig};
\ No newline at end of file
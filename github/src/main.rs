@@ -1,10 +1,12 @@
 use anyhow::{anyhow, Result};
 use clap::{Arg, ArgAction, Command};
 use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
 use tracing::{error, info, level_filters::LevelFilter};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-use github_mcp_server::{GitHubMcpServer, GitHubServerConfig};
+use github_mcp_server::{BackoffPolicy, GitHubAppCredentials, GitHubMcpServer, GitHubServerConfig};
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -37,6 +39,97 @@ async fn main() -> Result<()> {
                         .help("Restrict the server to read-only operations")
                         .action(ArgAction::SetTrue)
                 )
+                .arg(
+                    Arg::new("dry-run")
+                        .long("dry-run")
+                        .help("Log write operations instead of sending them to GitHub")
+                        .action(ArgAction::SetTrue)
+                )
+                .arg(
+                    Arg::new("protocol-version")
+                        .long("protocol-version")
+                        .value_name("VERSION")
+                        .help("Advertise this MCP protocol version instead of negotiating one with the client")
+                        .action(ArgAction::Set)
+                )
+                .arg(
+                    Arg::new("tool-timeout")
+                        .long("tool-timeout")
+                        .value_name("SECONDS")
+                        .help("Abort tool calls that run longer than this many seconds (0 disables the limit)")
+                        .value_parser(clap::value_parser!(u64))
+                        .default_value("300")
+                        .action(ArgAction::Set)
+                )
+                .arg(
+                    Arg::new("max-retries")
+                        .long("max-retries")
+                        .value_name("COUNT")
+                        .help("Retry rate-limited and 5xx GitHub responses up to this many times (0 disables retries)")
+                        .value_parser(clap::value_parser!(u32))
+                        .default_value("3")
+                        .action(ArgAction::Set)
+                )
+                .arg(
+                    Arg::new("retry-base-delay")
+                        .long("retry-base-delay")
+                        .value_name("MILLISECONDS")
+                        .help("Delay before the first retry")
+                        .value_parser(clap::value_parser!(u64))
+                        .default_value("1000")
+                        .action(ArgAction::Set)
+                )
+                .arg(
+                    Arg::new("retry-max-delay")
+                        .long("retry-max-delay")
+                        .value_name("MILLISECONDS")
+                        .help("Longest backoff delay between retries of 5xx responses (and of rate limits that give no reset time); rate-limit resets are capped by --max-rate-limit-wait instead")
+                        .value_parser(clap::value_parser!(u64))
+                        .default_value("30000")
+                        .action(ArgAction::Set)
+                )
+                .arg(
+                    Arg::new("retry-multiplier")
+                        .long("retry-multiplier")
+                        .value_name("FACTOR")
+                        .help("Factor the delay grows by after each retry")
+                        .value_parser(clap::value_parser!(f64))
+                        .default_value("2.0")
+                        .action(ArgAction::Set)
+                )
+                .arg(
+                    Arg::new("retry-jitter")
+                        .long("retry-jitter")
+                        .value_name("FRACTION")
+                        .help("Randomly spread each delay by up to this fraction either way (0 to 1)")
+                        .value_parser(clap::value_parser!(f64))
+                        .default_value("0.2")
+                        .action(ArgAction::Set)
+                )
+                .arg(
+                    Arg::new("no-rate-limit-wait")
+                        .long("no-rate-limit-wait")
+                        .help("Fail rate-limited requests straight away instead of sleeping until the limit resets")
+                        .action(ArgAction::SetTrue)
+                )
+                .arg(
+                    Arg::new("max-rate-limit-wait")
+                        .long("max-rate-limit-wait")
+                        .value_name("SECONDS")
+                        .help("Fail instead of waiting when a rate limit resets further away than this many seconds")
+                        .value_parser(clap::value_parser!(u64))
+                        .default_value("60")
+                        .action(ArgAction::Set)
+                )
+                .arg(
+                    Arg::new("uploads-dir")
+                        .long("uploads-dir")
+                        .value_name("DIR")
+                        .env("GITHUB_UPLOADS_DIR")
+                        .help("Directory release assets may be uploaded from; uploads are refused when unset")
+                        .value_parser(clap::value_parser!(PathBuf))
+                        .action(ArgAction::Set)
+                )
                 .arg(
                     Arg::new("log-file")
                         .long("log-file")
@@ -65,8 +158,14 @@ async fn main() -> Result<()> {
 
     match matches.subcommand() {
         Some(("stdio", sub_matches)) => {
-            let token = env::var("GITHUB_PERSONAL_ACCESS_TOKEN")
-                .map_err(|_| anyhow!("GITHUB_PERSONAL_ACCESS_TOKEN not set"))?;
+            let app_auth = app_credentials_from_env()?;
+            let token = match env::var("GITHUB_PERSONAL_ACCESS_TOKEN") {
+                Ok(token) => token,
+                Err(_) if app_auth.is_some() => String::new(),
+                Err(_) => return Err(anyhow!(
+                    "GITHUB_PERSONAL_ACCESS_TOKEN not set (or set GITHUB_APP_ID, GITHUB_APP_INSTALLATION_ID and GITHUB_APP_PRIVATE_KEY_PATH to authenticate as a GitHub App)"
+                )),
+            };
 
             let toolsets_str = sub_matches.get_one::<String>("toolsets").unwrap();
             let enabled_toolsets: Vec<String> = if toolsets_str == "all" {
@@ -75,6 +174,19 @@ async fn main() -> Result<()> {
                 toolsets_str.split(',').map(|s| s.trim().to_string()).collect()
             };
 
+            let backoff = BackoffPolicy {
+                base_delay: Duration::from_millis(*sub_matches.get_one::<u64>("retry-base-delay").unwrap()),
+                max_delay: Duration::from_millis(*sub_matches.get_one::<u64>("retry-max-delay").unwrap()),
+                multiplier: *sub_matches.get_one::<f64>("retry-multiplier").unwrap(),
+                jitter: *sub_matches.get_one::<f64>("retry-jitter").unwrap(),
+            };
+            if !(0.0..=1.0).contains(&backoff.jitter) {
+                return Err(anyhow!("--retry-jitter must be between 0 and 1"));
+            }
+            if backoff.multiplier < 1.0 {
+                return Err(anyhow!("--retry-multiplier must be at least 1"));
+            }
+
             let config = GitHubServerConfig {
                 version: VERSION.to_string(),
                 host: sub_matches.get_one::<String>("gh-host").cloned(),
@@ -82,7 +194,19 @@ async fn main() -> Result<()> {
                 enabled_toolsets,
                 dynamic_toolsets: sub_matches.get_flag("dynamic-toolsets"),
                 read_only: sub_matches.get_flag("read-only"),
+                dry_run: sub_matches.get_flag("dry-run"),
                 enable_command_logging: sub_matches.get_flag("enable-command-logging"),
+                protocol_version: sub_matches.get_one::<String>("protocol-version").cloned(),
+                tool_timeout: sub_matches.get_one::<u64>("tool-timeout")
+                    .copied()
+                    .filter(|secs| *secs > 0)
+                    .map(Duration::from_secs),
+                backoff,
+                max_retries: *sub_matches.get_one::<u32>("max-retries").unwrap(),
+                respect_rate_limit: !sub_matches.get_flag("no-rate-limit-wait"),
+                max_rate_limit_wait: Duration::from_secs(*sub_matches.get_one::<u64>("max-rate-limit-wait").unwrap()),
+                app_auth,
+                uploads_dir: sub_matches.get_one::<PathBuf>("uploads-dir").cloned(),
             };
 
             info!("Starting GitHub MCP Server v{}", VERSION);
@@ -100,6 +224,34 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Reads GitHub App credentials from the environment. Returns `None` when no
+/// App variables are set, so the server falls back to the personal access token.
+fn app_credentials_from_env() -> Result<Option<GitHubAppCredentials>> {
+    let app_id = env::var("GITHUB_APP_ID").ok();
+    let installation_id = env::var("GITHUB_APP_INSTALLATION_ID").ok();
+    let key_path = env::var("GITHUB_APP_PRIVATE_KEY_PATH").ok();
+
+    match (app_id, installation_id, key_path) {
+        (None, None, None) => Ok(None),
+        (Some(app_id), Some(installation_id), Some(key_path)) => {
+            let installation_id = installation_id.parse::<u64>().map_err(|_| {
+                anyhow!("GITHUB_APP_INSTALLATION_ID must be a numeric installation id")
+            })?;
+            let private_key_pem = std::fs::read_to_string(&key_path).map_err(|e| {
+                anyhow!("Failed to read GitHub App private key from {}: {}", key_path, e)
+            })?;
+            Ok(Some(GitHubAppCredentials {
+                app_id,
+                installation_id,
+                private_key_pem,
+            }))
+        }
+        _ => Err(anyhow!(
+            "GITHUB_APP_ID, GITHUB_APP_INSTALLATION_ID and GITHUB_APP_PRIVATE_KEY_PATH must all be set to use GitHub App authentication"
+        )),
+    }
+}
+
 fn init_logging() -> Result<()> {
     let filter = env::var("RUST_LOG")
         .unwrap_or_else(|_| "github_mcp_server=info".to_string());
@@ -8,7 +8,15 @@ use std::sync::Arc;
 use tracing::{debug, error, info};
 use url::Url;
 
-use crate::github::GitHubClient;
+use crate::github::{asset_content_type, FileContent, GitHubClient};
+
+// URI templates for each resource shape
+pub const REPO_CONTENTS_URI: &str = "repo://{owner}/{repo}/contents{/path*}";
+pub const BRANCH_CONTENTS_URI: &str = "repo://{owner}/{repo}/refs/heads/{branch}/contents{/path*}";
+pub const COMMIT_CONTENTS_URI: &str = "repo://{owner}/{repo}/sha/{sha}/contents{/path*}";
+pub const TAG_CONTENTS_URI: &str = "repo://{owner}/{repo}/refs/tags/{tag}/contents{/path*}";
+pub const PR_CONTENTS_URI: &str = "repo://{owner}/{repo}/refs/pull/{prNumber}/head/contents{/path*}";
+pub const PR_FILES_URI: &str = "repo://{owner}/{repo}/refs/pull/{prNumber}/files";
 
 pub type ResourceHandlerFunc = Box<dyn Fn(String) -> BoxFuture<'static, Result<Value>> + Send + Sync>;
 
@@ -42,7 +50,7 @@ impl ResourceRegistry {
             })
         });
 
-        self.handlers.insert("repo://{owner}/{repo}/contents{/path*}".to_string(), repo_handler);
+        self.handlers.insert(REPO_CONTENTS_URI.to_string(), repo_handler);
 
         // Branch-specific content handler
         let github_client = self.github_client.clone();
@@ -55,7 +63,7 @@ impl ResourceRegistry {
             })
         });
 
-        self.handlers.insert("repo://{owner}/{repo}/refs/heads/{branch}/contents{/path*}".to_string(), branch_handler);
+        self.handlers.insert(BRANCH_CONTENTS_URI.to_string(), branch_handler);
 
         // Commit-specific content handler
         let github_client = self.github_client.clone();
@@ -68,7 +76,7 @@ impl ResourceRegistry {
             })
         });
 
-        self.handlers.insert("repo://{owner}/{repo}/sha/{sha}/contents{/path*}".to_string(), commit_handler);
+        self.handlers.insert(COMMIT_CONTENTS_URI.to_string(), commit_handler);
 
         // Tag-specific content handler
         let github_client = self.github_client.clone();
@@ -81,7 +89,7 @@ impl ResourceRegistry {
             })
         });
 
-        self.handlers.insert("repo://{owner}/{repo}/refs/tags/{tag}/contents{/path*}".to_string(), tag_handler);
+        self.handlers.insert(TAG_CONTENTS_URI.to_string(), tag_handler);
 
         // Pull request content handler
         let github_client = self.github_client.clone();
@@ -94,7 +102,20 @@ impl ResourceRegistry {
             })
         });
 
-        self.handlers.insert("repo://{owner}/{repo}/refs/pull/{prNumber}/head/contents{/path*}".to_string(), pr_handler);
+        self.handlers.insert(PR_CONTENTS_URI.to_string(), pr_handler);
+
+        // Pull request changed files handler
+        let github_client = self.github_client.clone();
+        let pr_files_handler: ResourceHandlerFunc = Box::new(move |uri: String| {
+            let client = github_client.clone();
+            
+            Box::pin(async move {
+                debug!("Handling pull request files resource: {}", uri);
+                handle_pull_request_files_resource(&client, &uri).await
+            })
+        });
+
+        self.handlers.insert(PR_FILES_URI.to_string(), pr_files_handler);
 
         info!("Registered {} resource handlers", self.handlers.len());
     }
@@ -102,35 +123,41 @@ impl ResourceRegistry {
     pub async fn list_resources(&self) -> Result<Vec<Resource>> {
         let resources = vec![
             Resource {
-                uri: "repo://{owner}/{repo}/contents{/path*}".to_string(),
+                uri: REPO_CONTENTS_URI.to_string(),
                 name: "Repository content".to_string(),
                 description: Some("Repository content and file structure".to_string()),
                 mime_type: Some("text/plain".to_string()),
             },
             Resource {
-                uri: "repo://{owner}/{repo}/refs/heads/{branch}/contents{/path*}".to_string(),
+                uri: BRANCH_CONTENTS_URI.to_string(),
                 name: "Branch content".to_string(),
                 description: Some("Content from a specific branch".to_string()),
                 mime_type: Some("text/plain".to_string()),
             },
             Resource {
-                uri: "repo://{owner}/{repo}/sha/{sha}/contents{/path*}".to_string(),
+                uri: COMMIT_CONTENTS_URI.to_string(),
                 name: "Commit content".to_string(),
                 description: Some("Content from a specific commit".to_string()),
                 mime_type: Some("text/plain".to_string()),
             },
             Resource {
-                uri: "repo://{owner}/{repo}/refs/tags/{tag}/contents{/path*}".to_string(),
+                uri: TAG_CONTENTS_URI.to_string(),
                 name: "Tag content".to_string(),
                 description: Some("Content from a specific tag".to_string()),
                 mime_type: Some("text/plain".to_string()),
             },
             Resource {
-                uri: "repo://{owner}/{repo}/refs/pull/{prNumber}/head/contents{/path*}".to_string(),
+                uri: PR_CONTENTS_URI.to_string(),
                 name: "Pull request content".to_string(),
                 description: Some("Content from a pull request head".to_string()),
                 mime_type: Some("text/plain".to_string()),
             },
+            Resource {
+                uri: PR_FILES_URI.to_string(),
+                name: "Pull request files".to_string(),
+                description: Some("Files changed by a pull request, with their patches".to_string()),
+                mime_type: Some("application/json".to_string()),
+            },
         ];
 
         Ok(resources)
@@ -139,14 +166,11 @@ impl ResourceRegistry {
     pub async fn read_resource(&self, uri: &str) -> Result<Value> {
         debug!("Reading resource: {}", uri);
 
-        // Find matching handler by checking URI patterns
-        for (pattern, handler) in &self.handlers {
-            if uri_matches_pattern(uri, pattern) {
-                return handler(uri.to_string()).await;
-            }
-        }
+        let pattern = best_matching_pattern(uri, self.handlers.keys().map(String::as_str))
+            .ok_or_else(|| anyhow!("No handler found for resource URI: {}", uri))?;
+        debug!("Resource {} matched {}", uri, pattern);
 
-        Err(anyhow!("No handler found for resource URI: {}", uri))
+        self.handlers[pattern](uri.to_string()).await
     }
 
     // Note: handlers cannot be cloned, so we handle resources directly
@@ -161,28 +185,8 @@ async fn handle_repository_resource(client: &GitHubClient, uri: &str) -> Result<
     match client.get_file_contents(owner, repo, path, None).await {
         Ok(content) => {
             if content.r#type == "file" {
-                let text = if let Some(encoded_content) = &content.content {
-                    match content.encoding.as_deref() {
-                        Some("base64") => {
-                            match base64::decode(encoded_content.replace('\n', "")) {
-                                Ok(bytes) => String::from_utf8_lossy(&bytes).to_string(),
-                                Err(_) => format!("Binary file: {}", content.name),
-                            }
-                        }
-                        _ => encoded_content.clone(),
-                    }
-                } else {
-                    format!("File: {}", content.name)
-                };
-
                 Ok(serde_json::json!({
-                    "contents": [
-                        {
-                            "uri": uri,
-                            "mimeType": "text/plain",
-                            "text": text
-                        }
-                    ]
+                    "contents": [file_resource_content(uri, &content)]
                 }))
             } else {
                 Ok(serde_json::json!({
@@ -213,28 +217,8 @@ async fn handle_branch_resource(client: &GitHubClient, uri: &str) -> Result<Valu
     match client.get_file_contents(owner, repo, path, Some(branch)).await {
         Ok(content) => {
             if content.r#type == "file" {
-                let text = if let Some(encoded_content) = &content.content {
-                    match content.encoding.as_deref() {
-                        Some("base64") => {
-                            match base64::decode(encoded_content.replace('\n', "")) {
-                                Ok(bytes) => String::from_utf8_lossy(&bytes).to_string(),
-                                Err(_) => format!("Binary file: {}", content.name),
-                            }
-                        }
-                        _ => encoded_content.clone(),
-                    }
-                } else {
-                    format!("File: {}", content.name)
-                };
-
                 Ok(serde_json::json!({
-                    "contents": [
-                        {
-                            "uri": uri,
-                            "mimeType": "text/plain",
-                            "text": text
-                        }
-                    ]
+                    "contents": [file_resource_content(uri, &content)]
                 }))
             } else {
                 Ok(serde_json::json!({
@@ -265,28 +249,8 @@ async fn handle_commit_resource(client: &GitHubClient, uri: &str) -> Result<Valu
     match client.get_file_contents(owner, repo, path, Some(sha)).await {
         Ok(content) => {
             if content.r#type == "file" {
-                let text = if let Some(encoded_content) = &content.content {
-                    match content.encoding.as_deref() {
-                        Some("base64") => {
-                            match base64::decode(encoded_content.replace('\n', "")) {
-                                Ok(bytes) => String::from_utf8_lossy(&bytes).to_string(),
-                                Err(_) => format!("Binary file: {}", content.name),
-                            }
-                        }
-                        _ => encoded_content.clone(),
-                    }
-                } else {
-                    format!("File: {}", content.name)
-                };
-
                 Ok(serde_json::json!({
-                    "contents": [
-                        {
-                            "uri": uri,
-                            "mimeType": "text/plain",
-                            "text": text
-                        }
-                    ]
+                    "contents": [file_resource_content(uri, &content)]
                 }))
             } else {
                 Ok(serde_json::json!({
@@ -317,28 +281,8 @@ async fn handle_tag_resource(client: &GitHubClient, uri: &str) -> Result<Value>
     match client.get_file_contents(owner, repo, path, Some(tag)).await {
         Ok(content) => {
             if content.r#type == "file" {
-                let text = if let Some(encoded_content) = &content.content {
-                    match content.encoding.as_deref() {
-                        Some("base64") => {
-                            match base64::decode(encoded_content.replace('\n', "")) {
-                                Ok(bytes) => String::from_utf8_lossy(&bytes).to_string(),
-                                Err(_) => format!("Binary file: {}", content.name),
-                            }
-                        }
-                        _ => encoded_content.clone(),
-                    }
-                } else {
-                    format!("File: {}", content.name)
-                };
-
                 Ok(serde_json::json!({
-                    "contents": [
-                        {
-                            "uri": uri,
-                            "mimeType": "text/plain",
-                            "text": text
-                        }
-                    ]
+                    "contents": [file_resource_content(uri, &content)]
                 }))
             } else {
                 Ok(serde_json::json!({
@@ -373,28 +317,8 @@ async fn handle_pull_request_resource(client: &GitHubClient, uri: &str) -> Resul
             match client.get_file_contents(owner, repo, path, Some(&head_sha)).await {
                 Ok(content) => {
                     if content.r#type == "file" {
-                        let text = if let Some(encoded_content) = &content.content {
-                            match content.encoding.as_deref() {
-                                Some("base64") => {
-                                    match base64::decode(encoded_content.replace('\n', "")) {
-                                        Ok(bytes) => String::from_utf8_lossy(&bytes).to_string(),
-                                        Err(_) => format!("Binary file: {}", content.name),
-                                    }
-                                }
-                                _ => encoded_content.clone(),
-                            }
-                        } else {
-                            format!("File: {}", content.name)
-                        };
-
                         Ok(serde_json::json!({
-                            "contents": [
-                                {
-                                    "uri": uri,
-                                    "mimeType": "text/plain",
-                                    "text": text
-                                }
-                            ]
+                            "contents": [file_resource_content(uri, &content)]
                         }))
                     } else {
                         Ok(serde_json::json!({
@@ -421,12 +345,135 @@ async fn handle_pull_request_resource(client: &GitHubClient, uri: &str) -> Resul
     }
 }
 
+/// Resource content for a file. UTF-8 files are returned as `text`; anything
+/// else keeps its base64 data as a `blob` typed from the file extension.
+fn file_resource_content(uri: &str, content: &FileContent) -> Value {
+    let text_content = |text: String| {
+        serde_json::json!({
+            "uri": uri,
+            "mimeType": "text/plain",
+            "text": text
+        })
+    };
+
+    let Some(encoded_content) = &content.content else {
+        return text_content(format!("File: {}", content.name));
+    };
+    if content.encoding.as_deref() != Some("base64") {
+        return text_content(encoded_content.clone());
+    }
+
+    let encoded_content = encoded_content.replace('\n', "");
+    match base64::decode(&encoded_content) {
+        Ok(bytes) => match String::from_utf8(bytes) {
+            Ok(text) => text_content(text),
+            Err(_) => serde_json::json!({
+                "uri": uri,
+                "mimeType": asset_content_type(&content.name),
+                "blob": encoded_content
+            }),
+        },
+        Err(_) => text_content(format!("Binary file: {}", content.name)),
+    }
+}
+
+/// Upper bound on pages fetched for the files resource; GitHub stops listing
+/// pull request files after 3000 entries.
+const PR_FILES_MAX_PAGES: u32 = 30;
+const PR_FILES_PER_PAGE: u8 = 100;
+
+async fn handle_pull_request_files_resource(client: &GitHubClient, uri: &str) -> Result<Value> {
+    let parts = parse_pr_files_uri(uri)?;
+    let owner = parts.get("owner").ok_or_else(|| anyhow!("Missing owner"))?;
+    let repo = parts.get("repo").ok_or_else(|| anyhow!("Missing repo"))?;
+    let pr_number = parts.get("prNumber").ok_or_else(|| anyhow!("Missing prNumber"))?;
+    let pr_number: u64 = pr_number.parse()?;
+
+    let mut files = Vec::new();
+    for page in 1..=PR_FILES_MAX_PAGES {
+        match client.list_pull_request_files(owner, repo, pr_number, Some(PR_FILES_PER_PAGE), Some(page)).await {
+            Ok(result) => {
+                files.extend(result.items);
+                if !result.pagination.has_next {
+                    break;
+                }
+            }
+            Err(e) => {
+                error!("Failed to list pull request files: {}", e);
+                return Err(e);
+            }
+        }
+    }
+
+    Ok(serde_json::json!({
+        "contents": [
+            {
+                "uri": uri,
+                "mimeType": "application/json",
+                "text": serde_json::to_string_pretty(&files)?
+            }
+        ]
+    }))
+}
+
+/// Matches a URI against a `repo://` template segment by segment. `{name}`
+/// matches exactly one non-empty segment, and a trailing `{/path*}` matches
+/// any remaining segments.
 fn uri_matches_pattern(uri: &str, pattern: &str) -> bool {
-    // Simple pattern matching - in a real implementation you'd want more sophisticated matching
-    if pattern.contains("{owner}") && pattern.contains("{repo}") {
-        return uri.starts_with("repo://");
+    let (Some(uri), Some(pattern)) = (uri.strip_prefix("repo://"), pattern.strip_prefix("repo://")) else {
+        return false;
+    };
+    let (pattern, open_ended) = match pattern.strip_suffix("{/path*}") {
+        Some(prefix) => (prefix, true),
+        None => (pattern, false),
+    };
+
+    let uri_segments: Vec<&str> = uri.split('/').collect();
+    let pattern_segments: Vec<&str> = pattern.split('/').collect();
+    if uri_segments.len() < pattern_segments.len()
+        || (!open_ended && uri_segments.len() != pattern_segments.len())
+    {
+        return false;
     }
-    false
+
+    pattern_segments.iter().zip(&uri_segments).all(|(expected, actual)| {
+        if expected.starts_with('{') && expected.ends_with('}') {
+            !actual.is_empty()
+        } else {
+            expected == actual
+        }
+    })
+}
+
+/// Picks the pattern that matches `uri` with the most literal segments
+/// (`refs/heads/`, `sha/`, `refs/tags/`, `refs/pull/`, `contents`), so the
+/// choice never depends on the order patterns are tried in.
+fn best_matching_pattern<'a>(uri: &str, patterns: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    patterns
+        .into_iter()
+        .filter(|pattern| uri_matches_pattern(uri, pattern))
+        .max_by_key(|pattern| {
+            pattern
+                .split('/')
+                .filter(|segment| !segment.is_empty() && !segment.contains('{'))
+                .count()
+        })
+}
+
+/// Percent-decodes one URI path segment, so `some%20file.rs` becomes `some file.rs`.
+fn decode_segment(segment: &str) -> Result<String> {
+    urlencoding::decode(segment)
+        .map(|decoded| decoded.into_owned())
+        .map_err(|_| anyhow!("Invalid percent-encoding in resource URI segment: {}", segment))
+}
+
+/// Decodes each segment of a split path and rejoins them with `/`.
+fn decode_path(segments: &[&str]) -> Result<String> {
+    Ok(segments
+        .iter()
+        .map(|segment| decode_segment(segment))
+        .collect::<Result<Vec<_>>>()?
+        .join("/"))
 }
 
 fn parse_repo_uri(uri: &str) -> Result<HashMap<String, String>> {
@@ -436,11 +483,11 @@ fn parse_repo_uri(uri: &str) -> Result<HashMap<String, String>> {
     if let Some(stripped) = uri.strip_prefix("repo://") {
         let segments: Vec<&str> = stripped.split('/').collect();
         if segments.len() >= 3 {
-            parts.insert("owner".to_string(), segments[0].to_string());
-            parts.insert("repo".to_string(), segments[1].to_string());
+            parts.insert("owner".to_string(), decode_segment(segments[0])?);
+            parts.insert("repo".to_string(), decode_segment(segments[1])?);
             
             if segments.len() > 3 && segments[2] == "contents" {
-                let path = segments[3..].join("/");
+                let path = decode_path(&segments[3..])?;
                 if !path.is_empty() {
                     parts.insert("path".to_string(), path);
                 }
@@ -458,12 +505,12 @@ fn parse_branch_uri(uri: &str) -> Result<HashMap<String, String>> {
     if let Some(stripped) = uri.strip_prefix("repo://") {
         let segments: Vec<&str> = stripped.split('/').collect();
         if segments.len() >= 6 {
-            parts.insert("owner".to_string(), segments[0].to_string());
-            parts.insert("repo".to_string(), segments[1].to_string());
-            parts.insert("branch".to_string(), segments[4].to_string());
+            parts.insert("owner".to_string(), decode_segment(segments[0])?);
+            parts.insert("repo".to_string(), decode_segment(segments[1])?);
+            parts.insert("branch".to_string(), decode_segment(segments[4])?);
             
             if segments.len() > 6 && segments[5] == "contents" {
-                let path = segments[6..].join("/");
+                let path = decode_path(&segments[6..])?;
                 if !path.is_empty() {
                     parts.insert("path".to_string(), path);
                 }
@@ -481,12 +528,12 @@ fn parse_commit_uri(uri: &str) -> Result<HashMap<String, String>> {
     if let Some(stripped) = uri.strip_prefix("repo://") {
         let segments: Vec<&str> = stripped.split('/').collect();
         if segments.len() >= 5 {
-            parts.insert("owner".to_string(), segments[0].to_string());
-            parts.insert("repo".to_string(), segments[1].to_string());
-            parts.insert("sha".to_string(), segments[3].to_string());
+            parts.insert("owner".to_string(), decode_segment(segments[0])?);
+            parts.insert("repo".to_string(), decode_segment(segments[1])?);
+            parts.insert("sha".to_string(), decode_segment(segments[3])?);
             
             if segments.len() > 5 && segments[4] == "contents" {
-                let path = segments[5..].join("/");
+                let path = decode_path(&segments[5..])?;
                 if !path.is_empty() {
                     parts.insert("path".to_string(), path);
                 }
@@ -504,12 +551,12 @@ fn parse_tag_uri(uri: &str) -> Result<HashMap<String, String>> {
     if let Some(stripped) = uri.strip_prefix("repo://") {
         let segments: Vec<&str> = stripped.split('/').collect();
         if segments.len() >= 6 {
-            parts.insert("owner".to_string(), segments[0].to_string());
-            parts.insert("repo".to_string(), segments[1].to_string());
-            parts.insert("tag".to_string(), segments[4].to_string());
+            parts.insert("owner".to_string(), decode_segment(segments[0])?);
+            parts.insert("repo".to_string(), decode_segment(segments[1])?);
+            parts.insert("tag".to_string(), decode_segment(segments[4])?);
             
             if segments.len() > 6 && segments[5] == "contents" {
-                let path = segments[6..].join("/");
+                let path = decode_path(&segments[6..])?;
                 if !path.is_empty() {
                     parts.insert("path".to_string(), path);
                 }
@@ -527,12 +574,12 @@ fn parse_pr_uri(uri: &str) -> Result<HashMap<String, String>> {
     if let Some(stripped) = uri.strip_prefix("repo://") {
         let segments: Vec<&str> = stripped.split('/').collect();
         if segments.len() >= 7 {
-            parts.insert("owner".to_string(), segments[0].to_string());
-            parts.insert("repo".to_string(), segments[1].to_string());
-            parts.insert("prNumber".to_string(), segments[4].to_string());
+            parts.insert("owner".to_string(), decode_segment(segments[0])?);
+            parts.insert("repo".to_string(), decode_segment(segments[1])?);
+            parts.insert("prNumber".to_string(), decode_segment(segments[4])?);
             
             if segments.len() > 7 && segments[6] == "contents" {
-                let path = segments[7..].join("/");
+                let path = decode_path(&segments[7..])?;
                 if !path.is_empty() {
                     parts.insert("path".to_string(), path);
                 }
@@ -541,4 +588,221 @@ fn parse_pr_uri(uri: &str) -> Result<HashMap<String, String>> {
     }
     
     Ok(parts)
-}
\ No newline at end of file
+}
+
+fn parse_pr_files_uri(uri: &str) -> Result<HashMap<String, String>> {
+    // Parse repo://owner/repo/refs/pull/123/files
+    let mut parts = HashMap::new();
+    
+    if let Some(stripped) = uri.strip_prefix("repo://") {
+        let segments: Vec<&str> = stripped.split('/').collect();
+        if segments.len() == 6 && segments[5] == "files" {
+            parts.insert("owner".to_string(), decode_segment(segments[0])?);
+            parts.insert("repo".to_string(), decode_segment(segments[1])?);
+            parts.insert("prNumber".to_string(), decode_segment(segments[4])?);
+        }
+    }
+    
+    Ok(parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::test_client;
+
+    #[test]
+    fn test_uri_matches_pattern() {
+        let contents = "repo://{owner}/{repo}/contents{/path*}";
+        let pr_contents = "repo://{owner}/{repo}/refs/pull/{prNumber}/head/contents{/path*}";
+        let pr_files = "repo://{owner}/{repo}/refs/pull/{prNumber}/files";
+
+        assert!(uri_matches_pattern("repo://octo/repo/contents/src/main.rs", contents));
+        assert!(uri_matches_pattern("repo://octo/repo/contents", contents));
+        assert!(!uri_matches_pattern("repo://octo/repo/refs/pull/42/files", contents));
+
+        assert!(uri_matches_pattern("repo://octo/repo/refs/pull/42/head/contents/README.md", pr_contents));
+        assert!(!uri_matches_pattern("repo://octo/repo/refs/pull/42/files", pr_contents));
+
+        assert!(uri_matches_pattern("repo://octo/repo/refs/pull/42/files", pr_files));
+        assert!(!uri_matches_pattern("repo://octo/repo/refs/pull/42/files/extra", pr_files));
+        assert!(!uri_matches_pattern("repo://octo/repo/refs/pull//files", pr_files));
+    }
+
+    #[test]
+    fn test_best_matching_pattern_picks_handler_per_shape() {
+        let patterns = [
+            REPO_CONTENTS_URI,
+            BRANCH_CONTENTS_URI,
+            COMMIT_CONTENTS_URI,
+            TAG_CONTENTS_URI,
+            PR_CONTENTS_URI,
+            PR_FILES_URI,
+        ];
+        let cases = [
+            ("repo://octo/repo/contents/src/main.rs", REPO_CONTENTS_URI),
+            ("repo://octo/repo/refs/heads/main/contents/README.md", BRANCH_CONTENTS_URI),
+            ("repo://octo/repo/sha/6dcb09b/contents/src/lib.rs", COMMIT_CONTENTS_URI),
+            ("repo://octo/repo/refs/tags/v1.0.0/contents/Cargo.toml", TAG_CONTENTS_URI),
+            ("repo://octo/repo/refs/pull/42/head/contents/docs/index.md", PR_CONTENTS_URI),
+            ("repo://octo/repo/refs/pull/42/files", PR_FILES_URI),
+        ];
+
+        // Every order must pick the same handler
+        for rotation in 0..patterns.len() {
+            let mut ordered = patterns;
+            ordered.rotate_left(rotation);
+            for (uri, expected) in cases {
+                assert_eq!(best_matching_pattern(uri, ordered), Some(expected), "{}", uri);
+            }
+        }
+
+        // A directory named like a marker stays in the plain contents form
+        assert_eq!(
+            best_matching_pattern("repo://octo/repo/contents/refs/heads/x", patterns),
+            Some(REPO_CONTENTS_URI)
+        );
+        assert_eq!(best_matching_pattern("repo://octo/repo/refs/remotes/main", patterns), None);
+        assert_eq!(best_matching_pattern("https://github.com/octo/repo", patterns), None);
+    }
+
+    #[tokio::test]
+    async fn test_pull_request_files_resource() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET")).and(path("/api/v3/repos/octo/repo/pulls/42/files"))
+            .and(query_param("per_page", "100"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {
+                    "sha": "bbcd538c8e72b8c175046e27cc8f907076331401",
+                    "filename": "src/lib.rs",
+                    "status": "modified",
+                    "additions": 2,
+                    "deletions": 1,
+                    "changes": 3,
+                    "patch": "@@ -1,2 +1,3 @@\n-old\n+new\n+more"
+                },
+                {
+                    "sha": "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391",
+                    "filename": "docs/logo.png",
+                    "status": "added",
+                    "additions": 0,
+                    "deletions": 0,
+                    "changes": 0
+                }
+            ])))
+            .expect(1).mount(&server).await;
+
+        let client = Arc::new(test_client(&server.uri()).await);
+        let registry = ResourceRegistry::new(client);
+
+        let uri = "repo://octo/repo/refs/pull/42/files";
+        let result = registry.read_resource(uri).await.unwrap();
+        let content = &result["contents"][0];
+        assert_eq!(content["uri"], uri);
+        assert_eq!(content["mimeType"], "application/json");
+
+        let files: Vec<Value> = serde_json::from_str(content["text"].as_str().unwrap()).unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0]["filename"], "src/lib.rs");
+        assert_eq!(files[0]["patch"], "@@ -1,2 +1,3 @@\n-old\n+new\n+more");
+        assert_eq!(files[1]["status"], "added");
+        assert!(files[1]["patch"].is_null());
+    }
+
+    #[test]
+    fn test_parse_uris_percent_decode_segments() {
+        let parts = parse_repo_uri("repo://octo/repo/contents/docs/some%20file%2Bv2.md").unwrap();
+        assert_eq!(parts["path"], "docs/some file+v2.md");
+
+        let parts = parse_branch_uri("repo://octo/repo/refs/heads/feature%2Fx/contents/a%20b.rs").unwrap();
+        assert_eq!(parts["branch"], "feature/x");
+        assert_eq!(parts["path"], "a b.rs");
+
+        let parts = parse_tag_uri("repo://octo/repo/refs/tags/v1.0%2Bbuild/contents/notes.md").unwrap();
+        assert_eq!(parts["tag"], "v1.0+build");
+
+        // A literal `+` is not a space in a path
+        let parts = parse_commit_uri("repo://octo/repo/sha/abc123/contents/c++/main.cc").unwrap();
+        assert_eq!(parts["path"], "c++/main.cc");
+
+        assert!(parse_repo_uri("repo://octo/repo/contents/%FF").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_repository_resource_reads_file_with_encoded_name() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET")).and(path("/api/v3/repos/octo/repo/contents/src/some%20file%2Bv2.rs"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "type": "file",
+                "encoding": "base64",
+                "size": 12,
+                "name": "some file+v2.rs",
+                "path": "src/some file+v2.rs",
+                "content": "Zm4gbWFpbigpIHt9",
+                "sha": "3d21ec53a331a6f037a91c368710b99387d012c1",
+                "url": "https://api.github.com/repos/octo/repo/contents/src/some%20file%2Bv2.rs",
+                "html_url": "https://github.com/octo/repo/blob/main/src/some%20file%2Bv2.rs",
+                "git_url": "https://api.github.com/repos/octo/repo/git/blobs/3d21ec53a331a6f037a91c368710b99387d012c1",
+                "download_url": "https://raw.githubusercontent.com/octo/repo/main/src/some%20file%2Bv2.rs"
+            })))
+            .expect(1).mount(&server).await;
+
+        let client = Arc::new(test_client(&server.uri()).await);
+        let registry = ResourceRegistry::new(client);
+
+        let uri = "repo://octo/repo/contents/src/some%20file%2Bv2.rs";
+        let result = registry.read_resource(uri).await.unwrap();
+        assert_eq!(result["contents"][0]["uri"], uri);
+        assert_eq!(result["contents"][0]["text"], "fn main() {}");
+    }
+
+    #[tokio::test]
+    async fn test_binary_file_resource_returns_blob() {
+        use base64::engine::general_purpose::STANDARD;
+        use base64::Engine;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        // PNG signature and the start of an IHDR chunk; not valid UTF-8
+        let png: Vec<u8> = vec![0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52];
+        let encoded = STANDARD.encode(&png);
+        // GitHub wraps base64 content across lines
+        let wrapped = format!("{}\n{}", &encoded[..12], &encoded[12..]);
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET")).and(path("/api/v3/repos/octo/repo/contents/docs/logo.png"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "type": "file",
+                "encoding": "base64",
+                "size": png.len(),
+                "name": "logo.png",
+                "path": "docs/logo.png",
+                "content": wrapped,
+                "sha": "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391",
+                "url": "https://api.github.com/repos/octo/repo/contents/docs/logo.png",
+                "html_url": "https://github.com/octo/repo/blob/main/docs/logo.png",
+                "git_url": "https://api.github.com/repos/octo/repo/git/blobs/e69de29bb2d1d6434b8b29ae775ad8c2e48c5391",
+                "download_url": "https://raw.githubusercontent.com/octo/repo/main/docs/logo.png"
+            })))
+            .expect(1).mount(&server).await;
+
+        let client = Arc::new(test_client(&server.uri()).await);
+        let registry = ResourceRegistry::new(client);
+
+        let uri = "repo://octo/repo/contents/docs/logo.png";
+        let result = registry.read_resource(uri).await.unwrap();
+        let content = &result["contents"][0];
+        assert_eq!(content["uri"], uri);
+        assert_eq!(content["mimeType"], "image/png");
+        assert!(content.get("text").is_none());
+        assert_eq!(STANDARD.decode(content["blob"].as_str().unwrap()).unwrap(), png);
+    }
+}
@@ -7,14 +7,93 @@ use crate::mcp_core::{
 };
 use serde_json::{Map, Value};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::sync::RwLock;
+use tokio::task::AbortHandle;
 use tracing::{debug, error, info, warn};
 
-use crate::github::{GitHubClient, GitHubConfig};
-use crate::tools::{ToolRegistry, ToolsetGroup};
+use crate::github::{BackoffPolicy, GitHubAppCredentials, GitHubClient, GitHubConfig};
+use crate::tools::{InvalidParamsError, ToolRegistry, ToolTimeoutError, ToolsetGroup};
 use crate::resources::ResourceRegistry;
 
+/// MCP protocol revisions this server speaks, newest first.
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+/// Picks the protocol version to answer `initialize` with: the client's
+/// version when supported, otherwise the newest supported revision that is
+/// not newer than the request, falling back to our latest revision.
+pub fn negotiate_protocol_version(requested: Option<&str>) -> &'static str {
+    let latest = SUPPORTED_PROTOCOL_VERSIONS[0];
+    let Some(requested) = requested.filter(|v| is_protocol_version(v)) else {
+        return latest;
+    };
+
+    // Revisions are YYYY-MM-DD dates, so string order is chronological
+    SUPPORTED_PROTOCOL_VERSIONS
+        .iter()
+        .copied()
+        .find(|supported| *supported <= requested)
+        .unwrap_or(latest)
+}
+
+fn is_protocol_version(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    bytes.len() == 10
+        && bytes.iter().enumerate().all(|(i, b)| match i {
+            4 | 7 => *b == b'-',
+            _ => b.is_ascii_digit(),
+        })
+}
+
+/// Tool calls running on their own task, keyed by JSON-RPC request id, so a
+/// `notifications/cancelled` for that id can abort them.
+#[derive(Clone, Default)]
+pub struct InFlightCalls {
+    running: Arc<Mutex<HashMap<String, AbortHandle>>>,
+}
+
+impl InFlightCalls {
+    /// Runs `call` on a new task tracked under `id` until it completes.
+    pub fn spawn<F>(&self, id: &Value, call: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let key = id.to_string();
+        let calls = self.clone();
+        let finished_key = key.clone();
+
+        // Hold the lock across spawn so the task can't finish before it is tracked
+        let mut running = self.running.lock().unwrap();
+        let task = tokio::spawn(async move {
+            call.await;
+            calls.running.lock().unwrap().remove(&finished_key);
+        });
+        running.insert(key, task.abort_handle());
+    }
+
+    /// Aborts the call running under `id`; returns false if it already finished.
+    pub fn cancel(&self, id: &Value) -> bool {
+        match self.running.lock().unwrap().remove(&id.to_string()) {
+            Some(handle) => {
+                handle.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.running.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
 pub struct GitHubMcpServer {
     server: McpServer,
     github_client: Arc<GitHubClient>,
@@ -31,15 +110,48 @@ pub struct GitHubServerConfig {
     pub enabled_toolsets: Vec<String>,
     pub dynamic_toolsets: bool,
     pub read_only: bool,
+    pub dry_run: bool,
     pub enable_command_logging: bool,
+    /// Advertise this protocol version instead of negotiating one.
+    pub protocol_version: Option<String>,
+    /// Abort tool calls that run longer than this.
+    pub tool_timeout: Option<Duration>,
+    /// Delays between retries of rate-limited and 5xx GitHub responses.
+    pub backoff: BackoffPolicy,
+    /// Retries after a rate limit or 5xx before giving up.
+    pub max_retries: u32,
+    /// Wait out rate limits instead of failing straight away.
+    pub respect_rate_limit: bool,
+    /// Longest rate-limit reset to wait out before failing instead.
+    pub max_rate_limit_wait: Duration,
+    /// Authenticate as a GitHub App installation instead of with `token`.
+    pub app_auth: Option<GitHubAppCredentials>,
+    /// Directory release assets may be uploaded from.
+    pub uploads_dir: Option<PathBuf>,
 }
 
 impl GitHubMcpServer {
     pub async fn new(config: GitHubServerConfig) -> Result<Self> {
+        if let Some(version) = &config.protocol_version {
+            if !SUPPORTED_PROTOCOL_VERSIONS.contains(&version.as_str()) {
+                return Err(anyhow!(
+                    "Unsupported protocol version {}, expected one of: {}",
+                    version,
+                    SUPPORTED_PROTOCOL_VERSIONS.join(", ")
+                ));
+            }
+        }
+
         let github_config = GitHubConfig {
             token: config.token.clone(),
             host: config.host.clone(),
             user_agent: format!("github-mcp-server/{}", config.version),
+            backoff: config.backoff.clone(),
+            max_retries: config.max_retries,
+            respect_rate_limit: config.respect_rate_limit,
+            max_rate_limit_wait: config.max_rate_limit_wait,
+            app_auth: config.app_auth.clone(),
+            uploads_dir: config.uploads_dir.clone(),
         };
 
         let github_client = Arc::new(GitHubClient::new(github_config).await?);
@@ -47,7 +159,9 @@ impl GitHubMcpServer {
         let tools = Arc::new(RwLock::new(ToolRegistry::new(
             config.enabled_toolsets.clone(),
             config.read_only,
+            config.dry_run,
             config.dynamic_toolsets,
+            config.tool_timeout,
             github_client.clone(),
         )));
 
@@ -84,7 +198,7 @@ impl GitHubMcpServer {
     }
 
     pub async fn handle_request(&self, request: JsonRpcRequest) -> JsonRpcResponse {
-        debug!("Handling request: {:?}", request);
+        debug!("Handling request: {}", redacted_json(&request));
         
         let response = match request.method.as_str() {
             "initialize" => self.handle_initialize(request).await,
@@ -92,6 +206,10 @@ impl GitHubMcpServer {
             "tools/call" => self.handle_call_tool(request).await,
             "resources/list" => self.handle_list_resources(request).await,
             "resources/read" => self.handle_read_resource(request).await,
+            "resources/subscribe" | "resources/unsubscribe" => {
+                debug!("Rejecting unsupported method: {}", request.method);
+                subscription_not_supported(request.id, &request.method)
+            }
             _ => {
                 warn!("Unknown method: {}", request.method);
                 JsonRpcResponse::error(
@@ -104,26 +222,28 @@ impl GitHubMcpServer {
         };
 
         if self.config.enable_command_logging {
-            debug!("Response: {:?}", response);
+            debug!("Response: {}", redacted_json(&response));
         }
 
         response
     }
 
     async fn handle_initialize(&self, request: JsonRpcRequest) -> JsonRpcResponse {
-        let capabilities = serde_json::json!({
-            "tools": {
-                "list": true,
-                "call": true
-            },
-            "resources": {
-                "list": true,
-                "read": true
-            }
-        });
+        let capabilities = server_capabilities();
+
+        let requested = request.params.as_ref()
+            .and_then(|params| params.get("protocolVersion"))
+            .and_then(|v| v.as_str());
+        let protocol_version = match &self.config.protocol_version {
+            Some(version) => version.as_str(),
+            None => negotiate_protocol_version(requested),
+        };
+        if requested.is_some_and(|v| v != protocol_version) {
+            info!("Client requested protocol {:?}, answering with {}", requested, protocol_version);
+        }
 
         let result = serde_json::json!({
-            "protocolVersion": "0.1.0",
+            "protocolVersion": protocol_version,
             "capabilities": capabilities,
             "serverInfo": {
                 "name": "github-mcp-server",
@@ -155,6 +275,11 @@ impl GitHubMcpServer {
     }
 
     async fn handle_call_tool(&self, request: JsonRpcRequest) -> JsonRpcResponse {
+        Self::call_tool_response(&self.tools, request).await
+    }
+
+    /// Runs a `tools/call` without borrowing the server, so it can run on its own task.
+    async fn call_tool_response(tools: &RwLock<ToolRegistry>, request: JsonRpcRequest) -> JsonRpcResponse {
         let params = request.params.unwrap_or_default();
         
         let tool_name = match params.get("name").and_then(|v| v.as_str()) {
@@ -174,8 +299,27 @@ impl GitHubMcpServer {
             .cloned()
             .unwrap_or_default();
 
-        match self.tools.read().await.call_tool(tool_name, arguments).await {
+        match tools.read().await.call_tool(tool_name, arguments).await {
             Ok(result) => JsonRpcResponse::success(request.id, result),
+            Err(e) if e.is::<ToolTimeoutError>() => {
+                JsonRpcResponse::error(
+                    request.id,
+                    -32603,
+                    &e.to_string(),
+                    e.downcast_ref::<ToolTimeoutError>()
+                        .map(|err| serde_json::json!({"timeout_ms": err.timeout.as_millis() as u64})),
+                )
+            }
+            Err(e) if e.is::<InvalidParamsError>() => {
+                warn!("Rejected tool call to {}: {}", tool_name, e);
+                JsonRpcResponse::error(
+                    request.id,
+                    -32602,
+                    &e.to_string(),
+                    e.downcast_ref::<InvalidParamsError>()
+                        .map(|err| serde_json::json!({"field": err.field})),
+                )
+            }
             Err(e) => {
                 error!("Tool call failed: {}", e);
                 JsonRpcResponse::error(
@@ -243,10 +387,23 @@ impl GitHubMcpServer {
         self.initialize().await?;
 
         let stdin = tokio::io::stdin();
-        let mut stdout = tokio::io::stdout();
         let mut reader = BufReader::new(stdin);
         let mut line = String::new();
 
+        // Tool calls answer out of order, so every response goes through one writer
+        let (responses, mut outgoing) = tokio::sync::mpsc::unbounded_channel::<JsonRpcResponse>();
+        let writer = tokio::spawn(async move {
+            let mut stdout = tokio::io::stdout();
+            while let Some(response) = outgoing.recv().await {
+                let response_json = serde_json::to_string(&response)?;
+                stdout.write_all(response_json.as_bytes()).await?;
+                stdout.write_all(b"\n").await?;
+                stdout.flush().await?;
+            }
+            Ok::<(), anyhow::Error>(())
+        });
+        let in_flight = InFlightCalls::default();
+
         info!("GitHub MCP Server running on stdio");
 
         loop {
@@ -260,12 +417,33 @@ impl GitHubMcpServer {
                     }
 
                     match serde_json::from_str::<JsonRpcRequest>(trimmed) {
+                        Ok(request) if request.method == "notifications/cancelled" => {
+                            let request_id = request.params.as_ref().and_then(|p| p.get("requestId"));
+                            match request_id {
+                                Some(id) if in_flight.cancel(id) => info!("Cancelled tool call {}", id),
+                                Some(id) => debug!("Cancellation for {} arrived after it finished", id),
+                                None => warn!("Ignoring cancellation without a requestId"),
+                            }
+                        }
+                        Ok(request) if request.method == "tools/call" && request.id.is_some() => {
+                            let id = request.id.clone().unwrap_or_default();
+                            let tools = self.tools.clone();
+                            let responses = responses.clone();
+                            let log_commands = self.config.enable_command_logging;
+                            if log_commands {
+                                debug!("Handling request: {}", redacted_json(&request));
+                            }
+                            in_flight.spawn(&id, async move {
+                                let response = Self::call_tool_response(&tools, request).await;
+                                if log_commands {
+                                    debug!("Response: {}", redacted_json(&response));
+                                }
+                                let _ = responses.send(response);
+                            });
+                        }
                         Ok(request) => {
                             let response = self.handle_request(request).await;
-                            let response_json = serde_json::to_string(&response)?;
-                            stdout.write_all(response_json.as_bytes()).await?;
-                            stdout.write_all(b"\n").await?;
-                            stdout.flush().await?;
+                            let _ = responses.send(response);
                         }
                         Err(e) => {
                             error!("Failed to parse JSON-RPC request: {}", e);
@@ -275,10 +453,7 @@ impl GitHubMcpServer {
                                 "Parse error",
                                 Some(serde_json::json!({"error": e.to_string()})),
                             );
-                            let response_json = serde_json::to_string(&error_response)?;
-                            stdout.write_all(response_json.as_bytes()).await?;
-                            stdout.write_all(b"\n").await?;
-                            stdout.flush().await?;
+                            let _ = responses.send(error_response);
                         }
                     }
                 }
@@ -289,6 +464,10 @@ impl GitHubMcpServer {
             }
         }
 
+        // Let calls still running finish and flush their responses
+        drop(responses);
+        writer.await??;
+
         Ok(())
     }
 }
@@ -327,6 +506,82 @@ where
     }
 }
 
+/// Placeholder logged in place of credentials and other sensitive values.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Whether a field holds a credential: the `Authorization` header, or any key
+/// mentioning a secret, token or password (`secret`, `access_token`, `db_password`, ...).
+fn is_sensitive_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    key == "authorization" || ["secret", "token", "password"].iter().any(|word| key.contains(word))
+}
+
+/// Copy of `value` with every sensitive field masked, at any depth. Tool results carry
+/// their payload as serialized JSON inside `text` strings, so those are redacted too.
+pub fn redact_sensitive(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| {
+                    let value = if is_sensitive_key(key) && !value.is_null() {
+                        Value::String(REDACTED.to_string())
+                    } else {
+                        redact_sensitive(value)
+                    };
+                    (key.clone(), value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(redact_sensitive).collect()),
+        Value::String(text) if text.trim_start().starts_with(['{', '[']) => {
+            match serde_json::from_str::<Value>(text) {
+                Ok(embedded) => Value::String(redact_sensitive(&embedded).to_string()),
+                Err(_) => value.clone(),
+            }
+        }
+        _ => value.clone(),
+    }
+}
+
+/// Renders a request or response for the command log with sensitive fields masked.
+pub fn redacted_json<T: serde::Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(value) => redact_sensitive(&value).to_string(),
+        Err(e) => format!("<unserializable: {}>", e),
+    }
+}
+
+/// JSON-RPC error code for methods the server recognizes but deliberately doesn't support.
+pub const NOT_SUPPORTED_ERROR_CODE: i32 = -32000;
+
+/// Capabilities advertised on `initialize`. Resource subscriptions are explicitly
+/// off so clients don't expect `resources/subscribe` to succeed.
+pub fn server_capabilities() -> Value {
+    serde_json::json!({
+        "tools": {
+            "list": true,
+            "call": true
+        },
+        "resources": {
+            "list": true,
+            "read": true,
+            "subscribe": false,
+            "listChanged": false
+        }
+    })
+}
+
+/// Answers `resources/subscribe` and `resources/unsubscribe` with a clear "not supported"
+/// error instead of "Method not found", matching the advertised capabilities.
+pub fn subscription_not_supported(id: Option<Value>, method: &str) -> JsonRpcResponse {
+    JsonRpcResponse::error(
+        id,
+        NOT_SUPPORTED_ERROR_CODE,
+        "Resource subscriptions are not supported by this server",
+        Some(serde_json::json!({"method": method})),
+    )
+}
+
 #[derive(Debug, Clone)]
 pub struct PaginationParams {
     pub page: u32,
@@ -355,4 +610,129 @@ pub fn extract_pagination_params(args: &Map<String, Value>) -> Result<Pagination
     }
     
     Ok(PaginationParams { page, per_page })
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::{DEFAULT_MAX_RATE_LIMIT_WAIT, DEFAULT_MAX_RETRIES};
+
+    #[tokio::test]
+    async fn test_in_flight_call_is_aborted_on_cancel() {
+        let calls = InFlightCalls::default();
+        let (done_tx, done_rx) = tokio::sync::oneshot::channel::<()>();
+        calls.spawn(&serde_json::json!(7), async move {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            let _ = done_tx.send(());
+        });
+        assert_eq!(calls.len(), 1);
+
+        assert!(!calls.cancel(&serde_json::json!("7")));
+        assert!(calls.cancel(&serde_json::json!(7)));
+        assert!(calls.is_empty());
+        assert!(done_rx.await.is_err());
+
+        calls.spawn(&serde_json::json!(8), async {});
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !calls.is_empty() {
+                tokio::task::yield_now().await;
+            }
+        }).await.unwrap();
+        assert!(!calls.cancel(&serde_json::json!(8)));
+    }
+
+    #[test]
+    fn test_command_log_masks_webhook_secret() {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: Some(serde_json::json!(1)),
+            method: "tools/call".to_string(),
+            params: Some(serde_json::json!({
+                "name": "create_webhook",
+                "arguments": {
+                    "owner": "octo",
+                    "repo": "hello",
+                    "events": ["push"],
+                    "config": {
+                        "url": "https://example.com/hook",
+                        "content_type": "json",
+                        "secret": "s3cr3t-value"
+                    }
+                }
+            })),
+        };
+
+        let logged = redacted_json(&request);
+        assert!(!logged.contains("s3cr3t-value"), "secret leaked: {}", logged);
+        let logged: Value = serde_json::from_str(&logged).unwrap();
+        let config = &logged["params"]["arguments"]["config"];
+        assert_eq!(config["secret"], REDACTED);
+        assert_eq!(config["url"], "https://example.com/hook");
+
+        let response = JsonRpcResponse::success(Some(serde_json::json!(1)), serde_json::json!({
+            "content": [{
+                "type": "text",
+                "text": serde_json::json!({"id": 1, "config": {"secret": "s3cr3t-value"}}).to_string()
+            }],
+            "headers": {"Authorization": "Bearer ghp_abc", "Accept": "application/json"}
+        }));
+        let logged = redacted_json(&response);
+        assert!(!logged.contains("s3cr3t-value") && !logged.contains("ghp_abc"), "secret leaked: {}", logged);
+        assert!(logged.contains("application/json"));
+    }
+
+    #[tokio::test]
+    async fn test_initialize_advertises_mcp_protocol_version() {
+        let server = GitHubMcpServer::new(GitHubServerConfig {
+            version: "0.1.0".to_string(),
+            host: None,
+            token: "test".to_string(),
+            enabled_toolsets: vec![],
+            dynamic_toolsets: false,
+            read_only: true,
+            dry_run: false,
+            enable_command_logging: false,
+            protocol_version: None,
+            tool_timeout: None,
+            backoff: BackoffPolicy::default(),
+            max_retries: DEFAULT_MAX_RETRIES,
+            respect_rate_limit: true,
+            max_rate_limit_wait: DEFAULT_MAX_RATE_LIMIT_WAIT,
+            app_auth: None,
+            uploads_dir: None,
+        }).await.unwrap();
+
+        let initialize = |version: Value| JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: Some(serde_json::json!(1)),
+            method: "initialize".to_string(),
+            params: Some(serde_json::json!({"protocolVersion": version, "capabilities": {}})),
+        };
+
+        let response = server.handle_request(initialize(serde_json::json!("2024-11-05"))).await;
+        assert_eq!(response.result.unwrap()["protocolVersion"], "2024-11-05");
+
+        let response = server.handle_request(initialize(Value::Null)).await;
+        let advertised = response.result.unwrap()["protocolVersion"].as_str().unwrap().to_string();
+        assert!(is_protocol_version(&advertised));
+        assert!(SUPPORTED_PROTOCOL_VERSIONS.contains(&advertised.as_str()));
+
+        // Unknown revisions negotiate down to the newest one we support
+        assert_eq!(negotiate_protocol_version(Some("2025-01-01")), "2024-11-05");
+        assert_eq!(negotiate_protocol_version(Some("2099-01-01")), SUPPORTED_PROTOCOL_VERSIONS[0]);
+        assert_eq!(negotiate_protocol_version(Some("2024-01-01")), SUPPORTED_PROTOCOL_VERSIONS[0]);
+        assert_eq!(negotiate_protocol_version(Some("0.1.0")), SUPPORTED_PROTOCOL_VERSIONS[0]);
+    }
+
+    #[test]
+    fn test_subscription_capability_matches_handling() {
+        let capabilities = server_capabilities();
+        assert_eq!(capabilities["resources"]["subscribe"], false);
+
+        let response = subscription_not_supported(Some(serde_json::json!(7)), "resources/subscribe");
+        let error = response.error.expect("expected an error response");
+        assert_eq!(error.code, NOT_SUPPORTED_ERROR_CODE);
+        assert_ne!(error.code, -32601);
+        assert_eq!(error.data, Some(serde_json::json!({"method": "resources/subscribe"})));
+    }
+}
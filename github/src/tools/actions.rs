@@ -0,0 +1,168 @@
+use anyhow::Result;
+use futures::future::BoxFuture;
+use crate::mcp_core::tools::Tool;
+use serde_json::{Map, Value};
+use std::sync::Arc;
+use tracing::{debug, error};
+
+use crate::github::{GitHubClient, WorkflowRunFilters};
+use crate::server::{required_param, optional_param, extract_pagination_params};
+use super::toolsets::Toolset;
+use super::registry::ToolHandlerFunc;
+
+pub async fn create_actions_toolset(github_client: Arc<GitHubClient>, read_only: bool) -> Result<Toolset> {
+    let mut toolset = Toolset::new("actions", "GitHub Actions workflow tools");
+
+    // List workflow runs tool
+    add_list_workflow_runs_tool(&mut toolset, github_client.clone());
+
+    if !read_only {
+        // Dispatch workflow tool
+        add_dispatch_workflow_tool(&mut toolset, github_client.clone());
+    }
+
+    Ok(toolset)
+}
+
+fn add_list_workflow_runs_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "list_workflow_runs".to_string(),
+        description: "List GitHub Actions workflow runs for a repository".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "workflow_id": {
+                    "type": "string",
+                    "description": "Workflow ID or file name (e.g. ci.yml). Omit to list runs of all workflows"
+                },
+                "status": {
+                    "type": "string",
+                    "description": "Filter by run status or conclusion",
+                    "enum": ["queued", "in_progress", "completed", "success", "failure", "cancelled", "skipped", "waiting", "pending"]
+                },
+                "branch": {
+                    "type": "string",
+                    "description": "Filter by branch name"
+                },
+                "page": {
+                    "type": "number",
+                    "description": "Page number for pagination (min 1)"
+                },
+                "perPage": {
+                    "type": "number",
+                    "description": "Results per page for pagination (min 1, max 100)"
+                }
+            },
+            "required": ["owner", "repo"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let filters = WorkflowRunFilters {
+                workflow_id: optional_param(&args, "workflow_id")?,
+                status: optional_param(&args, "status")?,
+                branch: optional_param(&args, "branch")?,
+            };
+            let pagination = extract_pagination_params(&args)?;
+
+            debug!("Listing workflow runs for {}/{}", owner, repo);
+
+            match client.list_workflow_runs(
+                &owner,
+                &repo,
+                &filters,
+                Some(pagination.per_page as u8),
+                Some(pagination.page),
+            ).await {
+                Ok(runs) => {
+                    debug!("Found {} workflow runs", runs.workflow_runs.len());
+                    Ok(serde_json::to_value(runs)?)
+                }
+                Err(e) => {
+                    error!("Failed to list workflow runs: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("list_workflow_runs".to_string(), tool, handler);
+}
+
+fn add_dispatch_workflow_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "dispatch_workflow".to_string(),
+        description: "Trigger a workflow_dispatch event for a GitHub Actions workflow".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "workflow_id": {
+                    "type": "string",
+                    "description": "Workflow ID or file name (e.g. ci.yml)"
+                },
+                "ref": {
+                    "type": "string",
+                    "description": "Branch or tag to run the workflow on"
+                },
+                "inputs": {
+                    "type": "object",
+                    "description": "Input values defined by the workflow's workflow_dispatch trigger"
+                }
+            },
+            "required": ["owner", "repo", "workflow_id", "ref"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let workflow_id: String = required_param(&args, "workflow_id")?;
+            let git_ref: String = required_param(&args, "ref")?;
+            let inputs: Option<Map<String, Value>> = optional_param(&args, "inputs")?;
+
+            debug!("Dispatching workflow {} on {} in {}/{}", workflow_id, git_ref, owner, repo);
+
+            match client.dispatch_workflow(&owner, &repo, &workflow_id, &git_ref, inputs.as_ref()).await {
+                Ok(()) => {
+                    debug!("Successfully dispatched workflow");
+                    Ok(serde_json::json!({
+                        "dispatched": true,
+                        "workflow_id": workflow_id,
+                        "ref": git_ref,
+                        "message": "Workflow dispatch accepted; use list_workflow_runs to find the new run"
+                    }))
+                }
+                Err(e) => {
+                    error!("Failed to dispatch workflow: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("dispatch_workflow".to_string(), tool, handler);
+}
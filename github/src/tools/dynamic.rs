@@ -141,6 +141,8 @@ fn get_toolset_description(name: &str) -> &'static str {
         "issues" => "Issue management tools",
         "pull_requests" => "Pull request management tools",
         "users" => "User management tools",
+        "actions" => "GitHub Actions workflow tools",
+        "projects" => "Organization project (Projects v2) tools",
         "code_security" => "Code security scanning tools",
         "secret_protection" => "Secret scanning tools",
         "notifications" => "Notification management tools",
@@ -155,22 +157,65 @@ fn get_toolset_tool_names(name: &str) -> Vec<&'static str> {
         "repos" => vec![
             "search_repositories",
             "get_file_contents", 
+            "get_file_blame",
             "get_repository",
+            "get_repo_default_branch",
+            "get_repository_overview",
             "create_or_update_file",
+            "delete_file",
+            "create_commit_with_files",
+            "create_tag",
+            "create_branch",
+            "upload_release_asset",
+            "get_repo_languages",
+            "get_repo_contributors_stats",
+            "get_repo_topics",
+            "replace_repo_topics",
+            "get_repo_subscription",
+            "set_repo_subscription",
+            "delete_repo_subscription",
+            "list_commits",
+            "get_commit",
+            "list_deployments",
+            "list_environments",
         ],
         "issues" => vec![
             "get_issue",
             "list_issues",
+            "list_org_issues",
             "create_issue",
+            "list_sub_issues",
+            "add_sub_issue",
+            "add_reaction",
+            "list_issue_comments",
+            "create_issue_comment",
+            "update_issue_comment",
+            "delete_issue_comment",
         ],
         "pull_requests" => vec![
             "get_pull_request",
+            "check_mergeable",
             "list_pull_requests", 
             "create_pull_request",
+            "merge_pull_request",
         ],
         "users" => vec![
             "search_users",
         ],
+        "actions" => vec![
+            "list_workflow_runs",
+            "dispatch_workflow",
+        ],
+        "projects" => vec![
+            "list_project_items",
+            "get_project_fields",
+        ],
+        "code_security" => vec![
+            "list_dependabot_alerts",
+        ],
+        "secret_protection" => vec![
+            "list_secret_scanning_alerts",
+        ],
         "context" => vec![
             "get_me",
         ],
@@ -5,10 +5,10 @@ use serde_json::{Map, Value};
 use std::sync::Arc;
 use tracing::{debug, error};
 
-use crate::github::GitHubClient;
+use crate::github::{GitHubClient, IssueFilters};
 use crate::server::{required_param, optional_param, extract_pagination_params};
 use super::toolsets::Toolset;
-use super::registry::ToolHandlerFunc;
+use super::registry::{validate_enum_param, validate_milestone_param, validate_reaction_content, validate_rfc3339_param, InvalidParamsError, ToolHandlerFunc, REACTION_CONTENTS};
 
 pub async fn create_issues_toolset(github_client: Arc<GitHubClient>, read_only: bool) -> Result<Toolset> {
     let mut toolset = Toolset::new("issues", "Issue management tools");
@@ -19,9 +19,29 @@ pub async fn create_issues_toolset(github_client: Arc<GitHubClient>, read_only:
     // List issues tool
     add_list_issues_tool(&mut toolset, github_client.clone());
 
+    // List organization issues tool
+    add_list_org_issues_tool(&mut toolset, github_client.clone());
+
+    // List sub-issues tool
+    add_list_sub_issues_tool(&mut toolset, github_client.clone());
+
+    // List issue comments tool
+    add_list_issue_comments_tool(&mut toolset, github_client.clone());
+
     if !read_only {
         // Create issue tool
         add_create_issue_tool(&mut toolset, github_client.clone());
+
+        // Add sub-issue tool
+        add_add_sub_issue_tool(&mut toolset, github_client.clone());
+
+        // Add reaction tool
+        add_add_reaction_tool(&mut toolset, github_client.clone());
+
+        // Issue comment write tools
+        add_create_issue_comment_tool(&mut toolset, github_client.clone());
+        add_update_issue_comment_tool(&mut toolset, github_client.clone());
+        add_delete_issue_comment_tool(&mut toolset, github_client.clone());
     }
 
     Ok(toolset)
@@ -45,6 +65,10 @@ fn add_get_issue_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
                 "issue_number": {
                     "type": "number",
                     "description": "Issue number"
+                },
+                "include_reactions": {
+                    "type": "boolean",
+                    "description": "Include reaction counts (default false)"
                 }
             },
             "required": ["owner", "repo", "issue_number"]
@@ -58,10 +82,11 @@ fn add_get_issue_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
             let owner: String = required_param(&args, "owner")?;
             let repo: String = required_param(&args, "repo")?;
             let issue_number: u64 = required_param::<f64>(&args, "issue_number")? as u64;
+            let include_reactions: bool = optional_param(&args, "include_reactions")?.unwrap_or(false);
 
             debug!("Getting issue #{} for {}/{}", issue_number, owner, repo);
 
-            match client.get_issue(&owner, &repo, issue_number).await {
+            match client.get_issue(&owner, &repo, issue_number, include_reactions).await {
                 Ok(issue) => {
                     debug!("Successfully retrieved issue");
                     Ok(serde_json::to_value(issue)?)
@@ -110,6 +135,18 @@ fn add_list_issues_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>)
                     "type": "string",
                     "description": "Filter by creator"
                 },
+                "mentioned": {
+                    "type": "string",
+                    "description": "Filter by a user mentioned in the issue"
+                },
+                "milestone": {
+                    "type": "string",
+                    "description": "Filter by milestone number, 'none' for no milestone, or '*' for any"
+                },
+                "since": {
+                    "type": "string",
+                    "description": "Only issues updated at or after this RFC 3339 timestamp"
+                },
                 "sort": {
                     "type": "string",
                     "description": "Sort field",
@@ -139,33 +176,37 @@ fn add_list_issues_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>)
         Box::pin(async move {
             let owner: String = required_param(&args, "owner")?;
             let repo: String = required_param(&args, "repo")?;
-            let state: Option<String> = optional_param(&args, "state")?;
-            let labels: Option<Vec<String>> = optional_param(&args, "labels")?;
-            let assignee: Option<String> = optional_param(&args, "assignee")?;
-            let creator: Option<String> = optional_param(&args, "creator")?;
-            let sort: Option<String> = optional_param(&args, "sort")?;
-            let direction: Option<String> = optional_param(&args, "direction")?;
+            let filters = IssueFilters {
+                state: optional_param(&args, "state")?,
+                labels: optional_param(&args, "labels")?,
+                assignee: optional_param(&args, "assignee")?,
+                creator: optional_param(&args, "creator")?,
+                mentioned: optional_param(&args, "mentioned")?,
+                milestone: optional_param(&args, "milestone")?,
+                sort: optional_param(&args, "sort")?,
+                direction: optional_param(&args, "direction")?,
+                since: optional_param(&args, "since")?,
+            };
             let pagination = extract_pagination_params(&args)?;
 
+            if let Some(milestone) = &filters.milestone {
+                validate_milestone_param(milestone)?;
+            }
+            if let Some(since) = &filters.since {
+                validate_rfc3339_param("since", since)?;
+            }
+
             debug!("Listing issues for {}/{}", owner, repo);
 
             match client.list_issues(
                 &owner,
                 &repo,
-                state.as_deref(),
-                labels,
-                assignee.as_deref(),
-                creator.as_deref(),
-                None, // mentioned
-                None, // milestone
-                sort.as_deref(),
-                direction.as_deref(),
-                None, // since
+                &filters,
                 Some(pagination.per_page as u8),
                 Some(pagination.page),
             ).await {
                 Ok(issues) => {
-                    debug!("Successfully retrieved {} issues", issues.len());
+                    debug!("Successfully retrieved {} issues", issues.items.len());
                     Ok(serde_json::to_value(issues)?)
                 }
                 Err(e) => {
@@ -179,6 +220,177 @@ fn add_list_issues_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>)
     toolset.add_tool("list_issues".to_string(), tool, handler);
 }
 
+fn add_list_issue_comments_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "list_issue_comments".to_string(),
+        description: "List comments on an issue or pull request, oldest first".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "issue_number": {
+                    "type": "number",
+                    "description": "Issue or pull request number"
+                },
+                "page": {
+                    "type": "number",
+                    "description": "Page number for pagination (min 1)"
+                },
+                "perPage": {
+                    "type": "number",
+                    "description": "Results per page for pagination (min 1, max 100)"
+                }
+            },
+            "required": ["owner", "repo", "issue_number"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let issue_number: u64 = required_param::<f64>(&args, "issue_number")? as u64;
+            let pagination = extract_pagination_params(&args)?;
+
+            debug!("Listing comments on issue #{} for {}/{}", issue_number, owner, repo);
+
+            match client.list_issue_comments(
+                &owner,
+                &repo,
+                issue_number,
+                Some(pagination.per_page as u8),
+                Some(pagination.page),
+            ).await {
+                Ok(comments) => {
+                    debug!("Successfully retrieved {} issue comments", comments.items.len());
+                    Ok(serde_json::to_value(comments)?)
+                }
+                Err(e) => {
+                    error!("Failed to list issue comments: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("list_issue_comments".to_string(), tool, handler);
+}
+
+fn add_list_org_issues_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "list_org_issues".to_string(),
+        description: "List and filter issues across all repositories in an organization".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "org": {
+                    "type": "string",
+                    "description": "Organization login"
+                },
+                "state": {
+                    "type": "string",
+                    "description": "Issue state",
+                    "enum": ["open", "closed", "all"]
+                },
+                "labels": {
+                    "type": "array",
+                    "description": "Filter by labels; issues must have all of them",
+                    "items": {"type": "string"}
+                },
+                "assignee": {
+                    "type": "string",
+                    "description": "Filter by assignee"
+                },
+                "creator": {
+                    "type": "string",
+                    "description": "Filter by creator"
+                },
+                "mentioned": {
+                    "type": "string",
+                    "description": "Filter by a user mentioned in the issue"
+                },
+                "sort": {
+                    "type": "string",
+                    "description": "Sort field (defaults to best match)",
+                    "enum": ["created", "updated", "comments"]
+                },
+                "direction": {
+                    "type": "string",
+                    "description": "Sort direction",
+                    "enum": ["asc", "desc"]
+                },
+                "page": {
+                    "type": "number",
+                    "description": "Page number for pagination (min 1)"
+                },
+                "perPage": {
+                    "type": "number",
+                    "description": "Results per page for pagination (min 1, max 100)"
+                }
+            },
+            "required": ["org"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+
+        Box::pin(async move {
+            let org: String = required_param(&args, "org")?;
+            let filters = IssueFilters {
+                state: optional_param(&args, "state")?,
+                labels: optional_param(&args, "labels")?,
+                assignee: optional_param(&args, "assignee")?,
+                creator: optional_param(&args, "creator")?,
+                mentioned: optional_param(&args, "mentioned")?,
+                sort: optional_param(&args, "sort")?,
+                direction: optional_param(&args, "direction")?,
+                ..Default::default()
+            };
+            let pagination = extract_pagination_params(&args)?;
+
+            if let Some(state) = &filters.state {
+                validate_enum_param("state", state, &["open", "closed", "all"])?;
+            }
+            if let Some(sort) = &filters.sort {
+                validate_enum_param("sort", sort, &["created", "updated", "comments"])?;
+            }
+            if let Some(direction) = &filters.direction {
+                validate_enum_param("direction", direction, &["asc", "desc"])?;
+            }
+
+            debug!("Listing issues for organization {}", org);
+
+            match client.list_org_issues(
+                &org,
+                &filters,
+                Some(pagination.per_page as u8),
+                Some(pagination.page),
+            ).await {
+                Ok(issues) => {
+                    debug!("Successfully retrieved {} issues", issues.items.len());
+                    Ok(serde_json::to_value(issues)?)
+                }
+                Err(e) => {
+                    error!("Failed to list organization issues: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("list_org_issues".to_string(), tool, handler);
+}
+
 fn add_create_issue_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
     let tool = Tool {
         name: "create_issue".to_string(),
@@ -257,4 +469,338 @@ fn add_create_issue_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>
     });
 
     toolset.add_tool("create_issue".to_string(), tool, handler);
-}
\ No newline at end of file
+}
+
+fn add_list_sub_issues_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "list_sub_issues".to_string(),
+        description: "List the sub-issues of an issue".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "issue_number": {
+                    "type": "number",
+                    "description": "Parent issue number"
+                },
+                "perPage": {
+                    "type": "number",
+                    "description": "Maximum number of sub-issues to return (min 1, max 100)"
+                }
+            },
+            "required": ["owner", "repo", "issue_number"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let issue_number: u64 = required_param::<f64>(&args, "issue_number")? as u64;
+            let pagination = extract_pagination_params(&args)?;
+
+            debug!("Listing sub-issues of #{} for {}/{}", issue_number, owner, repo);
+
+            match client.list_sub_issues(&owner, &repo, issue_number, pagination.per_page).await {
+                Ok(sub_issues) => {
+                    debug!("Successfully retrieved {} sub-issues", sub_issues.sub_issues.len());
+                    Ok(serde_json::to_value(sub_issues)?)
+                }
+                Err(e) => {
+                    error!("Failed to list sub-issues: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("list_sub_issues".to_string(), tool, handler);
+}
+
+fn add_add_sub_issue_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "add_sub_issue".to_string(),
+        description: "Add an existing issue as a sub-issue of another issue".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "parent_number": {
+                    "type": "number",
+                    "description": "Parent issue number"
+                },
+                "child_number": {
+                    "type": "number",
+                    "description": "Issue number to add as a sub-issue"
+                }
+            },
+            "required": ["owner", "repo", "parent_number", "child_number"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let parent_number: u64 = required_param::<f64>(&args, "parent_number")? as u64;
+            let child_number: u64 = required_param::<f64>(&args, "child_number")? as u64;
+
+            debug!("Adding #{} as sub-issue of #{} for {}/{}", child_number, parent_number, owner, repo);
+
+            match client.add_sub_issue(&owner, &repo, parent_number, child_number).await {
+                Ok(link) => {
+                    debug!("Successfully added sub-issue");
+                    Ok(serde_json::to_value(link)?)
+                }
+                Err(e) => {
+                    error!("Failed to add sub-issue: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("add_sub_issue".to_string(), tool, handler);
+}
+
+fn add_add_reaction_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "add_reaction".to_string(),
+        description: "Add a reaction to an issue, pull request, or issue comment".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "issue_number": {
+                    "type": "number",
+                    "description": "Issue or pull request number to react to"
+                },
+                "comment_id": {
+                    "type": "number",
+                    "description": "Issue comment ID to react to instead of the issue itself"
+                },
+                "content": {
+                    "type": "string",
+                    "enum": REACTION_CONTENTS,
+                    "description": "Reaction type"
+                }
+            },
+            "required": ["owner", "repo", "content"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let issue_number: Option<u64> = optional_param::<f64>(&args, "issue_number")?.map(|n| n as u64);
+            let comment_id: Option<u64> = optional_param::<f64>(&args, "comment_id")?.map(|n| n as u64);
+            let content: String = required_param(&args, "content")?;
+
+            validate_reaction_content(&content)?;
+            if issue_number.is_some() == comment_id.is_some() {
+                return Err(InvalidParamsError {
+                    field: "issue_number".to_string(),
+                    message: "exactly one of 'issue_number' or 'comment_id' is required".to_string(),
+                }
+                .into());
+            }
+
+            debug!("Adding {} reaction in {}/{}", content, owner, repo);
+
+            match client.add_reaction(&owner, &repo, issue_number, comment_id, &content).await {
+                Ok(reaction) => {
+                    debug!("Successfully added reaction {}", reaction.id);
+                    Ok(serde_json::to_value(reaction)?)
+                }
+                Err(e) => {
+                    error!("Failed to add reaction: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("add_reaction".to_string(), tool, handler);
+}
+
+fn add_create_issue_comment_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "create_issue_comment".to_string(),
+        description: "Add a comment to an issue or pull request".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "issue_number": {
+                    "type": "number",
+                    "description": "Issue or pull request number"
+                },
+                "body": {
+                    "type": "string",
+                    "description": "Comment text (Markdown)"
+                }
+            },
+            "required": ["owner", "repo", "issue_number", "body"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let issue_number: u64 = required_param::<f64>(&args, "issue_number")? as u64;
+            let body: String = required_param(&args, "body")?;
+
+            debug!("Commenting on issue #{} for {}/{}", issue_number, owner, repo);
+
+            match client.create_issue_comment(&owner, &repo, issue_number, &body).await {
+                Ok(comment) => {
+                    debug!("Successfully created issue comment {}", comment.id);
+                    Ok(serde_json::to_value(comment)?)
+                }
+                Err(e) => {
+                    error!("Failed to create issue comment: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("create_issue_comment".to_string(), tool, handler);
+}
+
+fn add_update_issue_comment_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "update_issue_comment".to_string(),
+        description: "Replace the body of an issue or pull request comment".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "comment_id": {
+                    "type": "number",
+                    "description": "ID of the comment to edit"
+                },
+                "body": {
+                    "type": "string",
+                    "description": "New comment text (Markdown)"
+                }
+            },
+            "required": ["owner", "repo", "comment_id", "body"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let comment_id: u64 = required_param::<f64>(&args, "comment_id")? as u64;
+            let body: String = required_param(&args, "body")?;
+
+            debug!("Updating issue comment {} for {}/{}", comment_id, owner, repo);
+
+            match client.update_issue_comment(&owner, &repo, comment_id, &body).await {
+                Ok(comment) => Ok(serde_json::to_value(comment)?),
+                Err(e) => {
+                    error!("Failed to update issue comment: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("update_issue_comment".to_string(), tool, handler);
+}
+
+fn add_delete_issue_comment_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "delete_issue_comment".to_string(),
+        description: "Delete an issue or pull request comment".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "comment_id": {
+                    "type": "number",
+                    "description": "ID of the comment to delete"
+                }
+            },
+            "required": ["owner", "repo", "comment_id"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let comment_id: u64 = required_param::<f64>(&args, "comment_id")? as u64;
+
+            debug!("Deleting issue comment {} for {}/{}", comment_id, owner, repo);
+
+            match client.delete_issue_comment(&owner, &repo, comment_id).await {
+                Ok(()) => Ok(serde_json::json!({ "deleted": true, "id": comment_id })),
+                Err(e) => {
+                    error!("Failed to delete issue comment: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("delete_issue_comment".to_string(), tool, handler);
+}
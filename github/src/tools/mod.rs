@@ -4,6 +4,9 @@ pub mod repos;
 pub mod issues;
 pub mod pull_requests;
 pub mod users;
+pub mod actions;
+pub mod projects;
+pub mod security;
 pub mod context;
 pub mod dynamic;
 
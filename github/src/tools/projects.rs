@@ -0,0 +1,116 @@
+use anyhow::Result;
+use futures::future::BoxFuture;
+use crate::mcp_core::tools::Tool;
+use serde_json::{Map, Value};
+use std::sync::Arc;
+use tracing::{debug, error};
+
+use crate::github::GitHubClient;
+use crate::server::{required_param, optional_param, extract_pagination_params};
+use super::toolsets::Toolset;
+use super::registry::ToolHandlerFunc;
+
+pub async fn create_projects_toolset(github_client: Arc<GitHubClient>) -> Result<Toolset> {
+    let mut toolset = Toolset::new("projects", "Organization project (Projects v2) tools");
+
+    // List project items tool
+    add_list_project_items_tool(&mut toolset, github_client.clone());
+
+    // Get project fields tool
+    add_get_project_fields_tool(&mut toolset, github_client.clone());
+
+    Ok(toolset)
+}
+
+fn project_input_schema(per_page_description: &str) -> Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "org": {
+                "type": "string",
+                "description": "Organization login"
+            },
+            "project_number": {
+                "type": "number",
+                "description": "Project number, as shown in the project URL"
+            },
+            "perPage": {
+                "type": "number",
+                "description": per_page_description
+            },
+            "after": {
+                "type": "string",
+                "description": "Cursor from a previous page's page_info.end_cursor"
+            }
+        },
+        "required": ["org", "project_number"]
+    })
+}
+
+fn add_list_project_items_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "list_project_items".to_string(),
+        description: "List the items of an organization project with their field values".to_string(),
+        input_schema: project_input_schema("Maximum number of items to return (min 1, max 100)"),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let org: String = required_param(&args, "org")?;
+            let project_number: u64 = required_param::<f64>(&args, "project_number")? as u64;
+            let after: Option<String> = optional_param(&args, "after")?;
+            let pagination = extract_pagination_params(&args)?;
+
+            debug!("Listing items of project #{} for org {}", project_number, org);
+
+            match client.list_project_items(&org, project_number, pagination.per_page, after.as_deref()).await {
+                Ok(items) => {
+                    debug!("Successfully retrieved {} project items", items.items.len());
+                    Ok(serde_json::to_value(items)?)
+                }
+                Err(e) => {
+                    error!("Failed to list project items: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("list_project_items".to_string(), tool, handler);
+}
+
+fn add_get_project_fields_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "get_project_fields".to_string(),
+        description: "Get the fields of an organization project, including single-select options and iterations".to_string(),
+        input_schema: project_input_schema("Maximum number of fields to return (min 1, max 100)"),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let org: String = required_param(&args, "org")?;
+            let project_number: u64 = required_param::<f64>(&args, "project_number")? as u64;
+            let after: Option<String> = optional_param(&args, "after")?;
+            let pagination = extract_pagination_params(&args)?;
+
+            debug!("Getting fields of project #{} for org {}", project_number, org);
+
+            match client.get_project_fields(&org, project_number, pagination.per_page, after.as_deref()).await {
+                Ok(fields) => {
+                    debug!("Successfully retrieved {} project fields", fields.fields.len());
+                    Ok(serde_json::to_value(fields)?)
+                }
+                Err(e) => {
+                    error!("Failed to get project fields: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("get_project_fields".to_string(), tool, handler);
+}
@@ -5,7 +5,7 @@ use serde_json::{Map, Value};
 use std::sync::Arc;
 use tracing::{debug, error};
 
-use crate::github::GitHubClient;
+use crate::github::{GitHubClient, MergeOptions};
 use crate::server::{required_param, optional_param, extract_pagination_params};
 use super::toolsets::Toolset;
 use super::registry::ToolHandlerFunc;
@@ -16,12 +16,18 @@ pub async fn create_pull_requests_toolset(github_client: Arc<GitHubClient>, read
     // Get pull request tool
     add_get_pull_request_tool(&mut toolset, github_client.clone());
 
+    // Check mergeable tool
+    add_check_mergeable_tool(&mut toolset, github_client.clone());
+
     // List pull requests tool
     add_list_pull_requests_tool(&mut toolset, github_client.clone());
 
     if !read_only {
         // Create pull request tool
         add_create_pull_request_tool(&mut toolset, github_client.clone());
+
+        // Merge pull request tool
+        add_merge_pull_request_tool(&mut toolset, github_client.clone());
     }
 
     Ok(toolset)
@@ -77,6 +83,56 @@ fn add_get_pull_request_tool(toolset: &mut Toolset, github_client: Arc<GitHubCli
     toolset.add_tool("get_pull_request".to_string(), tool, handler);
 }
 
+fn add_check_mergeable_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "check_mergeable".to_string(),
+        description: "Get a pull request's mergeability, waiting briefly for GitHub to compute it if needed".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "pull_number": {
+                    "type": "number",
+                    "description": "Pull request number"
+                }
+            },
+            "required": ["owner", "repo", "pull_number"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let pull_number: u64 = required_param::<f64>(&args, "pull_number")? as u64;
+
+            debug!("Checking mergeability of pull request #{} for {}/{}", pull_number, owner, repo);
+
+            match client.check_mergeable(&owner, &repo, pull_number).await {
+                Ok(status) => {
+                    debug!("Mergeability resolved to {:?} after {} attempts", status.mergeable, status.attempts);
+                    Ok(serde_json::to_value(status)?)
+                }
+                Err(e) => {
+                    error!("Failed to check mergeability: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("check_mergeable".to_string(), tool, handler);
+}
+
 fn add_list_pull_requests_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
     let tool = Tool {
         name: "list_pull_requests".to_string(),
@@ -155,7 +211,7 @@ fn add_list_pull_requests_tool(toolset: &mut Toolset, github_client: Arc<GitHubC
                 Some(pagination.page),
             ).await {
                 Ok(prs) => {
-                    debug!("Successfully retrieved {} pull requests", prs.len());
+                    debug!("Successfully retrieved {} pull requests", prs.items.len());
                     Ok(serde_json::to_value(prs)?)
                 }
                 Err(e) => {
@@ -245,4 +301,72 @@ fn add_create_pull_request_tool(toolset: &mut Toolset, github_client: Arc<GitHub
     });
 
     toolset.add_tool("create_pull_request".to_string(), tool, handler);
-}
\ No newline at end of file
+}
+
+fn add_merge_pull_request_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "merge_pull_request".to_string(),
+        description: "Merge a pull request".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "pull_number": {
+                    "type": "number",
+                    "description": "Pull request number"
+                },
+                "commit_title": {
+                    "type": "string",
+                    "description": "Title for the merge commit"
+                },
+                "commit_message": {
+                    "type": "string",
+                    "description": "Extra detail to append to the merge commit message"
+                },
+                "merge_method": {
+                    "type": "string",
+                    "description": "Merge method to use",
+                    "enum": ["merge", "squash", "rebase"]
+                }
+            },
+            "required": ["owner", "repo", "pull_number"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let pull_number: u64 = required_param::<f64>(&args, "pull_number")? as u64;
+            let options = MergeOptions {
+                commit_title: optional_param(&args, "commit_title")?,
+                commit_message: optional_param(&args, "commit_message")?,
+                merge_method: optional_param(&args, "merge_method")?,
+            };
+
+            debug!("Merging pull request #{} for {}/{}", pull_number, owner, repo);
+
+            match client.merge_pull_request(&owner, &repo, pull_number, &options).await {
+                Ok(result) => {
+                    debug!("Merged pull request #{} as {}", pull_number, result.sha);
+                    Ok(serde_json::to_value(result)?)
+                }
+                Err(e) => {
+                    error!("Failed to merge pull request: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("merge_pull_request".to_string(), tool, handler);
+}
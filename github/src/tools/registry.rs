@@ -5,20 +5,78 @@ use crate::mcp_core::tools::{Tool, ToolHandler};
 use serde_json::{Map, Value};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tracing::{debug, error, info};
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
 
 use crate::github::GitHubClient;
 use super::toolsets::*;
 
 pub type ToolHandlerFunc = Box<dyn Fn(Map<String, Value>) -> BoxFuture<'static, Result<Value>> + Send + Sync>;
 
+/// Tools that change state on GitHub. These are only registered outside
+/// read-only mode and are short-circuited when the server runs with `--dry-run`.
+pub const WRITE_TOOLS: &[&str] = &[
+    "create_issue",
+    "add_sub_issue",
+    "add_reaction",
+    "create_issue_comment",
+    "update_issue_comment",
+    "delete_issue_comment",
+    "create_pull_request",
+    "merge_pull_request",
+    "create_or_update_file",
+    "delete_file",
+    "create_commit_with_files",
+    "create_tag",
+    "create_branch",
+    "upload_release_asset",
+    "set_repo_subscription",
+    "delete_repo_subscription",
+    "replace_repo_topics",
+    "dispatch_workflow",
+];
+
+/// Raised when a tool call runs past the registry's timeout. The handler's
+/// future is dropped, which cancels any request it still had in flight.
+#[derive(Debug)]
+pub struct ToolTimeoutError {
+    pub tool: String,
+    pub timeout: Duration,
+}
+
+impl std::fmt::Display for ToolTimeoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Tool {} timed out after {:?}", self.tool, self.timeout)
+    }
+}
+
+impl std::error::Error for ToolTimeoutError {}
+
+/// Raised when tool arguments don't match the tool's declared input schema.
+/// The server maps this to a JSON-RPC `-32602` instead of an execution failure.
+#[derive(Debug)]
+pub struct InvalidParamsError {
+    pub field: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for InvalidParamsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid params: {}", self.message)
+    }
+}
+
+impl std::error::Error for InvalidParamsError {}
+
 pub struct ToolRegistry {
     tools: HashMap<String, Tool>,
     handlers: HashMap<String, ToolHandlerFunc>,
     toolsets: ToolsetGroup,
     enabled_toolsets: Vec<String>,
     read_only: bool,
+    dry_run: bool,
     dynamic_toolsets: bool,
+    tool_timeout: Option<Duration>,
     github_client: Arc<GitHubClient>,
 }
 
@@ -26,7 +84,9 @@ impl ToolRegistry {
     pub fn new(
         enabled_toolsets: Vec<String>,
         read_only: bool,
+        dry_run: bool,
         dynamic_toolsets: bool,
+        tool_timeout: Option<Duration>,
         github_client: Arc<GitHubClient>,
     ) -> Self {
         Self {
@@ -35,7 +95,9 @@ impl ToolRegistry {
             toolsets: ToolsetGroup::new(),
             enabled_toolsets,
             read_only,
+            dry_run,
             dynamic_toolsets,
+            tool_timeout,
             github_client,
         }
     }
@@ -93,6 +155,31 @@ impl ToolRegistry {
                     ).await?;
                     self.register_toolset("users", users_toolset);
                 }
+                "actions" => {
+                    let actions_toolset = super::actions::create_actions_toolset(
+                        self.github_client.clone(),
+                        self.read_only,
+                    ).await?;
+                    self.register_toolset("actions", actions_toolset);
+                }
+                "projects" => {
+                    let projects_toolset = super::projects::create_projects_toolset(
+                        self.github_client.clone(),
+                    ).await?;
+                    self.register_toolset("projects", projects_toolset);
+                }
+                "code_security" => {
+                    let code_security_toolset = super::security::create_code_security_toolset(
+                        self.github_client.clone(),
+                    ).await?;
+                    self.register_toolset("code_security", code_security_toolset);
+                }
+                "secret_protection" => {
+                    let secret_protection_toolset = super::security::create_secret_protection_toolset(
+                        self.github_client.clone(),
+                    ).await?;
+                    self.register_toolset("secret_protection", secret_protection_toolset);
+                }
                 _ => {
                     debug!("Unknown toolset: {}", toolset_name);
                 }
@@ -131,6 +218,30 @@ impl ToolRegistry {
         ).await?;
         self.register_toolset("users", users_toolset);
 
+        // Actions tools
+        let actions_toolset = super::actions::create_actions_toolset(
+            self.github_client.clone(),
+            self.read_only,
+        ).await?;
+        self.register_toolset("actions", actions_toolset);
+
+        // Projects tools
+        let projects_toolset = super::projects::create_projects_toolset(
+            self.github_client.clone(),
+        ).await?;
+        self.register_toolset("projects", projects_toolset);
+
+        // Security alert tools
+        let code_security_toolset = super::security::create_code_security_toolset(
+            self.github_client.clone(),
+        ).await?;
+        self.register_toolset("code_security", code_security_toolset);
+
+        let secret_protection_toolset = super::security::create_secret_protection_toolset(
+            self.github_client.clone(),
+        ).await?;
+        self.register_toolset("secret_protection", secret_protection_toolset);
+
         Ok(())
     }
 
@@ -142,6 +253,12 @@ impl ToolRegistry {
                 debug!("Tool {} already registered, skipping", tool_name);
                 continue;
             }
+            // Toolsets already leave write tools out in read-only mode; this
+            // keeps one that slipped through from ever being exposed
+            if self.read_only && is_write_tool(&tool_name) {
+                warn!("Not registering write tool {} in read-only mode", tool_name);
+                continue;
+            }
 
             debug!("Registering tool: {}", tool_name);
             self.tools.insert(tool_name.clone(), tool);
@@ -161,7 +278,28 @@ impl ToolRegistry {
         let handler = self.handlers.get(name)
             .ok_or_else(|| anyhow!("Tool not found: {}", name))?;
 
-        match handler(arguments).await {
+        if let Some(tool) = self.tools.get(name) {
+            validate_arguments(&tool.input_schema, &arguments)?;
+        }
+
+        if self.dry_run && is_write_tool(name) {
+            warn!("Dry run: skipping {} with args: {:?}", name, arguments);
+            return Ok(dry_run_result(name, arguments));
+        }
+
+        let result = match self.tool_timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, handler(arguments)).await {
+                Ok(result) => result,
+                Err(_) => {
+                    let err = ToolTimeoutError { tool: name.to_string(), timeout };
+                    error!("{}", err);
+                    return Err(err.into());
+                }
+            },
+            None => handler(arguments).await,
+        };
+
+        match result {
             Ok(result) => {
                 debug!("Tool {} executed successfully", name);
                 Ok(result)
@@ -216,6 +354,31 @@ impl ToolRegistry {
                 ).await?;
                 self.register_toolset("users", users_toolset);
             }
+            "actions" => {
+                let actions_toolset = super::actions::create_actions_toolset(
+                    self.github_client.clone(),
+                    self.read_only,
+                ).await?;
+                self.register_toolset("actions", actions_toolset);
+            }
+            "projects" => {
+                let projects_toolset = super::projects::create_projects_toolset(
+                    self.github_client.clone(),
+                ).await?;
+                self.register_toolset("projects", projects_toolset);
+            }
+            "code_security" => {
+                let code_security_toolset = super::security::create_code_security_toolset(
+                    self.github_client.clone(),
+                ).await?;
+                self.register_toolset("code_security", code_security_toolset);
+            }
+            "secret_protection" => {
+                let secret_protection_toolset = super::security::create_secret_protection_toolset(
+                    self.github_client.clone(),
+                ).await?;
+                self.register_toolset("secret_protection", secret_protection_toolset);
+            }
             _ => {
                 return Err(anyhow!("Unknown toolset: {}", toolset_name));
             }
@@ -223,4 +386,292 @@ impl ToolRegistry {
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Whether `name` changes state on GitHub (hidden when read-only, not sent in dry-run).
+pub fn is_write_tool(name: &str) -> bool {
+    WRITE_TOOLS.contains(&name)
+}
+
+/// Synthetic success returned for write tools in dry-run mode. It echoes the
+/// validated arguments so callers can see exactly what would have been sent.
+pub fn dry_run_result(name: &str, arguments: Map<String, Value>) -> Value {
+    serde_json::json!({
+        "dry_run": true,
+        "tool": name,
+        "arguments": arguments,
+        "message": format!("Dry run: {} was not sent to GitHub", name)
+    })
+}
+
+/// Checks required properties and primitive types against a tool's input schema.
+/// This is intentionally not a full JSON Schema implementation.
+pub fn validate_arguments(schema: &Value, arguments: &Map<String, Value>) -> std::result::Result<(), InvalidParamsError> {
+    if let Some(required) = schema.get("required").and_then(|v| v.as_array()) {
+        for field in required.iter().filter_map(|v| v.as_str()) {
+            match arguments.get(field) {
+                None | Some(Value::Null) => {
+                    return Err(InvalidParamsError {
+                        field: field.to_string(),
+                        message: format!("missing required parameter '{}'", field),
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    let properties = match schema.get("properties").and_then(|v| v.as_object()) {
+        Some(properties) => properties,
+        None => return Ok(()),
+    };
+
+    for (field, value) in arguments {
+        if value.is_null() {
+            continue;
+        }
+
+        let expected = match properties.get(field).and_then(|p| p.get("type")).and_then(|t| t.as_str()) {
+            Some(expected) => expected,
+            None => continue,
+        };
+
+        let matches = match expected {
+            "string" => value.is_string(),
+            "number" => value.is_number(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "boolean" => value.is_boolean(),
+            "array" => value.is_array(),
+            "object" => value.is_object(),
+            _ => true,
+        };
+
+        if !matches {
+            return Err(InvalidParamsError {
+                field: field.clone(),
+                message: format!("parameter '{}' must be of type {}", field, expected),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Rejects date parameters that GitHub would silently ignore or misinterpret.
+pub fn validate_rfc3339_param(field: &str, value: &str) -> std::result::Result<(), InvalidParamsError> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|_| ())
+        .map_err(|_| InvalidParamsError {
+            field: field.to_string(),
+            message: format!("parameter '{}' must be an RFC 3339 timestamp (e.g. 2024-01-01T00:00:00Z)", field),
+        })
+}
+
+/// Issue milestone filters are a milestone number, `none`, or `*`.
+pub fn validate_milestone_param(value: &str) -> std::result::Result<(), InvalidParamsError> {
+    if value == "none" || value == "*" || (!value.is_empty() && value.chars().all(|c| c.is_ascii_digit())) {
+        Ok(())
+    } else {
+        Err(InvalidParamsError {
+            field: "milestone".to_string(),
+            message: "parameter 'milestone' must be a milestone number, 'none', or '*'".to_string(),
+        })
+    }
+}
+
+/// Reaction types accepted by the GitHub reactions API.
+pub const REACTION_CONTENTS: [&str; 8] = ["+1", "-1", "laugh", "confused", "heart", "hooray", "rocket", "eyes"];
+
+pub fn validate_reaction_content(content: &str) -> std::result::Result<(), InvalidParamsError> {
+    if REACTION_CONTENTS.contains(&content) {
+        Ok(())
+    } else {
+        Err(InvalidParamsError {
+            field: "content".to_string(),
+            message: format!("parameter 'content' must be one of: {}", REACTION_CONTENTS.join(", ")),
+        })
+    }
+}
+
+/// Rejects values outside a fixed set GitHub accepts for a parameter.
+pub fn validate_enum_param(field: &str, value: &str, allowed: &[&str]) -> std::result::Result<(), InvalidParamsError> {
+    if allowed.contains(&value) {
+        Ok(())
+    } else {
+        Err(InvalidParamsError {
+            field: field.to_string(),
+            message: format!("parameter '{}' must be one of: {}", field, allowed.join(", ")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::test_client;
+
+    fn issue_schema() -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {"type": "string"},
+                "repo": {"type": "string"},
+                "issue_number": {"type": "number"}
+            },
+            "required": ["owner", "repo", "issue_number"]
+        })
+    }
+
+    #[tokio::test]
+    async fn test_call_tool_rejects_missing_required_arg() {
+        let client = Arc::new(test_client("http://127.0.0.1").await);
+
+        let mut registry = ToolRegistry::new(vec![], false, false, false, None, client);
+        let mut toolset = Toolset::new("test", "Test tools");
+        let handler: ToolHandlerFunc = Box::new(|_args| {
+            Box::pin(async { Ok(Value::Null) })
+        });
+        toolset.add_tool("get_issue".to_string(), Tool {
+            name: "get_issue".to_string(),
+            description: "Get an issue".to_string(),
+            input_schema: issue_schema(),
+        }, handler);
+        registry.register_toolset("test", toolset);
+
+        let mut args = Map::new();
+        args.insert("owner".to_string(), Value::String("octo".to_string()));
+        args.insert("repo".to_string(), Value::String("repo".to_string()));
+
+        let err = registry.call_tool("get_issue", args).await.unwrap_err();
+        let invalid = err.downcast_ref::<InvalidParamsError>().expect("expected InvalidParamsError");
+        assert_eq!(invalid.field, "issue_number");
+        assert_eq!(err.to_string(), "Invalid params: missing required parameter 'issue_number'");
+    }
+
+    #[tokio::test]
+    async fn test_slow_tool_is_aborted_at_timeout() {
+        let client = Arc::new(test_client("http://127.0.0.1").await);
+
+        let timeout = Duration::from_millis(50);
+        let mut registry = ToolRegistry::new(vec![], false, false, false, Some(timeout), client);
+        let (finished_tx, finished_rx) = tokio::sync::oneshot::channel::<()>();
+        let finished_tx = Arc::new(std::sync::Mutex::new(Some(finished_tx)));
+        let mut toolset = Toolset::new("test", "Test tools");
+        let handler: ToolHandlerFunc = Box::new(move |_args| {
+            let finished_tx = finished_tx.lock().unwrap().take();
+            Box::pin(async move {
+                tokio::time::sleep(Duration::from_secs(30)).await;
+                if let Some(tx) = finished_tx {
+                    let _ = tx.send(());
+                }
+                Ok(Value::Null)
+            })
+        });
+        toolset.add_tool("slow_tool".to_string(), Tool {
+            name: "slow_tool".to_string(),
+            description: "Never finishes in time".to_string(),
+            input_schema: serde_json::json!({"type": "object", "properties": {}}),
+        }, handler);
+        registry.register_toolset("test", toolset);
+
+        let started = std::time::Instant::now();
+        let err = registry.call_tool("slow_tool", Map::new()).await.unwrap_err();
+        assert!(started.elapsed() < Duration::from_secs(5));
+        let timed_out = err.downcast_ref::<ToolTimeoutError>().expect("expected ToolTimeoutError");
+        assert_eq!(timed_out.tool, "slow_tool");
+        assert_eq!(err.to_string(), "Tool slow_tool timed out after 50ms");
+
+        // The handler's future was dropped rather than left running
+        assert!(finished_rx.await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_create_issue_skips_github() {
+        // Nothing listens on this host, so any real request would fail.
+        let client = Arc::new(test_client("http://127.0.0.1").await);
+
+        let mut registry = ToolRegistry::new(vec![], false, true, false, None, client.clone());
+        let toolset = super::super::issues::create_issues_toolset(client, false).await.unwrap();
+        registry.register_toolset("issues", toolset);
+
+        let mut args = Map::new();
+        args.insert("owner".to_string(), Value::String("octo".to_string()));
+        args.insert("repo".to_string(), Value::String("repo".to_string()));
+        args.insert("title".to_string(), Value::String("Dry run".to_string()));
+
+        let result = registry.call_tool("create_issue", args).await.unwrap();
+        assert_eq!(result["dry_run"], true);
+        assert_eq!(result["tool"], "create_issue");
+        assert_eq!(result["arguments"]["title"], "Dry run");
+    }
+
+    #[tokio::test]
+    async fn test_read_only_registry_omits_write_tools() {
+        let client = Arc::new(test_client("http://127.0.0.1").await);
+
+        let mut read_only = ToolRegistry::new(vec!["all".to_string()], true, false, false, None, client.clone());
+        read_only.initialize().await.unwrap();
+        let names: Vec<String> = read_only.list_tools().await.unwrap().into_iter().map(|t| t.name).collect();
+        assert!(names.contains(&"get_file_contents".to_string()));
+        assert!(!names.contains(&"delete_file".to_string()));
+        assert!(!names.contains(&"create_or_update_file".to_string()));
+        for name in &names {
+            assert!(!is_write_tool(name), "write tool {} registered in read-only mode", name);
+        }
+
+        let mut writable = ToolRegistry::new(vec!["all".to_string()], false, false, false, None, client);
+        writable.initialize().await.unwrap();
+        let names: Vec<String> = writable.list_tools().await.unwrap().into_iter().map(|t| t.name).collect();
+        assert!(names.contains(&"delete_file".to_string()));
+        assert!(names.contains(&"create_or_update_file".to_string()));
+    }
+
+    #[test]
+    fn test_validate_arguments_rejects_wrong_type() {
+        let mut args = Map::new();
+        args.insert("owner".to_string(), Value::String("octo".to_string()));
+        args.insert("repo".to_string(), Value::String("repo".to_string()));
+        args.insert("issue_number".to_string(), Value::String("42".to_string()));
+
+        let err = validate_arguments(&issue_schema(), &args).unwrap_err();
+        assert_eq!(err.field, "issue_number");
+        assert!(err.message.contains("must be of type number"));
+    }
+
+    #[test]
+    fn test_validate_rfc3339_param() {
+        assert!(validate_rfc3339_param("since", "2024-01-01T00:00:00Z").is_ok());
+        assert!(validate_rfc3339_param("since", "2024-01-01T00:00:00+02:00").is_ok());
+
+        let err = validate_rfc3339_param("until", "2024-01-01").unwrap_err();
+        assert_eq!(err.field, "until");
+    }
+
+    #[test]
+    fn test_validate_milestone_param() {
+        for value in ["3", "none", "*"] {
+            assert!(validate_milestone_param(value).is_ok());
+        }
+        assert_eq!(validate_milestone_param("v1.0").unwrap_err().field, "milestone");
+    }
+
+    #[test]
+    fn test_validate_reaction_content() {
+        for content in REACTION_CONTENTS {
+            assert!(validate_reaction_content(content).is_ok());
+        }
+
+        let err = validate_reaction_content("thumbsup").unwrap_err();
+        assert_eq!(err.field, "content");
+        assert!(validate_reaction_content("").is_err());
+    }
+
+    #[test]
+    fn test_validate_enum_param() {
+        assert!(validate_enum_param("direction", "asc", &["asc", "desc"]).is_ok());
+
+        let err = validate_enum_param("direction", "up", &["asc", "desc"]).unwrap_err();
+        assert_eq!(err.field, "direction");
+        assert_eq!(err.message, "parameter 'direction' must be one of: asc, desc");
+    }
+}
@@ -5,10 +5,10 @@ use serde_json::{Map, Value};
 use std::sync::Arc;
 use tracing::{debug, error};
 
-use crate::github::GitHubClient;
+use crate::github::{CommitFilters, FileChange, GitHubClient, NewTag, Tagger};
 use crate::server::{required_param, optional_param, extract_pagination_params};
 use super::toolsets::Toolset;
-use super::registry::ToolHandlerFunc;
+use super::registry::{validate_rfc3339_param, ToolHandlerFunc};
 
 pub async fn create_repos_toolset(github_client: Arc<GitHubClient>, read_only: bool) -> Result<Toolset> {
     let mut toolset = Toolset::new("repos", "Repository management tools");
@@ -19,12 +19,69 @@ pub async fn create_repos_toolset(github_client: Arc<GitHubClient>, read_only: b
     // Get file contents tool
     add_get_file_contents_tool(&mut toolset, github_client.clone());
 
+    // Get file blame tool
+    add_get_file_blame_tool(&mut toolset, github_client.clone());
+
     // Get repository tool
     add_get_repository_tool(&mut toolset, github_client.clone());
 
+    // Get repository default branch tool
+    add_get_repo_default_branch_tool(&mut toolset, github_client.clone());
+
+    // Get repository overview tool
+    add_get_repository_overview_tool(&mut toolset, github_client.clone());
+
+    // Get repository languages tool
+    add_get_repo_languages_tool(&mut toolset, github_client.clone());
+
+    // Get repository contributor statistics tool
+    add_get_repo_contributors_stats_tool(&mut toolset, github_client.clone());
+
+    // Get repository topics tool
+    add_get_repo_topics_tool(&mut toolset, github_client.clone());
+
+    // List commits tool
+    add_list_commits_tool(&mut toolset, github_client.clone());
+
+    // Get commit tool
+    add_get_commit_tool(&mut toolset, github_client.clone());
+
+    // List deployments tool
+    add_list_deployments_tool(&mut toolset, github_client.clone());
+
+    // List environments tool
+    add_list_environments_tool(&mut toolset, github_client.clone());
+
+    // Get repository subscription tool
+    add_get_repo_subscription_tool(&mut toolset, github_client.clone());
+
     if !read_only {
         // Create or update file tool
         add_create_or_update_file_tool(&mut toolset, github_client.clone());
+
+        // Delete file tool
+        add_delete_file_tool(&mut toolset, github_client.clone());
+
+        // Multi-file commit tool
+        add_create_commit_with_files_tool(&mut toolset, github_client.clone());
+
+        // Replace repository topics tool
+        add_replace_repo_topics_tool(&mut toolset, github_client.clone());
+
+        // Create annotated tag tool
+        add_create_tag_tool(&mut toolset, github_client.clone());
+
+        // Create branch tool
+        add_create_branch_tool(&mut toolset, github_client.clone());
+
+        // Upload release asset tool
+        add_upload_release_asset_tool(&mut toolset, github_client.clone());
+
+        // Set repository subscription tool
+        add_set_repo_subscription_tool(&mut toolset, github_client.clone());
+
+        // Delete repository subscription tool
+        add_delete_repo_subscription_tool(&mut toolset, github_client.clone());
     }
 
     Ok(toolset)
@@ -273,4 +330,1112 @@ fn add_create_or_update_file_tool(toolset: &mut Toolset, github_client: Arc<GitH
     });
 
     toolset.add_tool("create_or_update_file".to_string(), tool, handler);
-}
\ No newline at end of file
+}
+
+fn add_delete_file_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "delete_file".to_string(),
+        description: "Delete a file from a repository in a new commit".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Path of the file to delete"
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Commit message"
+                },
+                "sha": {
+                    "type": "string",
+                    "description": "Blob SHA of the file being deleted"
+                },
+                "branch": {
+                    "type": "string",
+                    "description": "Branch to delete the file from; defaults to the default branch"
+                }
+            },
+            "required": ["owner", "repo", "path", "message", "sha"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let path: String = required_param(&args, "path")?;
+            let message: String = required_param(&args, "message")?;
+            let sha: String = required_param(&args, "sha")?;
+            let branch: Option<String> = optional_param(&args, "branch")?;
+
+            debug!("Deleting file {}/{} path: {}", owner, repo, path);
+
+            match client.delete_file(&owner, &repo, &path, &message, &sha, branch.as_deref()).await {
+                Ok(deletion) => {
+                    debug!("Deleted {} in commit {}", deletion.path, deletion.commit.sha);
+                    Ok(serde_json::to_value(deletion)?)
+                }
+                Err(e) => {
+                    error!("Failed to delete file: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("delete_file".to_string(), tool, handler);
+}
+
+fn add_create_commit_with_files_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "create_commit_with_files".to_string(),
+        description: "Commit several files to a branch in a single commit".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "branch": {
+                    "type": "string",
+                    "description": "Branch to commit to; it must already exist"
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Commit message"
+                },
+                "files": {
+                    "type": "array",
+                    "description": "Files to create or overwrite",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "path": {
+                                "type": "string",
+                                "description": "File path"
+                            },
+                            "content": {
+                                "type": "string",
+                                "description": "File content"
+                            }
+                        },
+                        "required": ["path", "content"]
+                    },
+                    "minItems": 1
+                }
+            },
+            "required": ["owner", "repo", "branch", "message", "files"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let branch: String = required_param(&args, "branch")?;
+            let message: String = required_param(&args, "message")?;
+            let files: Vec<FileChange> = required_param(&args, "files")?;
+
+            debug!("Committing {} files to {}/{} branch: {}", files.len(), owner, repo, branch);
+
+            match client.create_commit_with_files(&owner, &repo, &branch, &message, &files).await {
+                Ok(commit) => {
+                    debug!("Successfully created commit {}", commit.sha);
+                    Ok(serde_json::to_value(commit)?)
+                }
+                Err(e) => {
+                    error!("Failed to create multi-file commit: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("create_commit_with_files".to_string(), tool, handler);
+}
+
+fn add_create_tag_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "create_tag".to_string(),
+        description: "Create an annotated tag and its refs/tags ref".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "tag": {
+                    "type": "string",
+                    "description": "Tag name, e.g. v1.2.0"
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Tag message"
+                },
+                "object": {
+                    "type": "string",
+                    "description": "SHA of the object to tag"
+                },
+                "type": {
+                    "type": "string",
+                    "description": "Type of the tagged object",
+                    "enum": ["commit", "tree", "blob"],
+                    "default": "commit"
+                },
+                "tagger": {
+                    "type": "object",
+                    "description": "Tag author; defaults to the authenticated user",
+                    "properties": {
+                        "name": {
+                            "type": "string",
+                            "description": "Author name"
+                        },
+                        "email": {
+                            "type": "string",
+                            "description": "Author email"
+                        },
+                        "date": {
+                            "type": "string",
+                            "description": "When the tag was made (ISO 8601)"
+                        }
+                    },
+                    "required": ["name", "email"]
+                }
+            },
+            "required": ["owner", "repo", "tag", "message", "object"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let new_tag = NewTag {
+                tag: required_param(&args, "tag")?,
+                message: required_param(&args, "message")?,
+                object: required_param(&args, "object")?,
+                object_type: optional_param(&args, "type")?.unwrap_or_else(|| "commit".to_string()),
+                tagger: optional_param::<Tagger>(&args, "tagger")?,
+            };
+
+            debug!("Creating tag {} on {} in {}/{}", new_tag.tag, new_tag.object, owner, repo);
+
+            match client.create_tag(&owner, &repo, &new_tag).await {
+                Ok(created) => {
+                    debug!("Successfully created tag object {}", created.sha);
+                    Ok(serde_json::to_value(created)?)
+                }
+                Err(e) => {
+                    error!("Failed to create tag: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("create_tag".to_string(), tool, handler);
+}
+
+fn add_create_branch_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "create_branch".to_string(),
+        description: "Create a branch at the head of another branch".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "branch": {
+                    "type": "string",
+                    "description": "Name of the new branch"
+                },
+                "from_branch": {
+                    "type": "string",
+                    "description": "Branch to start from; defaults to the repository's default branch"
+                }
+            },
+            "required": ["owner", "repo", "branch"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let branch: String = required_param(&args, "branch")?;
+            let from_branch: Option<String> = optional_param(&args, "from_branch")?;
+
+            debug!("Creating branch {} in {}/{}", branch, owner, repo);
+
+            match client.create_branch(&owner, &repo, &branch, from_branch.as_deref()).await {
+                Ok(created) => {
+                    debug!("Created {} at {}", created.ref_name, created.sha);
+                    Ok(serde_json::to_value(created)?)
+                }
+                Err(e) => {
+                    error!("Failed to create branch: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("create_branch".to_string(), tool, handler);
+}
+
+fn add_upload_release_asset_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "upload_release_asset".to_string(),
+        description: "Upload a local file as a release asset and return its browser download URL".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "release_id": {
+                    "type": "number",
+                    "description": "ID of the release to attach the asset to"
+                },
+                "file_path": {
+                    "type": "string",
+                    "description": "Path of the file to upload, relative to the server's uploads directory (--uploads-dir); refused when that is unset"
+                },
+                "name": {
+                    "type": "string",
+                    "description": "Asset name; defaults to the file name"
+                },
+                "content_type": {
+                    "type": "string",
+                    "description": "MIME type; guessed from the file extension when omitted"
+                }
+            },
+            "required": ["owner", "repo", "release_id", "file_path"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let release_id: u64 = required_param::<f64>(&args, "release_id")? as u64;
+            let file_path: String = required_param(&args, "file_path")?;
+            let name: Option<String> = optional_param(&args, "name")?;
+            let content_type: Option<String> = optional_param(&args, "content_type")?;
+
+            debug!("Uploading {} to release {} in {}/{}", file_path, release_id, owner, repo);
+
+            match client.upload_release_asset(
+                &owner,
+                &repo,
+                release_id,
+                std::path::Path::new(&file_path),
+                name.as_deref(),
+                content_type.as_deref(),
+            ).await {
+                Ok(asset) => {
+                    debug!("Uploaded release asset {}", asset.browser_download_url);
+                    Ok(serde_json::to_value(asset)?)
+                }
+                Err(e) => {
+                    error!("Failed to upload release asset: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("upload_release_asset".to_string(), tool, handler);
+}
+
+fn add_get_file_blame_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "get_file_blame".to_string(),
+        description: "Show who last changed each range of lines in a file, with commit and author".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "File path"
+                },
+                "ref": {
+                    "type": "string",
+                    "description": "Git reference (branch, tag, or commit SHA); defaults to HEAD"
+                }
+            },
+            "required": ["owner", "repo", "path"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let path: String = required_param(&args, "path")?;
+            let reference: Option<String> = optional_param(&args, "ref")?;
+
+            debug!("Getting blame for {}/{} path: {}", owner, repo, path);
+
+            match client.get_file_blame(&owner, &repo, &path, reference.as_deref()).await {
+                Ok(blame) => {
+                    debug!("Retrieved {} blame ranges", blame.ranges.len());
+                    Ok(serde_json::to_value(blame)?)
+                }
+                Err(e) => {
+                    error!("Failed to get file blame: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("get_file_blame".to_string(), tool, handler);
+}
+
+fn add_get_repo_default_branch_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "get_repo_default_branch".to_string(),
+        description: "Get a repository's default branch and the SHA of its latest commit".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                }
+            },
+            "required": ["owner", "repo"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+
+            debug!("Getting default branch for {}/{}", owner, repo);
+
+            match client.get_repo_default_branch(&owner, &repo).await {
+                Ok(branch) => {
+                    debug!("Default branch {} is at {}", branch.default_branch, branch.sha);
+                    Ok(serde_json::to_value(branch)?)
+                }
+                Err(e) => {
+                    error!("Failed to get default branch: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("get_repo_default_branch".to_string(), tool, handler);
+}
+
+fn add_get_repository_overview_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "get_repository_overview".to_string(),
+        description: "Get repository metadata, open issue and pull request counts, default branch, latest release, and top languages in one call".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                }
+            },
+            "required": ["owner", "repo"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+
+            debug!("Getting overview for {}/{}", owner, repo);
+
+            match client.get_repository_overview(&owner, &repo).await {
+                Ok(overview) => {
+                    debug!("Repository overview has {} missing pieces", overview.errors.len());
+                    Ok(serde_json::to_value(overview)?)
+                }
+                Err(e) => {
+                    error!("Failed to get repository overview: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("get_repository_overview".to_string(), tool, handler);
+}
+
+fn add_get_repo_languages_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "get_repo_languages".to_string(),
+        description: "Get the languages used in a repository with bytes of code for each".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                }
+            },
+            "required": ["owner", "repo"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+
+            debug!("Getting languages for {}/{}", owner, repo);
+
+            match client.get_repo_languages(&owner, &repo).await {
+                Ok(languages) => {
+                    debug!("Successfully retrieved {} languages", languages.len());
+                    Ok(serde_json::to_value(languages)?)
+                }
+                Err(e) => {
+                    error!("Failed to get repository languages: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("get_repo_languages".to_string(), tool, handler);
+}
+
+fn add_get_repo_contributors_stats_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "get_repo_contributors_stats".to_string(),
+        description: "Get weekly additions, deletions and commits per contributor (retries while GitHub computes the statistics)".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                }
+            },
+            "required": ["owner", "repo"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+
+            debug!("Getting contributor statistics for {}/{}", owner, repo);
+
+            match client.get_repo_contributors_stats(&owner, &repo).await {
+                Ok(stats) => {
+                    debug!("Successfully retrieved statistics for {} contributors", stats.len());
+                    Ok(serde_json::to_value(stats)?)
+                }
+                Err(e) => {
+                    error!("Failed to get contributor statistics: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("get_repo_contributors_stats".to_string(), tool, handler);
+}
+
+fn add_get_repo_topics_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "get_repo_topics".to_string(),
+        description: "Get the topics of a repository".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                }
+            },
+            "required": ["owner", "repo"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+
+            debug!("Getting topics for {}/{}", owner, repo);
+
+            match client.get_repo_topics(&owner, &repo).await {
+                Ok(topics) => {
+                    debug!("Successfully retrieved {} topics", topics.names.len());
+                    Ok(serde_json::to_value(topics)?)
+                }
+                Err(e) => {
+                    error!("Failed to get repository topics: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("get_repo_topics".to_string(), tool, handler);
+}
+
+fn add_list_deployments_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "list_deployments".to_string(),
+        description: "List deployments for a repository with the latest state of each".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "environment": {
+                    "type": "string",
+                    "description": "Only deployments to this environment (e.g. production)"
+                },
+                "ref": {
+                    "type": "string",
+                    "description": "Only deployments of this branch, tag, or SHA"
+                },
+                "page": {
+                    "type": "number",
+                    "description": "Page number for pagination (min 1)"
+                },
+                "perPage": {
+                    "type": "number",
+                    "description": "Results per page for pagination (min 1, max 100)"
+                }
+            },
+            "required": ["owner", "repo"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let environment: Option<String> = optional_param(&args, "environment")?;
+            let git_ref: Option<String> = optional_param(&args, "ref")?;
+            let pagination = extract_pagination_params(&args)?;
+
+            debug!("Listing deployments for {}/{}", owner, repo);
+
+            match client.list_deployments(
+                &owner,
+                &repo,
+                environment.as_deref(),
+                git_ref.as_deref(),
+                Some(pagination.per_page as u8),
+                Some(pagination.page),
+            ).await {
+                Ok(deployments) => {
+                    debug!("Found {} deployments", deployments.len());
+                    Ok(serde_json::to_value(deployments)?)
+                }
+                Err(e) => {
+                    error!("Failed to list deployments: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("list_deployments".to_string(), tool, handler);
+}
+
+fn add_list_environments_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "list_environments".to_string(),
+        description: "List deployment environments for a repository with their protection rules".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "page": {
+                    "type": "number",
+                    "description": "Page number for pagination (min 1)"
+                },
+                "perPage": {
+                    "type": "number",
+                    "description": "Results per page for pagination (min 1, max 100)"
+                }
+            },
+            "required": ["owner", "repo"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let pagination = extract_pagination_params(&args)?;
+
+            debug!("Listing environments for {}/{}", owner, repo);
+
+            match client.list_environments(
+                &owner,
+                &repo,
+                Some(pagination.per_page as u8),
+                Some(pagination.page),
+            ).await {
+                Ok(environments) => {
+                    debug!("Found {} environments", environments.total_count);
+                    Ok(serde_json::to_value(environments)?)
+                }
+                Err(e) => {
+                    error!("Failed to list environments: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("list_environments".to_string(), tool, handler);
+}
+
+fn add_replace_repo_topics_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "replace_repo_topics".to_string(),
+        description: "Replace all topics of a repository".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "names": {
+                    "type": "array",
+                    "description": "Topic names (lowercase letters, numbers and hyphens). An empty array clears all topics",
+                    "items": {"type": "string"}
+                }
+            },
+            "required": ["owner", "repo", "names"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let names: Vec<String> = required_param(&args, "names")?;
+
+            debug!("Replacing topics for {}/{}: {:?}", owner, repo, names);
+
+            match client.replace_repo_topics(&owner, &repo, &names).await {
+                Ok(topics) => {
+                    debug!("Successfully replaced topics");
+                    Ok(serde_json::to_value(topics)?)
+                }
+                Err(e) => {
+                    error!("Failed to replace repository topics: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("replace_repo_topics".to_string(), tool, handler);
+}
+
+fn add_get_repo_subscription_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "get_repo_subscription".to_string(),
+        description: "Get whether you are watching a repository (subscribed, ignored, reason)".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                }
+            },
+            "required": ["owner", "repo"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+
+            debug!("Getting subscription for {}/{}", owner, repo);
+
+            match client.get_repo_subscription(&owner, &repo).await {
+                Ok(subscription) => Ok(serde_json::to_value(subscription)?),
+                Err(e) => {
+                    error!("Failed to get repository subscription: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("get_repo_subscription".to_string(), tool, handler);
+}
+
+fn add_set_repo_subscription_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "set_repo_subscription".to_string(),
+        description: "Watch a repository, or ignore all of its notifications".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "subscribed": {
+                    "type": "boolean",
+                    "description": "Receive notifications from this repository"
+                },
+                "ignored": {
+                    "type": "boolean",
+                    "description": "Block all notifications from this repository"
+                }
+            },
+            "required": ["owner", "repo", "subscribed", "ignored"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let subscribed: bool = required_param(&args, "subscribed")?;
+            let ignored: bool = required_param(&args, "ignored")?;
+
+            debug!("Setting subscription for {}/{}: subscribed={}, ignored={}", owner, repo, subscribed, ignored);
+
+            match client.set_repo_subscription(&owner, &repo, subscribed, ignored).await {
+                Ok(subscription) => Ok(serde_json::to_value(subscription)?),
+                Err(e) => {
+                    error!("Failed to set repository subscription: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("set_repo_subscription".to_string(), tool, handler);
+}
+
+fn add_delete_repo_subscription_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "delete_repo_subscription".to_string(),
+        description: "Stop watching a repository".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                }
+            },
+            "required": ["owner", "repo"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+
+            debug!("Deleting subscription for {}/{}", owner, repo);
+
+            match client.delete_repo_subscription(&owner, &repo).await {
+                Ok(subscription) => Ok(serde_json::to_value(subscription)?),
+                Err(e) => {
+                    error!("Failed to delete repository subscription: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("delete_repo_subscription".to_string(), tool, handler);
+}
+
+fn add_list_commits_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "list_commits".to_string(),
+        description: "List commits of a repository with sha, subject, author and date".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "sha": {
+                    "type": "string",
+                    "description": "Branch name or commit SHA to start listing from"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Only commits containing this file path"
+                },
+                "author": {
+                    "type": "string",
+                    "description": "GitHub login or email address of the commit author"
+                },
+                "committer": {
+                    "type": "string",
+                    "description": "GitHub login or email address of the committer"
+                },
+                "since": {
+                    "type": "string",
+                    "description": "Only commits after this RFC 3339 timestamp (e.g. 2024-01-01T00:00:00Z)"
+                },
+                "until": {
+                    "type": "string",
+                    "description": "Only commits before this RFC 3339 timestamp"
+                },
+                "page": {
+                    "type": "number",
+                    "description": "Page number for pagination (min 1)"
+                },
+                "perPage": {
+                    "type": "number",
+                    "description": "Results per page for pagination (min 1, max 100)"
+                }
+            },
+            "required": ["owner", "repo"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let filters = CommitFilters {
+                sha: optional_param(&args, "sha")?,
+                path: optional_param(&args, "path")?,
+                author: optional_param(&args, "author")?,
+                committer: optional_param(&args, "committer")?,
+                since: optional_param(&args, "since")?,
+                until: optional_param(&args, "until")?,
+            };
+            let pagination = extract_pagination_params(&args)?;
+
+            if let Some(since) = &filters.since {
+                validate_rfc3339_param("since", since)?;
+            }
+            if let Some(until) = &filters.until {
+                validate_rfc3339_param("until", until)?;
+            }
+
+            debug!("Listing commits for {}/{}", owner, repo);
+
+            match client.list_commits(
+                &owner,
+                &repo,
+                &filters,
+                Some(pagination.per_page as u8),
+                Some(pagination.page),
+            ).await {
+                Ok(commits) => {
+                    debug!("Found {} commits", commits.len());
+                    Ok(serde_json::to_value(commits)?)
+                }
+                Err(e) => {
+                    error!("Failed to list commits: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("list_commits".to_string(), tool, handler);
+}
+
+fn add_get_commit_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "get_commit".to_string(),
+        description: "Get a commit's full message, parents, stats and changed files, optionally with its diff".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "ref": {
+                    "type": "string",
+                    "description": "Commit SHA, branch name or tag name"
+                },
+                "include_diff": {
+                    "type": "boolean",
+                    "description": "Include per-file patches and the raw unified diff",
+                    "default": false
+                }
+            },
+            "required": ["owner", "repo", "ref"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let reference: String = required_param(&args, "ref")?;
+            let include_diff: bool = optional_param(&args, "include_diff")?.unwrap_or(false);
+
+            debug!("Getting commit {} for {}/{}", reference, owner, repo);
+
+            match client.get_commit(&owner, &repo, &reference, include_diff).await {
+                Ok(commit) => {
+                    debug!("Commit {} changed {} files", commit.summary.sha, commit.files.len());
+                    Ok(serde_json::to_value(commit)?)
+                }
+                Err(e) => {
+                    error!("Failed to get commit: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("get_commit".to_string(), tool, handler);
+}
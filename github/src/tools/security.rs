@@ -0,0 +1,173 @@
+use anyhow::Result;
+use futures::future::BoxFuture;
+use crate::mcp_core::tools::Tool;
+use serde_json::{Map, Value};
+use std::sync::Arc;
+use tracing::{debug, error};
+
+use crate::github::GitHubClient;
+use crate::server::{required_param, optional_param, extract_pagination_params};
+use super::toolsets::Toolset;
+use super::registry::ToolHandlerFunc;
+
+pub async fn create_code_security_toolset(github_client: Arc<GitHubClient>) -> Result<Toolset> {
+    let mut toolset = Toolset::new("code_security", "Code security scanning tools");
+
+    // List Dependabot alerts tool
+    add_list_dependabot_alerts_tool(&mut toolset, github_client.clone());
+
+    Ok(toolset)
+}
+
+pub async fn create_secret_protection_toolset(github_client: Arc<GitHubClient>) -> Result<Toolset> {
+    let mut toolset = Toolset::new("secret_protection", "Secret scanning tools");
+
+    // List secret scanning alerts tool
+    add_list_secret_scanning_alerts_tool(&mut toolset, github_client.clone());
+
+    Ok(toolset)
+}
+
+fn add_list_secret_scanning_alerts_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "list_secret_scanning_alerts".to_string(),
+        description: "List secret scanning alerts for a repository with their state and secret type".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "state": {
+                    "type": "string",
+                    "description": "Filter by alert state",
+                    "enum": ["open", "resolved"]
+                },
+                "secret_type": {
+                    "type": "string",
+                    "description": "Comma-separated secret types to include (e.g. github_personal_access_token)"
+                },
+                "page": {
+                    "type": "number",
+                    "description": "Page number for pagination (min 1)"
+                },
+                "perPage": {
+                    "type": "number",
+                    "description": "Results per page for pagination (min 1, max 100)"
+                }
+            },
+            "required": ["owner", "repo"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let state: Option<String> = optional_param(&args, "state")?;
+            let secret_type: Option<String> = optional_param(&args, "secret_type")?;
+            let pagination = extract_pagination_params(&args)?;
+
+            debug!("Listing secret scanning alerts for {}/{}", owner, repo);
+
+            match client.list_secret_scanning_alerts(
+                &owner,
+                &repo,
+                state.as_deref(),
+                secret_type.as_deref(),
+                Some(pagination.per_page as u8),
+                Some(pagination.page),
+            ).await {
+                Ok(alerts) => {
+                    debug!("Found {} secret scanning alerts", alerts.len());
+                    Ok(serde_json::to_value(alerts)?)
+                }
+                Err(e) => {
+                    error!("Failed to list secret scanning alerts: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("list_secret_scanning_alerts".to_string(), tool, handler);
+}
+
+fn add_list_dependabot_alerts_tool(toolset: &mut Toolset, github_client: Arc<GitHubClient>) {
+    let tool = Tool {
+        name: "list_dependabot_alerts".to_string(),
+        description: "List Dependabot alerts for a repository with their state, severity, and affected package".to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "owner": {
+                    "type": "string",
+                    "description": "Repository owner"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "state": {
+                    "type": "string",
+                    "description": "Comma-separated alert states to include: auto_dismissed, dismissed, fixed, open"
+                },
+                "severity": {
+                    "type": "string",
+                    "description": "Comma-separated severities to include: low, medium, high, critical"
+                },
+                "ecosystem": {
+                    "type": "string",
+                    "description": "Comma-separated package ecosystems to include (e.g. npm, pip, cargo)"
+                },
+                "perPage": {
+                    "type": "number",
+                    "description": "Maximum number of alerts to return (min 1, max 100)"
+                }
+            },
+            "required": ["owner", "repo"]
+        }),
+    };
+
+    let handler: ToolHandlerFunc = Box::new(move |args: Map<String, Value>| {
+        let client = github_client.clone();
+        
+        Box::pin(async move {
+            let owner: String = required_param(&args, "owner")?;
+            let repo: String = required_param(&args, "repo")?;
+            let state: Option<String> = optional_param(&args, "state")?;
+            let severity: Option<String> = optional_param(&args, "severity")?;
+            let ecosystem: Option<String> = optional_param(&args, "ecosystem")?;
+            let pagination = extract_pagination_params(&args)?;
+
+            debug!("Listing Dependabot alerts for {}/{}", owner, repo);
+
+            match client.list_dependabot_alerts(
+                &owner,
+                &repo,
+                state.as_deref(),
+                severity.as_deref(),
+                ecosystem.as_deref(),
+                Some(pagination.per_page as u8),
+            ).await {
+                Ok(alerts) => {
+                    debug!("Found {} Dependabot alerts", alerts.len());
+                    Ok(serde_json::to_value(alerts)?)
+                }
+                Err(e) => {
+                    error!("Failed to list Dependabot alerts: {}", e);
+                    Err(e)
+                }
+            }
+        }) as BoxFuture<'static, Result<Value>>
+    });
+
+    toolset.add_tool("list_dependabot_alerts".to_string(), tool, handler);
+}
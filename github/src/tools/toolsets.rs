@@ -73,6 +73,8 @@ pub const ALL_TOOLSETS: &[&str] = &[
     "issues",
     "pull_requests", 
     "users",
+    "actions",
+    "projects",
     "code_security",
     "secret_protection",
     "notifications",
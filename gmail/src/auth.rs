@@ -1,5 +1,5 @@
+use crate::config::get_config_dir;
 use crate::error::{GmailError, Result};
-use dirs::home_dir;
 use oauth2::{
     basic::BasicClient, AuthUrl, AuthorizationCode, ClientId, ClientSecret, CsrfToken,
     PkceCodeChallenge, RedirectUrl, Scope, TokenResponse, TokenUrl, reqwest::async_http_client,
@@ -60,8 +60,21 @@ impl GoogleAuth {
         })
     }
 
+    /// Builds auth state from an already-loaded config and token, skipping the
+    /// config directory lookups `new` does.
+    pub fn with_credentials(config: OAuthConfig, credentials: OAuthCredentials) -> Result<Self> {
+        let client = Self::create_oauth_client(&config)?;
+
+        Ok(Self {
+            client,
+            config,
+            credentials: Some(credentials),
+            http_client: Client::new(),
+        })
+    }
+
     async fn load_oauth_config() -> Result<OAuthConfig> {
-        let config_dir = Self::get_config_dir()?;
+        let config_dir = get_config_dir()?;
         let oauth_path = std::env::var("GMAIL_OAUTH_PATH")
             .unwrap_or_else(|_| config_dir.join("gcp-oauth.keys.json").to_string_lossy().to_string());
 
@@ -106,19 +119,8 @@ impl GoogleAuth {
         Ok(BasicClient::new(client_id, Some(client_secret), auth_url, Some(token_url)))
     }
 
-    fn get_config_dir() -> Result<PathBuf> {
-        let home = home_dir().ok_or_else(|| GmailError::AuthError("Unable to find home directory".to_string()))?;
-        let config_dir = home.join(".gmail-mcp");
-        
-        if !config_dir.exists() {
-            fs::create_dir_all(&config_dir)?;
-        }
-        
-        Ok(config_dir)
-    }
-
     async fn load_credentials() -> Result<OAuthCredentials> {
-        let config_dir = Self::get_config_dir()?;
+        let config_dir = get_config_dir()?;
         let credentials_path = std::env::var("GMAIL_CREDENTIALS_PATH")
             .unwrap_or_else(|_| config_dir.join("credentials.json").to_string_lossy().to_string());
 
@@ -242,7 +244,7 @@ impl GoogleAuth {
     }
 
     async fn save_credentials(&self, credentials: &OAuthCredentials) -> Result<()> {
-        let config_dir = Self::get_config_dir()?;
+        let config_dir = get_config_dir()?;
         let credentials_path = config_dir.join("credentials.json");
         
         let content = serde_json::to_string_pretty(credentials)?;
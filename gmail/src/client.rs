@@ -4,6 +4,8 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
 use tracing::{debug, error};
 
 const GMAIL_API_BASE: &str = "https://gmail.googleapis.com/gmail/v1";
@@ -76,6 +78,34 @@ pub struct LabelColor {
     pub background_color: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GmailProfile {
+    #[serde(rename = "emailAddress")]
+    pub email_address: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AutoForwarding {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(rename = "emailAddress")]
+    pub email_address: Option<String>,
+    pub disposition: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Delegate {
+    #[serde(rename = "delegateEmail")]
+    pub delegate_email: String,
+    #[serde(rename = "verificationStatus")]
+    pub verification_status: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DelegateListResponse {
+    pub delegates: Option<Vec<Delegate>>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MessageListResponse {
     pub messages: Option<Vec<MessageRef>>,
@@ -92,6 +122,21 @@ pub struct MessageRef {
     pub thread_id: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GmailDraft {
+    pub id: String,
+    pub message: GmailMessage,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DraftListResponse {
+    pub drafts: Option<Vec<GmailDraft>>,
+    #[serde(rename = "nextPageToken")]
+    pub next_page_token: Option<String>,
+    #[serde(rename = "resultSizeEstimate")]
+    pub result_size_estimate: Option<u32>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LabelListResponse {
     pub labels: Option<Vec<GmailLabel>>,
@@ -121,31 +166,66 @@ pub struct CreateLabelRequest {
     pub label_list_visibility: Option<String>,
 }
 
+/// Clones share one connection pool and one auth state, so calls made through
+/// different clones run concurrently; only token access takes the auth lock.
+#[derive(Clone)]
 pub struct GmailClient {
     client: Client,
-    auth: GoogleAuth,
+    auth: Arc<Mutex<GoogleAuth>>,
+    api_base: String,
 }
 
 impl GmailClient {
     pub async fn new() -> Result<Self> {
         let auth = GoogleAuth::new().await?;
-        let client = Client::new();
-        
-        Ok(Self { client, auth })
+        Ok(Self::with_auth(auth, GMAIL_API_BASE))
+    }
+
+    /// Uses `auth` against `api_base` instead of the public Gmail endpoint.
+    pub fn with_auth(auth: GoogleAuth, api_base: &str) -> Self {
+        Self {
+            client: Client::new(),
+            auth: Arc::new(Mutex::new(auth)),
+            api_base: api_base.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// A client with a fixed access token, for tests against a mock `api_base`.
+    #[cfg(test)]
+    pub(crate) fn for_tests(api_base: &str) -> Self {
+        use crate::auth::{OAuthConfig, OAuthCredentials};
+
+        let config = OAuthConfig {
+            client_id: "client".to_string(),
+            client_secret: "secret".to_string(),
+            auth_uri: "https://accounts.google.com/o/oauth2/auth".to_string(),
+            token_uri: "https://oauth2.googleapis.com/token".to_string(),
+            redirect_uris: vec![],
+        };
+        let credentials = OAuthCredentials {
+            access_token: "token".to_string(),
+            refresh_token: None,
+            expires_in: None,
+            token_type: "Bearer".to_string(),
+        };
+        Self::with_auth(GoogleAuth::with_credentials(config, credentials).unwrap(), api_base)
     }
 
-    pub async fn authenticate(&mut self, callback_url: &str) -> Result<()> {
-        self.auth.authenticate(callback_url).await
+    pub async fn authenticate(&self, callback_url: &str) -> Result<()> {
+        self.auth.lock().await.authenticate(callback_url).await
     }
 
-    async fn make_request<T>(&mut self, method: &str, endpoint: &str, body: Option<Value>) -> Result<T>
+    async fn make_request<T>(&self, method: &str, endpoint: &str, body: Option<Value>) -> Result<T>
     where
         T: for<'de> Deserialize<'de>,
     {
-        self.auth.refresh_token_if_needed().await?;
-        let token = self.auth.get_access_token()?;
-        
-        let url = format!("{}/{}", GMAIL_API_BASE, endpoint);
+        let token = {
+            let mut auth = self.auth.lock().await;
+            auth.refresh_token_if_needed().await?;
+            auth.get_access_token()?.to_string()
+        };
+
+        let url = format!("{}/{}", self.api_base, endpoint);
         debug!("Making {} request to: {}", method, url);
 
         let mut request = match method {
@@ -166,17 +246,37 @@ impl GmailClient {
         
         if !response.status().is_success() {
             let status = response.status();
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string);
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
             error!("API request failed with status {}: {}", status, error_text);
-            return Err(GmailError::ApiError(format!("HTTP {}: {}", status, error_text)));
+            return Err(api_error(status, retry_after.as_deref(), &error_text));
         }
 
         let json_response: T = response.json().await?;
         Ok(json_response)
     }
 
+    pub async fn get_profile(&self) -> Result<GmailProfile> {
+        self.make_request("GET", "users/me/profile", None).await
+    }
+
+    // Settings operations
+    pub async fn get_auto_forwarding(&self) -> Result<AutoForwarding> {
+        self.make_request("GET", "users/me/settings/autoForwarding", None).await
+    }
+
+    pub async fn list_delegates(&self) -> Result<DelegateListResponse> {
+        self.make_request("GET", "users/me/settings/delegates", None)
+            .await
+            .map_err(delegates_error)
+    }
+
     // Message operations
-    pub async fn send_message(&mut self, raw_message: &str, thread_id: Option<String>) -> Result<GmailMessage> {
+    pub async fn send_message(&self, raw_message: &str, thread_id: Option<String>) -> Result<GmailMessage> {
         let request = SendMessageRequest {
             raw: raw_message.to_string(),
             thread_id,
@@ -185,7 +285,7 @@ impl GmailClient {
         self.make_request("POST", "users/me/messages/send", Some(serde_json::to_value(request)?)).await
     }
 
-    pub async fn create_draft(&mut self, raw_message: &str, thread_id: Option<String>) -> Result<Value> {
+    pub async fn create_draft(&self, raw_message: &str, thread_id: Option<String>) -> Result<Value> {
         let message_request = SendMessageRequest {
             raw: raw_message.to_string(),
             thread_id,
@@ -198,7 +298,26 @@ impl GmailClient {
         self.make_request("POST", "users/me/drafts", Some(draft_request)).await
     }
 
-    pub async fn get_message(&mut self, message_id: &str, format: Option<&str>) -> Result<GmailMessage> {
+    /// Lists drafts; each entry only carries the draft id and its message/thread ids.
+    pub async fn list_drafts(&self, max_results: Option<u32>) -> Result<DraftListResponse> {
+        let endpoint = match max_results {
+            Some(max) => format!("users/me/drafts?maxResults={}", max),
+            None => "users/me/drafts".to_string(),
+        };
+
+        self.make_request("GET", &endpoint, None).await
+    }
+
+    pub async fn get_draft(&self, draft_id: &str, format: Option<&str>) -> Result<GmailDraft> {
+        let endpoint = match format {
+            Some(fmt) => format!("users/me/drafts/{}?format={}", draft_id, fmt),
+            None => format!("users/me/drafts/{}", draft_id),
+        };
+
+        self.make_request("GET", &endpoint, None).await
+    }
+
+    pub async fn get_message(&self, message_id: &str, format: Option<&str>) -> Result<GmailMessage> {
         let endpoint = match format {
             Some(fmt) => format!("users/me/messages/{}?format={}", message_id, fmt),
             None => format!("users/me/messages/{}", message_id),
@@ -207,17 +326,18 @@ impl GmailClient {
         self.make_request("GET", &endpoint, None).await
     }
 
-    pub async fn search_messages(&mut self, query: &str, max_results: Option<u32>) -> Result<MessageListResponse> {
-        let mut endpoint = format!("users/me/messages?q={}", urlencoding::encode(query));
-        
-        if let Some(max) = max_results {
-            endpoint.push_str(&format!("&maxResults={}", max));
-        }
-        
+    /// Fetches an attachment body; `data` comes back base64url-encoded.
+    pub async fn get_attachment(&self, message_id: &str, attachment_id: &str) -> Result<MessageBody> {
+        let endpoint = format!("users/me/messages/{}/attachments/{}", message_id, attachment_id);
+        self.make_request("GET", &endpoint, None).await
+    }
+
+    pub async fn search_messages(&self, query: &str, max_results: Option<u32>, include_spam_trash: bool) -> Result<MessageListResponse> {
+        let endpoint = message_list_endpoint(query, max_results, include_spam_trash);
         self.make_request("GET", &endpoint, None).await
     }
 
-    pub async fn modify_message(&mut self, message_id: &str, add_labels: Option<Vec<String>>, remove_labels: Option<Vec<String>>) -> Result<GmailMessage> {
+    pub async fn modify_message(&self, message_id: &str, add_labels: Option<Vec<String>>, remove_labels: Option<Vec<String>>) -> Result<GmailMessage> {
         let request = ModifyMessageRequest {
             add_label_ids: add_labels,
             remove_label_ids: remove_labels,
@@ -227,18 +347,18 @@ impl GmailClient {
         self.make_request("POST", &endpoint, Some(serde_json::to_value(request)?)).await
     }
 
-    pub async fn delete_message(&mut self, message_id: &str) -> Result<()> {
+    pub async fn delete_message(&self, message_id: &str) -> Result<()> {
         let endpoint = format!("users/me/messages/{}", message_id);
         let _: Value = self.make_request("DELETE", &endpoint, None).await?;
         Ok(())
     }
 
     // Label operations
-    pub async fn list_labels(&mut self) -> Result<LabelListResponse> {
+    pub async fn list_labels(&self) -> Result<LabelListResponse> {
         self.make_request("GET", "users/me/labels", None).await
     }
 
-    pub async fn create_label(&mut self, name: &str, message_list_visibility: Option<String>, label_list_visibility: Option<String>) -> Result<GmailLabel> {
+    pub async fn create_label(&self, name: &str, message_list_visibility: Option<String>, label_list_visibility: Option<String>) -> Result<GmailLabel> {
         let request = CreateLabelRequest {
             name: name.to_string(),
             message_list_visibility,
@@ -248,24 +368,24 @@ impl GmailClient {
         self.make_request("POST", "users/me/labels", Some(serde_json::to_value(request)?)).await
     }
 
-    pub async fn get_label(&mut self, label_id: &str) -> Result<GmailLabel> {
+    pub async fn get_label(&self, label_id: &str) -> Result<GmailLabel> {
         let endpoint = format!("users/me/labels/{}", label_id);
         self.make_request("GET", &endpoint, None).await
     }
 
-    pub async fn update_label(&mut self, label_id: &str, updates: HashMap<String, Value>) -> Result<GmailLabel> {
+    pub async fn update_label(&self, label_id: &str, updates: HashMap<String, Value>) -> Result<GmailLabel> {
         let endpoint = format!("users/me/labels/{}", label_id);
         self.make_request("PUT", &endpoint, Some(serde_json::to_value(updates)?)).await
     }
 
-    pub async fn delete_label(&mut self, label_id: &str) -> Result<()> {
+    pub async fn delete_label(&self, label_id: &str) -> Result<()> {
         let endpoint = format!("users/me/labels/{}", label_id);
         let _: Value = self.make_request("DELETE", &endpoint, None).await?;
         Ok(())
     }
 
     // Batch operations
-    pub async fn batch_modify_messages(&mut self, message_ids: &[String], add_labels: Option<Vec<String>>, remove_labels: Option<Vec<String>>) -> Result<Vec<std::result::Result<GmailMessage, GmailError>>> {
+    pub async fn batch_modify_messages(&self, message_ids: &[String], add_labels: Option<Vec<String>>, remove_labels: Option<Vec<String>>) -> Result<Vec<std::result::Result<GmailMessage, GmailError>>> {
         let mut results = Vec::new();
         
         for message_id in message_ids {
@@ -276,7 +396,7 @@ impl GmailClient {
         Ok(results)
     }
 
-    pub async fn batch_delete_messages(&mut self, message_ids: &[String]) -> Result<Vec<std::result::Result<(), GmailError>>> {
+    pub async fn batch_delete_messages(&self, message_ids: &[String]) -> Result<Vec<std::result::Result<(), GmailError>>> {
         let mut results = Vec::new();
         
         for message_id in message_ids {
@@ -286,4 +406,198 @@ impl GmailClient {
         
         Ok(results)
     }
-}
\ No newline at end of file
+}
+
+/// The `messages.list` endpoint. Spam and trash are skipped unless
+/// `include_spam_trash` is set.
+pub fn message_list_endpoint(query: &str, max_results: Option<u32>, include_spam_trash: bool) -> String {
+    let mut endpoint = format!("users/me/messages?q={}", urlencoding::encode(query));
+
+    if let Some(max) = max_results {
+        endpoint.push_str(&format!("&maxResults={}", max));
+    }
+    if include_spam_trash {
+        endpoint.push_str("&includeSpamTrash=true");
+    }
+
+    endpoint
+}
+
+/// Gmail's error envelope: `{"error": {"code", "message", "errors": [{"reason", ...}]}}`.
+#[derive(Debug, Deserialize)]
+struct ApiErrorBody {
+    error: ApiErrorDetail,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiErrorDetail {
+    message: String,
+    #[serde(default)]
+    errors: Vec<ApiErrorReason>,
+    status: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiErrorReason {
+    reason: Option<String>,
+}
+
+/// Maps a failed response onto a typed error. Gmail reports quota exhaustion
+/// as either a 429 or a 403 with a rate-limit reason, so both become `RateLimited`.
+pub fn api_error(status: reqwest::StatusCode, retry_after: Option<&str>, body: &str) -> GmailError {
+    let detail = serde_json::from_str::<ApiErrorBody>(body).ok().map(|b| b.error);
+    let reason = detail
+        .as_ref()
+        .and_then(|d| d.errors.iter().find_map(|e| e.reason.clone()).or_else(|| d.status.clone()));
+    let message = detail
+        .as_ref()
+        .map(|d| d.message.clone())
+        .unwrap_or_else(|| body.to_string());
+    let rate_limited = matches!(
+        reason.as_deref(),
+        Some("rateLimitExceeded" | "userRateLimitExceeded" | "dailyLimitExceeded")
+    );
+
+    match status {
+        reqwest::StatusCode::TOO_MANY_REQUESTS => GmailError::RateLimited {
+            message,
+            retry_after: retry_after.and_then(|value| value.trim().parse().ok()),
+        },
+        reqwest::StatusCode::FORBIDDEN if rate_limited => GmailError::RateLimited {
+            message,
+            retry_after: retry_after.and_then(|value| value.trim().parse().ok()),
+        },
+        reqwest::StatusCode::FORBIDDEN => GmailError::Forbidden(message),
+        reqwest::StatusCode::NOT_FOUND => GmailError::NotFound(message),
+        reqwest::StatusCode::BAD_REQUEST => GmailError::InvalidArgument {
+            reason: reason.unwrap_or_else(|| "badRequest".to_string()),
+            message,
+        },
+        _ => GmailError::ApiError(format!("HTTP {}: {}", status, body)),
+    }
+}
+
+/// Delegation is only available to Workspace accounts; consumer accounts get a 403.
+pub fn delegates_error(error: GmailError) -> GmailError {
+    match error {
+        GmailError::Forbidden(details) => GmailError::Forbidden(format!(
+            "Delegates are not available for this account type: {}",
+            details
+        )),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_settings_fixtures() {
+        let forwarding: AutoForwarding = serde_json::from_value(serde_json::json!({
+            "enabled": true,
+            "emailAddress": "archive@example.com",
+            "disposition": "leaveInInbox"
+        }))
+        .unwrap();
+        assert!(forwarding.enabled);
+        assert_eq!(forwarding.email_address.as_deref(), Some("archive@example.com"));
+        assert_eq!(forwarding.disposition.as_deref(), Some("leaveInInbox"));
+
+        let disabled: AutoForwarding = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert!(!disabled.enabled);
+
+        let delegates: DelegateListResponse = serde_json::from_value(serde_json::json!({
+            "delegates": [
+                {"delegateEmail": "assistant@example.com", "verificationStatus": "accepted"},
+                {"delegateEmail": "new@example.com", "verificationStatus": "pending"}
+            ]
+        }))
+        .unwrap();
+        let delegates = delegates.delegates.unwrap();
+        assert_eq!(delegates.len(), 2);
+        assert_eq!(delegates[0].delegate_email, "assistant@example.com");
+        assert_eq!(delegates[1].verification_status.as_deref(), Some("pending"));
+    }
+
+    #[test]
+    fn test_message_list_endpoint_forwards_include_spam_trash() {
+        assert_eq!(
+            message_list_endpoint("from:a@example.com", Some(25), true),
+            "users/me/messages?q=from%3Aa%40example.com&maxResults=25&includeSpamTrash=true"
+        );
+        assert_eq!(
+            message_list_endpoint("is:unread", None, false),
+            "users/me/messages?q=is%3Aunread"
+        );
+    }
+
+    #[test]
+    fn test_api_error_maps_gmail_error_bodies() {
+        let body = |code: u16, message: &str, reason: &str| {
+            serde_json::json!({
+                "error": {
+                    "code": code,
+                    "message": message,
+                    "errors": [{"domain": "global", "reason": reason, "message": message}]
+                }
+            })
+            .to_string()
+        };
+
+        let error = api_error(
+            reqwest::StatusCode::TOO_MANY_REQUESTS,
+            Some("30"),
+            &body(429, "Too many concurrent requests for user", "rateLimitExceeded"),
+        );
+        assert!(matches!(
+            &error,
+            GmailError::RateLimited { message, retry_after: Some(30) } if message == "Too many concurrent requests for user"
+        ));
+        assert!(error.to_string().ends_with("(retry after 30s)"));
+
+        let error = api_error(
+            reqwest::StatusCode::FORBIDDEN,
+            None,
+            &body(403, "User-rate limit exceeded", "userRateLimitExceeded"),
+        );
+        assert!(matches!(error, GmailError::RateLimited { retry_after: None, .. }));
+
+        let error = api_error(
+            reqwest::StatusCode::FORBIDDEN,
+            None,
+            &body(403, "Request had insufficient authentication scopes.", "insufficientPermissions"),
+        );
+        assert!(matches!(&error, GmailError::Forbidden(m) if m == "Request had insufficient authentication scopes."));
+
+        let error = api_error(reqwest::StatusCode::NOT_FOUND, None, &body(404, "Requested entity was not found.", "notFound"));
+        assert!(matches!(&error, GmailError::NotFound(m) if m == "Requested entity was not found."));
+
+        let error = api_error(reqwest::StatusCode::BAD_REQUEST, None, &body(400, "Invalid label: FOO", "invalidArgument"));
+        assert!(matches!(
+            &error,
+            GmailError::InvalidArgument { reason, message } if reason == "invalidArgument" && message == "Invalid label: FOO"
+        ));
+    }
+
+    #[test]
+    fn test_api_error_falls_back_for_unstructured_bodies() {
+        let error = api_error(reqwest::StatusCode::BAD_REQUEST, None, "Bad Request");
+        assert!(matches!(
+            &error,
+            GmailError::InvalidArgument { reason, message } if reason == "badRequest" && message == "Bad Request"
+        ));
+
+        let error = api_error(reqwest::StatusCode::INTERNAL_SERVER_ERROR, None, "oops");
+        assert_eq!(error.to_string(), "API request failed: HTTP 500 Internal Server Error: oops");
+    }
+
+    #[test]
+    fn test_delegates_error_explains_forbidden() {
+        let error = delegates_error(GmailError::Forbidden("insufficient permissions".to_string()));
+        assert!(error.to_string().contains("not available for this account type"));
+
+        let error = delegates_error(GmailError::ApiError("HTTP 500".to_string()));
+        assert_eq!(error.to_string(), "API request failed: HTTP 500");
+    }
+}
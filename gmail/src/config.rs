@@ -0,0 +1,83 @@
+use crate::error::{GmailError, Result};
+use dirs::home_dir;
+use std::fs;
+use std::path::PathBuf;
+
+/// Overrides where downloaded attachments and raw messages are saved.
+pub const DOWNLOADS_DIR_ENV: &str = "GMAIL_DOWNLOADS_DIR";
+
+/// Directory that `attachments` on outgoing mail are read from. Attachments are
+/// refused when this is unset.
+pub const ATTACHMENTS_DIR_ENV: &str = "GMAIL_ATTACHMENTS_DIR";
+
+/// `~/.gmail-mcp`, created on first use.
+pub fn get_config_dir() -> Result<PathBuf> {
+    let home = home_dir().ok_or_else(|| GmailError::AuthError("Unable to find home directory".to_string()))?;
+    let config_dir = home.join(".gmail-mcp");
+
+    if !config_dir.exists() {
+        fs::create_dir_all(&config_dir)?;
+    }
+
+    Ok(config_dir)
+}
+
+/// Default save location for downloads: `$GMAIL_DOWNLOADS_DIR` when set,
+/// otherwise `~/.gmail-mcp/downloads`. Created if absent.
+pub fn get_downloads_dir() -> Result<PathBuf> {
+    let override_dir = std::env::var(DOWNLOADS_DIR_ENV).ok();
+    let downloads_dir = resolve_downloads_dir(override_dir.as_deref(), get_config_dir)?;
+
+    if !downloads_dir.exists() {
+        fs::create_dir_all(&downloads_dir)?;
+    }
+
+    Ok(downloads_dir)
+}
+
+/// `$GMAIL_ATTACHMENTS_DIR` with a leading `~` expanded, if set.
+pub fn get_attachments_dir() -> Option<PathBuf> {
+    std::env::var(ATTACHMENTS_DIR_ENV)
+        .ok()
+        .filter(|dir| !dir.trim().is_empty())
+        .map(|dir| expand_home(dir.trim()))
+}
+
+/// Picks the downloads directory from an optional override, expanding a leading `~`.
+/// The config dir is only looked up (and created) when there is no override.
+pub fn resolve_downloads_dir(
+    override_dir: Option<&str>,
+    config_dir: impl FnOnce() -> Result<PathBuf>,
+) -> Result<PathBuf> {
+    match override_dir.map(str::trim).filter(|dir| !dir.is_empty()) {
+        Some(dir) => Ok(expand_home(dir)),
+        None => Ok(config_dir()?.join("downloads")),
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ if path == "~" => home_dir().unwrap_or_else(|| PathBuf::from(path)),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_downloads_dir() {
+        let config_dir = || Ok(PathBuf::from("/home/user/.gmail-mcp"));
+        let unused = || -> Result<PathBuf> { panic!("config dir must not be touched when overridden") };
+
+        assert_eq!(resolve_downloads_dir(None, config_dir).unwrap(), PathBuf::from("/home/user/.gmail-mcp/downloads"));
+        assert_eq!(resolve_downloads_dir(Some("  "), config_dir).unwrap(), PathBuf::from("/home/user/.gmail-mcp/downloads"));
+        assert_eq!(resolve_downloads_dir(Some("/data/mail"), unused).unwrap(), PathBuf::from("/data/mail"));
+
+        if let Some(home) = home_dir() {
+            assert_eq!(resolve_downloads_dir(Some("~/Downloads/gmail"), unused).unwrap(), home.join("Downloads/gmail"));
+        }
+    }
+}
@@ -11,12 +11,30 @@ pub enum GmailError {
     #[error("Invalid email address: {0}")]
     InvalidEmail(String),
 
+    #[error("Invalid search query: {0}")]
+    InvalidQuery(String),
+
     #[error("Label not found: {0}")]
     LabelNotFound(String),
 
     #[error("Message not found: {0}")]
     MessageNotFound(String),
 
+    #[error("Permission denied: {0}")]
+    Forbidden(String),
+
+    #[error("Rate limit exceeded: {message}{}", retry_hint(*.retry_after))]
+    RateLimited {
+        message: String,
+        retry_after: Option<u64>,
+    },
+
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    #[error("Invalid argument ({reason}): {message}")]
+    InvalidArgument { reason: String, message: String },
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
@@ -33,4 +51,10 @@ pub enum GmailError {
     McpError(String),
 }
 
+fn retry_hint(retry_after: Option<u64>) -> String {
+    retry_after
+        .map(|seconds| format!(" (retry after {}s)", seconds))
+        .unwrap_or_default()
+}
+
 pub type Result<T> = std::result::Result<T, GmailError>;
\ No newline at end of file
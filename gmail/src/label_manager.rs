@@ -28,7 +28,7 @@ pub struct LabelManager;
 impl LabelManager {
     /// Creates a new Gmail label
     pub async fn create_label(
-        client: &mut GmailClient,
+        client: &GmailClient,
         label_name: &str,
         message_list_visibility: Option<String>,
         label_list_visibility: Option<String>,
@@ -38,7 +38,7 @@ impl LabelManager {
 
         match client.create_label(label_name, Some(message_visibility), Some(label_visibility)).await {
             Ok(label) => Ok(label),
-            Err(GmailError::ApiError(msg)) if msg.contains("already exists") => {
+            Err(e) if e.to_string().contains("already exists") => {
                 Err(GmailError::ApiError(format!("Label \"{}\" already exists. Please use a different name.", label_name)))
             }
             Err(e) => Err(GmailError::ApiError(format!("Failed to create label: {}", e))),
@@ -47,14 +47,14 @@ impl LabelManager {
 
     /// Updates an existing Gmail label
     pub async fn update_label(
-        client: &mut GmailClient,
+        client: &GmailClient,
         label_id: &str,
         updates: HashMap<String, serde_json::Value>,
     ) -> Result<GmailLabel> {
         // Verify the label exists before updating
         match client.get_label(label_id).await {
             Ok(_) => {},
-            Err(GmailError::ApiError(msg)) if msg.contains("404") => {
+            Err(GmailError::NotFound(_)) => {
                 return Err(GmailError::LabelNotFound(format!("Label with ID \"{}\" not found.", label_id)));
             }
             Err(e) => return Err(e),
@@ -65,11 +65,11 @@ impl LabelManager {
     }
 
     /// Deletes a Gmail label
-    pub async fn delete_label(client: &mut GmailClient, label_id: &str) -> Result<DeleteLabelResult> {
+    pub async fn delete_label(client: &GmailClient, label_id: &str) -> Result<DeleteLabelResult> {
         // Get the label to check if it's a system label and get its name
         let label = match client.get_label(label_id).await {
             Ok(label) => label,
-            Err(GmailError::ApiError(msg)) if msg.contains("404") => {
+            Err(GmailError::NotFound(_)) => {
                 return Err(GmailError::LabelNotFound(format!("Label with ID \"{}\" not found.", label_id)));
             }
             Err(e) => return Err(e),
@@ -87,8 +87,19 @@ impl LabelManager {
         })
     }
 
+    /// Gets a single Gmail label by ID
+    pub async fn get_label(client: &GmailClient, label_id: &str) -> Result<GmailLabel> {
+        match client.get_label(label_id).await {
+            Ok(label) => Ok(label),
+            Err(GmailError::NotFound(_)) => {
+                Err(GmailError::LabelNotFound(format!("Label with ID \"{}\" not found.", label_id)))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /// Gets a detailed list of all Gmail labels
-    pub async fn list_labels(client: &mut GmailClient) -> Result<LabelManagerResult> {
+    pub async fn list_labels(client: &GmailClient) -> Result<LabelManagerResult> {
         let response = client.list_labels().await
             .map_err(|e| GmailError::ApiError(format!("Failed to list labels: {}", e)))?;
 
@@ -119,7 +130,7 @@ impl LabelManager {
     }
 
     /// Finds a label by name
-    pub async fn find_label_by_name(client: &mut GmailClient, label_name: &str) -> Result<Option<GmailLabel>> {
+    pub async fn find_label_by_name(client: &GmailClient, label_name: &str) -> Result<Option<GmailLabel>> {
         let labels_result = Self::list_labels(client).await?;
         
         // Case-insensitive match across both system and user labels
@@ -135,7 +146,7 @@ impl LabelManager {
 
     /// Creates label if it doesn't exist or returns existing label
     pub async fn get_or_create_label(
-        client: &mut GmailClient,
+        client: &GmailClient,
         label_name: &str,
         message_list_visibility: Option<String>,
         label_list_visibility: Option<String>,
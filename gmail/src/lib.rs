@@ -1,5 +1,6 @@
 pub mod auth;
 pub mod client;
+pub mod config;
 pub mod error;
 pub mod label_manager;
 pub mod mcp_types;
@@ -33,7 +33,7 @@ async fn main() -> Result<()> {
             .map(|s| s.as_str())
             .unwrap_or("http://localhost:3000/oauth2callback");
         
-        let mut server = GmailMcpServer::new().await?;
+        let server = GmailMcpServer::new().await?;
         server.authenticate(callback_url).await?;
         info!("Authentication completed successfully");
         return Ok(());
@@ -41,7 +41,7 @@ async fn main() -> Result<()> {
 
     // Start the MCP server
     info!("Starting Gmail MCP server...");
-    let mut server = GmailMcpServer::new().await?;
+    let server = GmailMcpServer::new().await?;
     server.run().await?;
     
     Ok(())
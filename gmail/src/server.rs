@@ -3,68 +3,96 @@ use crate::error::{GmailError, Result};
 use crate::mcp_types::*;
 use crate::tools::GmailTools;
 use serde_json::json;
-use std::io::{self, BufRead, Write};
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
 use tracing::{debug, error, info};
 
+#[derive(Clone)]
 pub struct GmailMcpServer {
-    client: Arc<Mutex<GmailClient>>,
+    client: GmailClient,
 }
 
 impl GmailMcpServer {
     pub async fn new() -> Result<Self> {
-        let client = Arc::new(Mutex::new(GmailClient::new().await?));
+        let client = GmailClient::new().await?;
         Ok(Self { client })
     }
 
-    pub async fn authenticate(&mut self, callback_url: &str) -> Result<()> {
-        let mut client = self.client.lock().await;
-        client.authenticate(callback_url).await
+    pub fn with_client(client: GmailClient) -> Self {
+        Self { client }
     }
 
-    pub async fn run(&mut self) -> Result<()> {
-        info!("Starting Gmail MCP server...");
+    pub async fn authenticate(&self, callback_url: &str) -> Result<()> {
+        self.client.authenticate(callback_url).await
+    }
 
-        let stdin = io::stdin();
-        let mut stdout = io::stdout();
+    pub async fn run(&self) -> Result<()> {
+        info!("Starting Gmail MCP server...");
 
-        for line in stdin.lock().lines() {
-            let line = line.map_err(|e| GmailError::IoError(e))?;
-            
-            if line.trim().is_empty() {
-                continue;
-            }
+        self.serve(BufReader::new(tokio::io::stdin()), tokio::io::stdout()).await
+    }
 
-            debug!("Received request: {}", line);
+    /// Reads requests line by line and answers each from its own spawned task,
+    /// so a long `batch_delete_emails` doesn't hold up a `read_email` sent after
+    /// it. Finished responses are queued to a single writer and go out in the
+    /// order they complete; clients match them to requests by id. Returns once
+    /// input ends and every request read so far has been answered.
+    pub async fn serve<R, W>(&self, reader: R, mut writer: W) -> Result<()>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let (responses, mut outbox) = mpsc::unbounded_channel::<McpResponse>();
+        let mut lines = reader.lines();
 
-            let request: McpRequest = match serde_json::from_str(&line) {
-                Ok(req) => req,
-                Err(e) => {
-                    error!("Failed to parse request: {}", e);
-                    let error_response = McpResponse {
-                        jsonrpc: "2.0".to_string(),
-                        id: None,
-                        result: None,
-                        error: Some(McpError {
-                            code: -32700,
-                            message: "Parse error".to_string(),
-                            data: None,
-                        }),
-                    };
-                    let response_json = serde_json::to_string(&error_response)?;
-                    writeln!(stdout, "{}", response_json)?;
-                    stdout.flush()?;
+        let read_requests = async move {
+            while let Some(line) = lines.next_line().await.map_err(GmailError::IoError)? {
+                if line.trim().is_empty() {
                     continue;
                 }
-            };
 
-            let response = self.handle_request(request).await;
-            let response_json = serde_json::to_string(&response)?;
-            writeln!(stdout, "{}", response_json)?;
-            stdout.flush()?;
-        }
+                debug!("Received request: {}", line.trim());
+
+                let request: McpRequest = match serde_json::from_str(line.trim()) {
+                    Ok(req) => req,
+                    Err(e) => {
+                        error!("Failed to parse request: {}", e);
+                        let error_response = McpResponse {
+                            jsonrpc: "2.0".to_string(),
+                            id: None,
+                            result: None,
+                            error: Some(McpError {
+                                code: -32700,
+                                message: "Parse error".to_string(),
+                                data: None,
+                            }),
+                        };
+                        let _ = responses.send(error_response);
+                        continue;
+                    }
+                };
 
+                let server = self.clone();
+                let responses = responses.clone();
+                tokio::spawn(async move {
+                    let _ = responses.send(server.handle_request(request).await);
+                });
+            }
+            // Dropping the last sender here lets the writer stop once the
+            // spawned requests have all sent theirs
+            Ok::<_, GmailError>(())
+        };
+
+        let write_responses = async {
+            while let Some(response) = outbox.recv().await {
+                let response_json = serde_json::to_string(&response)?;
+                writer.write_all(format!("{}\n", response_json).as_bytes()).await?;
+                writer.flush().await?;
+            }
+            Ok::<_, GmailError>(())
+        };
+
+        tokio::try_join!(read_requests, write_responses)?;
         Ok(())
     }
 
@@ -148,7 +176,12 @@ impl GmailMcpServer {
                         "mimeType": {
                             "type": "string",
                             "enum": ["text/plain", "text/html", "multipart/alternative"],
-                            "description": "Email content type"
+                            "description": "Email content type; overridden when it does not match the body, HTML body and attachments given"
+                        },
+                        "attachments": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "description": "Files to attach, as paths relative to GMAIL_ATTACHMENTS_DIR (sends as multipart/mixed); refused when that variable is unset"
                         },
                         "cc": {
                             "type": "array",
@@ -172,6 +205,43 @@ impl GmailMcpServer {
                     "required": ["to", "subject", "body"]
                 }),
             },
+            Tool {
+                name: "reply_all".to_string(),
+                description: Some("Replies to all recipients of an email, keeping it in the same thread".to_string()),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "messageId": {
+                            "type": "string",
+                            "description": "ID of the email to reply to"
+                        },
+                        "body": {
+                            "type": "string",
+                            "description": "Reply body content"
+                        },
+                        "htmlBody": {
+                            "type": "string",
+                            "description": "HTML version of the reply body"
+                        },
+                        "to": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "description": "Override the computed To recipients (defaults to the original sender)"
+                        },
+                        "cc": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "description": "Override the computed Cc recipients (defaults to the original To/Cc minus yourself)"
+                        },
+                        "bcc": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "description": "List of BCC recipients"
+                        }
+                    },
+                    "required": ["messageId", "body"]
+                }),
+            },
             Tool {
                 name: "draft_email".to_string(),
                 description: Some("Create an email draft".to_string()),
@@ -198,7 +268,12 @@ impl GmailMcpServer {
                         "mimeType": {
                             "type": "string",
                             "enum": ["text/plain", "text/html", "multipart/alternative"],
-                            "description": "Email content type"
+                            "description": "Email content type; overridden when it does not match the body, HTML body and attachments given"
+                        },
+                        "attachments": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "description": "Files to attach, as paths relative to GMAIL_ATTACHMENTS_DIR (sends as multipart/mixed); refused when that variable is unset"
                         },
                         "cc": {
                             "type": "array",
@@ -222,6 +297,33 @@ impl GmailMcpServer {
                     "required": ["to", "subject", "body"]
                 }),
             },
+            Tool {
+                name: "list_drafts".to_string(),
+                description: Some("Lists drafts with their subjects and recipients".to_string()),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "maxResults": {
+                            "type": "number",
+                            "description": "Maximum number of drafts to return (default 25, max 500)"
+                        }
+                    }
+                }),
+            },
+            Tool {
+                name: "get_draft".to_string(),
+                description: Some("Retrieves a draft's subject, recipients, and body".to_string()),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "draftId": {
+                            "type": "string",
+                            "description": "ID of the draft to retrieve"
+                        }
+                    },
+                    "required": ["draftId"]
+                }),
+            },
             Tool {
                 name: "read_email".to_string(),
                 description: Some("Retrieves the content of a specific email".to_string()),
@@ -231,14 +333,44 @@ impl GmailMcpServer {
                         "messageId": {
                             "type": "string",
                             "description": "ID of the email message to retrieve"
+                        },
+                        "embedInlineImages": {
+                            "type": "boolean",
+                            "description": "Replace cid: image references in the HTML body with data: URIs instead of listing them (default false)"
+                        },
+                        "allowLarge": {
+                            "type": "boolean",
+                            "description": "Load the body of messages over 10 MB, which are otherwise shown as headers only (default false)"
                         }
                     },
                     "required": ["messageId"]
                 }),
             },
+            Tool {
+                name: "download_attachment".to_string(),
+                description: Some("Saves an email attachment to the downloads directory and returns its path".to_string()),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "messageId": {
+                            "type": "string",
+                            "description": "ID of the email message the attachment belongs to"
+                        },
+                        "attachmentId": {
+                            "type": "string",
+                            "description": "Attachment ID as listed by read_email"
+                        },
+                        "filename": {
+                            "type": "string",
+                            "description": "Name to save the file under; an existing file is never overwritten"
+                        }
+                    },
+                    "required": ["messageId", "attachmentId"]
+                }),
+            },
             Tool {
                 name: "search_emails".to_string(),
-                description: Some("Searches for emails using Gmail search syntax".to_string()),
+                description: Some("Searches for emails using Gmail search syntax. Spam and trash are excluded unless includeSpamTrash is true".to_string()),
                 input_schema: json!({
                     "type": "object",
                     "properties": {
@@ -246,12 +378,29 @@ impl GmailMcpServer {
                             "type": "string",
                             "description": "Gmail search query (e.g., 'from:example@gmail.com')"
                         },
+                        "filters": {
+                            "type": "object",
+                            "description": "Structured filters composed into a Gmail query; combined with 'query' when both are given",
+                            "properties": {
+                                "from": {"type": "string", "description": "Sender address or name"},
+                                "to": {"type": "string", "description": "Recipient address or name"},
+                                "subject": {"type": "string", "description": "Words in the subject"},
+                                "after": {"type": "string", "description": "Only messages after this date (YYYY/MM/DD)"},
+                                "before": {"type": "string", "description": "Only messages before this date (YYYY/MM/DD)"},
+                                "hasAttachment": {"type": "boolean", "description": "Only messages with attachments"},
+                                "label": {"type": "string", "description": "Label name"}
+                            }
+                        },
                         "maxResults": {
                             "type": "number",
-                            "description": "Maximum number of results to return"
+                            "description": "Maximum number of results to return (default 25, max 500)"
+                        },
+                        "includeSpamTrash": {
+                            "type": "boolean",
+                            "description": "Also search messages in Spam and Trash, which Gmail skips by default",
+                            "default": false
                         }
-                    },
-                    "required": ["query"]
+                    }
                 }),
             },
             Tool {
@@ -305,6 +454,36 @@ impl GmailMcpServer {
                     "properties": {}
                 }),
             },
+            Tool {
+                name: "get_auto_forwarding".to_string(),
+                description: Some("Retrieves the account's auto-forwarding settings".to_string()),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {}
+                }),
+            },
+            Tool {
+                name: "list_delegates".to_string(),
+                description: Some("Lists the delegates with access to this account (Workspace accounts only)".to_string()),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {}
+                }),
+            },
+            Tool {
+                name: "get_label".to_string(),
+                description: Some("Retrieves the details of a single Gmail label".to_string()),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "id": {
+                            "type": "string",
+                            "description": "ID of the label to retrieve"
+                        }
+                    },
+                    "required": ["id"]
+                }),
+            },
             Tool {
                 name: "create_label".to_string(),
                 description: Some("Creates a new Gmail label".to_string()),
@@ -477,21 +656,28 @@ impl GmailMcpServer {
 
         debug!("Calling tool: {}", call_request.name);
 
-        let mut client_guard = self.client.lock().await;
+        let client = &self.client;
         let result = match call_request.name.as_str() {
-            "send_email" => GmailTools::send_email(&mut *client_guard, call_request.arguments.unwrap_or(json!({}))).await,
-            "draft_email" => GmailTools::draft_email(&mut *client_guard, call_request.arguments.unwrap_or(json!({}))).await,
-            "read_email" => GmailTools::read_email(&mut *client_guard, call_request.arguments.unwrap_or(json!({}))).await,
-            "search_emails" => GmailTools::search_emails(&mut *client_guard, call_request.arguments.unwrap_or(json!({}))).await,
-            "modify_email" => GmailTools::modify_email(&mut *client_guard, call_request.arguments.unwrap_or(json!({}))).await,
-            "delete_email" => GmailTools::delete_email(&mut *client_guard, call_request.arguments.unwrap_or(json!({}))).await,
-            "list_email_labels" => GmailTools::list_email_labels(&mut *client_guard, call_request.arguments.unwrap_or(json!({}))).await,
-            "create_label" => GmailTools::create_label(&mut *client_guard, call_request.arguments.unwrap_or(json!({}))).await,
-            "update_label" => GmailTools::update_label(&mut *client_guard, call_request.arguments.unwrap_or(json!({}))).await,
-            "delete_label" => GmailTools::delete_label(&mut *client_guard, call_request.arguments.unwrap_or(json!({}))).await,
-            "get_or_create_label" => GmailTools::get_or_create_label(&mut *client_guard, call_request.arguments.unwrap_or(json!({}))).await,
-            "batch_modify_emails" => GmailTools::batch_modify_emails(&mut *client_guard, call_request.arguments.unwrap_or(json!({}))).await,
-            "batch_delete_emails" => GmailTools::batch_delete_emails(&mut *client_guard, call_request.arguments.unwrap_or(json!({}))).await,
+            "send_email" => GmailTools::send_email(client, call_request.arguments.unwrap_or(json!({}))).await,
+            "reply_all" => GmailTools::reply_all(client, call_request.arguments.unwrap_or(json!({}))).await,
+            "draft_email" => GmailTools::draft_email(client, call_request.arguments.unwrap_or(json!({}))).await,
+            "list_drafts" => GmailTools::list_drafts(client, call_request.arguments.unwrap_or(json!({}))).await,
+            "get_draft" => GmailTools::get_draft(client, call_request.arguments.unwrap_or(json!({}))).await,
+            "read_email" => GmailTools::read_email(client, call_request.arguments.unwrap_or(json!({}))).await,
+            "download_attachment" => GmailTools::download_attachment(client, call_request.arguments.unwrap_or(json!({}))).await,
+            "search_emails" => GmailTools::search_emails(client, call_request.arguments.unwrap_or(json!({}))).await,
+            "modify_email" => GmailTools::modify_email(client, call_request.arguments.unwrap_or(json!({}))).await,
+            "delete_email" => GmailTools::delete_email(client, call_request.arguments.unwrap_or(json!({}))).await,
+            "list_email_labels" => GmailTools::list_email_labels(client, call_request.arguments.unwrap_or(json!({}))).await,
+            "get_auto_forwarding" => GmailTools::get_auto_forwarding(client, call_request.arguments.unwrap_or(json!({}))).await,
+            "list_delegates" => GmailTools::list_delegates(client, call_request.arguments.unwrap_or(json!({}))).await,
+            "get_label" => GmailTools::get_label(client, call_request.arguments.unwrap_or(json!({}))).await,
+            "create_label" => GmailTools::create_label(client, call_request.arguments.unwrap_or(json!({}))).await,
+            "update_label" => GmailTools::update_label(client, call_request.arguments.unwrap_or(json!({}))).await,
+            "delete_label" => GmailTools::delete_label(client, call_request.arguments.unwrap_or(json!({}))).await,
+            "get_or_create_label" => GmailTools::get_or_create_label(client, call_request.arguments.unwrap_or(json!({}))).await,
+            "batch_modify_emails" => GmailTools::batch_modify_emails(client, call_request.arguments.unwrap_or(json!({}))).await,
+            "batch_delete_emails" => GmailTools::batch_delete_emails(client, call_request.arguments.unwrap_or(json!({}))).await,
             _ => {
                 error!("Unknown tool: {}", call_request.name);
                 return McpResponse {
@@ -529,4 +715,65 @@ impl GmailMcpServer {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::time::{Duration, Instant};
+    use tokio::io::{duplex, AsyncReadExt};
+    use wiremock::matchers::{method, path_regex};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_concurrent_reads_do_not_block_each_other() {
+        const READS: i64 = 5;
+        let delay = Duration::from_millis(200);
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/users/me/labels/Label_\d+$"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({"id": "Label_1", "name": "Receipts", "type": "user"}))
+                    .set_delay(delay),
+            )
+            .expect(READS as u64)
+            .mount(&mock_server)
+            .await;
+
+        let server = GmailMcpServer::with_client(GmailClient::for_tests(&mock_server.uri()));
+        let input: String = (1..=READS)
+            .map(|id| {
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "method": "tools/call",
+                    "params": {"name": "get_label", "arguments": {"id": format!("Label_{}", id)}}
+                })
+                .to_string()
+                    + "\n"
+            })
+            .collect();
+        let (writer, mut output) = duplex(64 * 1024);
+
+        let started = Instant::now();
+        server.serve(input.as_bytes(), writer).await.unwrap();
+        let elapsed = started.elapsed();
+        assert!(elapsed < delay * 3, "{} reads took {:?}; they ran one after the other", READS, elapsed);
+
+        let mut written = String::new();
+        output.read_to_string(&mut written).await.unwrap();
+        let mut ids: Vec<i64> = written
+            .lines()
+            .map(|line| {
+                let response: Value = serde_json::from_str(line).unwrap();
+                assert!(response["error"].is_null(), "{}", line);
+                response["id"].as_i64().unwrap()
+            })
+            .collect();
+        ids.sort();
+        assert_eq!(ids, (1..=READS).collect::<Vec<_>>());
+    }
+}
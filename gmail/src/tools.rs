@@ -1,11 +1,15 @@
-use crate::client::GmailClient;
+use crate::client::{GmailClient, GmailLabel, GmailMessage};
+use crate::config::{get_attachments_dir, get_downloads_dir};
 use crate::error::{GmailError, Result};
 use crate::label_manager::LabelManager;
 use crate::utils::{
-    create_email_message, encode_message_for_gmail, extract_attachments, extract_email_content,
-    format_email_for_display, get_header_value, SendEmailArgs,
+    attachment_save_path, build_search_query, cid_references, compute_reply_all_recipients, create_email_message, reply_subject, draft_summary,
+    embed_inline_images, encode_message_for_gmail, extract_attachments, extract_email_content, extract_inline_parts,
+    find_inline_part, format_draft_summary, format_email_for_display, get_header_value, inline_images_note, SearchFilters,
+    SendEmailArgs,
 };
 use crate::mcp_types::{Content, CallToolResult};
+use base64::{engine::general_purpose, Engine as _};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::HashMap;
@@ -14,13 +18,95 @@ use std::collections::HashMap;
 pub struct ReadEmailArgs {
     #[serde(rename = "messageId")]
     pub message_id: String,
+    /// Replace `cid:` image references in the HTML body with `data:` URIs.
+    #[serde(rename = "embedInlineImages", default)]
+    pub embed_inline_images: bool,
+    /// Load the full body even when the message exceeds `MAX_FULL_MESSAGE_BYTES`.
+    #[serde(rename = "allowLarge", default)]
+    pub allow_large: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListDraftsArgs {
+    #[serde(rename = "maxResults")]
+    pub max_results: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetDraftArgs {
+    #[serde(rename = "draftId")]
+    pub draft_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DownloadAttachmentArgs {
+    #[serde(rename = "messageId")]
+    pub message_id: String,
+    #[serde(rename = "attachmentId")]
+    pub attachment_id: String,
+    /// Name to save under; defaults to one derived from the attachment id.
+    pub filename: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReplyAllArgs {
+    #[serde(rename = "messageId")]
+    pub message_id: String,
+    pub body: String,
+    #[serde(rename = "htmlBody")]
+    pub html_body: Option<String>,
+    pub to: Option<Vec<String>>,
+    pub cc: Option<Vec<String>>,
+    pub bcc: Option<Vec<String>>,
+}
+
+/// Results returned by `search_emails` when `maxResults` is omitted.
+pub const DEFAULT_SEARCH_RESULTS: u32 = 25;
+/// Upper bound on `maxResults`; each result costs a metadata request.
+pub const MAX_SEARCH_RESULTS: u32 = 500;
+
+/// Messages estimated above this size are shown as headers only, since a
+/// `full` read holds every base64 body part in memory at once.
+pub const MAX_FULL_MESSAGE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Renders a `metadata`-format message as headers plus a marker in place of
+/// the body when its `sizeEstimate` exceeds `limit`; `None` means the full
+/// message can be loaded.
+pub fn oversized_message_preview(summary: &GmailMessage, limit: u64) -> Option<String> {
+    let size = summary.size_estimate.filter(|size| *size > limit)?;
+    let marker = format!(
+        "[Body not loaded: this message is about {:.1} MB, above the {} MB limit. Call read_email again with allowLarge set to true to load it anyway.]",
+        size as f64 / (1024.0 * 1024.0),
+        limit / (1024 * 1024)
+    );
+    let content = crate::utils::EmailContent {
+        text: marker,
+        html: String::new(),
+    };
+
+    Some(format_email_for_display(summary, &content, &[]))
+}
+
+/// Resolves the requested `maxResults` to the value sent to Gmail, and
+/// whether the request had to be clamped into `1..=MAX_SEARCH_RESULTS`.
+pub fn clamp_max_results(requested: Option<u32>) -> (u32, bool) {
+    match requested {
+        None => (DEFAULT_SEARCH_RESULTS, false),
+        Some(n) => {
+            let limit = n.clamp(1, MAX_SEARCH_RESULTS);
+            (limit, limit != n)
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SearchEmailsArgs {
-    pub query: String,
+    pub query: Option<String>,
+    pub filters: Option<SearchFilters>,
     #[serde(rename = "maxResults")]
     pub max_results: Option<u32>,
+    #[serde(rename = "includeSpamTrash", default)]
+    pub include_spam_trash: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -60,6 +146,11 @@ pub struct UpdateLabelArgs {
     pub label_list_visibility: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetLabelArgs {
+    pub id: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DeleteLabelArgs {
     pub id: String,
@@ -97,11 +188,11 @@ pub struct BatchDeleteEmailsArgs {
 pub struct GmailTools;
 
 impl GmailTools {
-    pub async fn send_email(client: &mut GmailClient, args: Value) -> Result<CallToolResult> {
+    pub async fn send_email(client: &GmailClient, args: Value) -> Result<CallToolResult> {
         let args: SendEmailArgs = serde_json::from_value(args)
             .map_err(|e| GmailError::JsonError(e))?;
 
-        let message = create_email_message(&args)?;
+        let message = create_email_message(&args, get_attachments_dir().as_deref())?;
         let encoded_message = encode_message_for_gmail(&message);
 
         let response = client.send_message(&encoded_message, args.thread_id).await?;
@@ -112,11 +203,80 @@ impl GmailTools {
         })
     }
 
-    pub async fn draft_email(client: &mut GmailClient, args: Value) -> Result<CallToolResult> {
+    pub async fn reply_all(client: &GmailClient, args: Value) -> Result<CallToolResult> {
+        let args: ReplyAllArgs = serde_json::from_value(args)
+            .map_err(|e| GmailError::JsonError(e))?;
+
+        let original = client.get_message(&args.message_id, Some("metadata")).await?;
+        let empty_headers = vec![];
+        let headers = original
+            .payload
+            .as_ref()
+            .and_then(|p| p.headers.as_ref())
+            .unwrap_or(&empty_headers);
+
+        let header = |name: &str| get_header_value(headers, name).unwrap_or_default();
+        let sender = get_header_value(headers, "Reply-To").unwrap_or_else(|| header("From"));
+        let message_id_header = header("Message-ID");
+
+        let profile = client.get_profile().await?;
+        let (default_to, default_cc) =
+            compute_reply_all_recipients(&sender, &header("To"), &header("Cc"), &profile.email_address);
+
+        let to = args.to.unwrap_or(default_to);
+        let cc = args.cc.unwrap_or(default_cc);
+        if to.is_empty() {
+            return Err(GmailError::McpError(
+                "No recipients left for reply; pass 'to' explicitly".to_string(),
+            ));
+        }
+
+        let (in_reply_to, references) = if message_id_header.is_empty() {
+            (None, None)
+        } else {
+            let references = match header("References") {
+                existing if existing.is_empty() => message_id_header.clone(),
+                existing => format!("{} {}", existing, message_id_header),
+            };
+            (Some(message_id_header), Some(references))
+        };
+
+        let send_args = SendEmailArgs {
+            to,
+            subject: reply_subject(&header("Subject")),
+            body: args.body,
+            html_body: args.html_body.clone(),
+            mime_type: args.html_body.map(|_| "multipart/alternative".to_string()),
+            cc: Some(cc),
+            bcc: args.bcc,
+            thread_id: original.thread_id.clone(),
+            in_reply_to,
+            references,
+            attachments: None,
+        };
+
+        let message = create_email_message(&send_args, None)?;
+        let encoded_message = encode_message_for_gmail(&message);
+        let response = client.send_message(&encoded_message, send_args.thread_id.clone()).await?;
+
+        let mut recipients = send_args.to.clone();
+        recipients.extend(send_args.cc.clone().unwrap_or_default());
+
+        Ok(CallToolResult {
+            content: vec![Content::text(format!(
+                "Reply sent successfully with ID: {}\nRecipients: {}",
+                response.id,
+                recipients.join(", ")
+            ))],
+            is_error: Some(false),
+        })
+    }
+
+    pub async fn draft_email(client: &GmailClient, args: Value) -> Result<CallToolResult> {
         let args: SendEmailArgs = serde_json::from_value(args)
             .map_err(|e| GmailError::JsonError(e))?;
 
-        let message = create_email_message(&args)?;
+        let message = create_email_message(&args, get_attachments_dir().as_deref())?;
         let encoded_message = encode_message_for_gmail(&message);
 
         let response = client.create_draft(&encoded_message, args.thread_id).await?;
@@ -132,13 +292,71 @@ impl GmailTools {
         })
     }
 
-    pub async fn read_email(client: &mut GmailClient, args: Value) -> Result<CallToolResult> {
+    pub async fn list_drafts(client: &GmailClient, args: Value) -> Result<CallToolResult> {
+        let args: ListDraftsArgs = serde_json::from_value(args)
+            .map_err(GmailError::JsonError)?;
+
+        let (max_results, _) = clamp_max_results(args.max_results);
+        let response = client.list_drafts(Some(max_results)).await?;
+        let drafts = response.drafts.unwrap_or_default();
+
+        if drafts.is_empty() {
+            return Ok(CallToolResult {
+                content: vec![Content::text("No drafts found".to_string())],
+                is_error: Some(false),
+            });
+        }
+
+        // The list only carries ids; subjects and recipients need a metadata fetch
+        let mut results = Vec::new();
+        for draft_ref in drafts {
+            let draft = client.get_draft(&draft_ref.id, Some("metadata")).await?;
+            results.push(format_draft_summary(&draft_summary(&draft)));
+        }
+
+        Ok(CallToolResult {
+            content: vec![Content::text(results.join("\n"))],
+            is_error: Some(false),
+        })
+    }
+
+    pub async fn get_draft(client: &GmailClient, args: Value) -> Result<CallToolResult> {
+        let args: GetDraftArgs = serde_json::from_value(args)
+            .map_err(GmailError::JsonError)?;
+
+        let draft = client.get_draft(&args.draft_id, Some("full")).await?;
+        let content = draft.message.payload.as_ref()
+            .map(extract_email_content)
+            .unwrap_or(crate::utils::EmailContent {
+                text: String::new(),
+                html: String::new(),
+            });
+        let body = if !content.text.is_empty() { content.text } else { content.html };
+
+        Ok(CallToolResult {
+            content: vec![Content::text(format!("{}\n{}", format_draft_summary(&draft_summary(&draft)), body))],
+            is_error: Some(false),
+        })
+    }
+
+    pub async fn read_email(client: &GmailClient, args: Value) -> Result<CallToolResult> {
         let args: ReadEmailArgs = serde_json::from_value(args)
             .map_err(|e| GmailError::JsonError(e))?;
 
+        // A metadata read is cheap and tells us whether the full body is safe to load
+        if !args.allow_large {
+            let summary = client.get_message(&args.message_id, Some("metadata")).await?;
+            if let Some(preview) = oversized_message_preview(&summary, MAX_FULL_MESSAGE_BYTES) {
+                return Ok(CallToolResult {
+                    content: vec![Content::text(preview)],
+                    is_error: Some(false),
+                });
+            }
+        }
+
         let message = client.get_message(&args.message_id, Some("full")).await?;
 
-        let content = if let Some(payload) = &message.payload {
+        let mut content = if let Some(payload) = &message.payload {
             extract_email_content(payload)
         } else {
             crate::utils::EmailContent {
@@ -153,7 +371,34 @@ impl GmailTools {
             vec![]
         };
 
-        let formatted_message = format_email_for_display(&message, &content, &attachments);
+        let inline_parts = message.payload.as_ref().map(extract_inline_parts).unwrap_or_default();
+        let mut inline_note = String::new();
+        if !inline_parts.is_empty() && !content.html.is_empty() {
+            if args.embed_inline_images {
+                let mut bodies = Vec::new();
+                for content_id in cid_references(&content.html) {
+                    let Some(part) = find_inline_part(&inline_parts, &content_id) else {
+                        continue;
+                    };
+                    let data = match (&part.data, &part.attachment_id) {
+                        (Some(data), _) => Some(data.clone()),
+                        (None, Some(attachment_id)) => {
+                            client.get_attachment(&args.message_id, attachment_id).await?.data
+                        }
+                        (None, None) => None,
+                    };
+                    if let Some(data) = data {
+                        bodies.push((content_id, data));
+                    }
+                }
+                content.html = embed_inline_images(&content.html, &inline_parts, &bodies);
+            } else {
+                inline_note = inline_images_note(&content.html, &inline_parts);
+            }
+        }
+
+        let mut formatted_message = format_email_for_display(&message, &content, &attachments);
+        formatted_message.push_str(&inline_note);
 
         Ok(CallToolResult {
             content: vec![Content::text(formatted_message)],
@@ -161,17 +406,68 @@ impl GmailTools {
         })
     }
 
-    pub async fn search_emails(client: &mut GmailClient, args: Value) -> Result<CallToolResult> {
+    pub async fn download_attachment(client: &GmailClient, args: Value) -> Result<CallToolResult> {
+        let args: DownloadAttachmentArgs = serde_json::from_value(args).map_err(GmailError::JsonError)?;
+
+        let body = client.get_attachment(&args.message_id, &args.attachment_id).await?;
+        let data = body
+            .data
+            .ok_or_else(|| GmailError::ApiError(format!("Attachment {} has no data", args.attachment_id)))?;
+        // Attachment data sometimes comes padded, unlike message bodies
+        let bytes = general_purpose::URL_SAFE_NO_PAD
+            .decode(data.trim_end_matches('='))
+            .map_err(|e| GmailError::ApiError(format!("Attachment data is not valid base64: {}", e)))?;
+
+        let path = attachment_save_path(&get_downloads_dir()?, args.filename.as_deref(), &args.attachment_id);
+        std::fs::write(&path, &bytes)?;
+
+        Ok(CallToolResult {
+            content: vec![Content::text(format!(
+                "Attachment saved to {} ({} bytes)",
+                path.display(),
+                bytes.len()
+            ))],
+            is_error: Some(false),
+        })
+    }
+
+    pub async fn search_emails(client: &GmailClient, args: Value) -> Result<CallToolResult> {
         let args: SearchEmailsArgs = serde_json::from_value(args)
             .map_err(|e| GmailError::JsonError(e))?;
 
+        let mut query_parts = Vec::new();
+        if let Some(query) = args.query.as_deref().filter(|q| !q.trim().is_empty()) {
+            query_parts.push(query.trim().to_string());
+        }
+        if let Some(filters) = &args.filters {
+            let structured = build_search_query(filters)?;
+            if !structured.is_empty() {
+                query_parts.push(structured);
+            }
+        }
+        if query_parts.is_empty() {
+            return Err(GmailError::InvalidQuery(
+                "Provide either a query string or at least one filter".to_string(),
+            ));
+        }
+        let query = query_parts.join(" ");
+
+        let (max_results, clamped) = clamp_max_results(args.max_results);
         let response = client
-            .search_messages(&args.query, args.max_results)
+            .search_messages(&query, Some(max_results), args.include_spam_trash)
             .await?;
 
         let messages = response.messages.unwrap_or_default();
 
         let mut results = Vec::new();
+        if clamped {
+            results.push(format!(
+                "Note: maxResults {} is outside 1-{}; returning at most {} results.\n",
+                args.max_results.unwrap_or_default(),
+                MAX_SEARCH_RESULTS,
+                max_results
+            ));
+        }
         for msg_ref in messages {
             let detail = client
                 .get_message(&msg_ref.id, Some("metadata"))
@@ -200,7 +496,7 @@ impl GmailTools {
         })
     }
 
-    pub async fn modify_email(client: &mut GmailClient, args: Value) -> Result<CallToolResult> {
+    pub async fn modify_email(client: &GmailClient, args: Value) -> Result<CallToolResult> {
         let args: ModifyEmailArgs = serde_json::from_value(args)
             .map_err(|e| GmailError::JsonError(e))?;
 
@@ -217,7 +513,7 @@ impl GmailTools {
         })
     }
 
-    pub async fn delete_email(client: &mut GmailClient, args: Value) -> Result<CallToolResult> {
+    pub async fn delete_email(client: &GmailClient, args: Value) -> Result<CallToolResult> {
         let args: DeleteEmailArgs = serde_json::from_value(args)
             .map_err(|e| GmailError::JsonError(e))?;
 
@@ -229,7 +525,7 @@ impl GmailTools {
         })
     }
 
-    pub async fn list_email_labels(client: &mut GmailClient, _args: Value) -> Result<CallToolResult> {
+    pub async fn list_email_labels(client: &GmailClient, _args: Value) -> Result<CallToolResult> {
         let label_results = LabelManager::list_labels(client).await?;
 
         let text = format!(
@@ -257,7 +553,65 @@ impl GmailTools {
         })
     }
 
-    pub async fn create_label(client: &mut GmailClient, args: Value) -> Result<CallToolResult> {
+    pub async fn get_auto_forwarding(client: &GmailClient, _args: Value) -> Result<CallToolResult> {
+        let forwarding = client.get_auto_forwarding().await?;
+
+        let text = if forwarding.enabled {
+            format!(
+                "Auto-forwarding is enabled\nForwarding to: {}\nDisposition: {}",
+                forwarding.email_address.as_deref().unwrap_or("unknown"),
+                forwarding.disposition.as_deref().unwrap_or("unknown")
+            )
+        } else {
+            "Auto-forwarding is disabled".to_string()
+        };
+
+        Ok(CallToolResult {
+            content: vec![Content::text(text)],
+            is_error: Some(false),
+        })
+    }
+
+    pub async fn list_delegates(client: &GmailClient, _args: Value) -> Result<CallToolResult> {
+        let delegates = client.list_delegates().await?.delegates.unwrap_or_default();
+
+        let text = if delegates.is_empty() {
+            "No delegates configured".to_string()
+        } else {
+            format!(
+                "Found {} delegates:\n\n{}",
+                delegates.len(),
+                delegates
+                    .iter()
+                    .map(|d| format!(
+                        "Email: {}\nStatus: {}\n",
+                        d.delegate_email,
+                        d.verification_status.as_deref().unwrap_or("unknown")
+                    ))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            )
+        };
+
+        Ok(CallToolResult {
+            content: vec![Content::text(text)],
+            is_error: Some(false),
+        })
+    }
+
+    pub async fn get_label(client: &GmailClient, args: Value) -> Result<CallToolResult> {
+        let args: GetLabelArgs = serde_json::from_value(args)
+            .map_err(|e| GmailError::JsonError(e))?;
+
+        let label = LabelManager::get_label(client, &args.id).await?;
+
+        Ok(CallToolResult {
+            content: vec![Content::text(format_label_details(&label))],
+            is_error: Some(false),
+        })
+    }
+
+    pub async fn create_label(client: &GmailClient, args: Value) -> Result<CallToolResult> {
         let args: CreateLabelArgs = serde_json::from_value(args)
             .map_err(|e| GmailError::JsonError(e))?;
 
@@ -280,7 +634,7 @@ impl GmailTools {
         })
     }
 
-    pub async fn update_label(client: &mut GmailClient, args: Value) -> Result<CallToolResult> {
+    pub async fn update_label(client: &GmailClient, args: Value) -> Result<CallToolResult> {
         let args: UpdateLabelArgs = serde_json::from_value(args)
             .map_err(|e| GmailError::JsonError(e))?;
 
@@ -309,7 +663,7 @@ impl GmailTools {
         })
     }
 
-    pub async fn delete_label(client: &mut GmailClient, args: Value) -> Result<CallToolResult> {
+    pub async fn delete_label(client: &GmailClient, args: Value) -> Result<CallToolResult> {
         let args: DeleteLabelArgs = serde_json::from_value(args)
             .map_err(|e| GmailError::JsonError(e))?;
 
@@ -321,7 +675,7 @@ impl GmailTools {
         })
     }
 
-    pub async fn get_or_create_label(client: &mut GmailClient, args: Value) -> Result<CallToolResult> {
+    pub async fn get_or_create_label(client: &GmailClient, args: Value) -> Result<CallToolResult> {
         let args: GetOrCreateLabelArgs = serde_json::from_value(args)
             .map_err(|e| GmailError::JsonError(e))?;
 
@@ -351,7 +705,7 @@ impl GmailTools {
         })
     }
 
-    pub async fn batch_modify_emails(client: &mut GmailClient, args: Value) -> Result<CallToolResult> {
+    pub async fn batch_modify_emails(client: &GmailClient, args: Value) -> Result<CallToolResult> {
         let args: BatchModifyEmailsArgs = serde_json::from_value(args)
             .map_err(|e| GmailError::JsonError(e))?;
 
@@ -367,21 +721,19 @@ impl GmailTools {
 
         if failure_count > 0 {
             result_text.push_str(&format!("Failed to process: {} messages\n\nFailed message IDs:\n", failure_count));
-            for (i, result) in results.iter().enumerate() {
-                if let Err(e) = result {
-                    let message_id = args.message_ids.get(i).map(|id| &id[..16.min(id.len())]).unwrap_or("unknown");
-                    result_text.push_str(&format!("- {}... ({})\n", message_id, e));
-                }
-            }
+            result_text.push_str(&format_failed_ids(&args.message_ids, &results));
         }
 
         Ok(CallToolResult {
-            content: vec![Content::text(result_text)],
+            content: vec![
+                Content::text(result_text),
+                Content::text(serde_json::to_string_pretty(&batch_results_json(&args.message_ids, &results))?),
+            ],
             is_error: Some(false),
         })
     }
 
-    pub async fn batch_delete_emails(client: &mut GmailClient, args: Value) -> Result<CallToolResult> {
+    pub async fn batch_delete_emails(client: &GmailClient, args: Value) -> Result<CallToolResult> {
         let args: BatchDeleteEmailsArgs = serde_json::from_value(args)
             .map_err(|e| GmailError::JsonError(e))?;
 
@@ -395,17 +747,197 @@ impl GmailTools {
 
         if failure_count > 0 {
             result_text.push_str(&format!("Failed to delete: {} messages\n\nFailed message IDs:\n", failure_count));
-            for (i, result) in results.iter().enumerate() {
-                if let Err(e) = result {
-                    let message_id = args.message_ids.get(i).map(|id| &id[..16.min(id.len())]).unwrap_or("unknown");
-                    result_text.push_str(&format!("- {}... ({})\n", message_id, e));
-                }
-            }
+            result_text.push_str(&format_failed_ids(&args.message_ids, &results));
         }
 
         Ok(CallToolResult {
-            content: vec![Content::text(result_text)],
+            content: vec![
+                Content::text(result_text),
+                Content::text(serde_json::to_string_pretty(&batch_results_json(&args.message_ids, &results))?),
+            ],
             is_error: Some(false),
         })
     }
-}
\ No newline at end of file
+}
+
+/// Per-id outcome of a batch operation: `{"results": [{"id", "ok", "error"?}]}`.
+fn batch_results_json<T>(message_ids: &[String], results: &[std::result::Result<T, GmailError>]) -> Value {
+    let entries: Vec<Value> = message_ids
+        .iter()
+        .zip(results)
+        .map(|(id, result)| match result {
+            Ok(_) => json!({"id": id, "ok": true}),
+            Err(e) => json!({"id": id, "ok": false, "error": e.to_string()}),
+        })
+        .collect();
+
+    json!({ "results": entries })
+}
+
+fn format_failed_ids<T>(message_ids: &[String], results: &[std::result::Result<T, GmailError>]) -> String {
+    message_ids
+        .iter()
+        .zip(results)
+        .filter_map(|(id, result)| result.as_ref().err().map(|e| format!("- {} ({})\n", id, e)))
+        .collect()
+}
+
+fn format_label_details(label: &GmailLabel) -> String {
+    let count = |value: Option<u32>| value.map(|v| v.to_string()).unwrap_or_else(|| "unknown".to_string());
+
+    let mut text = format!(
+        "ID: {}\nName: {}\nType: {}\nMessages: {} total, {} unread\nMessage list visibility: {}\nLabel list visibility: {}",
+        label.id,
+        label.name,
+        label.label_type.as_deref().unwrap_or("unknown"),
+        count(label.messages_total),
+        count(label.messages_unread),
+        label.message_list_visibility.as_deref().unwrap_or("default"),
+        label.label_list_visibility.as_deref().unwrap_or("default"),
+    );
+
+    if let Some(color) = &label.color {
+        text.push_str(&format!(
+            "\nColor: text {}, background {}",
+            color.text_color.as_deref().unwrap_or("none"),
+            color.background_color.as_deref().unwrap_or("none")
+        ));
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_single_label_response() {
+        let response = json!({
+            "id": "Label_42",
+            "name": "Receipts",
+            "type": "user",
+            "messageListVisibility": "show",
+            "labelListVisibility": "labelShowIfUnread",
+            "messagesTotal": 120,
+            "messagesUnread": 3,
+            "threadsTotal": 118,
+            "threadsUnread": 3,
+            "color": {"textColor": "#ffffff", "backgroundColor": "#16a765"}
+        });
+
+        let label: GmailLabel = serde_json::from_value(response).unwrap();
+        assert_eq!(label.id, "Label_42");
+        assert_eq!(label.messages_total, Some(120));
+        assert_eq!(label.messages_unread, Some(3));
+
+        let text = format_label_details(&label);
+        assert!(text.contains("Messages: 120 total, 3 unread"));
+        assert!(text.contains("Label list visibility: labelShowIfUnread"));
+        assert!(text.contains("background #16a765"));
+    }
+
+    #[test]
+    fn test_oversized_message_is_previewed_from_metadata() {
+        let metadata = |size: Option<u64>| -> GmailMessage {
+            serde_json::from_value(json!({
+                "id": "18c1f2a3b4d5e6f7",
+                "threadId": "18c1f2a3b4d5e6f7",
+                "sizeEstimate": size,
+                "payload": {
+                    "mimeType": "multipart/mixed",
+                    "headers": [
+                        {"name": "Subject", "value": "Scans from the office"},
+                        {"name": "From", "value": "scanner@example.com"}
+                    ]
+                }
+            }))
+            .unwrap()
+        };
+
+        let preview = oversized_message_preview(&metadata(Some(40 * 1024 * 1024)), MAX_FULL_MESSAGE_BYTES).unwrap();
+        assert!(preview.contains("Scans from the office"));
+        assert!(preview.contains("scanner@example.com"));
+        assert!(preview.contains("[Body not loaded: this message is about 40.0 MB, above the 10 MB limit."));
+        assert!(preview.contains("allowLarge"));
+
+        assert!(oversized_message_preview(&metadata(Some(MAX_FULL_MESSAGE_BYTES)), MAX_FULL_MESSAGE_BYTES).is_none());
+        assert!(oversized_message_preview(&metadata(None), MAX_FULL_MESSAGE_BYTES).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_read_email_skips_full_body_of_oversized_message() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let message_path = "/users/me/messages/18c1f2a3b4d5e6f7";
+        Mock::given(method("GET"))
+            .and(path(message_path))
+            .and(query_param("format", "metadata"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "18c1f2a3b4d5e6f7",
+                "threadId": "18c1f2a3b4d5e6f7",
+                "sizeEstimate": 40 * 1024 * 1024,
+                "payload": {"mimeType": "multipart/mixed", "headers": [{"name": "Subject", "value": "Scans"}]}
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(message_path))
+            .and(query_param("format", "full"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "18c1f2a3b4d5e6f7",
+                "threadId": "18c1f2a3b4d5e6f7",
+                "payload": {"mimeType": "text/plain", "headers": [], "body": {"data": "Qm9keQ"}}
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let client = GmailClient::for_tests(&mock_server.uri());
+        let text = |result: &CallToolResult| match &result.content[0] {
+            Content::Text(content) => content.text.clone(),
+        };
+
+        // Oversized: only the metadata is fetched
+        let preview = GmailTools::read_email(&client, json!({"messageId": "18c1f2a3b4d5e6f7"})).await.unwrap();
+        assert!(text(&preview).contains("[Body not loaded"));
+
+        // allowLarge: straight to the full body, without the metadata probe
+        let full = GmailTools::read_email(&client, json!({"messageId": "18c1f2a3b4d5e6f7", "allowLarge": true}))
+            .await
+            .unwrap();
+        assert!(text(&full).contains("Body"));
+        assert!(!text(&full).contains("[Body not loaded"));
+    }
+
+    #[test]
+    fn test_clamp_max_results() {
+        assert_eq!(clamp_max_results(None), (DEFAULT_SEARCH_RESULTS, false));
+        assert_eq!(clamp_max_results(Some(10)), (10, false));
+        assert_eq!(clamp_max_results(Some(MAX_SEARCH_RESULTS)), (MAX_SEARCH_RESULTS, false));
+        assert_eq!(clamp_max_results(Some(100_000)), (MAX_SEARCH_RESULTS, true));
+        assert_eq!(clamp_max_results(Some(0)), (1, true));
+    }
+
+    #[test]
+    fn test_batch_results_json_keeps_full_failed_ids() {
+        let message_ids = vec![
+            "18c1f2a3b4d5e6f7a8b9c0d1".to_string(),
+            "18c1f2a3b4d5e6f7a8b9c0d2e3f4".to_string(),
+        ];
+        let results: Vec<std::result::Result<(), GmailError>> = vec![
+            Ok(()),
+            Err(GmailError::MessageNotFound("18c1f2a3b4d5e6f7a8b9c0d2e3f4".to_string())),
+        ];
+
+        let structured = batch_results_json(&message_ids, &results);
+        assert_eq!(structured["results"][0], json!({"id": "18c1f2a3b4d5e6f7a8b9c0d1", "ok": true}));
+        assert_eq!(structured["results"][1]["id"], "18c1f2a3b4d5e6f7a8b9c0d2e3f4");
+        assert_eq!(structured["results"][1]["ok"], false);
+        assert!(structured["results"][1]["error"].as_str().unwrap().contains("Message not found"));
+
+        assert!(format_failed_ids(&message_ids, &results).contains("- 18c1f2a3b4d5e6f7a8b9c0d2e3f4 ("));
+    }
+}
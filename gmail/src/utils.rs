@@ -1,8 +1,11 @@
 use crate::client::{MessageHeader, MessagePayload};
+use crate::config::ATTACHMENTS_DIR_ENV;
 use crate::error::{GmailError, Result};
 use base64::{engine::general_purpose, Engine as _};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::warn;
 use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -13,7 +16,8 @@ pub struct EmailContent {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EmailAttachment {
-    pub id: String,
+    /// Gmail attachment id; `None` when the part's data came inline with the message.
+    pub id: Option<String>,
     pub filename: String,
     pub mime_type: String,
     pub size: u64,
@@ -24,12 +28,32 @@ pub struct SendEmailArgs {
     pub to: Vec<String>,
     pub subject: String,
     pub body: String,
+    #[serde(rename = "htmlBody")]
     pub html_body: Option<String>,
+    #[serde(rename = "mimeType")]
     pub mime_type: Option<String>,
     pub cc: Option<Vec<String>>,
     pub bcc: Option<Vec<String>>,
+    #[serde(rename = "threadId")]
     pub thread_id: Option<String>,
+    #[serde(rename = "inReplyTo")]
     pub in_reply_to: Option<String>,
+    pub references: Option<String>,
+    /// Files sent as attachments, relative to `GMAIL_ATTACHMENTS_DIR`.
+    pub attachments: Option<Vec<String>>,
+}
+
+/// Structured search filters that are composed into a Gmail `q` string.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SearchFilters {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub subject: Option<String>,
+    pub after: Option<String>,
+    pub before: Option<String>,
+    #[serde(rename = "hasAttachment")]
+    pub has_attachment: Option<bool>,
+    pub label: Option<String>,
 }
 
 pub fn validate_email(email: &str) -> bool {
@@ -47,20 +71,91 @@ pub fn encode_email_header(text: &str) -> String {
     }
 }
 
-pub fn create_email_message(args: &SendEmailArgs) -> Result<String> {
-    let encoded_subject = encode_email_header(&args.subject);
-    
-    // Determine content type based on available content and explicit mime_type
-    let mut mime_type = args.mime_type.as_deref().unwrap_or("text/plain");
-    
-    // If html_body is provided and mime_type isn't explicitly set to text/plain,
-    // use multipart/alternative to include both versions
-    if args.html_body.is_some() && mime_type != "text/plain" {
-        mime_type = "multipart/alternative";
+/// Works out the top-level MIME type from what the message actually contains:
+/// `multipart/mixed` when files are attached, otherwise the body type.
+pub fn infer_mime_type(args: &SendEmailArgs) -> &'static str {
+    if args.attachments.as_ref().is_some_and(|paths| !paths.is_empty()) {
+        "multipart/mixed"
+    } else {
+        body_mime_type(args)
     }
+}
 
-    // Generate a random boundary string for multipart messages
-    let boundary = format!("----=_NextPart_{}", Uuid::new_v4().simple());
+/// `multipart/alternative` when both a text and an HTML body are present,
+/// otherwise whichever single body there is. A lone `body` is only sent as
+/// HTML when the caller asked for `text/html`.
+fn body_mime_type(args: &SendEmailArgs) -> &'static str {
+    match (&args.html_body, args.body.is_empty()) {
+        (Some(_), false) => "multipart/alternative",
+        (Some(_), true) => "text/html",
+        (None, _) if args.mime_type.as_deref() == Some("text/html") => "text/html",
+        (None, _) => "text/plain",
+    }
+}
+
+/// Best-effort content type for an attachment, from its file extension.
+fn attachment_mime_type(filename: &str) -> &'static str {
+    let extension = Path::new(filename)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    match extension.as_str() {
+        "txt" => "text/plain",
+        "htm" | "html" => "text/html",
+        "csv" => "text/csv",
+        "json" => "application/json",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Resolves an attachment name inside `attachments_dir`, refusing anything that
+/// lands outside it (absolute paths, `..`, symlinks pointing elsewhere).
+pub fn resolve_attachment_path(attachments_dir: Option<&Path>, name: &str) -> Result<PathBuf> {
+    let dir = attachments_dir.ok_or_else(|| {
+        GmailError::Forbidden(format!(
+            "Attachments are disabled; set {} to the directory they may be read from",
+            ATTACHMENTS_DIR_ENV
+        ))
+    })?;
+    let root = dir.canonicalize().map_err(|e| {
+        std::io::Error::new(e.kind(), format!("Attachments directory {} is not usable: {}", dir.display(), e))
+    })?;
+    let path = root.join(name).canonicalize().map_err(|e| {
+        std::io::Error::new(e.kind(), format!("Failed to read attachment {}: {}", name, e))
+    })?;
+
+    if !path.starts_with(&root) {
+        return Err(GmailError::Forbidden(format!(
+            "Attachment {} is outside {}",
+            name, ATTACHMENTS_DIR_ENV
+        )));
+    }
+    Ok(path)
+}
+
+/// Builds the raw RFC 822 message. Attachment names are resolved inside
+/// `attachments_dir`; see [`resolve_attachment_path`].
+pub fn create_email_message(args: &SendEmailArgs, attachments_dir: Option<&Path>) -> Result<String> {
+    let encoded_subject = encode_email_header(&args.subject);
+
+    // The structure follows the content; an explicit mimeType that disagrees
+    // with it (e.g. text/plain alongside htmlBody) is overridden.
+    let mime_type = infer_mime_type(args);
+    let body_type = body_mime_type(args);
+    if let Some(requested) = args.mime_type.as_deref() {
+        if requested != mime_type && requested != body_type {
+            warn!(
+                "Ignoring mimeType {} which does not match the message content; sending as {}",
+                requested, mime_type
+            );
+        }
+    }
 
     // Validate email addresses
     for email in &args.to {
@@ -85,6 +180,21 @@ pub fn create_email_message(args: &SendEmailArgs) -> Result<String> {
         }
     }
 
+    // Read attachments before building so a bad path fails early
+    let mut attachments = Vec::new();
+    for name in args.attachments.iter().flatten() {
+        let path = resolve_attachment_path(attachments_dir, name)?;
+        let data = std::fs::read(&path).map_err(|e| {
+            std::io::Error::new(e.kind(), format!("Failed to read attachment {}: {}", name, e))
+        })?;
+        let filename = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("attachment")
+            .to_string();
+        attachments.push((filename, data));
+    }
+
     // Common email headers
     let mut email_parts = vec![
         "From: me".to_string(),
@@ -108,14 +218,52 @@ pub fn create_email_message(args: &SendEmailArgs) -> Result<String> {
     // Add thread-related headers if specified
     if let Some(in_reply_to) = &args.in_reply_to {
         email_parts.push(format!("In-Reply-To: {}", in_reply_to));
-        email_parts.push(format!("References: {}", in_reply_to));
+        let references = args.references.as_deref().unwrap_or(in_reply_to);
+        email_parts.push(format!("References: {}", references));
     }
 
     email_parts.push("MIME-Version: 1.0".to_string());
 
-    // Construct the email based on the content type
-    match mime_type {
+    if attachments.is_empty() {
+        push_body_part(&mut email_parts, body_type, args);
+    } else {
+        let boundary = format!("----=_MixedPart_{}", Uuid::new_v4().simple());
+        email_parts.push(format!("Content-Type: multipart/mixed; boundary=\"{}\"", boundary));
+        email_parts.push("".to_string());
+
+        email_parts.push(format!("--{}", boundary));
+        push_body_part(&mut email_parts, body_type, args);
+        email_parts.push("".to_string());
+
+        for (filename, data) in &attachments {
+            let content_type = attachment_mime_type(filename);
+            let filename = encode_email_header(filename);
+            email_parts.push(format!("--{}", boundary));
+            email_parts.push(format!("Content-Type: {}; name=\"{}\"", content_type, filename));
+            email_parts.push(format!("Content-Disposition: attachment; filename=\"{}\"", filename));
+            email_parts.push("Content-Transfer-Encoding: base64".to_string());
+            email_parts.push("".to_string());
+            let encoded = general_purpose::STANDARD.encode(data);
+            // RFC 2045 caps encoded lines at 76 characters
+            for line in encoded.as_bytes().chunks(76) {
+                email_parts.push(String::from_utf8_lossy(line).into_owned());
+            }
+            email_parts.push("".to_string());
+        }
+
+        email_parts.push(format!("--{}--", boundary));
+    }
+
+    Ok(email_parts.join("\r\n"))
+}
+
+/// Appends the Content-Type headers and body of the message text itself.
+fn push_body_part(email_parts: &mut Vec<String>, body_type: &str, args: &SendEmailArgs) {
+    match body_type {
         "multipart/alternative" => {
+            // Generate a random boundary string for multipart messages
+            let boundary = format!("----=_NextPart_{}", Uuid::new_v4().simple());
+
             // Multipart email with both plain text and HTML
             email_parts.push(format!("Content-Type: multipart/alternative; boundary=\"{}\"", boundary));
             email_parts.push("".to_string());
@@ -154,8 +302,6 @@ pub fn create_email_message(args: &SendEmailArgs) -> Result<String> {
             email_parts.push(args.body.clone());
         }
     }
-
-    Ok(email_parts.join("\r\n"))
 }
 
 pub fn encode_message_for_gmail(message: &str) -> String {
@@ -166,8 +312,10 @@ pub fn extract_email_content(message_part: &MessagePayload) -> EmailContent {
     let mut text_content = String::new();
     let mut html_content = String::new();
 
-    // If the part has a body with data, process it based on MIME type
-    if let Some(body) = &message_part.body {
+    // If the part has a body with data, process it based on MIME type. Named
+    // parts are attachments (e.g. an attached .txt), not part of the message body.
+    // Gmail has already undone any Content-Transfer-Encoding in `full` payloads.
+    if let Some(body) = message_part.body.as_ref().filter(|_| !is_named_part(message_part)) {
         if let Some(data) = &body.data {
             if let Ok(decoded) = general_purpose::URL_SAFE_NO_PAD.decode(data) {
                 if let Ok(content) = String::from_utf8(decoded) {
@@ -203,15 +351,88 @@ pub fn extract_email_content(message_part: &MessagePayload) -> EmailContent {
     }
 }
 
+/// Decodes quoted-printable text (RFC 2045): `=XX` escapes become bytes and
+/// `=` at the end of a line is a soft line break. Malformed escapes are kept as-is.
+/// Only needed for parts of a `format=raw` message; `full` payloads arrive decoded.
+pub fn decode_quoted_printable(input: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(input.len());
+    let mut i = 0;
+
+    while i < input.len() {
+        if input[i] != b'=' {
+            output.push(input[i]);
+            i += 1;
+            continue;
+        }
+
+        let rest = &input[i + 1..];
+        if rest.starts_with(b"\r\n") {
+            i += 3;
+        } else if rest.starts_with(b"\n") {
+            i += 2;
+        } else if let Some(byte) = decode_hex_pair(rest) {
+            output.push(byte);
+            i += 3;
+        } else {
+            output.push(b'=');
+            i += 1;
+        }
+    }
+
+    output
+}
+
+/// Where to save a downloaded attachment inside `dir`. Only the final path
+/// component of `filename` is used, and an existing file is never overwritten:
+/// `report.pdf` becomes `report (1).pdf`, `report (2).pdf`, ...
+pub fn attachment_save_path(dir: &Path, filename: Option<&str>, attachment_id: &str) -> PathBuf {
+    let name = filename
+        .and_then(|name| Path::new(name).file_name())
+        .and_then(|name| name.to_str())
+        .filter(|name| !name.is_empty() && *name != "..")
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("attachment-{}", attachment_id.chars().take(16).collect::<String>()));
+
+    let candidate = dir.join(&name);
+    if !candidate.exists() {
+        return candidate;
+    }
+
+    let path = Path::new(&name);
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or(&name);
+    let extension = path.extension().and_then(|s| s.to_str());
+    (1..)
+        .map(|n| match extension {
+            Some(ext) => dir.join(format!("{} ({}).{}", stem, n, ext)),
+            None => dir.join(format!("{} ({})", stem, n)),
+        })
+        .find(|candidate| !candidate.exists())
+        .expect("unbounded counter always finds a free name")
+}
+
+fn decode_hex_pair(bytes: &[u8]) -> Option<u8> {
+    let high = (*bytes.first()? as char).to_digit(16)?;
+    let low = (*bytes.get(1)? as char).to_digit(16)?;
+    Some((high * 16 + low) as u8)
+}
+
 pub fn extract_attachments(message_part: &MessagePayload) -> Vec<EmailAttachment> {
     let mut attachments = Vec::new();
 
     fn process_attachment_parts(part: &MessagePayload, attachments: &mut Vec<EmailAttachment>) {
         if let Some(body) = &part.body {
-            if let Some(attachment_id) = &body.attachment_id {
-                let filename = part.filename.as_deref().unwrap_or(&format!("attachment-{}", attachment_id)).to_string();
+            // Small named parts (e.g. an attached .txt) can arrive with their data
+            // inline instead of an attachment id; they are still attachments.
+            let inline_named = body.data.is_some() && is_named_part(part);
+            if body.attachment_id.is_some() || inline_named {
+                // Inline images in multipart/related usually come with an empty filename
+                let filename = part
+                    .filename
+                    .clone()
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or_else(|| format!("attachment-{}", body.attachment_id.as_deref().unwrap_or_default()));
                 attachments.push(EmailAttachment {
-                    id: attachment_id.clone(),
+                    id: body.attachment_id.clone(),
                     filename,
                     mime_type: part.mime_type.as_deref().unwrap_or("application/octet-stream").to_string(),
                     size: body.size.unwrap_or(0),
@@ -230,6 +451,124 @@ pub fn extract_attachments(message_part: &MessagePayload) -> Vec<EmailAttachment
     attachments
 }
 
+/// A part carrying a `Content-ID`, which HTML bodies reference as `cid:<id>`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InlinePart {
+    pub content_id: String,
+    pub mime_type: String,
+    pub filename: Option<String>,
+    pub attachment_id: Option<String>,
+    /// Base64url body, present when Gmail inlined it in the message.
+    pub data: Option<String>,
+    pub size: u64,
+}
+
+/// Collects every part with a `Content-ID` header, with the angle brackets stripped.
+pub fn extract_inline_parts(message_part: &MessagePayload) -> Vec<InlinePart> {
+    let mut inline_parts = Vec::new();
+
+    fn collect(part: &MessagePayload, inline_parts: &mut Vec<InlinePart>) {
+        let content_id = part
+            .headers
+            .as_deref()
+            .and_then(|headers| get_header_value(headers, "content-id"))
+            .map(|id| id.trim().trim_start_matches('<').trim_end_matches('>').to_string())
+            .filter(|id| !id.is_empty());
+
+        if let Some(content_id) = content_id {
+            let body = part.body.as_ref();
+            inline_parts.push(InlinePart {
+                content_id,
+                mime_type: part.mime_type.as_deref().unwrap_or("application/octet-stream").to_string(),
+                filename: part.filename.clone().filter(|name| !name.is_empty()),
+                attachment_id: body.and_then(|b| b.attachment_id.clone()),
+                data: body.and_then(|b| b.data.clone()),
+                size: body.and_then(|b| b.size).unwrap_or(0),
+            });
+        }
+
+        for subpart in part.parts.iter().flatten() {
+            collect(subpart, inline_parts);
+        }
+    }
+
+    collect(message_part, &mut inline_parts);
+    inline_parts
+}
+
+fn cid_regex() -> Regex {
+    Regex::new(r#"(?i)cid:([^"'\s)>]+)"#).unwrap()
+}
+
+/// Content-IDs referenced from the HTML as `cid:` URLs, in order of first use.
+/// References are URL-decoded as RFC 2392 requires.
+pub fn cid_references(html: &str) -> Vec<String> {
+    let mut references: Vec<String> = Vec::new();
+    for capture in cid_regex().captures_iter(html) {
+        let reference = decode_cid(&capture[1]);
+        if !references.contains(&reference) {
+            references.push(reference);
+        }
+    }
+    references
+}
+
+fn decode_cid(raw: &str) -> String {
+    urlencoding::decode(raw).map(|s| s.into_owned()).unwrap_or_else(|_| raw.to_string())
+}
+
+/// Finds the related part a `cid:` reference points at by its Content-ID.
+pub fn find_inline_part<'a>(parts: &'a [InlinePart], content_id: &str) -> Option<&'a InlinePart> {
+    parts.iter().find(|part| part.content_id == content_id)
+}
+
+/// Rewrites `cid:` URLs into `data:` URIs using the given base64url bodies,
+/// keyed by Content-ID. References without a body are left untouched.
+pub fn embed_inline_images(html: &str, parts: &[InlinePart], bodies: &[(String, String)]) -> String {
+    cid_regex()
+        .replace_all(html, |capture: &regex::Captures| {
+            let content_id = decode_cid(&capture[1]);
+            let part = find_inline_part(parts, &content_id);
+            let body = bodies.iter().find(|(id, _)| *id == content_id).map(|(_, data)| data);
+            match (part, body.and_then(|data| general_purpose::URL_SAFE_NO_PAD.decode(data.trim_end_matches('=')).ok())) {
+                (Some(part), Some(bytes)) => format!(
+                    "data:{};base64,{}",
+                    part.mime_type,
+                    general_purpose::STANDARD.encode(bytes)
+                ),
+                _ => capture[0].to_string(),
+            }
+        })
+        .into_owned()
+}
+
+/// Lists each `cid:` reference in the HTML with the part it resolves to.
+pub fn inline_images_note(html: &str, parts: &[InlinePart]) -> String {
+    let lines: Vec<String> = cid_references(html)
+        .into_iter()
+        .map(|content_id| match find_inline_part(parts, &content_id) {
+            Some(part) => format!(
+                "- cid:{} -> {} ({}, {} KB)",
+                content_id,
+                part.filename.as_deref().unwrap_or("unnamed"),
+                part.mime_type,
+                part.size / 1024
+            ),
+            None => format!("- cid:{} -> not found in message", content_id),
+        })
+        .collect();
+
+    if lines.is_empty() {
+        String::new()
+    } else {
+        format!("\n\nInline images ({}):\n{}", lines.len(), lines.join("\n"))
+    }
+}
+
+fn is_named_part(part: &MessagePayload) -> bool {
+    part.filename.as_deref().is_some_and(|name| !name.is_empty())
+}
+
 pub fn get_header_value(headers: &[MessageHeader], name: &str) -> Option<String> {
     headers
         .iter()
@@ -274,7 +613,10 @@ pub fn format_email_for_display(
             attachments.len(),
             attachments
                 .iter()
-                .map(|a| format!("- {} ({}, {} KB)", a.filename, a.mime_type, a.size / 1024))
+                .map(|a| match &a.id {
+                    Some(id) => format!("- {} ({}, {} KB, ID: {})", a.filename, a.mime_type, a.size / 1024, id),
+                    None => format!("- {} ({}, {} KB)", a.filename, a.mime_type, a.size / 1024),
+                })
                 .collect::<Vec<_>>()
                 .join("\n")
         )
@@ -286,4 +628,563 @@ pub fn format_email_for_display(
         "Thread ID: {}\nSubject: {}\nFrom: {}\nTo: {}\nDate: {}\n\n{}{}{}",
         thread_id, subject, from, to, date, content_type_note, body, attachment_info
     )
-}
\ No newline at end of file
+}
+
+/// Subject and recipients of a draft's message, for reviewing it before sending.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DraftSummary {
+    pub id: String,
+    pub message_id: String,
+    pub subject: String,
+    pub to: String,
+    pub cc: String,
+    pub bcc: String,
+    pub date: String,
+}
+
+/// Reads the summary from a draft fetched with `metadata` or `full` format.
+pub fn draft_summary(draft: &crate::client::GmailDraft) -> DraftSummary {
+    let empty_headers = vec![];
+    let headers = draft.message.payload.as_ref()
+        .and_then(|p| p.headers.as_ref())
+        .unwrap_or(&empty_headers);
+
+    DraftSummary {
+        id: draft.id.clone(),
+        message_id: draft.message.id.clone(),
+        subject: get_header_value(headers, "subject").unwrap_or_default(),
+        to: get_header_value(headers, "to").unwrap_or_default(),
+        cc: get_header_value(headers, "cc").unwrap_or_default(),
+        bcc: get_header_value(headers, "bcc").unwrap_or_default(),
+        date: get_header_value(headers, "date").unwrap_or_default(),
+    }
+}
+
+pub fn format_draft_summary(summary: &DraftSummary) -> String {
+    let mut formatted = format!(
+        "Draft ID: {}\nMessage ID: {}\nSubject: {}\nTo: {}\n",
+        summary.id, summary.message_id, summary.subject, summary.to
+    );
+    if !summary.cc.is_empty() {
+        formatted.push_str(&format!("Cc: {}\n", summary.cc));
+    }
+    if !summary.bcc.is_empty() {
+        formatted.push_str(&format!("Bcc: {}\n", summary.bcc));
+    }
+    formatted.push_str(&format!("Date: {}\n", summary.date));
+    formatted
+}
+
+/// Extracts bare addresses from an address header such as `"Doe, Jane" <jane@example.com>, bob@example.com`.
+pub fn parse_address_list(header: &str) -> Vec<String> {
+    let mut addresses = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in header.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                current.push(c);
+            }
+            ',' if !in_quotes => {
+                addresses.extend(extract_address(&current));
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+    }
+    addresses.extend(extract_address(&current));
+
+    addresses
+}
+
+fn extract_address(entry: &str) -> Option<String> {
+    let entry = entry.trim();
+    let address = match (entry.rfind('<'), entry.rfind('>')) {
+        (Some(start), Some(end)) if start < end => &entry[start + 1..end],
+        _ => entry,
+    };
+    let address = address.trim();
+
+    if validate_email(address) {
+        Some(address.to_string())
+    } else {
+        None
+    }
+}
+
+/// Recipients for a reply-all: the original sender goes to `To`, everyone else on
+/// `To`/`Cc` goes to `Cc`. The authenticated user is dropped and duplicates are
+/// removed case-insensitively. When replying to one's own message, the original
+/// `To` recipients are addressed directly instead.
+pub fn compute_reply_all_recipients(
+    from: &str,
+    to: &str,
+    cc: &str,
+    self_email: &str,
+) -> (Vec<String>, Vec<String>) {
+    let mut seen = std::collections::HashSet::new();
+    seen.insert(self_email.to_lowercase());
+
+    let mut dedup = |addresses: Vec<String>| -> Vec<String> {
+        addresses
+            .into_iter()
+            .filter(|address| seen.insert(address.to_lowercase()))
+            .collect()
+    };
+
+    let from_addresses = parse_address_list(from);
+    let sent_by_self = from_addresses
+        .iter()
+        .any(|address| address.eq_ignore_ascii_case(self_email));
+
+    let (reply_to, reply_cc) = if sent_by_self {
+        (dedup(parse_address_list(to)), dedup(parse_address_list(cc)))
+    } else {
+        let reply_to = dedup(from_addresses);
+        let mut others = parse_address_list(to);
+        others.extend(parse_address_list(cc));
+        (reply_to, dedup(others))
+    };
+
+    (reply_to, reply_cc)
+}
+
+/// Prefixes `Re: ` unless the subject already carries it.
+pub fn reply_subject(subject: &str) -> String {
+    if subject.trim_start().to_lowercase().starts_with("re:") {
+        subject.to_string()
+    } else {
+        format!("Re: {}", subject)
+    }
+}
+
+/// Checks that a date is in Gmail's `YYYY/MM/DD` search format.
+pub fn validate_search_date(date: &str) -> Result<()> {
+    let date_regex = Regex::new(r"^(\d{4})/(\d{2})/(\d{2})$").unwrap();
+    let invalid = || GmailError::InvalidQuery(format!("Date '{}' must be in YYYY/MM/DD format", date));
+
+    let captures = date_regex.captures(date).ok_or_else(invalid)?;
+    let month: u32 = captures[2].parse().map_err(|_| invalid())?;
+    let day: u32 = captures[3].parse().map_err(|_| invalid())?;
+
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return Err(invalid());
+    }
+
+    Ok(())
+}
+
+/// Quotes a search value when it contains whitespace so Gmail treats it as one term.
+fn quote_search_value(value: &str) -> String {
+    let value = value.trim().replace('"', "");
+    if value.chars().any(char::is_whitespace) {
+        format!("\"{}\"", value)
+    } else {
+        value
+    }
+}
+
+/// Composes structured filters into a Gmail search query string.
+pub fn build_search_query(filters: &SearchFilters) -> Result<String> {
+    let mut terms = Vec::new();
+
+    let text_filters = [
+        ("from", &filters.from),
+        ("to", &filters.to),
+        ("subject", &filters.subject),
+        ("label", &filters.label),
+    ];
+    for (operator, value) in text_filters {
+        if let Some(value) = value.as_deref().filter(|v| !v.trim().is_empty()) {
+            terms.push(format!("{}:{}", operator, quote_search_value(value)));
+        }
+    }
+
+    for (operator, value) in [("after", &filters.after), ("before", &filters.before)] {
+        if let Some(date) = value {
+            validate_search_date(date)?;
+            terms.push(format!("{}:{}", operator, date));
+        }
+    }
+
+    if filters.has_attachment == Some(true) {
+        terms.push("has:attachment".to_string());
+    }
+
+    Ok(terms.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn send_args(body: &str, html_body: Option<&str>, mime_type: Option<&str>) -> SendEmailArgs {
+        SendEmailArgs {
+            to: vec!["alice@example.com".to_string()],
+            subject: "Report".to_string(),
+            body: body.to_string(),
+            html_body: html_body.map(str::to_string),
+            mime_type: mime_type.map(str::to_string),
+            cc: None,
+            bcc: None,
+            thread_id: None,
+            in_reply_to: None,
+            references: None,
+            attachments: None,
+        }
+    }
+
+    fn top_level_content_type(message: &str) -> &str {
+        message
+            .split("\r\n")
+            .find_map(|line| line.strip_prefix("Content-Type: "))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_create_email_message_infers_single_part_types() {
+        let plain = create_email_message(&send_args("Hi", None, None), None).unwrap();
+        assert_eq!(top_level_content_type(&plain), "text/plain; charset=UTF-8");
+
+        let html = create_email_message(&send_args("", Some("<p>Hi</p>"), None), None).unwrap();
+        assert_eq!(top_level_content_type(&html), "text/html; charset=UTF-8");
+        assert!(html.ends_with("<p>Hi</p>"));
+
+        // An explicit text/html with only `body` sends the body as HTML
+        let html_body = create_email_message(&send_args("<p>Hi</p>", None, Some("text/html")), None).unwrap();
+        assert_eq!(top_level_content_type(&html_body), "text/html; charset=UTF-8");
+
+        // multipart/alternative without an HTML body has nothing to alternate
+        let overridden = create_email_message(&send_args("Hi", None, Some("multipart/alternative")), None).unwrap();
+        assert_eq!(top_level_content_type(&overridden), "text/plain; charset=UTF-8");
+    }
+
+    #[test]
+    fn test_create_email_message_uses_alternative_for_text_and_html() {
+        for mime_type in [None, Some("text/plain"), Some("text/html")] {
+            let message = create_email_message(&send_args("Hi", Some("<p>Hi</p>"), mime_type), None).unwrap();
+            assert!(top_level_content_type(&message).starts_with("multipart/alternative; boundary="), "{:?}", mime_type);
+            assert!(message.contains("Content-Type: text/plain; charset=UTF-8\r\nContent-Transfer-Encoding: 7bit\r\n\r\nHi"));
+            assert!(message.contains("Content-Type: text/html; charset=UTF-8\r\nContent-Transfer-Encoding: 7bit\r\n\r\n<p>Hi</p>"));
+        }
+    }
+
+    #[test]
+    fn test_create_email_message_uses_mixed_for_attachments() {
+        let dir = std::env::temp_dir().join(format!("gmail-mcp-test-{}", Uuid::new_v4().simple()));
+        std::fs::create_dir(&dir).unwrap();
+        let filename = "report.csv".to_string();
+        std::fs::write(dir.join(&filename), "a,b\n1,2\n").unwrap();
+
+        let mut args = send_args("Hi", Some("<p>Hi</p>"), Some("multipart/alternative"));
+        args.attachments = Some(vec![filename.clone()]);
+        let message = create_email_message(&args, Some(&dir));
+        std::fs::remove_dir_all(&dir).unwrap();
+        let message = message.unwrap();
+
+        assert!(top_level_content_type(&message).starts_with("multipart/mixed; boundary="));
+        assert!(message.contains("\r\nContent-Type: multipart/alternative; boundary="));
+        assert!(message.contains(&format!("Content-Type: text/csv; name=\"{}\"", filename)));
+        assert!(message.contains(&format!("Content-Disposition: attachment; filename=\"{}\"", filename)));
+        assert!(message.contains(&general_purpose::STANDARD.encode("a,b\n1,2\n")));
+
+        let mut plain = send_args("Hi", None, None);
+        plain.attachments = Some(vec![filename]);
+        assert!(create_email_message(&plain, Some(&dir)).is_err());
+    }
+
+    #[test]
+    fn test_resolve_attachment_path_stays_inside_dir() {
+        let root = std::env::temp_dir().join(format!("gmail-mcp-test-{}", Uuid::new_v4().simple()));
+        let dir = root.join("outbox");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("notes.txt"), "hi").unwrap();
+        std::fs::write(root.join("secret.txt"), "no").unwrap();
+        let secret = root.join("secret.txt").to_string_lossy().to_string();
+
+        let inside = resolve_attachment_path(Some(&dir), "notes.txt");
+        let disabled = resolve_attachment_path(None, "notes.txt");
+        let parent = resolve_attachment_path(Some(&dir), "../secret.txt");
+        let absolute = resolve_attachment_path(Some(&dir), &secret);
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert!(inside.unwrap().ends_with("outbox/notes.txt"));
+        assert!(matches!(disabled, Err(GmailError::Forbidden(_))));
+        assert!(matches!(parent, Err(GmailError::Forbidden(_))));
+        assert!(matches!(absolute, Err(GmailError::Forbidden(_))));
+    }
+
+    #[test]
+    fn test_attachment_save_path() {
+        let dir = std::env::temp_dir().join(format!("gmail-mcp-test-{}", Uuid::new_v4().simple()));
+        std::fs::create_dir(&dir).unwrap();
+
+        let fresh = attachment_save_path(&dir, Some("report.pdf"), "ANGjdJ8");
+        std::fs::write(dir.join("report.pdf"), "x").unwrap();
+        let taken = attachment_save_path(&dir, Some("report.pdf"), "ANGjdJ8");
+        let traversal = attachment_save_path(&dir, Some("../../etc/passwd"), "ANGjdJ8");
+        let unnamed = attachment_save_path(&dir, None, "ANGjdJ8_abcdefghijklmnop");
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(fresh, dir.join("report.pdf"));
+        assert_eq!(taken, dir.join("report (1).pdf"));
+        assert_eq!(traversal, dir.join("passwd"));
+        assert_eq!(unnamed, dir.join("attachment-ANGjdJ8_abcdefgh"));
+    }
+
+    #[test]
+    fn test_build_search_query_composes_filters() {
+        let filters = SearchFilters {
+            from: Some("alice@example.com".to_string()),
+            subject: Some("quarterly report".to_string()),
+            after: Some("2024/01/01".to_string()),
+            before: Some("2024/02/01".to_string()),
+            has_attachment: Some(true),
+            label: Some("Work".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            build_search_query(&filters).unwrap(),
+            "from:alice@example.com subject:\"quarterly report\" label:Work after:2024/01/01 before:2024/02/01 has:attachment"
+        );
+    }
+
+    #[test]
+    fn test_parse_drafts_list() {
+        use crate::client::DraftListResponse;
+
+        let list: DraftListResponse = serde_json::from_value(serde_json::json!({
+            "drafts": [
+                {"id": "r-123", "message": {"id": "18f1a", "threadId": "18f1a"}},
+                {"id": "r-456", "message": {"id": "18f2b", "threadId": "18f00"}}
+            ],
+            "resultSizeEstimate": 2
+        })).unwrap();
+
+        let drafts = list.drafts.unwrap();
+        assert_eq!(drafts.len(), 2);
+        assert_eq!(drafts[1].id, "r-456");
+        assert_eq!(drafts[1].message.thread_id.as_deref(), Some("18f00"));
+        assert_eq!(list.result_size_estimate, Some(2));
+
+        let empty: DraftListResponse = serde_json::from_value(serde_json::json!({"resultSizeEstimate": 0})).unwrap();
+        assert!(empty.drafts.is_none());
+    }
+
+    #[test]
+    fn test_parse_single_draft() {
+        use crate::client::GmailDraft;
+
+        let draft: GmailDraft = serde_json::from_value(serde_json::json!({
+            "id": "r-123",
+            "message": {
+                "id": "18f1a",
+                "threadId": "18f1a",
+                "labelIds": ["DRAFT"],
+                "payload": {
+                    "mimeType": "text/plain",
+                    "headers": [
+                        {"name": "To", "value": "alice@example.com"},
+                        {"name": "Cc", "value": "bob@example.com"},
+                        {"name": "Subject", "value": "Quarterly report"},
+                        {"name": "Date", "value": "Tue, 7 May 2024 09:30:00 +0000"}
+                    ],
+                    "body": {"size": 5, "data": "SGVsbG8"}
+                }
+            }
+        })).unwrap();
+
+        let summary = draft_summary(&draft);
+        assert_eq!(summary, DraftSummary {
+            id: "r-123".to_string(),
+            message_id: "18f1a".to_string(),
+            subject: "Quarterly report".to_string(),
+            to: "alice@example.com".to_string(),
+            cc: "bob@example.com".to_string(),
+            bcc: String::new(),
+            date: "Tue, 7 May 2024 09:30:00 +0000".to_string(),
+        });
+        assert_eq!(
+            format_draft_summary(&summary),
+            "Draft ID: r-123\nMessage ID: 18f1a\nSubject: Quarterly report\nTo: alice@example.com\nCc: bob@example.com\nDate: Tue, 7 May 2024 09:30:00 +0000\n"
+        );
+        assert_eq!(extract_email_content(draft.message.payload.as_ref().unwrap()).text, "Hello");
+    }
+
+    #[test]
+    fn test_compute_reply_all_recipients_excludes_self_and_dedups() {
+        let (to, cc) = compute_reply_all_recipients(
+            "Alice <alice@example.com>",
+            "me@example.com, \"Smith, Bob\" <bob@example.com>",
+            "ALICE@example.com, carol@example.com, bob@example.com, Me <ME@example.com>",
+            "me@example.com",
+        );
+
+        assert_eq!(to, vec!["alice@example.com"]);
+        assert_eq!(cc, vec!["bob@example.com", "carol@example.com"]);
+    }
+
+    #[test]
+    fn test_compute_reply_all_recipients_for_own_message() {
+        let (to, cc) = compute_reply_all_recipients(
+            "Me <me@example.com>",
+            "alice@example.com",
+            "bob@example.com",
+            "me@example.com",
+        );
+
+        assert_eq!(to, vec!["alice@example.com"]);
+        assert_eq!(cc, vec!["bob@example.com"]);
+    }
+
+    #[test]
+    fn test_build_search_query_rejects_bad_dates() {
+        let filters = SearchFilters {
+            after: Some("2024-01-01".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(build_search_query(&filters), Err(GmailError::InvalidQuery(_))));
+        assert!(validate_search_date("2024/13/01").is_err());
+    }
+
+    #[test]
+    fn test_decode_quoted_printable() {
+        let body = "Caf=C3=A9 menu: price =3D 5 EUR, see the long =\r\nline and the =\nnext one.=";
+        assert_eq!(
+            String::from_utf8(decode_quoted_printable(body.as_bytes())).unwrap(),
+            "Café menu: price = 5 EUR, see the long line and the next one.="
+        );
+
+        assert_eq!(decode_quoted_printable(b"=ZZ=4"), b"=ZZ=4".to_vec());
+    }
+
+    #[test]
+    fn test_extract_email_content_keeps_full_format_body_as_sent() {
+        use crate::client::MessageBody;
+
+        // Gmail decodes the transfer encoding for `full`, so "=3D" here is literal text
+        let body = "Set x=3D in the config";
+        let part = MessagePayload {
+            part_id: Some("0".to_string()),
+            mime_type: Some("text/plain".to_string()),
+            filename: None,
+            headers: Some(vec![MessageHeader {
+                name: "Content-Transfer-Encoding".to_string(),
+                value: "Quoted-Printable".to_string(),
+            }]),
+            body: Some(MessageBody {
+                attachment_id: None,
+                size: None,
+                data: Some(general_purpose::URL_SAFE_NO_PAD.encode(body)),
+            }),
+            parts: None,
+        };
+
+        let content = extract_email_content(&part);
+        assert_eq!(content.text, "Set x=3D in the config");
+    }
+
+    #[test]
+    fn test_cid_reference_matches_related_part_by_content_id() {
+        let payload: MessagePayload = serde_json::from_value(serde_json::json!({
+            "mimeType": "multipart/related",
+            "parts": [
+                {
+                    "mimeType": "text/html",
+                    "body": {"data": general_purpose::URL_SAFE_NO_PAD.encode(
+                        r#"<p>Hi</p><img src="cid:logo%40example.com"><img src='cid:missing'>"#
+                    )}
+                },
+                {
+                    "mimeType": "image/png",
+                    "filename": "",
+                    "headers": [{"name": "Content-ID", "value": "<banner@example.com>"}],
+                    "body": {"attachmentId": "ANGjdJ_banner", "size": 4096}
+                },
+                {
+                    "mimeType": "image/png",
+                    "filename": "logo.png",
+                    "headers": [{"name": "Content-Id", "value": "<logo@example.com>"}],
+                    "body": {"data": general_purpose::URL_SAFE_NO_PAD.encode([0x89, b'P', b'N', b'G']), "size": 4}
+                }
+            ]
+        }))
+        .unwrap();
+
+        let html = extract_email_content(&payload).html;
+        let parts = extract_inline_parts(&payload);
+        assert_eq!(cid_references(&html), vec!["logo@example.com", "missing"]);
+
+        let logo = find_inline_part(&parts, "logo@example.com").unwrap();
+        assert_eq!(logo.filename.as_deref(), Some("logo.png"));
+        assert!(find_inline_part(&parts, "missing").is_none());
+
+        let note = inline_images_note(&html, &parts);
+        assert!(note.contains("- cid:logo@example.com -> logo.png (image/png, 0 KB)"));
+        assert!(note.contains("- cid:missing -> not found in message"));
+
+        let bodies = vec![("logo@example.com".to_string(), logo.data.clone().unwrap())];
+        let embedded = embed_inline_images(&html, &parts, &bodies);
+        assert!(embedded.contains(r#"src="data:image/png;base64,iVBORw==""#));
+        assert!(embedded.contains("src='cid:missing'"));
+    }
+
+    #[test]
+    fn test_extract_from_three_level_nested_payload() {
+        use crate::client::MessageBody;
+
+        let part = |mime_type: &str, filename: Option<&str>, body: Option<MessageBody>, parts: Option<Vec<MessagePayload>>| MessagePayload {
+            part_id: None,
+            mime_type: Some(mime_type.to_string()),
+            filename: filename.map(str::to_string),
+            headers: None,
+            body,
+            parts,
+        };
+        let data = |text: &str| Some(MessageBody {
+            attachment_id: None,
+            size: Some(text.len() as u64),
+            data: Some(general_purpose::URL_SAFE_NO_PAD.encode(text)),
+        });
+        let attachment = |id: &str, size: u64| Some(MessageBody {
+            attachment_id: Some(id.to_string()),
+            size: Some(size),
+            data: None,
+        });
+
+        // multipart/mixed > multipart/alternative > multipart/related > text/html + inline image
+        let related = part("multipart/related", Some(""), None, Some(vec![
+            part("text/html", Some(""), data("<p>See the chart</p>"), None),
+            part("image/png", Some(""), attachment("img-1", 2048), None),
+        ]));
+        let alternative = part("multipart/alternative", Some(""), None, Some(vec![
+            part("text/plain", Some(""), data("See the chart"), None),
+            related,
+        ]));
+        let payload = part("multipart/mixed", Some(""), None, Some(vec![
+            alternative,
+            part("application/pdf", Some("report.pdf"), attachment("pdf-1", 90_000), None),
+            part("text/plain", Some("notes.txt"), data("attached notes"), None),
+        ]));
+
+        let content = extract_email_content(&payload);
+        assert_eq!(content.text, "See the chart");
+        assert_eq!(content.html, "<p>See the chart</p>");
+
+        let attachments = extract_attachments(&payload);
+        assert_eq!(attachments.len(), 3);
+        assert_eq!(attachments[0].filename, "attachment-img-1");
+        assert_eq!(attachments[0].mime_type, "image/png");
+        assert_eq!(attachments[1].id.as_deref(), Some("pdf-1"));
+        assert_eq!(attachments[1].filename, "report.pdf");
+        assert_eq!(attachments[1].mime_type, "application/pdf");
+        assert_eq!(attachments[1].size, 90_000);
+        assert_eq!(attachments[2].id, None);
+        assert_eq!(attachments[2].filename, "notes.txt");
+        assert_eq!(attachments[2].mime_type, "text/plain");
+        assert_eq!(attachments[2].size, "attached notes".len() as u64);
+    }
+}
@@ -1,5 +1,6 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
 use openapiv3::OpenAPI;
+use serde_json::Value;
 use std::fs;
 use std::path::Path;
 use tracing::{error, info};
@@ -12,22 +13,119 @@ pub enum ValidationError {
     ValidationFailed(Vec<String>),
 }
 
-async fn load_openapi_spec(spec_path: &str, base_url: Option<&str>) -> Result<OpenAPI> {
+/// Serialization format of a spec file, sniffed from its content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecFormat {
+    Json,
+    Yaml,
+}
+
+impl std::fmt::Display for SpecFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpecFormat::Json => write!(f, "JSON"),
+            SpecFormat::Yaml => write!(f, "YAML"),
+        }
+    }
+}
+
+/// Why a spec file could not be loaded, worded so the user knows what to fix.
+/// Syntax errors keep the parser's 1-based line and column (0 when unknown).
+#[derive(Debug, thiserror::Error)]
+pub enum SpecLoadError {
+    #[error("OpenAPI spec file not found at {path}")]
+    NotFound { path: String },
+    #[error("Failed to read OpenAPI spec file at {path}: {source}")]
+    Unreadable {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("OpenAPI spec at {path} failed to parse as {format}: {message}")]
+    Syntax {
+        path: String,
+        format: SpecFormat,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    #[error("OpenAPI spec at {path} is valid {format} but not a valid OpenAPI 3 document: {message}")]
+    NotOpenApi {
+        path: String,
+        format: SpecFormat,
+        message: String,
+    },
+}
+
+/// JSON documents start with `{` or `[`; anything else is treated as YAML.
+pub fn sniff_spec_format(raw_spec: &str) -> SpecFormat {
+    match raw_spec.trim_start().chars().next() {
+        Some('{') | Some('[') => SpecFormat::Json,
+        _ => SpecFormat::Yaml,
+    }
+}
+
+/// Parses a JSON or YAML spec, separating syntax errors from documents that
+/// parse fine but aren't OpenAPI 3.
+pub fn parse_openapi_spec(path: &str, raw_spec: &str) -> std::result::Result<OpenAPI, SpecLoadError> {
+    let raw_spec = raw_spec.trim_start_matches('\u{feff}');
+    let format = sniff_spec_format(raw_spec);
+    let syntax_error = |line: usize, column: usize, message: String| SpecLoadError::Syntax {
+        path: path.to_string(),
+        format,
+        line,
+        column,
+        message,
+    };
+
+    let document: Value = match format {
+        SpecFormat::Json => serde_json::from_str(raw_spec)
+            .map_err(|e| syntax_error(e.line(), e.column(), e.to_string()))?,
+        SpecFormat::Yaml => serde_yaml::from_str(raw_spec).map_err(|e| {
+            let (line, column) = e.location().map_or((0, 0), |l| (l.line(), l.column()));
+            syntax_error(line, column, e.to_string())
+        })?,
+    };
+
+    let not_openapi = |message: String| SpecLoadError::NotOpenApi {
+        path: path.to_string(),
+        format,
+        message,
+    };
+
+    match document.get("openapi").and_then(|v| v.as_str()) {
+        Some(version) if version.starts_with("3.") => {}
+        Some(version) => return Err(not_openapi(format!("unsupported version '{}', expected 3.x", version))),
+        None if document.get("swagger").is_some() => {
+            return Err(not_openapi("this is a Swagger 2.0 document; convert it to OpenAPI 3 first".to_string()))
+        }
+        None => return Err(not_openapi("missing top-level 'openapi' version field".to_string())),
+    }
+
+    serde_json::from_value(document).map_err(|e| not_openapi(e.to_string()))
+}
+
+/// Reads and parses the spec file at `spec_path`.
+pub fn read_openapi_spec(spec_path: &str) -> std::result::Result<OpenAPI, SpecLoadError> {
     let path = Path::new(spec_path);
-    
+
     if !path.exists() {
-        return Err(anyhow::anyhow!(
-            "OpenAPI specification file not found: {}",
-            spec_path
-        ));
+        return Err(SpecLoadError::NotFound { path: spec_path.to_string() });
     }
 
-    let raw_spec = fs::read_to_string(path)
-        .with_context(|| format!("Failed to read OpenAPI specification file: {}", spec_path))?;
+    let raw_spec = fs::read_to_string(path).map_err(|source| SpecLoadError::Unreadable {
+        path: spec_path.to_string(),
+        source,
+    })?;
 
-    // Parse the OpenAPI spec
-    let mut parsed: OpenAPI = serde_json::from_str(&raw_spec)
-        .with_context(|| "Failed to parse OpenAPI specification as JSON")?;
+    parse_openapi_spec(spec_path, &raw_spec)
+}
+
+async fn load_openapi_spec(spec_path: &str, base_url: Option<&str>) -> Result<OpenAPI> {
+    let mut parsed = read_openapi_spec(spec_path).map_err(|e| {
+        error!("{}", e);
+        e
+    })?;
 
     // Override base URL if specified
     if let Some(url) = base_url {
@@ -50,4 +148,14 @@ pub async fn init_proxy(spec_path: &str, base_url: Option<&str>) -> Result<MCPPr
 
     info!("MCP proxy initialized successfully");
     Ok(proxy)
-}
\ No newline at end of file
+}
+
+/// Re-reads the spec at `spec_path` and swaps it into `proxy`. Any failure,
+/// from a missing file to a spec that yields no base URL, leaves the tools
+/// the proxy already serves untouched.
+pub async fn reload_proxy(proxy: &MCPProxy, spec_path: &str, base_url: Option<&str>) -> Result<()> {
+    info!("Reloading OpenAPI specification from: {}", spec_path);
+
+    let openapi_spec = load_openapi_spec(spec_path, base_url).await?;
+    proxy.reload(openapi_spec)
+}
@@ -1,13 +1,14 @@
 use anyhow::Result;
 use std::env;
-use tracing::{info, warn};
+use std::sync::Arc;
+use tracing::{error, info, warn};
 use tracing_subscriber;
 
 mod init_server;
 mod openapi_mcp_server;
 mod mcp;
 
-use init_server::init_proxy;
+use init_server::{init_proxy, reload_proxy};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -29,8 +30,22 @@ async fn main() -> Result<()> {
     let base_url = env::var("OPENAPI_BASE_URL").ok();
 
     // Initialize and start the MCP proxy
-    let proxy = init_proxy(&spec_path, base_url.as_deref()).await?;
-    
+    let proxy = Arc::new(init_proxy(&spec_path, base_url.as_deref()).await?);
+
+    // Reload the spec on SIGHUP so long-running deployments pick up changes
+    #[cfg(unix)]
+    {
+        let proxy = Arc::clone(&proxy);
+        let mut hangup = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())?;
+        tokio::spawn(async move {
+            while hangup.recv().await.is_some() {
+                if let Err(e) = reload_proxy(&proxy, &spec_path, base_url.as_deref()).await {
+                    error!("Reload failed, keeping the previous tools: {}", e);
+                }
+            }
+        });
+    }
+
     info!("MCP server initialized, connecting to stdio transport");
     
     // Connect to stdio transport for MCP communication  
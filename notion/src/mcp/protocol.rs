@@ -51,4 +51,26 @@ pub struct TextContent {
     pub text: String,
 }
 
-pub type ToolResult = CallToolResult;
\ No newline at end of file
+pub type ToolResult = CallToolResult;
+
+/// JSON-RPC error code for arguments that don't match a tool's input schema.
+pub const INVALID_PARAMS: i64 = -32602;
+
+/// JSON-RPC error code for failures that have no more specific code.
+pub const INTERNAL_ERROR: i64 = -32603;
+
+/// A handler error that carries its own JSON-RPC code. Other errors are
+/// reported as [`INTERNAL_ERROR`].
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("{message}")]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    pub fn invalid_params(message: String, data: Value) -> Self {
+        Self { code: INVALID_PARAMS, message, data: Some(data) }
+    }
+}
\ No newline at end of file
@@ -3,9 +3,10 @@ use serde_json::Value;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 use std::collections::HashMap;
 
+use super::protocol::{RpcError, INTERNAL_ERROR};
 use super::transport::Transport;
 
 #[derive(Clone)]
@@ -20,6 +21,7 @@ type Handler = Arc<dyn Fn(Value) -> Pin<Box<dyn Future<Output = Result<Value>> +
 pub struct Server {
     options: ServerOptions,
     handlers: Arc<RwLock<HashMap<String, Handler>>>,
+    notifications: broadcast::Sender<Value>,
 }
 
 impl Server {
@@ -27,9 +29,19 @@ impl Server {
         Self {
             options,
             handlers: Arc::new(RwLock::new(HashMap::new())),
+            notifications: broadcast::channel(16).0,
         }
     }
 
+    /// Queues a JSON-RPC notification for every connected transport.
+    /// Dropped silently when nothing is connected.
+    pub fn notify(&self, method: &str) {
+        let _ = self.notifications.send(serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": method,
+        }));
+    }
+
     pub async fn add_handler<F, Fut, Req, Res>(&self, method: &str, handler: F)
     where
         F: Fn(Req) -> Fut + Send + Sync + 'static,
@@ -53,7 +65,27 @@ impl Server {
         handlers.insert(method_name, wrapped_handler);
     }
 
-    pub async fn connect<T: Transport + Send + 'static>(&self, mut transport: T) -> Result<()> {
+    pub async fn connect<T: Transport + Send + Sync + 'static>(&self, transport: T) -> Result<()> {
+        let transport = Arc::new(transport);
+
+        let mut notifications = self.notifications.subscribe();
+        let notifier = {
+            let transport = Arc::clone(&transport);
+            tokio::spawn(async move {
+                loop {
+                    match notifications.recv().await {
+                        Ok(notification) => {
+                            if transport.send(notification).await.is_err() {
+                                break;
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            })
+        };
+
         loop {
             match transport.receive().await {
                 Ok(message) => {
@@ -61,8 +93,10 @@ impl Server {
                     if let Some(method) = message.get("method").and_then(|m| m.as_str()) {
                         let handlers = self.handlers.read().await;
                         if let Some(handler) = handlers.get(method) {
-                            if let Ok(result) = handler(message).await {
-                                transport.send(result).await?;
+                            let id = message.get("id").cloned().unwrap_or(Value::Null);
+                            match handler(message).await {
+                                Ok(result) => transport.send(result).await?,
+                                Err(e) => transport.send(error_response(id, &e)).await?,
                             }
                         }
                     }
@@ -70,6 +104,29 @@ impl Server {
                 Err(_) => break,
             }
         }
+        notifier.abort();
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// JSON-RPC error response for a failed handler, keeping an [`RpcError`]'s code.
+pub fn error_response(id: Value, error: &anyhow::Error) -> Value {
+    let (code, data) = match error.downcast_ref::<RpcError>() {
+        Some(rpc_error) => (rpc_error.code, rpc_error.data.clone()),
+        None => (INTERNAL_ERROR, None),
+    };
+
+    let mut error_object = serde_json::json!({
+        "code": code,
+        "message": error.to_string(),
+    });
+    if let Some(data) = data {
+        error_object["data"] = data;
+    }
+
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": error_object,
+    })
+}
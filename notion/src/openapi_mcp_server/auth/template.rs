@@ -62,7 +62,7 @@ mod tests {
         
         let config = template.render_auth_config();
         match config.auth_type {
-            super::types::AuthType::Bearer => {},
+            crate::openapi_mcp_server::auth::types::AuthType::Bearer => {},
             _ => panic!("Expected bearer auth type"),
         }
         assert_eq!(config.token, Some("my-secret-token".to_string()));
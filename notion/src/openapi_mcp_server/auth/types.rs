@@ -20,6 +20,7 @@ pub struct AuthConfig {
     pub username: Option<String>,
     pub password: Option<String>,
     pub api_key: Option<String>,
+    #[serde(default)]
     pub headers: HashMap<String, String>,
 }
 
@@ -1,18 +1,42 @@
 use anyhow::Result;
-use openapiv3::{OpenAPI, Operation, Parameter, ParameterData, ReferenceOr};
+use openapiv3::{
+    OpenAPI, Operation, Parameter, ParameterData, ParameterSchemaOrContent, QueryStyle, ReferenceOr,
+    Schema, SchemaKind, Type,
+};
 use reqwest::{Client, Method};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Arc;
 use thiserror::Error;
-use tracing::{error, info, warn};
+use tokio::sync::Semaphore;
+use tracing::{error, info, info_span, warn, Instrument};
 
-use crate::openapi_mcp_server::openapi::file_upload::is_file_upload_parameter;
+use crate::openapi_mcp_server::openapi::file_upload::{
+    is_file_upload_parameter, plan_form_fields, resolve_request_body, resolve_schema, unbox_schema_ref, FormField,
+};
+
+/// Default cap on response bytes held in memory for a single operation.
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 5 * 1024 * 1024;
+
+/// Default cap on operations sent upstream at the same time.
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 3;
 
 #[derive(Debug, Clone)]
 pub struct HttpClientConfig {
     pub base_url: String,
     pub headers: HashMap<String, String>,
+    /// Bodies larger than this are cut off and returned as text with a truncation marker.
+    pub max_response_bytes: usize,
+    /// Operations beyond this many in flight wait for a slot instead of bursting upstream.
+    pub max_concurrent_requests: usize,
+}
+
+/// A response body read up to a byte limit.
+#[derive(Debug)]
+pub struct BoundedBody {
+    pub bytes: Vec<u8>,
+    pub truncated: bool,
 }
 
 #[derive(Debug)]
@@ -24,12 +48,19 @@ pub struct HttpClientResponse<T = Value> {
 
 #[derive(Error, Debug)]
 pub enum HttpClientError {
-    #[error("HTTP request failed with status {status}: {message}")]
+    #[error("HTTP request failed with status {status}: {message} (correlation id {correlation_id})")]
     RequestFailed {
         status: u16,
         message: String,
         data: Option<Value>,
-        headers: Option<reqwest::header::HeaderMap>,
+        headers: Option<Box<reqwest::header::HeaderMap>>,
+        correlation_id: String,
+    },
+    #[error("Request error: {source} (correlation id {correlation_id})")]
+    SendFailed {
+        #[source]
+        source: reqwest::Error,
+        correlation_id: String,
     },
     #[error("Request error: {0}")]
     RequestError(#[from] reqwest::Error),
@@ -46,6 +77,7 @@ pub struct HttpClient {
     client: Client,
     config: HttpClientConfig,
     openapi_spec: OpenAPI,
+    request_slots: Arc<Semaphore>,
 }
 
 impl HttpClient {
@@ -78,26 +110,36 @@ impl HttpClient {
             .default_headers(default_headers)
             .build()?;
 
+        let request_slots = Arc::new(Semaphore::new(config.max_concurrent_requests.max(1)));
+
         Ok(Self {
             client,
             config,
             openapi_spec,
+            request_slots,
         })
     }
 
-    pub async fn execute_operation(
+    /// Builds the outgoing request for an operation without sending it.
+    pub async fn build_request(
         &self,
         operation_info: &crate::openapi_mcp_server::openapi::parser::OperationInfo,
-        params: HashMap<String, Value>,
-    ) -> Result<HttpClientResponse<Value>, HttpClientError> {
+        mut params: HashMap<String, Value>,
+    ) -> Result<reqwest::Request, HttpClientError> {
         let operation = &operation_info.operation;
         let method = &operation_info.method;
         let path = &operation_info.path;
 
+        // Per-call headers are never part of the body or query string
+        let header_overrides = extract_header_overrides(&mut params)?;
+
+        // Omitted params get their schema default, as the spec implies upstream
+        self.apply_declared_defaults(operation, &mut params);
+
         info!("Executing {} {} with params: {:?}", method, path, params);
 
         // Check for file uploads
-        let file_params = is_file_upload_parameter(operation);
+        let file_params = is_file_upload_parameter(operation, &self.openapi_spec);
         let has_file_upload = !file_params.is_empty();
 
         // Build the URL
@@ -131,12 +173,14 @@ impl HttpClient {
                             body_params.remove(&param.name);
                         }
                         "query" => {
-                            // Add to query parameters
-                            if let Some(value_str) = param_value.as_str() {
-                                query_params.push((param.name.clone(), value_str.to_string()));
-                            } else {
-                                query_params.push((param.name.clone(), param_value.to_string()));
-                            }
+                            // Arrays and objects follow the parameter's style/explode;
+                            // explode defaults to true only for the form style
+                            let style = match param_ref {
+                                ReferenceOr::Item(Parameter::Query { style, .. }) => style,
+                                _ => &QueryStyle::Form,
+                            };
+                            let explode = param.explode.unwrap_or(matches!(style, QueryStyle::Form));
+                            query_params.extend(serialize_query_param(&param.name, param_value, style, explode));
                             body_params.remove(&param.name);
                         }
                         "header" => {
@@ -149,6 +193,9 @@ impl HttpClient {
             }
         }
 
+        // Specs sometimes use a placeholder without declaring it as a path parameter
+        url = substitute_path_placeholders(&url, &mut body_params);
+
         // Add query parameters to URL
         if !query_params.is_empty() {
             url.push('?');
@@ -207,40 +254,84 @@ impl HttpClient {
             }
         }
 
+        // Per-call headers take precedence over the configured defaults
+        if !header_overrides.is_empty() {
+            request_builder = request_builder.headers(header_overrides);
+        }
+
+        Ok(request_builder.build()?)
+    }
+
+    /// Sends an operation upstream. Every log line it emits, and the error it
+    /// returns once a request has gone out, carry the same `correlation_id`.
+    pub async fn execute_operation(
+        &self,
+        operation_info: &crate::openapi_mcp_server::openapi::parser::OperationInfo,
+        params: HashMap<String, Value>,
+    ) -> Result<HttpClientResponse<Value>, HttpClientError> {
+        let correlation_id = uuid::Uuid::new_v4().to_string();
+        let span = info_span!("operation", correlation_id = %correlation_id);
+
+        self.send_operation(operation_info, params, correlation_id)
+            .instrument(span)
+            .await
+    }
+
+    async fn send_operation(
+        &self,
+        operation_info: &crate::openapi_mcp_server::openapi::parser::OperationInfo,
+        params: HashMap<String, Value>,
+        correlation_id: String,
+    ) -> Result<HttpClientResponse<Value>, HttpClientError> {
+        let request = self.build_request(operation_info, params).await.map_err(|e| {
+            warn!("Failed to build request: {}", e);
+            e
+        })?;
+
+        // Hold a slot until the body is read; the semaphore is never closed
+        let _slot = self.request_slots.acquire().await
+            .map_err(|e| HttpClientError::OperationError(e.to_string()))?;
+
         // Execute request
-        let response = request_builder.send().await?;
+        info!("Sending {} {}", request.method(), request.url());
+        let mut response = match self.client.execute(request).await {
+            Ok(response) => response,
+            Err(source) => {
+                error!("Request could not be sent: {}", source);
+                return Err(HttpClientError::SendFailed { source, correlation_id });
+            }
+        };
         let status = response.status().as_u16();
+        info!("Received status {}", status);
         let headers = response.headers().clone();
+        let is_json = headers.get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.contains("application/json"))
+            .unwrap_or(false);
+        let max_bytes = self.config.max_response_bytes;
 
         if response.status().is_success() {
-            let data: Value = if headers.get(reqwest::header::CONTENT_TYPE)
-                .and_then(|v| v.to_str().ok())
-                .map(|v| v.contains("application/json"))
-                .unwrap_or(false)
-            {
-                response.json().await?
-            } else {
-                let text = response.text().await?;
-                Value::String(text)
-            };
+            let body = read_body_bounded(&mut response, max_bytes).await?;
+            if body.truncated {
+                warn!("Response exceeded {} bytes and was truncated", max_bytes);
+            }
+            let data = body_to_value(body, is_json, max_bytes)?;
 
             Ok(HttpClientResponse { data, status, headers })
         } else {
-            let error_data: Option<Value> = if headers.get(reqwest::header::CONTENT_TYPE)
-                .and_then(|v| v.to_str().ok())
-                .map(|v| v.contains("application/json"))
-                .unwrap_or(false)
-            {
-                response.json().await.ok()
-            } else {
-                response.text().await.ok().map(Value::String)
+            let error_data: Option<Value> = match read_body_bounded(&mut response, max_bytes).await {
+                Ok(body) => body_to_value(body, is_json, max_bytes).ok(),
+                Err(_) => None,
             };
 
+            error!("Request failed with status {}", status);
+
             Err(HttpClientError::RequestFailed {
                 status,
                 message: format!("Request failed with status {}", status),
                 data: error_data,
-                headers: Some(headers),
+                headers: Some(Box::new(headers)),
+                correlation_id,
             })
         }
     }
@@ -251,36 +342,18 @@ impl HttpClient {
         params: &HashMap<String, Value>,
     ) -> Result<reqwest::multipart::Form, HttpClientError> {
         let mut form = reqwest::multipart::Form::new();
-        let file_params = is_file_upload_parameter(operation);
-
-        for (key, value) in params {
-            if file_params.contains(key) {
-                // Handle file upload
-                match value {
-                    Value::String(file_path) => {
-                        self.add_file_to_form(&mut form, key, file_path).await?;
-                    }
-                    Value::Array(file_paths) => {
-                        for file_path_value in file_paths {
-                            if let Value::String(file_path) = file_path_value {
-                                self.add_file_to_form(&mut form, key, file_path).await?;
-                            }
-                        }
-                    }
-                    _ => {
-                        return Err(HttpClientError::FileError(format!(
-                            "File parameter {} must be a string path or array of string paths", 
-                            key
-                        )));
-                    }
+        let file_params = is_file_upload_parameter(operation, &self.openapi_spec);
+
+        let fields = plan_form_fields(&file_params, params).map_err(HttpClientError::FileError)?;
+
+        for field in fields {
+            match field {
+                FormField::File { name, path } => {
+                    self.add_file_to_form(&mut form, &name, &path).await?;
+                }
+                FormField::Text { name, value } => {
+                    form = form.text(name, value);
                 }
-            } else {
-                // Handle regular form field
-                let value_str = match value {
-                    Value::String(s) => s.clone(),
-                    _ => value.to_string(),
-                };
-                form = form.text(key.clone(), value_str);
             }
         }
 
@@ -328,6 +401,43 @@ impl HttpClient {
         Ok(())
     }
 
+    /// Inserts the schema `default` of every declared query parameter and top-level
+    /// JSON body property missing from `params`. Params passed explicitly, even as
+    /// `null`, are left as they are.
+    fn apply_declared_defaults(&self, operation: &Operation, params: &mut HashMap<String, Value>) {
+        for param_ref in &operation.parameters {
+            // Referenced parameters are sent as query params, matching build_request
+            if matches!(param_ref, ReferenceOr::Item(p) if !matches!(p, Parameter::Query { .. })) {
+                continue;
+            }
+            let Some(param) = self.resolve_parameter(param_ref) else {
+                continue;
+            };
+            let ParameterSchemaOrContent::Schema(schema_ref) = &param.format else {
+                continue;
+            };
+            if let Some(default) = resolve_schema(schema_ref, &self.openapi_spec).and_then(|s| s.schema_data.default) {
+                params.entry(param.name.clone()).or_insert(default);
+            }
+        }
+
+        let body_schema = operation
+            .request_body
+            .as_ref()
+            .and_then(|body_ref| resolve_request_body(body_ref, &self.openapi_spec))
+            .and_then(|body| body.content.get("application/json").and_then(|media| media.schema.clone()))
+            .and_then(|schema_ref| resolve_schema(&schema_ref, &self.openapi_spec));
+        let Some(Schema { schema_kind: SchemaKind::Type(Type::Object(object)), .. }) = body_schema else {
+            return;
+        };
+        for (name, property) in &object.properties {
+            let property = resolve_schema(&unbox_schema_ref(property), &self.openapi_spec);
+            if let Some(default) = property.and_then(|s| s.schema_data.default) {
+                params.entry(name.clone()).or_insert(default);
+            }
+        }
+    }
+
     fn resolve_parameter(&self, param_ref: &ReferenceOr<Parameter>) -> Option<ParameterData> {
         match param_ref {
             ReferenceOr::Item(param) => {
@@ -351,4 +461,143 @@ impl HttpClient {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Serializes one query parameter into `(key, value)` pairs following the OpenAPI
+/// `style`/`explode` rules: exploded form arrays repeat the key, non-exploded ones are
+/// joined with the style's delimiter, and `deepObject` emits `name[key]=value`.
+pub fn serialize_query_param(name: &str, value: &Value, style: &QueryStyle, explode: bool) -> Vec<(String, String)> {
+    match value {
+        Value::Null => Vec::new(),
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(query_scalar).collect();
+            let delimiter = match style {
+                QueryStyle::SpaceDelimited => " ",
+                QueryStyle::PipeDelimited => "|",
+                _ => ",",
+            };
+            if explode && matches!(style, QueryStyle::Form) {
+                items.into_iter().map(|item| (name.to_string(), item)).collect()
+            } else {
+                vec![(name.to_string(), items.join(delimiter))]
+            }
+        }
+        Value::Object(fields) => match style {
+            QueryStyle::DeepObject => fields
+                .iter()
+                .map(|(key, v)| (format!("{}[{}]", name, key), query_scalar(v)))
+                .collect(),
+            QueryStyle::Form if explode => fields
+                .iter()
+                .map(|(key, v)| (key.clone(), query_scalar(v)))
+                .collect(),
+            _ => {
+                let flattened: Vec<String> = fields
+                    .iter()
+                    .flat_map(|(key, v)| [key.clone(), query_scalar(v)])
+                    .collect();
+                vec![(name.to_string(), flattened.join(","))]
+            }
+        },
+        scalar => vec![(name.to_string(), query_scalar(scalar))],
+    }
+}
+
+/// Fills each `{name}` left in `url` from `params`, removing the values it uses.
+/// Placeholders with no matching param are left as they are.
+fn substitute_path_placeholders(url: &str, params: &mut HashMap<String, Value>) -> String {
+    let mut substituted = String::with_capacity(url.len());
+    let mut rest = url;
+
+    while let Some(open) = rest.find('{') {
+        let Some(close) = rest[open..].find('}').map(|i| open + i) else {
+            break;
+        };
+        let name = &rest[open + 1..close];
+        substituted.push_str(&rest[..open]);
+        match params.remove(name) {
+            Some(value) => substituted.push_str(&query_scalar(&value)),
+            None => substituted.push_str(&rest[open..=close]),
+        }
+        rest = &rest[close + 1..];
+    }
+
+    substituted.push_str(rest);
+    substituted
+}
+
+fn query_scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Reserved tool argument carrying per-call HTTP headers, e.g. `{"Notion-Version": "2022-06-28"}`.
+pub const HEADERS_ARGUMENT: &str = "_headers";
+
+/// Removes the reserved `_headers` argument from `params` and validates it into a header map.
+pub fn extract_header_overrides(
+    params: &mut HashMap<String, Value>,
+) -> Result<reqwest::header::HeaderMap, HttpClientError> {
+    let mut headers = reqwest::header::HeaderMap::new();
+
+    let overrides = match params.remove(HEADERS_ARGUMENT) {
+        Some(Value::Object(overrides)) => overrides,
+        Some(Value::Null) | None => return Ok(headers),
+        Some(_) => {
+            return Err(HttpClientError::OperationError(format!(
+                "{} must be an object of header names to string values",
+                HEADERS_ARGUMENT
+            )));
+        }
+    };
+
+    for (name, value) in overrides {
+        let value = value.as_str().ok_or_else(|| {
+            HttpClientError::OperationError(format!("Header {} must have a string value", name))
+        })?;
+        let header_name = reqwest::header::HeaderName::try_from(name.as_str())
+            .map_err(|_| HttpClientError::OperationError(format!("Invalid header name: {}", name)))?;
+        let header_value = reqwest::header::HeaderValue::try_from(value)
+            .map_err(|_| HttpClientError::OperationError(format!("Invalid value for header {}", name)))?;
+        headers.insert(header_name, header_value);
+    }
+
+    Ok(headers)
+}
+
+/// Reads `response` chunk by chunk and stops once `max_bytes` are collected,
+/// so an oversized body is never buffered in full.
+pub async fn read_body_bounded(response: &mut reqwest::Response, max_bytes: usize) -> Result<BoundedBody, reqwest::Error> {
+    let mut bytes = Vec::new();
+
+    while let Some(chunk) = response.chunk().await? {
+        let remaining = max_bytes - bytes.len();
+        if chunk.len() > remaining {
+            bytes.extend_from_slice(&chunk[..remaining]);
+            return Ok(BoundedBody { bytes, truncated: true });
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok(BoundedBody { bytes, truncated: false })
+}
+
+pub fn truncation_marker(max_bytes: usize) -> String {
+    format!("[response truncated after {} bytes]", max_bytes)
+}
+
+/// Parses a complete JSON body; anything else, including a truncated JSON body, is returned as text.
+fn body_to_value(body: BoundedBody, is_json: bool, max_bytes: usize) -> Result<Value, serde_json::Error> {
+    if body.truncated {
+        let partial = String::from_utf8_lossy(&body.bytes);
+        return Ok(Value::String(format!("{}\n{}", partial, truncation_marker(max_bytes))));
+    }
+
+    if is_json {
+        serde_json::from_slice(&body.bytes)
+    } else {
+        Ok(Value::String(String::from_utf8_lossy(&body.bytes).into_owned()))
+    }
+}
@@ -1,3 +1,6 @@
 pub mod http_client;
 
-pub use http_client::{HttpClient, HttpClientConfig, HttpClientError, HttpClientResponse};
\ No newline at end of file
+pub use http_client::{
+    read_body_bounded, serialize_query_param, truncation_marker, BoundedBody, HttpClient, HttpClientConfig, HttpClientError,
+    HttpClientResponse, DEFAULT_MAX_CONCURRENT_REQUESTS, DEFAULT_MAX_RESPONSE_BYTES,
+};
\ No newline at end of file
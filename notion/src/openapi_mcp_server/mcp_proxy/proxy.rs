@@ -1,7 +1,7 @@
 use anyhow::Result;
 use crate::mcp::protocol::{
     CallToolRequest, CallToolResult, Content, ListToolsRequest, ListToolsResult, 
-    TextContent, Tool,
+    RpcError, TextContent, Tool,
 };
 use crate::mcp::server::{Server, ServerOptions};
 use crate::mcp::transport::Transport;
@@ -9,20 +9,40 @@ use openapiv3::OpenAPI;
 use serde_json::{Map, Value};
 use std::collections::HashMap;
 use std::env;
+use std::sync::{Arc, PoisonError, RwLock};
 use tracing::{error, info, warn};
 
-use crate::openapi_mcp_server::client::{HttpClient, HttpClientConfig, HttpClientError};
-use crate::openapi_mcp_server::openapi::parser::{ConversionResult, OpenAPIToMCPConverter, OperationInfo};
+use crate::openapi_mcp_server::client::{
+    HttpClient, HttpClientConfig, HttpClientError, DEFAULT_MAX_CONCURRENT_REQUESTS, DEFAULT_MAX_RESPONSE_BYTES,
+};
+use crate::openapi_mcp_server::openapi::parser::{ConversionResult, MCPTool, OpenAPIToMCPConverter, OperationInfo};
 
-pub struct MCPProxy {
-    server: Server,
+/// Diagnostic tool listing every generated method with its HTTP mapping.
+pub const LIST_OPERATIONS_TOOL: &str = "__list_operations";
+
+/// Set to `true` or `1` to expose [`LIST_OPERATIONS_TOOL`].
+pub const LIST_OPERATIONS_ENV: &str = "OPENAPI_MCP_LIST_OPERATIONS";
+
+/// Overrides [`DEFAULT_MAX_RESPONSE_BYTES`].
+pub const MAX_RESPONSE_BYTES_ENV: &str = "OPENAPI_MCP_MAX_RESPONSE_BYTES";
+
+/// Overrides [`DEFAULT_MAX_CONCURRENT_REQUESTS`].
+pub const MAX_CONCURRENT_REQUESTS_ENV: &str = "OPENAPI_MCP_MAX_CONCURRENT_REQUESTS";
+
+/// Notification sent after a reload replaces the tool set.
+pub const TOOLS_LIST_CHANGED: &str = "notifications/tools/list_changed";
+
+/// Everything generated from one version of the spec, swapped as a unit on reload.
+struct ProxyState {
     http_client: HttpClient,
-    tools: HashMap<String, crate::openapi_mcp_server::openapi::parser::MCPTool>,
+    tools: HashMap<String, MCPTool>,
     openapi_lookup: HashMap<String, OperationInfo>,
+    /// Input schema of each method, keyed like `openapi_lookup`.
+    input_schemas: HashMap<String, Value>,
 }
 
-impl MCPProxy {
-    pub fn new(name: String, openapi_spec: OpenAPI) -> Result<Self> {
+impl ProxyState {
+    fn from_spec(openapi_spec: OpenAPI) -> Result<Self> {
         // Get base URL from the OpenAPI spec
         let base_url = openapi_spec.servers
             .first()
@@ -32,16 +52,41 @@ impl MCPProxy {
         info!("Using base URL: {}", base_url);
 
         // Parse headers from environment
-        let headers = Self::parse_headers_from_env();
+        let headers = MCPProxy::parse_headers_from_env();
         
         // Create HTTP client
-        let http_client_config = HttpClientConfig { base_url, headers };
+        let max_response_bytes = MCPProxy::parse_positive_env(MAX_RESPONSE_BYTES_ENV, DEFAULT_MAX_RESPONSE_BYTES);
+        let max_concurrent_requests = MCPProxy::parse_positive_env(MAX_CONCURRENT_REQUESTS_ENV, DEFAULT_MAX_CONCURRENT_REQUESTS);
+        let http_client_config = HttpClientConfig { base_url, headers, max_response_bytes, max_concurrent_requests };
         let http_client = HttpClient::new(http_client_config, openapi_spec.clone())?;
 
         // Convert OpenAPI spec to MCP tools
         let mut converter = OpenAPIToMCPConverter::new(openapi_spec);
         let ConversionResult { tools, openapi_lookup } = converter.convert_to_mcp_tools()?;
 
+        let input_schemas = tools
+            .iter()
+            .flat_map(|(tool_name, tool)| {
+                tool.methods
+                    .iter()
+                    .map(move |method| (format!("{}-{}", tool_name, method.name), method.input_schema.clone()))
+            })
+            .collect();
+
+        Ok(Self { http_client, tools, openapi_lookup, input_schemas })
+    }
+}
+
+pub struct MCPProxy {
+    server: Server,
+    state: Arc<RwLock<Arc<ProxyState>>>,
+    list_operations_enabled: bool,
+}
+
+impl MCPProxy {
+    pub fn new(name: String, openapi_spec: OpenAPI) -> Result<Self> {
+        let state = ProxyState::from_spec(openapi_spec)?;
+
         // Create MCP server
         let server_options = ServerOptions {
             name,
@@ -49,17 +94,51 @@ impl MCPProxy {
         };
         let server = Server::new(server_options);
 
-        info!("Created MCP proxy with {} tools", tools.len());
+        info!("Created MCP proxy with {} tools", state.tools.len());
+
+        let list_operations_enabled = matches!(
+            env::var(LIST_OPERATIONS_ENV).as_deref(),
+            Ok("true") | Ok("1")
+        );
+        if list_operations_enabled {
+            info!("Exposing diagnostic tool {}", LIST_OPERATIONS_TOOL);
+        }
 
         Ok(Self {
             server,
-            http_client,
-            tools,
-            openapi_lookup,
+            state: Arc::new(RwLock::new(Arc::new(state))),
+            list_operations_enabled,
         })
     }
 
-    pub async fn connect<T: Transport + Send + 'static>(&self, transport: T) -> Result<()> {
+    /// Regenerates the tools from `openapi_spec` and notifies connected
+    /// clients. If the new spec can't be turned into tools, the current ones
+    /// stay in place and the error is returned.
+    pub fn reload(&self, openapi_spec: OpenAPI) -> Result<()> {
+        let state = ProxyState::from_spec(openapi_spec)?;
+        info!("Reloaded OpenAPI spec with {} tools", state.tools.len());
+
+        *self.state.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(state);
+        self.server.notify(TOOLS_LIST_CHANGED);
+        Ok(())
+    }
+
+    /// Names of the tools currently advertised by `tools/list`, sorted.
+    pub fn tool_names(&self) -> Vec<String> {
+        let state = Self::snapshot(&self.state);
+        let mut names: Vec<String> = Self::list_tools(&state, self.list_operations_enabled)
+            .into_iter()
+            .map(|tool| tool.name)
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn snapshot(state: &RwLock<Arc<ProxyState>>) -> Arc<ProxyState> {
+        Arc::clone(&state.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    pub async fn connect<T: Transport + Send + Sync + 'static>(&self, transport: T) -> Result<()> {
         let server = self.server.clone();
         
         // Set up tool handlers
@@ -71,53 +150,87 @@ impl MCPProxy {
         Ok(())
     }
 
+    fn list_tools(state: &ProxyState, list_operations_enabled: bool) -> Vec<Tool> {
+        let mut mcp_tools = Vec::new();
+
+        // Convert each method to a separate tool
+        for (tool_name, tool_def) in &state.tools {
+            for method in &tool_def.methods {
+                let tool_name_with_method = format!("{}-{}", tool_name, method.name);
+                let truncated_name = Self::truncate_tool_name(&tool_name_with_method);
+
+                mcp_tools.push(Tool {
+                    name: truncated_name,
+                    description: method.description.clone(),
+                    input_schema: method.input_schema.clone(),
+                });
+            }
+        }
+
+        if list_operations_enabled {
+            mcp_tools.push(Tool {
+                name: LIST_OPERATIONS_TOOL.to_string(),
+                description: "List each generated tool with the HTTP method and path it calls".to_string(),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {}
+                }),
+            });
+        }
+
+        mcp_tools
+    }
+
     async fn setup_handlers(&self) {
-        let tools = self.tools.clone();
-        let openapi_lookup = self.openapi_lookup.clone();
-        let http_client = self.http_client.clone();
+        let list_state = Arc::clone(&self.state);
+        let call_state = Arc::clone(&self.state);
+        let list_operations_enabled = self.list_operations_enabled;
 
         // Handle list tools request
         self.server.add_handler(
             "tools/list",
             move |_request: ListToolsRequest| {
-                let tools = tools.clone();
+                let state = Self::snapshot(&list_state);
                 async move {
-                    let mut mcp_tools = Vec::new();
-
-                    // Convert each method to a separate tool
-                    for (tool_name, tool_def) in &tools {
-                        for method in &tool_def.methods {
-                            let tool_name_with_method = format!("{}-{}", tool_name, method.name);
-                            let truncated_name = Self::truncate_tool_name(&tool_name_with_method);
-                            
-                            mcp_tools.push(Tool {
-                                name: truncated_name,
-                                description: method.description.clone(),
-                                input_schema: method.input_schema.clone(),
-                            });
-                        }
-                    }
-
-                    Ok(ListToolsResult { tools: mcp_tools })
+                    Ok(ListToolsResult { tools: Self::list_tools(&state, list_operations_enabled) })
                 }
             },
-        );
+        ).await;
 
         // Handle call tool request
         self.server.add_handler(
             "tools/call",
             move |request: CallToolRequest| {
-                let openapi_lookup = openapi_lookup.clone();
-                let http_client = http_client.clone();
-                
+                // Calls already in flight keep the spec they started with
+                let state = Self::snapshot(&call_state);
+
                 async move {
+                    let openapi_lookup = &state.openapi_lookup;
+                    let http_client = &state.http_client;
+
                     let tool_name = &request.params.name;
                     let arguments = &request.params.arguments;
 
+                    if list_operations_enabled && tool_name == LIST_OPERATIONS_TOOL {
+                        let content = Content::Text(TextContent {
+                            text: serde_json::to_string(&describe_operations(openapi_lookup))?,
+                        });
+
+                        return Ok(CallToolResult {
+                            content: vec![content],
+                            is_error: false,
+                        });
+                    }
+
                     // Find the operation in OpenAPI spec
                     let operation_info = openapi_lookup.get(tool_name)
                         .ok_or_else(|| anyhow::anyhow!("Method {} not found", tool_name))?;
 
+                    // Reject values Notion would refuse before sending anything
+                    if let Some(input_schema) = state.input_schemas.get(tool_name.as_str()) {
+                        validate_enum_arguments(input_schema, arguments)?;
+                    }
+
                     // Convert arguments to HashMap<String, Value>
                     let params = Self::extract_params_from_arguments(arguments)?;
 
@@ -133,8 +246,8 @@ impl MCPProxy {
                                 is_error: false,
                             })
                         }
-                        Err(HttpClientError::RequestFailed { status, data, .. }) => {
-                            error!("HTTP request failed with status {}: {:?}", status, data);
+                        Err(HttpClientError::RequestFailed { status, data, correlation_id, .. }) => {
+                            error!("HTTP request {} failed with status {}: {:?}", correlation_id, status, data);
                             
                             let error_data = data.unwrap_or_else(|| {
                                 Value::Object({
@@ -161,7 +274,7 @@ impl MCPProxy {
                     }
                 }
             },
-        );
+        ).await;
     }
 
     fn parse_headers_from_env() -> HashMap<String, String> {
@@ -197,6 +310,19 @@ impl MCPProxy {
         }
     }
 
+    fn parse_positive_env(name: &str, default: usize) -> usize {
+        match env::var(name) {
+            Ok(value) => match value.parse::<usize>() {
+                Ok(parsed) if parsed > 0 => parsed,
+                _ => {
+                    warn!("{} must be a positive integer, using {}", name, default);
+                    default
+                }
+            },
+            Err(_) => default,
+        }
+    }
+
     fn extract_params_from_arguments(arguments: &Value) -> Result<HashMap<String, Value>> {
         match arguments {
             Value::Object(map) => Ok(map.iter().map(|(k, v)| (k.clone(), v.clone())).collect()),
@@ -211,4 +337,77 @@ impl MCPProxy {
             name[..64].to_string()
         }
     }
-}
\ No newline at end of file
+}
+
+/// Pairs each generated tool name with its `method` and `path`, sorted by name.
+pub fn describe_operations(openapi_lookup: &HashMap<String, OperationInfo>) -> Value {
+    let mut names: Vec<&String> = openapi_lookup.keys().collect();
+    names.sort();
+
+    Value::Array(
+        names
+            .into_iter()
+            .map(|name| {
+                let info = &openapi_lookup[name];
+                serde_json::json!({
+                    "name": name,
+                    "method": info.method,
+                    "path": info.path,
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Checks each argument whose property declares an `enum` (directly, through
+/// a `#/$defs/` ref, or on array items) against the allowed values, failing
+/// with an invalid-params error that lists them.
+pub fn validate_enum_arguments(input_schema: &Value, arguments: &Value) -> std::result::Result<(), RpcError> {
+    let (Some(properties), Some(arguments)) = (input_schema.get("properties"), arguments.as_object()) else {
+        return Ok(());
+    };
+
+    for (name, value) in arguments {
+        let Some(property) = properties.get(name).map(|p| resolve_def(input_schema, p)) else {
+            continue;
+        };
+
+        if let Some(allowed) = property.get("enum").and_then(Value::as_array) {
+            check_enum_value(name, value, allowed)?;
+        } else if let (Some(items), Some(values)) = (property.get("items"), value.as_array()) {
+            if let Some(allowed) = resolve_def(input_schema, items).get("enum").and_then(Value::as_array) {
+                for item in values {
+                    check_enum_value(name, item, allowed)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Follows `#/$defs/<name>` refs within `input_schema`, up to a fixed depth.
+fn resolve_def<'a>(input_schema: &'a Value, mut schema: &'a Value) -> &'a Value {
+    for _ in 0..8 {
+        let Some(name) = schema.get("$ref").and_then(Value::as_str).and_then(|r| r.strip_prefix("#/$defs/")) else {
+            break;
+        };
+        match input_schema.get("$defs").and_then(|defs| defs.get(name)) {
+            Some(target) => schema = target,
+            None => break,
+        }
+    }
+    schema
+}
+
+fn check_enum_value(name: &str, value: &Value, allowed: &[Value]) -> std::result::Result<(), RpcError> {
+    if allowed.contains(value) {
+        return Ok(());
+    }
+
+    let listed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+    Err(RpcError::invalid_params(
+        format!("Invalid value {} for '{}'; expected one of: {}", value, name, listed.join(", ")),
+        serde_json::json!({ "param": name, "value": value, "allowed": allowed }),
+    ))
+}
@@ -1,27 +1,34 @@
-use openapiv3::{Operation, ReferenceOr, Schema, SchemaKind, StringFormat, Type, VariantOrUnknownOrEmpty};
+use openapiv3::{OpenAPI, Operation, ReferenceOr, RequestBody, Schema, SchemaKind, StringFormat, Type, VariantOrUnknownOrEmpty};
+use serde_json::Value;
+use std::collections::HashMap;
 
-pub fn is_file_upload_parameter(operation: &Operation) -> Vec<String> {
+/// Returns the names of multipart properties that must be sent as file parts.
+///
+/// This resolves `$ref`s the same way the schema converter does, so every property
+/// the converter advertises as a local file path (`binary` format) is uploaded as a file.
+pub fn is_file_upload_parameter(operation: &Operation, spec: &OpenAPI) -> Vec<String> {
     let mut file_params = Vec::new();
 
     // Check requestBody for multipart/form-data
-    if let Some(request_body_ref) = &operation.request_body {
-        if let ReferenceOr::Item(request_body) = request_body_ref {
-            if let Some(media_type) = request_body.content.get("multipart/form-data") {
-                if let Some(schema_ref) = &media_type.schema {
-                    if let ReferenceOr::Item(schema) = schema_ref {
-                        if let SchemaKind::Type(Type::Object(object_type)) = &schema.schema_kind {
-                            for (prop_name, prop_schema_ref) in &object_type.properties {
-                                let cloned_schema = match prop_schema_ref {
-                                    ReferenceOr::Item(boxed_schema) => ReferenceOr::Item(boxed_schema.as_ref().clone()),
-                                    ReferenceOr::Reference { reference } => ReferenceOr::Reference { reference: reference.clone() },
-                                };
-                                if is_file_schema(&cloned_schema) {
-                                    file_params.push(prop_name.clone());
-                                }
-                            }
-                        }
-                    }
-                }
+    let request_body = match operation.request_body.as_ref().and_then(|body| resolve_request_body(body, spec)) {
+        Some(request_body) => request_body,
+        None => return file_params,
+    };
+
+    let schema = match request_body
+        .content
+        .get("multipart/form-data")
+        .and_then(|media_type| media_type.schema.as_ref())
+        .and_then(|schema_ref| resolve_schema(schema_ref, spec))
+    {
+        Some(schema) => schema,
+        None => return file_params,
+    };
+
+    if let SchemaKind::Type(Type::Object(object_type)) = &schema.schema_kind {
+        for (prop_name, prop_schema_ref) in &object_type.properties {
+            if is_file_schema(&unbox_schema_ref(prop_schema_ref), spec) {
+                file_params.push(prop_name.clone());
             }
         }
     }
@@ -29,37 +36,217 @@ pub fn is_file_upload_parameter(operation: &Operation) -> Vec<String> {
     file_params
 }
 
-fn is_file_schema(schema_ref: &ReferenceOr<Schema>) -> bool {
+/// A string schema with `format: binary`, which the converter exposes as a path to a local file.
+pub fn is_binary_string_schema(schema: &Schema) -> bool {
+    match &schema.schema_kind {
+        SchemaKind::Type(Type::String(string_type)) => {
+            matches!(&string_type.format, VariantOrUnknownOrEmpty::Item(StringFormat::Binary))
+        }
+        _ => false,
+    }
+}
+
+/// Strips the `#/components/<section>/` prefix from a reference, if present.
+pub fn component_name<'a>(reference: &'a str, section: &str) -> &'a str {
+    let prefix = format!("#/components/{}/", section);
+    reference.strip_prefix(prefix.as_str()).unwrap_or(reference)
+}
+
+fn is_file_schema(schema_ref: &ReferenceOr<Schema>, spec: &OpenAPI) -> bool {
+    let schema = match resolve_schema(schema_ref, spec) {
+        Some(schema) => schema,
+        None => return false,
+    };
+
+    if is_binary_string_schema(&schema) {
+        return true;
+    }
+
+    // Check if it's an array of files
+    if let SchemaKind::Type(Type::Array(array_type)) = &schema.schema_kind {
+        if let Some(items) = &array_type.items {
+            return is_file_schema(&unbox_schema_ref(items), spec);
+        }
+    }
+
+    false
+}
+
+/// Follows `#/components/requestBodies/...` refs, including aliases, to a concrete body.
+pub(crate) fn resolve_request_body(body_ref: &ReferenceOr<RequestBody>, spec: &OpenAPI) -> Option<RequestBody> {
+    let mut reference = match body_ref {
+        ReferenceOr::Item(body) => return Some(body.clone()),
+        ReferenceOr::Reference { reference } => reference,
+    };
+    let bodies = &spec.components.as_ref()?.request_bodies;
+
+    // Bounded so a cycle of aliases cannot loop forever
+    for _ in 0..=bodies.len() {
+        match bodies.get(component_name(reference, "requestBodies"))? {
+            ReferenceOr::Item(body) => return Some(body.clone()),
+            ReferenceOr::Reference { reference: next } => reference = next,
+        }
+    }
+
+    None
+}
+
+/// Follows `#/components/schemas/...` refs, including aliases, to a concrete schema.
+pub(crate) fn resolve_schema(schema_ref: &ReferenceOr<Schema>, spec: &OpenAPI) -> Option<Schema> {
+    let mut reference = match schema_ref {
+        ReferenceOr::Item(schema) => return Some(schema.clone()),
+        ReferenceOr::Reference { reference } => reference,
+    };
+    let schemas = &spec.components.as_ref()?.schemas;
+
+    // Bounded so a cycle of aliases cannot loop forever
+    for _ in 0..=schemas.len() {
+        match schemas.get(component_name(reference, "schemas"))? {
+            ReferenceOr::Item(schema) => return Some(schema.clone()),
+            ReferenceOr::Reference { reference: next } => reference = next,
+        }
+    }
+
+    None
+}
+
+pub(crate) fn unbox_schema_ref(schema_ref: &ReferenceOr<Box<Schema>>) -> ReferenceOr<Schema> {
     match schema_ref {
-        ReferenceOr::Item(schema) => {
-            match &schema.schema_kind {
-                SchemaKind::Type(Type::String(string_type)) => {
-                    // Check if it's a binary format
-                    match &string_type.format {
-                        VariantOrUnknownOrEmpty::Item(format) => {
-                            return format == &StringFormat::Binary;
+        ReferenceOr::Item(boxed_schema) => ReferenceOr::Item(boxed_schema.as_ref().clone()),
+        ReferenceOr::Reference { reference } => ReferenceOr::Reference { reference: reference.clone() },
+    }
+}
+
+/// A single multipart field, decided before any file is read from disk.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormField {
+    File { name: String, path: String },
+    Text { name: String, value: String },
+}
+
+/// Splits request params into file parts and text fields using the detected file params.
+pub fn plan_form_fields(file_params: &[String], params: &HashMap<String, Value>) -> Result<Vec<FormField>, String> {
+    let mut fields = Vec::new();
+
+    for (key, value) in params {
+        if file_params.contains(key) {
+            match value {
+                Value::String(file_path) => {
+                    fields.push(FormField::File { name: key.clone(), path: file_path.clone() });
+                }
+                Value::Array(file_paths) => {
+                    for file_path_value in file_paths {
+                        if let Value::String(file_path) = file_path_value {
+                            fields.push(FormField::File { name: key.clone(), path: file_path.clone() });
                         }
-                        _ => {}
                     }
                 }
-                SchemaKind::Type(Type::Array(array_type)) => {
-                    // Check if it's an array of files
-                    if let Some(items) = &array_type.items {
-                        let cloned_items = match items {
-                            ReferenceOr::Item(boxed_schema) => ReferenceOr::Item(boxed_schema.as_ref().clone()),
-                            ReferenceOr::Reference { reference } => ReferenceOr::Reference { reference: reference.clone() },
-                        };
-                        return is_file_schema(&cloned_items);
-                    }
+                _ => {
+                    return Err(format!(
+                        "File parameter {} must be a string path or array of string paths",
+                        key
+                    ));
                 }
-                _ => {}
             }
-        }
-        ReferenceOr::Reference { .. } => {
-            // For referenced schemas, we'd need to resolve them first
-            // For now, we'll assume they're not file schemas
-            return false;
+        } else {
+            let value_str = match value {
+                Value::String(s) => s.clone(),
+                _ => value.to_string(),
+            };
+            fields.push(FormField::Text { name: key.clone(), value: value_str });
         }
     }
-    false
-}
\ No newline at end of file
+
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn upload_spec() -> OpenAPI {
+        serde_json::from_value(serde_json::json!({
+            "openapi": "3.0.0",
+            "info": {"title": "Test", "version": "1.0.0"},
+            "paths": {
+                "/file_uploads": {
+                    "post": {
+                        "operationId": "uploadFile",
+                        "requestBody": {"$ref": "#/components/requestBodies/UploadBody"},
+                        "responses": {"200": {"description": "OK"}}
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "FileContent": {"type": "string", "format": "binary"}
+                },
+                "requestBodies": {
+                    "UploadBody": {
+                        "content": {
+                            "multipart/form-data": {
+                                "schema": {
+                                    "type": "object",
+                                    "properties": {
+                                        "file": {"$ref": "#/components/schemas/FileContent"},
+                                        "part_number": {"type": "string"}
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_binary_property_is_uploaded_as_file_part() {
+        let spec = upload_spec();
+        let operation = match spec.paths.paths.get("/file_uploads") {
+            Some(ReferenceOr::Item(path_item)) => path_item.post.clone().unwrap(),
+            _ => panic!("missing path"),
+        };
+
+        let file_params = is_file_upload_parameter(&operation, &spec);
+        assert_eq!(file_params, vec!["file".to_string()]);
+
+        let mut params = HashMap::new();
+        params.insert("file".to_string(), Value::String("/tmp/report.pdf".to_string()));
+        params.insert("part_number".to_string(), Value::String("1".to_string()));
+
+        let fields = plan_form_fields(&file_params, &params).unwrap();
+        assert!(fields.contains(&FormField::File { name: "file".to_string(), path: "/tmp/report.pdf".to_string() }));
+        assert!(fields.contains(&FormField::Text { name: "part_number".to_string(), value: "1".to_string() }));
+    }
+
+    #[test]
+    fn test_component_name_strips_prefix() {
+        assert_eq!(component_name("#/components/requestBodies/UploadBody", "requestBodies"), "UploadBody");
+        assert_eq!(component_name("UploadBody", "requestBodies"), "UploadBody");
+    }
+
+    #[test]
+    fn test_resolve_schema_follows_aliases_and_stops_on_cycles() {
+        let spec: OpenAPI = serde_json::from_value(serde_json::json!({
+            "openapi": "3.0.0",
+            "info": {"title": "Test API", "version": "1.0.0"},
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Upload": {"$ref": "#/components/schemas/FileContent"},
+                    "FileContent": {"type": "string", "format": "binary"},
+                    "Loop": {"$ref": "#/components/schemas/Loop"}
+                }
+            }
+        }))
+        .unwrap();
+        let reference = |name: &str| ReferenceOr::Reference { reference: format!("#/components/schemas/{}", name) };
+
+        let resolved = resolve_schema(&reference("Upload"), &spec).unwrap();
+        assert!(is_binary_string_schema(&resolved));
+        assert!(resolve_schema(&reference("Loop"), &spec).is_none());
+        assert!(resolve_schema(&reference("Missing"), &spec).is_none());
+    }
+}
@@ -1,9 +1,11 @@
 use anyhow::Result;
-use openapiv3::{AdditionalProperties, OpenAPI, Operation, Parameter, ParameterData, ParameterSchemaOrContent, ReferenceOr, RequestBody, Response, Schema, SchemaKind, StatusCode, StringFormat, Type, VariantOrUnknownOrEmpty};
+use openapiv3::{AdditionalProperties, OpenAPI, Operation, Parameter, ParameterData, ParameterSchemaOrContent, ReferenceOr, RequestBody, Response, Schema, SchemaKind, StatusCode, Type, VariantOrUnknownOrEmpty};
 use serde_json::{Map, Value};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use tracing::{error, warn};
 
+use super::file_upload::{component_name, is_binary_string_schema};
+
 #[derive(Debug, Clone)]
 pub struct MCPMethod {
     pub name: String,
@@ -24,6 +26,23 @@ pub struct OperationInfo {
     pub path: String,
 }
 
+/// Tool that untagged operations are grouped under.
+pub const DEFAULT_TOOL_NAME: &str = "API";
+
+/// Name of the MCP tool an operation is grouped under: its first tag with
+/// characters outside `[A-Za-z0-9_]` replaced by `_`, or `DEFAULT_TOOL_NAME`.
+pub fn tool_name_for_operation(operation: &Operation) -> String {
+    operation.tags.iter()
+        .map(|tag| tag.trim())
+        .find(|tag| !tag.is_empty())
+        .map(|tag| {
+            tag.chars()
+                .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+                .collect()
+        })
+        .unwrap_or_else(|| DEFAULT_TOOL_NAME.to_string())
+}
+
 pub struct ConversionResult {
     pub tools: HashMap<String, MCPTool>,
     pub openapi_lookup: HashMap<String, OperationInfo>,
@@ -44,11 +63,12 @@ impl OpenAPIToMCPConverter {
         }
     }
 
+    /// Converts every operation into an MCP method. Operations are grouped
+    /// into one tool per OpenAPI tag (the operation's first tag), falling back
+    /// to `"API"` for untagged operations. Lookup keys are `{tool}-{method}`.
     pub fn convert_to_mcp_tools(&mut self) -> Result<ConversionResult> {
-        let api_name = "API";
-        let mut tools = HashMap::new();
+        let mut tools: HashMap<String, MCPTool> = HashMap::new();
         let mut openapi_lookup = HashMap::new();
-        let mut methods = Vec::new();
 
         // Collect paths first to avoid borrowing conflicts
         let paths: Vec<(String, openapiv3::PathItem)> = self.openapi_spec.paths.paths.iter()
@@ -63,95 +83,39 @@ impl OpenAPIToMCPConverter {
 
         // Process each path and operation
         for (path, path_item) in paths {
-            // Process each HTTP method
-            if let Some(operation) = &path_item.get {
-                if let Some(method) = self.convert_operation_to_mcp_method(operation, "get", &path)? {
-                    let unique_name = self.ensure_unique_name(&method.name);
-                    let mut method = method;
-                    method.name = unique_name.clone();
-                    methods.push(method);
-                    openapi_lookup.insert(
-                        format!("{}-{}", api_name, unique_name),
-                        OperationInfo {
-                            operation: operation.clone(),
-                            method: "get".to_string(),
-                            path: path.clone(),
-                        },
-                    );
-                }
-                }
-            
-            if let Some(operation) = &path_item.post {
-                if let Some(method) = self.convert_operation_to_mcp_method(operation, "post", &path)? {
-                    let unique_name = self.ensure_unique_name(&method.name);
-                    let mut method = method;
-                    method.name = unique_name.clone();
-                    methods.push(method);
-                    openapi_lookup.insert(
-                        format!("{}-{}", api_name, unique_name),
-                        OperationInfo {
-                            operation: operation.clone(),
-                            method: "post".to_string(),
-                            path: path.clone(),
-                        },
-                    );
-                }
-                }
-            
-            if let Some(operation) = &path_item.put {
-                if let Some(method) = self.convert_operation_to_mcp_method(operation, "put", &path)? {
-                    let unique_name = self.ensure_unique_name(&method.name);
-                    let mut method = method;
-                    method.name = unique_name.clone();
-                    methods.push(method);
-                    openapi_lookup.insert(
-                        format!("{}-{}", api_name, unique_name),
-                        OperationInfo {
-                            operation: operation.clone(),
-                            method: "put".to_string(),
-                            path: path.clone(),
-                        },
-                    );
-                }
-            }
-            
-            if let Some(operation) = &path_item.delete {
-                if let Some(method) = self.convert_operation_to_mcp_method(operation, "delete", &path)? {
-                    let unique_name = self.ensure_unique_name(&method.name);
-                    let mut method = method;
-                    method.name = unique_name.clone();
-                    methods.push(method);
-                    openapi_lookup.insert(
-                        format!("{}-{}", api_name, unique_name),
-                        OperationInfo {
-                            operation: operation.clone(),
-                            method: "delete".to_string(),
-                            path: path.clone(),
-                        },
-                    );
-                }
-                }
-            
-            if let Some(operation) = &path_item.patch {
-                if let Some(method) = self.convert_operation_to_mcp_method(operation, "patch", &path)? {
+            let operations = [
+                ("get", &path_item.get),
+                ("post", &path_item.post),
+                ("put", &path_item.put),
+                ("delete", &path_item.delete),
+                ("patch", &path_item.patch),
+            ];
+
+            for (http_method, operation) in operations {
+                let Some(operation) = operation else {
+                    continue;
+                };
+                if let Some(mut method) = self.convert_operation_to_mcp_method(operation, http_method, &path)? {
                     let unique_name = self.ensure_unique_name(&method.name);
-                    let mut method = method;
                     method.name = unique_name.clone();
-                    methods.push(method);
+
+                    let tool_name = tool_name_for_operation(operation);
                     openapi_lookup.insert(
-                        format!("{}-{}", api_name, unique_name),
+                        format!("{}-{}", tool_name, unique_name),
                         OperationInfo {
                             operation: operation.clone(),
-                            method: "patch".to_string(),
+                            method: http_method.to_string(),
                             path: path.clone(),
                         },
                     );
+                    tools.entry(tool_name)
+                        .or_insert_with(|| MCPTool { methods: Vec::new() })
+                        .methods
+                        .push(method);
                 }
             }
         }
 
-        tools.insert(api_name.to_string(), MCPTool { methods });
-
         Ok(ConversionResult {
             tools,
             openapi_lookup,
@@ -290,6 +254,8 @@ impl OpenAPIToMCPConverter {
             }
         }
 
+        self.ensure_referenced_defs(&mut input_schema);
+
         // Build description including error responses
         let mut description = operation.summary.clone()
             .or_else(|| operation.description.clone())
@@ -443,8 +409,9 @@ impl OpenAPIToMCPConverter {
                         result.insert("type".to_string(), Value::String("string".to_string()));
                         match &string_type.format {
                             VariantOrUnknownOrEmpty::Item(format) => {
-                                // Convert binary format to uri-reference and enhance description
-                                if format == &StringFormat::Binary {
+                                // Convert binary format to uri-reference and enhance description.
+                                // The HTTP client uploads exactly these properties as file parts.
+                                if is_binary_string_schema(schema) {
                                     result.insert("format".to_string(), Value::String("uri-reference".to_string()));
                                     let binary_desc = "absolute paths to local files";
                                     let description = schema.schema_data.description.as_ref()
@@ -459,15 +426,19 @@ impl OpenAPIToMCPConverter {
                                 // No format specified or unknown format
                             }
                         }
+                        insert_enum(&mut result, &string_type.enumeration);
                     }
-                    SchemaKind::Type(Type::Number(_)) => {
+                    SchemaKind::Type(Type::Number(number_type)) => {
                         result.insert("type".to_string(), Value::String("number".to_string()));
+                        insert_enum(&mut result, &number_type.enumeration);
                     }
-                    SchemaKind::Type(Type::Integer(_)) => {
+                    SchemaKind::Type(Type::Integer(integer_type)) => {
                         result.insert("type".to_string(), Value::String("integer".to_string()));
+                        insert_enum(&mut result, &integer_type.enumeration);
                     }
-                    SchemaKind::Type(Type::Boolean(_)) => {
+                    SchemaKind::Type(Type::Boolean(boolean_type)) => {
                         result.insert("type".to_string(), Value::String("boolean".to_string()));
+                        insert_enum(&mut result, &boolean_type.enumeration);
                     }
                     SchemaKind::OneOf { one_of } => {
                         let mut schemas = Vec::new();
@@ -505,50 +476,97 @@ impl OpenAPIToMCPConverter {
                     result.insert("default".to_string(), default.clone());
                 }
 
-                // Handle enum values if they exist in schema_data
-                // Note: In openapiv3, enums are typically handled differently
-                // This is a placeholder for now
-
                 Ok(Value::Object(result))
             }
         }
     }
 
     fn internal_resolve_ref(&self, reference: &str) -> Option<Schema> {
-        if !reference.starts_with("#/") {
-            return None;
-        }
+        let mut reference = reference.to_string();
+        let mut seen = HashSet::new();
 
-        let parts: Vec<&str> = reference.trim_start_matches("#/").split('/').collect();
-        
-        // Navigate through the OpenAPI spec
-        match parts.as_slice() {
-            ["components", "schemas", schema_name] => {
-                if let Some(components) = &self.openapi_spec.components {
-                    if let Some(ReferenceOr::Item(schema)) = components.schemas.get(*schema_name) {
-                        return Some(schema.clone());
+        // Follow alias components (`A: {$ref: B}`) until a concrete schema is found
+        while seen.insert(reference.clone()) {
+            if !reference.starts_with("#/") {
+                return None;
+            }
+
+            let parts: Vec<&str> = reference.trim_start_matches("#/").split('/').collect();
+            
+            // Navigate through the OpenAPI spec
+            match parts.as_slice() {
+                ["components", "schemas", schema_name] => {
+                    match self.openapi_spec.components.as_ref()?.schemas.get(*schema_name)? {
+                        ReferenceOr::Item(schema) => return Some(schema.clone()),
+                        ReferenceOr::Reference { reference: next } => reference = next.clone(),
                     }
                 }
-            }
-            _ => {
-                // Handle other reference types as needed
+                _ => {
+                    // Handle other reference types as needed
+                    return None;
+                }
             }
         }
 
         None
     }
 
+    /// Adds a `$defs` entry for every `#/$defs/...` ref reachable from `schema`,
+    /// including refs nested inside other definitions.
+    fn ensure_referenced_defs(&mut self, schema: &mut Value) {
+        let Value::Object(schema_obj) = schema else {
+            return;
+        };
+
+        loop {
+            let mut refs = BTreeSet::new();
+            collect_def_refs(&Value::Object(schema_obj.clone()), &mut refs);
+
+            let defs = schema_obj
+                .entry("$defs")
+                .or_insert_with(|| Value::Object(Map::new()));
+            let Value::Object(defs) = defs else {
+                return;
+            };
+
+            let missing: Vec<String> = refs.into_iter().filter(|name| !defs.contains_key(name)).collect();
+            if missing.is_empty() {
+                if defs.is_empty() {
+                    schema_obj.remove("$defs");
+                }
+                return;
+            }
+
+            for name in missing {
+                let reference = format!("#/components/schemas/{}", name);
+                let converted = match self.internal_resolve_ref(&reference) {
+                    Some(resolved) => self
+                        .convert_openapi_schema_to_json_schema(&ReferenceOr::Item(resolved), &mut HashSet::from([reference]), true)
+                        .ok(),
+                    None => None,
+                };
+
+                let def = converted.unwrap_or_else(|| {
+                    warn!("Schema {} is referenced but could not be resolved", name);
+                    let mut placeholder = Map::new();
+                    placeholder.insert("description".to_string(), Value::String(format!("Unresolved schema {}", name)));
+                    Value::Object(placeholder)
+                });
+                defs.insert(name, def);
+            }
+        }
+    }
+
     fn convert_components_to_json_schema(&mut self) -> Map<String, Value> {
         let mut defs = Map::new();
         
         // Collect schemas first to avoid borrowing conflicts
         let schemas: Vec<(String, Schema)> = if let Some(components) = &self.openapi_spec.components {
             components.schemas.iter()
-                .filter_map(|(key, schema_ref)| {
-                    if let ReferenceOr::Item(schema) = schema_ref {
-                        Some((key.clone(), schema.clone()))
-                    } else {
-                        None
+                .filter_map(|(key, schema_ref)| match schema_ref {
+                    ReferenceOr::Item(schema) => Some((key.clone(), schema.clone())),
+                    ReferenceOr::Reference { reference } => {
+                        self.internal_resolve_ref(reference).map(|schema| (key.clone(), schema))
                     }
                 })
                 .collect()
@@ -598,7 +616,7 @@ impl OpenAPIToMCPConverter {
             ReferenceOr::Reference { reference } => {
                 // Try to resolve request body reference
                 if let Some(components) = &self.openapi_spec.components {
-                    if let Some(ReferenceOr::Item(body)) = components.request_bodies.get(reference) {
+                    if let Some(ReferenceOr::Item(body)) = components.request_bodies.get(component_name(reference, "requestBodies")) {
                         return Some(body.clone());
                     }
                 }
@@ -649,6 +667,7 @@ impl OpenAPIToMCPConverter {
                             schema_obj.insert("description".to_string(), Value::String(response.description.clone()));
                         }
                     }
+                    self.ensure_referenced_defs(&mut return_schema);
                     
                     return Some(return_schema);
                 }
@@ -685,4 +704,39 @@ impl OpenAPIToMCPConverter {
         self.name_counter += 1;
         format!("{:04}", self.name_counter)
     }
-}
\ No newline at end of file
+}
+
+/// Copies an OpenAPI `enum` into the converted schema; a `None` entry is `null`.
+fn insert_enum<T: serde::Serialize>(result: &mut Map<String, Value>, enumeration: &[Option<T>]) {
+    if enumeration.is_empty() {
+        return;
+    }
+    let values = enumeration
+        .iter()
+        .map(|value| serde_json::to_value(value).unwrap_or(Value::Null))
+        .collect();
+    result.insert("enum".to_string(), Value::Array(values));
+}
+
+/// Collects the names behind every `#/$defs/<name>` ref found anywhere in `value`.
+pub fn collect_def_refs(value: &Value, refs: &mut BTreeSet<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, nested) in map {
+                if key == "$ref" {
+                    if let Some(name) = nested.as_str().and_then(|r| r.strip_prefix("#/$defs/")) {
+                        refs.insert(name.to_string());
+                    }
+                } else {
+                    collect_def_refs(nested, refs);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_def_refs(item, refs);
+            }
+        }
+        _ => {}
+    }
+}
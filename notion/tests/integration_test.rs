@@ -1,5 +1,8 @@
+use notion_mcp_server::openapi_mcp_server::mcp_proxy::proxy::describe_operations;
 use notion_mcp_server::openapi_mcp_server::openapi::parser::OpenAPIToMCPConverter;
-use notion_mcp_server::openapi_mcp_server::client::{HttpClient, HttpClientConfig};
+use notion_mcp_server::openapi_mcp_server::client::{
+    serialize_query_param, HttpClient, HttpClientConfig, DEFAULT_MAX_CONCURRENT_REQUESTS, DEFAULT_MAX_RESPONSE_BYTES,
+};
 use openapiv3::OpenAPI;
 use std::collections::HashMap;
 
@@ -39,6 +42,57 @@ async fn test_openapi_parser() {
     assert!(!result.openapi_lookup.is_empty());
 }
 
+#[tokio::test]
+async fn test_tagged_operations_are_grouped_by_tag() {
+    let spec: OpenAPI = serde_json::from_value(serde_json::json!({
+        "openapi": "3.0.0",
+        "info": {"title": "Test API", "version": "1.0.0"},
+        "paths": {
+            "/pages/{page_id}": {
+                "get": {
+                    "operationId": "retrieve-a-page",
+                    "tags": ["Pages"],
+                    "responses": {"200": {"description": "OK"}}
+                },
+                "patch": {
+                    "operationId": "patch-page",
+                    "tags": ["Pages", "Blocks"],
+                    "responses": {"200": {"description": "OK"}}
+                }
+            },
+            "/data_sources/{id}": {
+                "get": {
+                    "operationId": "retrieve-a-data-source",
+                    "tags": ["Data sources"],
+                    "responses": {"200": {"description": "OK"}}
+                }
+            },
+            "/users/me": {
+                "get": {
+                    "operationId": "get-self",
+                    "responses": {"200": {"description": "OK"}}
+                }
+            }
+        }
+    }))
+    .expect("Failed to parse OpenAPI spec");
+
+    let mut converter = OpenAPIToMCPConverter::new(spec);
+    let result = converter.convert_to_mcp_tools().expect("Failed to convert to MCP tools");
+
+    let mut page_methods: Vec<&str> = result.tools["Pages"].methods.iter().map(|m| m.name.as_str()).collect();
+    page_methods.sort();
+    assert_eq!(page_methods, vec!["patch-page", "retrieve-a-page"]);
+    assert_eq!(result.tools["Data_sources"].methods[0].name, "retrieve-a-data-source");
+    assert_eq!(result.tools["API"].methods[0].name, "get-self");
+    assert!(!result.tools.contains_key("Blocks"));
+
+    assert_eq!(result.openapi_lookup["Pages-patch-page"].method, "patch");
+    assert_eq!(result.openapi_lookup["Data_sources-retrieve-a-data-source"].path, "/data_sources/{id}");
+    assert_eq!(result.openapi_lookup["API-get-self"].path, "/users/me");
+    assert_eq!(result.openapi_lookup.len(), 4);
+}
+
 #[tokio::test]
 async fn test_http_client_creation() {
     let openapi_spec = r#"{
@@ -60,6 +114,8 @@ async fn test_http_client_creation() {
     let config = HttpClientConfig {
         base_url: "https://api.example.com".to_string(),
         headers: HashMap::new(),
+        max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+        max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
     };
 
     let client = HttpClient::new(config, spec);
@@ -69,47 +125,830 @@ async fn test_http_client_creation() {
 #[test]
 fn test_file_upload_detection() {
     use notion_mcp_server::openapi_mcp_server::openapi::file_upload::is_file_upload_parameter;
-    use openapiv3::{Operation, RequestBody, MediaType, ReferenceOr, Schema, SchemaKind, Type, StringType};
-    use std::collections::BTreeMap;
+    use openapiv3::{
+        MediaType, Operation, ReferenceOr, RequestBody, Schema, SchemaKind, StringFormat, StringType, Type,
+        VariantOrUnknownOrEmpty,
+    };
 
     let mut operation = Operation::default();
-    
+
     // Create a multipart/form-data request body with a file field
-    let mut content = BTreeMap::new();
-    let mut schema_properties = BTreeMap::new();
-    
-    schema_properties.insert(
-        "file".to_string(),
-        ReferenceOr::Item(Schema {
-            schema_data: Default::default(),
-            schema_kind: SchemaKind::Type(Type::String(StringType {
-                format: Some("binary".to_string()),
-                ..Default::default()
-            })),
-        }),
-    );
+    let file = Schema {
+        schema_data: Default::default(),
+        schema_kind: SchemaKind::Type(Type::String(StringType {
+            format: VariantOrUnknownOrEmpty::Item(StringFormat::Binary),
+            ..Default::default()
+        })),
+    };
 
     let schema = Schema {
         schema_data: Default::default(),
         schema_kind: SchemaKind::Type(Type::Object(openapiv3::ObjectType {
-            properties: schema_properties,
+            properties: [("file".to_string(), ReferenceOr::Item(Box::new(file)))].into_iter().collect(),
             ..Default::default()
         })),
     };
 
-    content.insert(
+    let content = [(
         "multipart/form-data".to_string(),
         MediaType {
             schema: Some(ReferenceOr::Item(schema)),
             ..Default::default()
         },
-    );
+    )];
 
     operation.request_body = Some(ReferenceOr::Item(RequestBody {
-        content,
+        content: content.into_iter().collect(),
         ..Default::default()
     }));
 
-    let file_params = is_file_upload_parameter(&operation);
+    let spec: OpenAPI = serde_json::from_str(r#"{
+        "openapi": "3.0.0",
+        "info": {"title": "Test API", "version": "1.0.0"},
+        "paths": {}
+    }"#).expect("Failed to parse OpenAPI spec");
+
+    let file_params = is_file_upload_parameter(&operation, &spec);
     assert_eq!(file_params, vec!["file"]);
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_header_overrides_are_sent_as_headers_not_body() {
+    use notion_mcp_server::openapi_mcp_server::openapi::parser::OperationInfo;
+    use openapiv3::ReferenceOr;
+    use serde_json::{json, Value};
+
+    let spec: OpenAPI = serde_json::from_value(json!({
+        "openapi": "3.0.0",
+        "info": {"title": "Test API", "version": "1.0.0"},
+        "paths": {
+            "/v1/pages": {
+                "post": {
+                    "operationId": "createPage",
+                    "requestBody": {
+                        "content": {
+                            "application/json": {
+                                "schema": {
+                                    "type": "object",
+                                    "properties": {"title": {"type": "string"}}
+                                }
+                            }
+                        }
+                    },
+                    "responses": {"200": {"description": "OK"}}
+                }
+            }
+        }
+    })).expect("Failed to parse OpenAPI spec");
+
+    let operation = match spec.paths.paths.get("/v1/pages") {
+        Some(ReferenceOr::Item(path_item)) => path_item.post.clone().unwrap(),
+        _ => panic!("missing path"),
+    };
+    let operation_info = OperationInfo {
+        operation,
+        method: "post".to_string(),
+        path: "/v1/pages".to_string(),
+    };
+
+    let config = HttpClientConfig {
+        base_url: "https://api.example.com".to_string(),
+        headers: HashMap::from([("Notion-Version".to_string(), "2022-02-22".to_string())]),
+        max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+        max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+    };
+    let client = HttpClient::new(config, spec).unwrap();
+
+    let mut params = HashMap::new();
+    params.insert("title".to_string(), json!("Hello"));
+    params.insert("_headers".to_string(), json!({"Notion-Version": "2022-06-28"}));
+
+    let request = client.build_request(&operation_info, params).await.unwrap();
+    assert_eq!(request.headers().get("notion-version").unwrap(), "2022-06-28");
+
+    let body: Value = serde_json::from_slice(request.body().unwrap().as_bytes().unwrap()).unwrap();
+    assert_eq!(body, json!({"title": "Hello"}));
+}
+
+#[tokio::test]
+async fn test_undeclared_path_placeholders_are_filled_from_params() {
+    use notion_mcp_server::openapi_mcp_server::openapi::parser::OperationInfo;
+    use openapiv3::ReferenceOr;
+    use serde_json::{json, Value};
+
+    // `block_id` appears in the path but the operation never declares it
+    let spec: OpenAPI = serde_json::from_value(json!({
+        "openapi": "3.0.0",
+        "info": {"title": "Test API", "version": "1.0.0"},
+        "paths": {
+            "/v1/blocks/{block_id}/children": {
+                "patch": {
+                    "operationId": "appendBlockChildren",
+                    "requestBody": {
+                        "content": {
+                            "application/json": {
+                                "schema": {
+                                    "type": "object",
+                                    "properties": {"children": {"type": "array", "items": {"type": "object"}}}
+                                }
+                            }
+                        }
+                    },
+                    "responses": {"200": {"description": "OK"}}
+                }
+            }
+        }
+    })).expect("Failed to parse OpenAPI spec");
+
+    let operation = match spec.paths.paths.get("/v1/blocks/{block_id}/children") {
+        Some(ReferenceOr::Item(path_item)) => path_item.patch.clone().unwrap(),
+        _ => panic!("missing path"),
+    };
+    let operation_info = OperationInfo {
+        operation,
+        method: "patch".to_string(),
+        path: "/v1/blocks/{block_id}/children".to_string(),
+    };
+
+    let config = HttpClientConfig {
+        base_url: "https://api.example.com".to_string(),
+        headers: HashMap::new(),
+        max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+        max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+    };
+    let client = HttpClient::new(config, spec).unwrap();
+
+    let mut params = HashMap::new();
+    params.insert("block_id".to_string(), json!("b55c9c91"));
+    params.insert("children".to_string(), json!([{"type": "divider"}]));
+
+    let request = client.build_request(&operation_info, params).await.unwrap();
+    assert_eq!(request.url().as_str(), "https://api.example.com/v1/blocks/b55c9c91/children");
+
+    let body: Value = serde_json::from_slice(request.body().unwrap().as_bytes().unwrap()).unwrap();
+    assert_eq!(body, json!({"children": [{"type": "divider"}]}));
+}
+
+#[tokio::test]
+async fn test_omitted_params_are_sent_with_their_schema_default() {
+    use notion_mcp_server::openapi_mcp_server::openapi::parser::OperationInfo;
+    use openapiv3::ReferenceOr;
+    use serde_json::{json, Value};
+
+    let spec: OpenAPI = serde_json::from_value(json!({
+        "openapi": "3.0.0",
+        "info": {"title": "Test API", "version": "1.0.0"},
+        "paths": {
+            "/v1/databases/{database_id}/query": {
+                "post": {
+                    "operationId": "queryDatabase",
+                    "parameters": [
+                        {"name": "database_id", "in": "path", "required": true, "schema": {"type": "string"}},
+                        {"name": "filter_properties", "in": "query", "schema": {"type": "string", "default": "title"}}
+                    ],
+                    "requestBody": {
+                        "content": {
+                            "application/json": {
+                                "schema": {"$ref": "#/components/schemas/QueryBody"}
+                            }
+                        }
+                    },
+                    "responses": {"200": {"description": "OK"}}
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "PageSize": {"type": "integer", "default": 100},
+                "QueryBody": {
+                    "type": "object",
+                    "properties": {
+                        "page_size": {"$ref": "#/components/schemas/PageSize"},
+                        "archived": {"type": "boolean", "default": false},
+                        "start_cursor": {"type": "string", "nullable": true, "default": "first"},
+                        "sorts": {"type": "array", "items": {"type": "object"}}
+                    }
+                }
+            }
+        }
+    })).expect("Failed to parse OpenAPI spec");
+
+    let operation = match spec.paths.paths.get("/v1/databases/{database_id}/query") {
+        Some(ReferenceOr::Item(path_item)) => path_item.post.clone().unwrap(),
+        _ => panic!("missing path"),
+    };
+    let operation_info = OperationInfo {
+        operation,
+        method: "post".to_string(),
+        path: "/v1/databases/{database_id}/query".to_string(),
+    };
+
+    let config = HttpClientConfig {
+        base_url: "https://api.example.com".to_string(),
+        headers: HashMap::new(),
+        max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+        max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+    };
+    let client = HttpClient::new(config, spec).unwrap();
+
+    let mut params = HashMap::new();
+    params.insert("database_id".to_string(), json!("d9824bdc"));
+    params.insert("archived".to_string(), json!(true));
+    params.insert("start_cursor".to_string(), Value::Null);
+
+    let request = client.build_request(&operation_info, params).await.unwrap();
+    assert_eq!(
+        request.url().as_str(),
+        "https://api.example.com/v1/databases/d9824bdc/query?filter_properties=title"
+    );
+
+    // Defaults fill the gaps; explicit values, including null, win
+    let body: Value = serde_json::from_slice(request.body().unwrap().as_bytes().unwrap()).unwrap();
+    assert_eq!(body, json!({"page_size": 100, "archived": true, "start_cursor": null}));
+}
+
+#[test]
+fn test_describe_operations_pairs_names_with_http_mapping() {
+    let spec: OpenAPI = serde_json::from_value(serde_json::json!({
+        "openapi": "3.0.0",
+        "info": {"title": "Test API", "version": "1.0.0"},
+        "servers": [{"url": "https://api.example.com"}],
+        "paths": {
+            "/v1/pages/{page_id}": {
+                "get": {
+                    "operationId": "retrieve-a-page",
+                    "parameters": [{"name": "page_id", "in": "path", "required": true, "schema": {"type": "string"}}],
+                    "responses": {"200": {"description": "OK"}}
+                },
+                "patch": {
+                    "operationId": "patch-page",
+                    "parameters": [{"name": "page_id", "in": "path", "required": true, "schema": {"type": "string"}}],
+                    "responses": {"200": {"description": "OK"}}
+                }
+            }
+        }
+    }))
+    .expect("Failed to parse OpenAPI spec");
+
+    let mut converter = OpenAPIToMCPConverter::new(spec);
+    let result = converter.convert_to_mcp_tools().expect("Failed to convert to MCP tools");
+
+    let operations = describe_operations(&result.openapi_lookup);
+    assert_eq!(
+        operations,
+        serde_json::json!([
+            {"name": "API-patch-page", "method": "patch", "path": "/v1/pages/{page_id}"},
+            {"name": "API-retrieve-a-page", "method": "get", "path": "/v1/pages/{page_id}"}
+        ])
+    );
+}
+
+#[test]
+fn test_additional_properties_refs_are_emitted_into_defs() {
+    let spec: OpenAPI = serde_json::from_value(serde_json::json!({
+        "openapi": "3.0.0",
+        "info": {"title": "Test API", "version": "1.0.0"},
+        "servers": [{"url": "https://api.example.com"}],
+        "paths": {
+            "/v1/pages": {
+                "post": {
+                    "operationId": "post-page",
+                    "requestBody": {
+                        "content": {
+                            "application/json": {
+                                "schema": {
+                                    "type": "object",
+                                    "properties": {
+                                        "properties": {
+                                            "type": "object",
+                                            "additionalProperties": {"$ref": "#/components/schemas/PropertyValue"}
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    "responses": {"200": {"description": "OK"}}
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "PropertyValue": {"$ref": "#/components/schemas/RichTextValue"},
+                "RichTextValue": {
+                    "type": "object",
+                    "properties": {"plain_text": {"type": "string"}}
+                }
+            }
+        }
+    }))
+    .expect("Failed to parse OpenAPI spec");
+
+    let mut converter = OpenAPIToMCPConverter::new(spec);
+    let result = converter.convert_to_mcp_tools().expect("Failed to convert to MCP tools");
+    let input_schema = &result.tools["API"].methods[0].input_schema;
+
+    assert_eq!(
+        input_schema["properties"]["properties"]["additionalProperties"]["$ref"],
+        "#/$defs/PropertyValue"
+    );
+    assert_eq!(
+        input_schema["$defs"]["PropertyValue"]["properties"]["plain_text"]["type"],
+        "string"
+    );
+}
+
+#[tokio::test]
+async fn test_large_responses_are_read_up_to_the_bound() {
+    use notion_mcp_server::openapi_mcp_server::client::{read_body_bounded, truncation_marker};
+    use notion_mcp_server::openapi_mcp_server::openapi::parser::OperationInfo;
+    use openapiv3::ReferenceOr;
+    use serde_json::{json, Value};
+
+    let large_body = format!("{{\"results\": \"{}\"}}", "x".repeat(256 * 1024));
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/v1/search")
+        .with_header("content-type", "application/json")
+        .with_body(&large_body)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let mut response = reqwest::get(format!("{}/v1/search", server.url())).await.unwrap();
+    let body = read_body_bounded(&mut response, 1024).await.unwrap();
+    assert!(body.truncated);
+    assert_eq!(body.bytes.len(), 1024);
+
+    let spec: OpenAPI = serde_json::from_value(json!({
+        "openapi": "3.0.0",
+        "info": {"title": "Test API", "version": "1.0.0"},
+        "paths": {
+            "/v1/search": {
+                "get": {
+                    "operationId": "search",
+                    "responses": {"200": {"description": "OK"}}
+                }
+            }
+        }
+    })).expect("Failed to parse OpenAPI spec");
+    let operation = match spec.paths.paths.get("/v1/search") {
+        Some(ReferenceOr::Item(path_item)) => path_item.get.clone().unwrap(),
+        _ => panic!("missing path"),
+    };
+    let operation_info = OperationInfo {
+        operation,
+        method: "get".to_string(),
+        path: "/v1/search".to_string(),
+    };
+
+    let config = HttpClientConfig {
+        base_url: server.url(),
+        headers: HashMap::new(),
+        max_response_bytes: 1024,
+        max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+    };
+    let client = HttpClient::new(config, spec).unwrap();
+
+    let response = client.execute_operation(&operation_info, HashMap::new()).await.unwrap();
+    let text = match response.data {
+        Value::String(text) => text,
+        other => panic!("expected truncated text, got {}", other),
+    };
+    assert!(text.starts_with("{\"results\": \"xxx"));
+    assert!(text.ends_with(&truncation_marker(1024)));
+    assert!(text.len() < 2048);
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_array_query_params_repeat_the_key() {
+    use notion_mcp_server::openapi_mcp_server::openapi::parser::OperationInfo;
+    use openapiv3::{QueryStyle, ReferenceOr};
+    use serde_json::json;
+
+    let spec: OpenAPI = serde_json::from_value(json!({
+        "openapi": "3.0.0",
+        "info": {"title": "Test API", "version": "1.0.0"},
+        "paths": {
+            "/v1/pages/{page_id}/properties": {
+                "get": {
+                    "operationId": "getPageProperties",
+                    "parameters": [
+                        {"name": "page_id", "in": "path", "required": true, "schema": {"type": "string"}},
+                        {"name": "filter_properties", "in": "query", "schema": {"type": "array", "items": {"type": "string"}}}
+                    ],
+                    "responses": {"200": {"description": "OK"}}
+                }
+            }
+        }
+    })).expect("Failed to parse OpenAPI spec");
+
+    let operation = match spec.paths.paths.get("/v1/pages/{page_id}/properties") {
+        Some(ReferenceOr::Item(path_item)) => path_item.get.clone().unwrap(),
+        _ => panic!("missing path"),
+    };
+    let operation_info = OperationInfo {
+        operation,
+        method: "get".to_string(),
+        path: "/v1/pages/{page_id}/properties".to_string(),
+    };
+
+    let config = HttpClientConfig {
+        base_url: "https://api.example.com".to_string(),
+        headers: HashMap::new(),
+        max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+        max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+    };
+    let client = HttpClient::new(config, spec).unwrap();
+
+    let mut params = HashMap::new();
+    params.insert("page_id".to_string(), json!("abc"));
+    params.insert("filter_properties".to_string(), json!(["title", "a b"]));
+
+    let request = client.build_request(&operation_info, params).await.unwrap();
+    assert_eq!(
+        request.url().as_str(),
+        "https://api.example.com/v1/pages/abc/properties?filter_properties=title&filter_properties=a%20b"
+    );
+
+    // Non-exploded and deepObject styles
+    assert_eq!(
+        serialize_query_param("ids", &json!([1, 2]), &QueryStyle::Form, false),
+        vec![("ids".to_string(), "1,2".to_string())]
+    );
+    assert_eq!(
+        serialize_query_param("ids", &json!(["a", "b"]), &QueryStyle::PipeDelimited, false),
+        vec![("ids".to_string(), "a|b".to_string())]
+    );
+    assert_eq!(
+        serialize_query_param("filter", &json!({"status": "done"}), &QueryStyle::DeepObject, true),
+        vec![("filter[status]".to_string(), "done".to_string())]
+    );
+}
+
+#[tokio::test]
+async fn test_concurrent_operations_are_limited() {
+    use notion_mcp_server::openapi_mcp_server::openapi::parser::OperationInfo;
+    use openapiv3::ReferenceOr;
+    use serde_json::json;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    const CALLS: u32 = 6;
+    const LIMIT: u32 = 2;
+    const DELAY: Duration = Duration::from_millis(200);
+
+    // Each response body is held back by DELAY, so the limit shows up in the timing
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/v1/users")
+        .with_header("content-type", "application/json")
+        .with_chunked_body(|w| {
+            std::thread::sleep(DELAY);
+            w.write_all(b"{}")
+        })
+        .expect(CALLS as usize)
+        .create_async()
+        .await;
+
+    let spec: OpenAPI = serde_json::from_value(json!({
+        "openapi": "3.0.0",
+        "info": {"title": "Test API", "version": "1.0.0"},
+        "paths": {
+            "/v1/users": {
+                "get": {
+                    "operationId": "listUsers",
+                    "responses": {"200": {"description": "OK"}}
+                }
+            }
+        }
+    })).expect("Failed to parse OpenAPI spec");
+    let operation = match spec.paths.paths.get("/v1/users") {
+        Some(ReferenceOr::Item(path_item)) => path_item.get.clone().unwrap(),
+        _ => panic!("missing path"),
+    };
+    let operation_info = Arc::new(OperationInfo {
+        operation,
+        method: "get".to_string(),
+        path: "/v1/users".to_string(),
+    });
+
+    let config = HttpClientConfig {
+        base_url: server.url(),
+        headers: HashMap::new(),
+        max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+        max_concurrent_requests: LIMIT as usize,
+    };
+    let client = HttpClient::new(config, spec).unwrap();
+
+    let started = Instant::now();
+    let calls: Vec<_> = (0..CALLS)
+        .map(|_| {
+            let client = client.clone();
+            let operation_info = operation_info.clone();
+            tokio::spawn(async move { client.execute_operation(&operation_info, HashMap::new()).await })
+        })
+        .collect();
+    for call in calls {
+        assert_eq!(call.await.unwrap().unwrap().status, 200);
+    }
+    let elapsed = started.elapsed();
+
+    // Two at a time takes three rounds: slower than unlimited, well under one by one
+    assert!(elapsed >= DELAY * (CALLS / LIMIT), "more than {} requests overlapped: {:?}", LIMIT, elapsed);
+    assert!(elapsed < DELAY * (CALLS - 1), "requests ran one after another: {:?}", elapsed);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_request_log_and_error_share_a_correlation_id() {
+    use notion_mcp_server::openapi_mcp_server::client::HttpClientError;
+    use notion_mcp_server::openapi_mcp_server::openapi::parser::OperationInfo;
+    use openapiv3::ReferenceOr;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for CapturedLogs {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    let logs = CapturedLogs::default();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(logs.clone())
+        .with_ansi(false)
+        .with_max_level(tracing::Level::INFO)
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/v1/pages/missing")
+        .with_status(404)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"object": "error", "code": "object_not_found"}"#)
+        .create_async()
+        .await;
+
+    let spec: OpenAPI = serde_json::from_value(json!({
+        "openapi": "3.0.0",
+        "info": {"title": "Test API", "version": "1.0.0"},
+        "paths": {
+            "/v1/pages/missing": {
+                "get": {
+                    "operationId": "retrievePage",
+                    "responses": {"200": {"description": "OK"}}
+                }
+            }
+        }
+    })).expect("Failed to parse OpenAPI spec");
+    let operation = match spec.paths.paths.get("/v1/pages/missing") {
+        Some(ReferenceOr::Item(path_item)) => path_item.get.clone().unwrap(),
+        _ => panic!("missing path"),
+    };
+    let operation_info = OperationInfo {
+        operation,
+        method: "get".to_string(),
+        path: "/v1/pages/missing".to_string(),
+    };
+
+    let config = HttpClientConfig {
+        base_url: server.url(),
+        headers: HashMap::new(),
+        max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+        max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+    };
+    let client = HttpClient::new(config, spec).unwrap();
+
+    let error = client.execute_operation(&operation_info, HashMap::new()).await.unwrap_err();
+    let correlation_id = match &error {
+        HttpClientError::RequestFailed { status: 404, correlation_id, .. } => correlation_id.clone(),
+        other => panic!("expected a 404 RequestFailed, got {:?}", other),
+    };
+    assert!(error.to_string().contains(&correlation_id));
+
+    let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+    let tagged = format!("correlation_id={}", correlation_id);
+    let sent = logs.lines().find(|line| line.contains("Sending GET")).expect("request was not logged");
+    let failed = logs.lines().find(|line| line.contains("Request failed with status 404")).expect("error was not logged");
+    assert!(sent.contains(&tagged), "request log missing correlation id: {}", sent);
+    assert!(failed.contains(&tagged), "error log missing correlation id: {}", failed);
+
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_spec_load_errors_name_the_failure() {
+    use notion_mcp_server::init_server::{parse_openapi_spec, read_openapi_spec, SpecFormat, SpecLoadError};
+
+    let missing = read_openapi_spec("/nonexistent/notion-openapi.json").unwrap_err();
+    assert!(matches!(missing, SpecLoadError::NotFound { .. }));
+    assert_eq!(missing.to_string(), "OpenAPI spec file not found at /nonexistent/notion-openapi.json");
+
+    let json = parse_openapi_spec("spec.json", "{\n  \"openapi\": \"3.0.0\",\n  \"info\": }").unwrap_err();
+    assert!(matches!(json, SpecLoadError::Syntax { format: SpecFormat::Json, line: 3, column: 11, .. }));
+
+    let yaml = parse_openapi_spec("spec.yaml", "openapi: 3.0.0\ninfo: [unclosed\n").unwrap_err();
+    assert!(matches!(yaml, SpecLoadError::Syntax { format: SpecFormat::Yaml, line: 3, .. }));
+
+    let swagger = parse_openapi_spec("spec.json", r#"{"swagger": "2.0", "info": {}}"#).unwrap_err();
+    assert!(matches!(swagger, SpecLoadError::NotOpenApi { format: SpecFormat::Json, .. }));
+    assert!(swagger.to_string().contains("Swagger 2.0"));
+
+    let incomplete = parse_openapi_spec("spec.json", r#"{"openapi": "3.0.0"}"#).unwrap_err();
+    assert!(matches!(incomplete, SpecLoadError::NotOpenApi { .. }));
+
+    let spec = parse_openapi_spec(
+        "spec.yaml",
+        "openapi: 3.0.0\ninfo:\n  title: Notion API\n  version: '1'\npaths: {}\n",
+    ).unwrap();
+    assert_eq!(spec.info.title, "Notion API");
+}
+
+#[tokio::test]
+async fn test_failed_reload_keeps_previous_tools() {
+    use notion_mcp_server::init_server::{init_proxy, reload_proxy};
+    use std::io::Write;
+
+    let mut spec_file = tempfile::NamedTempFile::new().unwrap();
+    write!(
+        spec_file,
+        r#"{{
+            "openapi": "3.0.0",
+            "info": {{"title": "Test API", "version": "1.0.0"}},
+            "servers": [{{"url": "https://api.example.com"}}],
+            "paths": {{
+                "/pages/{{page_id}}": {{
+                    "get": {{
+                        "operationId": "retrieve-a-page",
+                        "tags": ["Pages"],
+                        "parameters": [{{"name": "page_id", "in": "path", "required": true, "schema": {{"type": "string"}}}}],
+                        "responses": {{"200": {{"description": "Success"}}}}
+                    }}
+                }}
+            }}
+        }}"#
+    )
+    .unwrap();
+    let spec_path = spec_file.path().to_str().unwrap().to_string();
+
+    let proxy = init_proxy(&spec_path, None).await.unwrap();
+    let before = proxy.tool_names();
+    assert_eq!(before, vec!["Pages-retrieve-a-page".to_string()]);
+
+    // A syntax error in the updated file
+    std::fs::write(&spec_path, "{\"openapi\": \"3.0.0\", \"info\": }").unwrap();
+    assert!(reload_proxy(&proxy, &spec_path, None).await.is_err());
+    assert_eq!(proxy.tool_names(), before);
+
+    // Valid OpenAPI, but no server to send requests to
+    std::fs::write(
+        &spec_path,
+        r#"{"openapi": "3.0.0", "info": {"title": "Test API", "version": "2.0.0"}, "paths": {}}"#,
+    )
+    .unwrap();
+    assert!(reload_proxy(&proxy, &spec_path, None).await.is_err());
+    assert_eq!(proxy.tool_names(), before);
+}
+
+#[test]
+fn test_out_of_enum_argument_is_rejected_locally() {
+    use notion_mcp_server::mcp::protocol::INVALID_PARAMS;
+    use notion_mcp_server::openapi_mcp_server::mcp_proxy::proxy::validate_enum_arguments;
+
+    let spec: OpenAPI = serde_json::from_value(serde_json::json!({
+        "openapi": "3.0.0",
+        "info": {"title": "Test API", "version": "1.0.0"},
+        "servers": [{"url": "https://api.example.com"}],
+        "paths": {
+            "/search": {
+                "post": {
+                    "operationId": "post-search",
+                    "tags": ["Search"],
+                    "parameters": [{
+                        "name": "sort_direction",
+                        "in": "query",
+                        "schema": {"type": "string", "enum": ["ascending", "descending"]}
+                    }],
+                    "requestBody": {
+                        "content": {"application/json": {"schema": {
+                            "type": "object",
+                            "properties": {
+                                "filter": {"$ref": "#/components/schemas/objectType"},
+                                "page_size": {"type": "integer"}
+                            }
+                        }}}
+                    },
+                    "responses": {"200": {"description": "Success"}}
+                }
+            }
+        },
+        "components": {"schemas": {
+            "objectType": {"type": "string", "enum": ["page", "database"]}
+        }}
+    }))
+    .unwrap();
+
+    let mut converter = OpenAPIToMCPConverter::new(spec);
+    let result = converter.convert_to_mcp_tools().unwrap();
+    let input_schema = &result.tools["Search"].methods[0].input_schema;
+    assert_eq!(input_schema["properties"]["sort_direction"]["enum"], serde_json::json!(["ascending", "descending"]));
+
+    let valid = serde_json::json!({"sort_direction": "descending", "filter": "page", "page_size": 10});
+    assert!(validate_enum_arguments(input_schema, &valid).is_ok());
+
+    let err = validate_enum_arguments(input_schema, &serde_json::json!({"sort_direction": "newest"})).unwrap_err();
+    assert_eq!(err.code, INVALID_PARAMS);
+    assert_eq!(
+        err.message,
+        "Invalid value \"newest\" for 'sort_direction'; expected one of: \"ascending\", \"descending\""
+    );
+
+    let err = validate_enum_arguments(input_schema, &serde_json::json!({"filter": "block"})).unwrap_err();
+    assert_eq!(err.code, INVALID_PARAMS);
+    assert_eq!(err.data.unwrap()["allowed"], serde_json::json!(["page", "database"]));
+}
+
+/// Replays `requests` to the server and records what it sends back; reports
+/// a closed connection once the script runs out.
+struct ScriptedTransport {
+    requests: std::sync::Mutex<std::collections::VecDeque<serde_json::Value>>,
+    sent: std::sync::Arc<std::sync::Mutex<Vec<serde_json::Value>>>,
+}
+
+#[async_trait::async_trait]
+impl notion_mcp_server::mcp::transport::Transport for ScriptedTransport {
+    async fn send(&self, message: serde_json::Value) -> anyhow::Result<()> {
+        self.sent.lock().unwrap().push(message);
+        Ok(())
+    }
+
+    async fn receive(&self) -> anyhow::Result<serde_json::Value> {
+        self.requests.lock().unwrap().pop_front().ok_or_else(|| anyhow::anyhow!("connection closed"))
+    }
+
+    async fn close(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_proxy_answers_tools_list_once_connected() {
+    use notion_mcp_server::openapi_mcp_server::mcp_proxy::proxy::MCPProxy;
+
+    let spec: OpenAPI = serde_json::from_value(serde_json::json!({
+        "openapi": "3.0.0",
+        "info": {"title": "Test API", "version": "1.0.0"},
+        "servers": [{"url": "https://api.example.com"}],
+        "paths": {
+            "/users/me": {
+                "get": {
+                    "operationId": "get-self",
+                    "tags": ["Users"],
+                    "responses": {"200": {"description": "Success"}}
+                }
+            }
+        }
+    }))
+    .unwrap();
+    let proxy = MCPProxy::new("Test API".to_string(), spec).unwrap();
+
+    let sent = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let transport = ScriptedTransport {
+        requests: std::sync::Mutex::new(
+            [serde_json::json!({"jsonrpc": "2.0", "id": "1", "method": "tools/list"})].into_iter().collect(),
+        ),
+        sent: std::sync::Arc::clone(&sent),
+    };
+    proxy.connect(transport).await.unwrap();
+
+    // Without a registered handler the request is dropped and nothing is sent
+    let sent = sent.lock().unwrap();
+    assert_eq!(sent.len(), 1);
+    let names: Vec<&str> = sent[0]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|tool| tool["name"].as_str())
+        .collect();
+    assert_eq!(names, vec!["Users-get-self"]);
+}
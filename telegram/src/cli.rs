@@ -1,8 +1,11 @@
-use crate::config::TelegramConfig;
+use crate::config::{ProxyConfig, TelegramConfig};
+use crate::error::TelegramError;
 use crate::telegram::TelegramClient;
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
+use std::future::Future;
 use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[command(name = "mcp-telegram")]
@@ -19,7 +22,7 @@ pub enum Commands {
     Start,
     /// Login to Telegram
     Login,
-    /// Show instructions on how to logout from Telegram
+    /// Revoke the Telegram session and delete the local session file
     Logout,
     /// Delete the local Telegram session file
     ClearSession,
@@ -83,6 +86,8 @@ pub async fn login() -> Result<()> {
     let config = TelegramConfig {
         api_id: api_id.parse().context("API ID must be a valid number")?,
         api_hash,
+        proxy: ProxyConfig::from_env()?,
+        catch_up: false,
     };
 
     let mut client = TelegramClient::new(config)?;
@@ -124,33 +129,89 @@ pub async fn login() -> Result<()> {
     }
 }
 
+/// Result of a logout: whether Telegram revoked the session and whether a
+/// local session file was removed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct LogoutOutcome {
+    pub revoked: bool,
+    pub removed_local: bool,
+}
+
 pub async fn logout() -> Result<()> {
-    println!("🚪 How to Logout from Telegram");
-    println!();
-    println!("To logout from your Telegram account, please follow these steps:");
-    println!();
-    println!("1. Open your Telegram app");
-    println!("2. Go to Settings");
-    println!("3. Select Privacy and Security");
-    println!("4. Scroll down to find 'Active Sessions'");
-    println!("5. Find and terminate the session with the name of your app");
-    println!("   (This is the app name you created on my.telegram.org/apps)");
-    println!();
-    println!("Note: After logging out, you can use the 'clear-session' command to remove local session data.");
+    let session_file = crate::config::get_session_file();
+    if session_files(&session_file)
+        .iter()
+        .all(|path| !path.exists())
+    {
+        println!("ℹ️ No session file found, nothing to log out.");
+        return Ok(());
+    }
 
-    Ok(())
+    let config = TelegramConfig::from_env()?;
+    let mut client = TelegramClient::new(config)?;
+
+    println!("🚪 Revoking Telegram session...");
+    match revoke_then_clear(client.log_out(), &session_file).await {
+        Ok(outcome) => {
+            if outcome.revoked {
+                println!("✅ Session revoked on Telegram's side.");
+            } else {
+                println!("ℹ️ Session was not authorized; nothing to revoke.");
+            }
+            println!("🗑️ Local session file deleted.");
+            Ok(())
+        }
+        Err(e) => {
+            println!("❌ Could not revoke the session; the local session file was kept.");
+            println!("Retry once connected, or terminate it from Settings > Privacy and Security > Active Sessions");
+            println!("in the Telegram app and then run 'clear-session'.");
+            Err(e)
+        }
+    }
+}
+
+/// Revokes the session server-side, then deletes the local session file.
+///
+/// The file is only deleted once revocation succeeded: removing it first
+/// would leave a session that stays valid on Telegram's side with no local
+/// handle left to revoke it.
+async fn revoke_then_clear<F>(revoke: F, session_file: &Path) -> Result<LogoutOutcome>
+where
+    F: Future<Output = Result<bool, TelegramError>>,
+{
+    let revoked = revoke
+        .await
+        .context("Failed to revoke the Telegram session")?;
+    let removed_local = remove_session_files(session_file)?;
+    Ok(LogoutOutcome {
+        revoked,
+        removed_local,
+    })
+}
+
+/// Paths the session may be stored under, with and without extension.
+fn session_files(session_file: &Path) -> [PathBuf; 2] {
+    [
+        session_file.with_extension("session"),
+        session_file.to_path_buf(),
+    ]
+}
+
+fn remove_session_files(session_file: &Path) -> io::Result<bool> {
+    let mut removed = false;
+    for path in session_files(session_file) {
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+            removed = true;
+        }
+    }
+    Ok(removed)
 }
 
 pub async fn clear_session() -> Result<()> {
     let session_file = crate::config::get_session_file();
-    let session_file_with_ext = session_file.with_extension("session");
 
-    if session_file_with_ext.exists() {
-        std::fs::remove_file(&session_file_with_ext)?;
-        println!("🗑️ Session file successfully deleted!");
-        println!("You can now safely create a new session by logging in again.");
-    } else if session_file.exists() {
-        std::fs::remove_file(&session_file)?;
+    if remove_session_files(&session_file)? {
         println!("🗑️ Session file successfully deleted!");
         println!("You can now safely create a new session by logging in again.");
     } else {
@@ -164,17 +225,73 @@ pub async fn clear_session() -> Result<()> {
 pub async fn tools() -> Result<()> {
     println!("🔧 Available Tools");
     println!();
-    
+
     let tools = vec![
-        ("send_message", "Send text messages or files to any user, group, or channel"),
+        (
+            "send_message",
+            "Send a text message to a user, group, or channel",
+        ),
+        (
+            "schedule_message",
+            "Queue a text message to be sent at a later time",
+        ),
+        (
+            "list_scheduled_messages",
+            "List messages queued for later delivery in a chat",
+        ),
+        (
+            "delete_scheduled_message",
+            "Cancel a scheduled message before it is sent",
+        ),
         ("edit_message", "Modify content of previously sent messages"),
         ("delete_message", "Remove one or multiple messages"),
-        ("get_messages", "Retrieve message history with advanced filtering options"),
-        ("search_dialogs", "Find users, groups, and channels by name or username"),
-        ("message_from_link", "Access specific messages using Telegram links"),
+        (
+            "get_messages",
+            "Retrieve message history with advanced filtering options",
+        ),
+        (
+            "mark_dialog_read",
+            "Mark a whole chat as read and clear its unread count",
+        ),
+        (
+            "search_messages",
+            "Search message text within a single chat",
+        ),
+        (
+            "search_dialogs",
+            "Find users, groups, and channels by name or username",
+        ),
+        (
+            "message_from_link",
+            "Access specific messages using Telegram links",
+        ),
+        ("mute_chat", "Mute a chat for a period or forever"),
+        ("unmute_chat", "Turn a chat's notifications back on"),
+        ("list_contacts", "List the contacts in your address book"),
+        ("add_contact", "Add a phone number to your address book"),
+        ("delete_contact", "Remove a user from your address book"),
         ("get_draft", "View current message draft for any chat"),
         ("set_draft", "Create or clear message drafts"),
-        ("media_download", "Download photos, videos, and documents from messages"),
+        (
+            "get_message_reactions",
+            "Show reaction counts and who reacted to a message",
+        ),
+        (
+            "get_read_participants",
+            "See who read your message in a small group",
+        ),
+        (
+            "get_media_info",
+            "Inspect a message's media without downloading it",
+        ),
+        (
+            "media_download",
+            "Download photos, videos, and documents from messages",
+        ),
+        (
+            "bulk_download_media",
+            "Download all media from a chat within a date range",
+        ),
     ];
 
     for (name, description) in tools {
@@ -191,6 +308,38 @@ pub async fn version() -> Result<()> {
     println!("📦 MCP Telegram Rust Server");
     println!("Version: {}", env!("CARGO_PKG_VERSION"));
     println!("Description: {}", env!("CARGO_PKG_DESCRIPTION"));
-    
+
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_logout_revokes_before_deleting_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let session_file = dir.path().join("session");
+        std::fs::write(&session_file, b"auth key").unwrap();
+
+        let revoke = async {
+            // The session must still be on disk while Telegram is revoking it
+            assert!(session_file.exists());
+            Ok(true)
+        };
+        let outcome = revoke_then_clear(revoke, &session_file).await.unwrap();
+        assert_eq!(
+            outcome,
+            LogoutOutcome {
+                revoked: true,
+                removed_local: true
+            }
+        );
+        assert!(!session_file.exists());
+
+        std::fs::write(&session_file, b"auth key").unwrap();
+        let failed = async { Err(TelegramError::Config("not connected".to_string())) };
+        assert!(revoke_then_clear(failed, &session_file).await.is_err());
+        assert!(session_file.exists());
+    }
+}
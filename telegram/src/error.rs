@@ -4,7 +4,42 @@ use thiserror::Error;
 pub enum TelegramError {
     #[error("Configuration error: {0}")]
     Config(String),
-    
+
+    #[error("Proxy error: {0}")]
+    Proxy(String),
+
+    #[error("Invalid arguments: {0}")]
+    InvalidArguments(String),
+
+    #[error("Invalid entity: {0}")]
+    InvalidEntity(String),
+
+    #[error("Invalid message ID: {0}")]
+    InvalidMessageId(i32),
+
+    #[error("Media download error: {0}")]
+    MediaDownload(String),
+
+    #[error("Not available for this chat: {0}")]
+    NotAvailable(String),
+
+    #[error("URL parsing error: {0}")]
+    UrlParsing(String),
+
+    /// A split message failed part-way; `sent` holds the ids already delivered.
+    #[error("Sent {} of {total} message parts (ids {sent:?}) before failing: {source}", .sent.len())]
+    PartialSend {
+        sent: Vec<i32>,
+        total: usize,
+        source: Box<TelegramError>,
+    },
+
+    #[error("Telegram API error: {0}")]
+    Api(#[from] grammers_client::InvocationError),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
-}
\ No newline at end of file
+}
@@ -1,24 +1,39 @@
+mod cli;
+mod config;
 mod error;
+mod mcp_types;
 mod server;
+mod telegram;
+mod types;
+mod utils;
 
 use anyhow::Result;
+use cli::CliCommand;
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize tracing
+    // Initialize tracing on stderr; stdout carries the JSON-RPC stream
     tracing_subscriber::registry()
         .with(
-            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| {
-                "mcp_telegram=debug".into()
-            }),
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| "mcp_telegram=debug".into()),
         )
-        .with(tracing_subscriber::fmt::layer())
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
         .init();
 
-    info!("Starting MCP Telegram server");
-    server::run().await?;
+    match cli::run_cli().await? {
+        CliCommand::Start => {
+            info!("Starting MCP Telegram server");
+            server::run().await?;
+        }
+        CliCommand::Login => cli::login().await?,
+        CliCommand::Logout => cli::logout().await?,
+        CliCommand::ClearSession => cli::clear_session().await?,
+        CliCommand::Tools => cli::tools().await?,
+        CliCommand::Version => cli::version().await?,
+    }
 
     Ok(())
-}
\ No newline at end of file
+}
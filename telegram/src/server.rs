@@ -1,12 +1,1052 @@
-use anyhow::Result;
-use tracing::info;
+use crate::config::TelegramConfig;
+use crate::error::TelegramError;
+use crate::mcp_types::{
+    CallToolParams, CallToolResult, Content, JsonRpcRequest, JsonRpcResponse, Tool,
+};
+use crate::telegram::TelegramClient;
+use crate::utils::{GetMessagesOptions, SendOptions};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tracing::{debug, error, info, warn};
+
+pub struct TelegramMcpServer {
+    client: TelegramClient,
+}
+
+impl TelegramMcpServer {
+    pub fn new(client: TelegramClient) -> Self {
+        Self { client }
+    }
+
+    fn get_tools(&self) -> Vec<Tool> {
+        vec![
+            Tool {
+                name: "send_message".to_string(),
+                description: "Send a text message to a user, group, or channel".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "entity": {
+                            "type": "string",
+                            "description": "Username, phone number, or numeric ID of the chat"
+                        },
+                        "message": {
+                            "type": "string",
+                            "description": "Text of the message"
+                        },
+                        "reply_to": {
+                            "type": "integer",
+                            "description": "ID of the message to reply to"
+                        },
+                        "silent": {
+                            "type": "boolean",
+                            "description": "Deliver without a notification sound",
+                            "default": false
+                        },
+                        "disable_preview": {
+                            "type": "boolean",
+                            "description": "Don't show a preview for links in the message; set to false to let Telegram render one",
+                            "default": true
+                        },
+                        "no_split": {
+                            "type": "boolean",
+                            "description": "Fail on text over 4096 UTF-16 code units instead of sending it as several messages",
+                            "default": false
+                        }
+                    },
+                    "required": ["entity", "message"]
+                }),
+            },
+            Tool {
+                name: "schedule_message".to_string(),
+                description: "Schedule a text message to be sent at a later time".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "entity": {
+                            "type": "string",
+                            "description": "Username, phone number, or numeric ID of the chat"
+                        },
+                        "message": {
+                            "type": "string",
+                            "description": "Text of the message"
+                        },
+                        "schedule_date": {
+                            "type": "string",
+                            "description": "RFC 3339 timestamp in the future, at most 365 days ahead"
+                        }
+                    },
+                    "required": ["entity", "message", "schedule_date"]
+                }),
+            },
+            Tool {
+                name: "list_scheduled_messages".to_string(),
+                description: "List messages scheduled for later delivery in a chat".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "entity": {
+                            "type": "string",
+                            "description": "Chat to list scheduled messages for"
+                        }
+                    },
+                    "required": ["entity"]
+                }),
+            },
+            Tool {
+                name: "delete_scheduled_message".to_string(),
+                description: "Cancel a scheduled message before it is sent".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "entity": {
+                            "type": "string",
+                            "description": "Chat containing the scheduled message"
+                        },
+                        "message_id": {
+                            "type": "integer",
+                            "description": "ID returned by schedule_message or list_scheduled_messages"
+                        }
+                    },
+                    "required": ["entity", "message_id"]
+                }),
+            },
+            Tool {
+                name: "edit_message".to_string(),
+                description: "Edit a previously sent message".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "entity": {
+                            "type": "string",
+                            "description": "Chat containing the message"
+                        },
+                        "message_id": {
+                            "type": "integer",
+                            "description": "ID of the message to edit"
+                        },
+                        "message": {
+                            "type": "string",
+                            "description": "New message text"
+                        }
+                    },
+                    "required": ["entity", "message_id", "message"]
+                }),
+            },
+            Tool {
+                name: "delete_message".to_string(),
+                description: "Delete one or more messages".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "entity": {
+                            "type": "string",
+                            "description": "Chat containing the messages"
+                        },
+                        "message_ids": {
+                            "type": "array",
+                            "items": {"type": "integer"},
+                            "description": "IDs of the messages to delete"
+                        }
+                    },
+                    "required": ["entity", "message_ids"]
+                }),
+            },
+            Tool {
+                name: "get_messages".to_string(),
+                description: "Retrieve message history with optional date and unread filters".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "entity": {
+                            "type": "string",
+                            "description": "Chat to read messages from"
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "description": "Maximum number of messages to return",
+                            "default": 10
+                        },
+                        "start_date": {
+                            "type": "string",
+                            "description": "Only include messages after this RFC 3339 timestamp"
+                        },
+                        "end_date": {
+                            "type": "string",
+                            "description": "Only include messages before this RFC 3339 timestamp"
+                        },
+                        "unread": {
+                            "type": "boolean",
+                            "description": "Only return incoming messages newer than the chat's read marker",
+                            "default": false
+                        },
+                        "mark_as_read": {
+                            "type": "boolean",
+                            "description": "Mark the chat as read up to the newest returned message",
+                            "default": false
+                        },
+                        "include_reply_context": {
+                            "type": "boolean",
+                            "description": "Inline the text and sender of replied-to messages (fetches at most 20 extra messages)",
+                            "default": false
+                        }
+                    },
+                    "required": ["entity"]
+                }),
+            },
+            Tool {
+                name: "mark_dialog_read".to_string(),
+                description: "Mark every message in a chat as read and clear its unread count; returns how many were unread".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "entity": {
+                            "type": "string",
+                            "description": "Chat to mark as read"
+                        }
+                    },
+                    "required": ["entity"]
+                }),
+            },
+            Tool {
+                name: "mute_chat".to_string(),
+                description: "Mute notifications from a chat for a period or forever".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "entity": {
+                            "type": "string",
+                            "description": "Chat to mute"
+                        },
+                        "duration": {
+                            "type": "string",
+                            "description": "How long to mute: \"forever\", seconds, or a number with a unit such as 30m, 8h, 2d or 1w (at most 365 days)",
+                            "default": "forever"
+                        }
+                    },
+                    "required": ["entity"]
+                }),
+            },
+            Tool {
+                name: "unmute_chat".to_string(),
+                description: "Turn notifications from a chat back on".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "entity": {
+                            "type": "string",
+                            "description": "Chat to unmute"
+                        }
+                    },
+                    "required": ["entity"]
+                }),
+            },
+            Tool {
+                name: "list_contacts".to_string(),
+                description: "List the contacts in the account's address book".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {}
+                }),
+            },
+            Tool {
+                name: "add_contact".to_string(),
+                description: "Add a phone number to the address book and return the matching Telegram user".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "phone": {
+                            "type": "string",
+                            "description": "Phone number in international format, e.g. +14155552671"
+                        },
+                        "first_name": {
+                            "type": "string",
+                            "description": "First name to save the contact under"
+                        },
+                        "last_name": {
+                            "type": "string",
+                            "description": "Last name to save the contact under",
+                            "default": ""
+                        }
+                    },
+                    "required": ["phone", "first_name"]
+                }),
+            },
+            Tool {
+                name: "delete_contact".to_string(),
+                description: "Remove a user from the address book and return the deleted contact".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "user_id": {
+                            "type": "integer",
+                            "description": "User ID of the contact, as returned by list_contacts"
+                        }
+                    },
+                    "required": ["user_id"]
+                }),
+            },
+            Tool {
+                name: "search_messages".to_string(),
+                description: "Search message text within a single chat and return matching message ids with snippets".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "entity": {
+                            "type": "string",
+                            "description": "Username, phone number, or numeric ID of the chat"
+                        },
+                        "query": {
+                            "type": "string",
+                            "description": "Text to search for"
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "description": "Maximum number of messages to return (max 100)",
+                            "default": 20
+                        }
+                    },
+                    "required": ["entity", "query"]
+                }),
+            },
+            Tool {
+                name: "search_dialogs".to_string(),
+                description: "Find users, groups, and channels by name or username".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "query": {
+                            "type": "string",
+                            "description": "Text to search for"
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "description": "Maximum number of dialogs to return",
+                            "default": 10
+                        },
+                        "global_search": {
+                            "type": "boolean",
+                            "description": "Also search public chats the account hasn't joined",
+                            "default": false
+                        }
+                    },
+                    "required": ["query"]
+                }),
+            },
+            Tool {
+                name: "message_from_link".to_string(),
+                description: "Get a message from a t.me link, including private t.me/c/ links".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "link": {
+                            "type": "string",
+                            "description": "Telegram message link (e.g., https://t.me/channel/123)"
+                        }
+                    },
+                    "required": ["link"]
+                }),
+            },
+            Tool {
+                name: "get_draft".to_string(),
+                description: "Get the current message draft for a chat".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "entity": {
+                            "type": "string",
+                            "description": "Chat to read the draft from"
+                        }
+                    },
+                    "required": ["entity"]
+                }),
+            },
+            Tool {
+                name: "set_draft".to_string(),
+                description: "Set or clear the message draft for a chat".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "entity": {
+                            "type": "string",
+                            "description": "Chat to set the draft for"
+                        },
+                        "message": {
+                            "type": "string",
+                            "description": "Draft text; an empty string clears the draft"
+                        }
+                    },
+                    "required": ["entity", "message"]
+                }),
+            },
+            Tool {
+                name: "get_message_reactions".to_string(),
+                description: "Get the reaction counts on a message and, where the chat allows it, who reacted".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "entity": {
+                            "type": "string",
+                            "description": "Chat containing the message"
+                        },
+                        "message_id": {
+                            "type": "integer",
+                            "description": "ID of the message"
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "description": "Maximum number of reactors to list (default 50, max 100, 0 for counts only)"
+                        }
+                    },
+                    "required": ["entity", "message_id"]
+                }),
+            },
+            Tool {
+                name: "get_read_participants".to_string(),
+                description: "List who has read one of your messages in a small group (up to 100 members, messages from the last 7 days)".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "entity": {
+                            "type": "string",
+                            "description": "Group containing the message"
+                        },
+                        "message_id": {
+                            "type": "integer",
+                            "description": "ID of a message you sent"
+                        }
+                    },
+                    "required": ["entity", "message_id"]
+                }),
+            },
+            Tool {
+                name: "get_media_info".to_string(),
+                description: "Get the type, size, MIME type, dimensions, and duration of a message's media without downloading it".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "entity": {
+                            "type": "string",
+                            "description": "Chat containing the message"
+                        },
+                        "message_id": {
+                            "type": "integer",
+                            "description": "ID of the message with media"
+                        }
+                    },
+                    "required": ["entity", "message_id"]
+                }),
+            },
+            Tool {
+                name: "media_download".to_string(),
+                description: "Download the photo, video, or document attached to a message".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "entity": {
+                            "type": "string",
+                            "description": "Chat containing the message"
+                        },
+                        "message_id": {
+                            "type": "integer",
+                            "description": "ID of the message with media"
+                        },
+                        "path": {
+                            "type": "string",
+                            "description": "Directory to save the file to (defaults to the downloads directory)"
+                        }
+                    },
+                    "required": ["entity", "message_id"]
+                }),
+            },
+            Tool {
+                name: "bulk_download_media".to_string(),
+                description: "Download all photos and documents from a chat within a date range".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "entity": {
+                            "type": "string",
+                            "description": "Chat to download media from"
+                        },
+                        "start_date": {
+                            "type": "string",
+                            "description": "Only include messages after this RFC 3339 timestamp"
+                        },
+                        "end_date": {
+                            "type": "string",
+                            "description": "Only include messages before this RFC 3339 timestamp"
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "description": "Maximum number of files to download (at most 100)",
+                            "default": 20
+                        },
+                        "path": {
+                            "type": "string",
+                            "description": "Directory to save files to (defaults to the downloads directory)"
+                        }
+                    },
+                    "required": ["entity"]
+                }),
+            },
+        ]
+    }
+
+    pub async fn handle_request(&self, request: JsonRpcRequest) -> JsonRpcResponse {
+        debug!("Handling request: {}", request.method);
+
+        match request.method.as_str() {
+            "initialize" => self.handle_initialize(request),
+            "ping" => JsonRpcResponse::success(request.id, json!({})),
+            "tools/list" => {
+                JsonRpcResponse::success(request.id, json!({ "tools": self.get_tools() }))
+            }
+            "tools/call" => self.handle_call_tool(request).await,
+            _ => {
+                warn!("Unknown method: {}", request.method);
+                JsonRpcResponse::error(request.id, -32601, "Method not found", None)
+            }
+        }
+    }
+
+    fn handle_initialize(&self, request: JsonRpcRequest) -> JsonRpcResponse {
+        JsonRpcResponse::success(
+            request.id,
+            json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": {
+                    "tools": {}
+                },
+                "serverInfo": {
+                    "name": "mcp-telegram",
+                    "version": env!("CARGO_PKG_VERSION")
+                }
+            }),
+        )
+    }
+
+    async fn handle_call_tool(&self, request: JsonRpcRequest) -> JsonRpcResponse {
+        let params: CallToolParams = match request.params.map(serde_json::from_value) {
+            Some(Ok(params)) => params,
+            Some(Err(e)) => {
+                return JsonRpcResponse::error(
+                    request.id,
+                    -32602,
+                    &format!("Invalid params: {}", e),
+                    None,
+                );
+            }
+            None => return JsonRpcResponse::error(request.id, -32602, "Missing params", None),
+        };
+
+        debug!("Calling tool: {}", params.name);
+
+        let args = params.arguments.unwrap_or_else(|| json!({}));
+        let result = match self.call_tool(&params.name, &args).await {
+            Some(result) => result,
+            None => {
+                return JsonRpcResponse::error(
+                    request.id,
+                    -32601,
+                    &format!("Unknown tool: {}", params.name),
+                    None,
+                );
+            }
+        };
+
+        if let Err(e) = &result {
+            error!("Tool {} failed: {}", params.name, e);
+        }
+
+        match serde_json::to_value(tool_result(result)) {
+            Ok(value) => JsonRpcResponse::success(request.id, value),
+            Err(e) => {
+                JsonRpcResponse::error(request.id, -32603, &format!("Internal error: {}", e), None)
+            }
+        }
+    }
+
+    /// Returns `None` for unknown tools so the caller can report a protocol error.
+    async fn call_tool(&self, name: &str, args: &Value) -> Option<Result<Value, TelegramError>> {
+        let result = match name {
+            "send_message" => self.call_send_message(args).await,
+            "schedule_message" => self.call_schedule_message(args).await,
+            "list_scheduled_messages" => self.call_list_scheduled_messages(args).await,
+            "delete_scheduled_message" => self.call_delete_scheduled_message(args).await,
+            "edit_message" => self.call_edit_message(args).await,
+            "delete_message" => self.call_delete_message(args).await,
+            "get_messages" => self.call_get_messages(args).await,
+            "mark_dialog_read" => self.call_mark_dialog_read(args).await,
+            "search_messages" => self.call_search_messages(args).await,
+            "search_dialogs" => self.call_search_dialogs(args).await,
+            "mute_chat" => self.call_mute_chat(args).await,
+            "unmute_chat" => self.call_unmute_chat(args).await,
+            "list_contacts" => self.call_list_contacts().await,
+            "add_contact" => self.call_add_contact(args).await,
+            "delete_contact" => self.call_delete_contact(args).await,
+            "message_from_link" => self.call_message_from_link(args).await,
+            "get_draft" => self.call_get_draft(args).await,
+            "set_draft" => self.call_set_draft(args).await,
+            "get_message_reactions" => self.call_get_message_reactions(args).await,
+            "get_read_participants" => self.call_get_read_participants(args).await,
+            "get_media_info" => self.call_get_media_info(args).await,
+            "media_download" => self.call_media_download(args).await,
+            "bulk_download_media" => self.call_bulk_download_media(args).await,
+            _ => return None,
+        };
+
+        Some(result)
+    }
+
+    async fn call_send_message(&self, args: &Value) -> Result<Value, TelegramError> {
+        let entity: String = required_param(args, "entity")?;
+        let message: String = required_param(args, "message")?;
+        let reply_to: Option<i32> = optional_param(args, "reply_to")?;
+        let defaults = SendOptions::default();
+        let options = SendOptions {
+            silent: optional_param(args, "silent")?.unwrap_or(defaults.silent),
+            disable_preview: optional_param(args, "disable_preview")?
+                .unwrap_or(defaults.disable_preview),
+        };
+
+        let no_split: bool = optional_param(args, "no_split")?.unwrap_or(false);
+
+        let message_ids = self
+            .client
+            .send_message(&entity, &message, reply_to, options, no_split)
+            .await?;
+
+        Ok(json!({
+            "message_ids": message_ids,
+            "entity": entity
+        }))
+    }
+
+    async fn call_schedule_message(&self, args: &Value) -> Result<Value, TelegramError> {
+        let entity: String = required_param(args, "entity")?;
+        let message: String = required_param(args, "message")?;
+        let schedule_date = optional_date_param(args, "schedule_date")?.ok_or_else(|| {
+            TelegramError::InvalidArguments(
+                "Missing required parameter 'schedule_date'".to_string(),
+            )
+        })?;
+
+        let message_id = self
+            .client
+            .schedule_message(&entity, &message, schedule_date)
+            .await?;
+
+        Ok(json!({
+            "message_id": message_id,
+            "entity": entity,
+            "schedule_date": schedule_date.to_rfc3339()
+        }))
+    }
+
+    async fn call_list_scheduled_messages(&self, args: &Value) -> Result<Value, TelegramError> {
+        let entity: String = required_param(args, "entity")?;
+
+        let messages = self.client.list_scheduled_messages(&entity).await?;
+
+        Ok(serde_json::to_value(messages)?)
+    }
+
+    async fn call_delete_scheduled_message(&self, args: &Value) -> Result<Value, TelegramError> {
+        let entity: String = required_param(args, "entity")?;
+        let message_id: i32 = required_param(args, "message_id")?;
+
+        self.client
+            .delete_scheduled_messages(&entity, &[message_id])
+            .await?;
+
+        Ok(json!(format!(
+            "Deleted scheduled message {} in {}",
+            message_id, entity
+        )))
+    }
+
+    async fn call_edit_message(&self, args: &Value) -> Result<Value, TelegramError> {
+        let entity: String = required_param(args, "entity")?;
+        let message_id: i32 = required_param(args, "message_id")?;
+        let message: String = required_param(args, "message")?;
+
+        self.client
+            .edit_message(&entity, message_id, &message)
+            .await?;
+
+        Ok(json!(format!(
+            "Message {} edited in {}",
+            message_id, entity
+        )))
+    }
+
+    async fn call_delete_message(&self, args: &Value) -> Result<Value, TelegramError> {
+        let entity: String = required_param(args, "entity")?;
+        let message_ids: Vec<i32> = required_param(args, "message_ids")?;
+
+        self.client.delete_messages(&entity, &message_ids).await?;
+
+        Ok(json!(format!(
+            "Deleted {} messages in {}",
+            message_ids.len(),
+            entity
+        )))
+    }
+
+    async fn call_get_messages(&self, args: &Value) -> Result<Value, TelegramError> {
+        let entity: String = required_param(args, "entity")?;
+        let limit: usize = optional_param(args, "limit")?.unwrap_or(10);
+        let start_date = optional_date_param(args, "start_date")?;
+        let end_date = optional_date_param(args, "end_date")?;
+        let options = GetMessagesOptions {
+            unread_only: optional_param(args, "unread")?.unwrap_or(false),
+            mark_as_read: optional_param(args, "mark_as_read")?.unwrap_or(false),
+            include_reply_context: optional_param(args, "include_reply_context")?.unwrap_or(false),
+        };
+
+        let messages = self
+            .client
+            .get_messages(&entity, limit, start_date, end_date, options)
+            .await?;
+
+        Ok(serde_json::to_value(messages)?)
+    }
+
+    async fn call_mark_dialog_read(&self, args: &Value) -> Result<Value, TelegramError> {
+        let entity: String = required_param(args, "entity")?;
+
+        let result = self.client.mark_dialog_read(&entity).await?;
+
+        Ok(serde_json::to_value(result)?)
+    }
+
+    async fn call_search_messages(&self, args: &Value) -> Result<Value, TelegramError> {
+        let entity: String = required_param(args, "entity")?;
+        let query: String = required_param(args, "query")?;
+        let limit: usize = optional_param(args, "limit")?.unwrap_or(20);
+
+        let hits = self.client.search_messages(&entity, &query, limit).await?;
+
+        Ok(serde_json::to_value(hits)?)
+    }
+
+    async fn call_mute_chat(&self, args: &Value) -> Result<Value, TelegramError> {
+        let entity: String = required_param(args, "entity")?;
+        let duration: String =
+            optional_param(args, "duration")?.unwrap_or_else(|| "forever".to_string());
+
+        let setting = self.client.mute_chat(&entity, &duration).await?;
+
+        Ok(serde_json::to_value(setting)?)
+    }
+
+    async fn call_unmute_chat(&self, args: &Value) -> Result<Value, TelegramError> {
+        let entity: String = required_param(args, "entity")?;
+
+        let setting = self.client.unmute_chat(&entity).await?;
+
+        Ok(serde_json::to_value(setting)?)
+    }
+
+    async fn call_list_contacts(&self) -> Result<Value, TelegramError> {
+        let contacts = self.client.list_contacts().await?;
+
+        Ok(serde_json::to_value(contacts)?)
+    }
+
+    async fn call_add_contact(&self, args: &Value) -> Result<Value, TelegramError> {
+        let phone: String = required_param(args, "phone")?;
+        let first_name: String = required_param(args, "first_name")?;
+        let last_name: String = optional_param(args, "last_name")?.unwrap_or_default();
+
+        let contact = self
+            .client
+            .add_contact(&phone, &first_name, &last_name)
+            .await?;
+
+        Ok(serde_json::to_value(contact)?)
+    }
+
+    async fn call_delete_contact(&self, args: &Value) -> Result<Value, TelegramError> {
+        let user_id: i64 = required_param(args, "user_id")?;
+
+        let contact = self.client.delete_contact(user_id).await?;
+
+        Ok(serde_json::to_value(contact)?)
+    }
+
+    async fn call_search_dialogs(&self, args: &Value) -> Result<Value, TelegramError> {
+        let query: String = required_param(args, "query")?;
+        let limit: usize = optional_param(args, "limit")?.unwrap_or(10);
+        let global_search: bool = optional_param(args, "global_search")?.unwrap_or(false);
+
+        let dialogs = self
+            .client
+            .search_dialogs(&query, limit, global_search)
+            .await?;
+
+        Ok(serde_json::to_value(dialogs)?)
+    }
+
+    async fn call_message_from_link(&self, args: &Value) -> Result<Value, TelegramError> {
+        let link: String = required_param(args, "link")?;
+
+        let message = self.client.message_from_link(&link).await?;
+
+        Ok(serde_json::to_value(message)?)
+    }
+
+    async fn call_get_draft(&self, args: &Value) -> Result<Value, TelegramError> {
+        let entity: String = required_param(args, "entity")?;
+
+        let draft = self.client.get_draft(&entity).await?;
+
+        Ok(json!(draft))
+    }
+
+    async fn call_set_draft(&self, args: &Value) -> Result<Value, TelegramError> {
+        let entity: String = required_param(args, "entity")?;
+        let message: String = required_param(args, "message")?;
+
+        self.client.set_draft(&entity, &message).await?;
+
+        Ok(json!(format!("Draft updated for {}", entity)))
+    }
+
+    async fn call_get_message_reactions(&self, args: &Value) -> Result<Value, TelegramError> {
+        let entity: String = required_param(args, "entity")?;
+        let message_id: i32 = required_param(args, "message_id")?;
+        let limit: usize = optional_param(args, "limit")?.unwrap_or(50);
+
+        let reactions = self
+            .client
+            .get_message_reactions(&entity, message_id, limit)
+            .await?;
+
+        Ok(serde_json::to_value(reactions)?)
+    }
+
+    async fn call_get_read_participants(&self, args: &Value) -> Result<Value, TelegramError> {
+        let entity: String = required_param(args, "entity")?;
+        let message_id: i32 = required_param(args, "message_id")?;
+
+        let participants = self
+            .client
+            .get_read_participants(&entity, message_id)
+            .await?;
+
+        Ok(serde_json::to_value(participants)?)
+    }
+
+    async fn call_get_media_info(&self, args: &Value) -> Result<Value, TelegramError> {
+        let entity: String = required_param(args, "entity")?;
+        let message_id: i32 = required_param(args, "message_id")?;
+
+        let info = self.client.get_media_info(&entity, message_id).await?;
+
+        Ok(serde_json::to_value(info)?)
+    }
+
+    async fn call_media_download(&self, args: &Value) -> Result<Value, TelegramError> {
+        let entity: String = required_param(args, "entity")?;
+        let message_id: i32 = required_param(args, "message_id")?;
+        let path: Option<String> = optional_param(args, "path")?;
+
+        let downloaded = self
+            .client
+            .download_media(&entity, message_id, path.as_deref())
+            .await?;
+
+        Ok(serde_json::to_value(downloaded)?)
+    }
+
+    async fn call_bulk_download_media(&self, args: &Value) -> Result<Value, TelegramError> {
+        let entity: String = required_param(args, "entity")?;
+        let start_date = optional_date_param(args, "start_date")?;
+        let end_date = optional_date_param(args, "end_date")?;
+        let limit: usize = optional_param(args, "limit")?.unwrap_or(20);
+        let path: Option<String> = optional_param(args, "path")?;
+
+        let result = self
+            .client
+            .bulk_download_media(&entity, start_date, end_date, limit, path.as_deref())
+            .await?;
+
+        Ok(serde_json::to_value(result)?)
+    }
+
+    pub async fn run_stdio(&self) -> Result<()> {
+        let stdin = tokio::io::stdin();
+        let mut stdout = tokio::io::stdout();
+        let mut reader = BufReader::new(stdin);
+        let mut line = String::new();
+
+        info!("MCP Telegram server running on stdio");
+
+        loop {
+            line.clear();
+            match reader.read_line(&mut line).await {
+                Ok(0) => break, // EOF
+                Ok(_) => {
+                    let trimmed = line.trim();
+                    if trimmed.is_empty() {
+                        continue;
+                    }
+
+                    let response = match serde_json::from_str::<JsonRpcRequest>(trimmed) {
+                        Ok(request) => {
+                            // Notifications carry no id and must not be answered
+                            if request.id.is_none() {
+                                debug!("Received notification: {}", request.method);
+                                continue;
+                            }
+                            self.handle_request(request).await
+                        }
+                        Err(e) => {
+                            error!("Failed to parse JSON-RPC request: {}", e);
+                            JsonRpcResponse::error(
+                                None,
+                                -32700,
+                                "Parse error",
+                                Some(json!({"error": e.to_string()})),
+                            )
+                        }
+                    };
+
+                    let response_json = serde_json::to_string(&response)?;
+                    stdout.write_all(response_json.as_bytes()).await?;
+                    stdout.write_all(b"\n").await?;
+                    stdout.flush().await?;
+                }
+                Err(e) => {
+                    error!("Failed to read from stdin: {}", e);
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Wraps a tool outcome in the MCP `CallToolResult` envelope. Handler errors
+/// become `isError: true` results rather than JSON-RPC errors, as clients expect.
+pub fn tool_result(result: Result<Value, TelegramError>) -> CallToolResult {
+    match result {
+        Ok(Value::String(text)) => CallToolResult {
+            content: vec![Content::text(text)],
+            is_error: false,
+        },
+        Ok(value) => CallToolResult {
+            content: vec![Content::text(
+                serde_json::to_string_pretty(&value).unwrap_or_else(|_| value.to_string()),
+            )],
+            is_error: false,
+        },
+        Err(e) => CallToolResult {
+            content: vec![Content::text(format!("Error: {}", e))],
+            is_error: true,
+        },
+    }
+}
+
+fn required_param<T>(args: &Value, name: &str) -> Result<T, TelegramError>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let value = args.get(name).filter(|v| !v.is_null()).ok_or_else(|| {
+        TelegramError::InvalidArguments(format!("Missing required parameter '{}'", name))
+    })?;
+
+    serde_json::from_value(value.clone()).map_err(|e| {
+        TelegramError::InvalidArguments(format!("Parameter '{}' is invalid: {}", name, e))
+    })
+}
+
+fn optional_param<T>(args: &Value, name: &str) -> Result<Option<T>, TelegramError>
+where
+    T: serde::de::DeserializeOwned,
+{
+    match args.get(name) {
+        Some(value) if !value.is_null() => {
+            serde_json::from_value(value.clone())
+                .map(Some)
+                .map_err(|e| {
+                    TelegramError::InvalidArguments(format!(
+                        "Parameter '{}' is invalid: {}",
+                        name, e
+                    ))
+                })
+        }
+        _ => Ok(None),
+    }
+}
+
+fn optional_date_param(args: &Value, name: &str) -> Result<Option<DateTime<Utc>>, TelegramError> {
+    match optional_param::<String>(args, name)? {
+        Some(value) => DateTime::parse_from_rfc3339(&value)
+            .map(|date| Some(date.with_timezone(&Utc)))
+            .map_err(|e| {
+                TelegramError::InvalidArguments(format!(
+                    "Parameter '{}' must be an RFC 3339 timestamp: {}",
+                    name, e
+                ))
+            }),
+        None => Ok(None),
+    }
+}
 
 pub async fn run() -> Result<()> {
-    info!("Starting MCP Telegram server (placeholder)");
-    info!("Server would handle MCP requests here");
-    
-    // This is a minimal placeholder that compiles
-    // TODO: Implement proper MCP server using rust-mcp-sdk
-    
-    Ok(())
-}
\ No newline at end of file
+    let config = TelegramConfig::from_env()?;
+    let mut client = TelegramClient::new(config)?;
+    client.connect().await?;
+
+    if !client.is_authorized().await {
+        return Err(anyhow!(
+            "Not logged in to Telegram. Run `mcp-telegram login` first."
+        ));
+    }
+
+    let server = TelegramMcpServer::new(client);
+    server.run_stdio().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handler_error_maps_to_is_error_result() {
+        let result = tool_result(Err(TelegramError::InvalidEntity(
+            "Could not resolve entity: nobody".to_string(),
+        )));
+        assert!(result.is_error);
+
+        let value = serde_json::to_value(&result).unwrap();
+        assert_eq!(value["isError"], true);
+        assert_eq!(value["content"][0]["type"], "text");
+        assert!(value["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("Could not resolve entity: nobody"));
+    }
+
+    #[test]
+    fn test_handler_success_maps_to_text_content() {
+        let result = tool_result(Ok(json!("Message sent to me")));
+        let value = serde_json::to_value(&result).unwrap();
+        assert_eq!(value["isError"], false);
+        assert_eq!(value["content"][0]["text"], "Message sent to me");
+    }
+
+    #[test]
+    fn test_required_param_reports_missing_field() {
+        let err = required_param::<String>(&json!({}), "entity").unwrap_err();
+        assert!(
+            matches!(err, TelegramError::InvalidArguments(ref msg) if msg.contains("'entity'"))
+        );
+    }
+}
@@ -1,25 +1,93 @@
-use crate::config::{get_downloads_dir, get_session_file, TelegramConfig};
+use crate::config::{get_downloads_dir, get_session_file, ProxyConfig, TelegramConfig};
 use crate::error::TelegramError;
-use crate::types::{Dialog, DownloadedMedia, Media, Message, Messages};
-use crate::utils::{get_unique_filename, parse_entity, parse_telegram_url};
+use crate::types::{
+    BulkDownloadResult, Contact, Dialog, DialogReadResult, DownloadedMedia, MediaFile, MediaInfo,
+    Message, MessageReactions, Messages, MuteSetting, ReadParticipant, SearchHit,
+};
+use crate::utils::{
+    attach_reply_context, cache_peers, contacts_request, delete_contact_request,
+    delete_scheduled_request, dialog_draft, dialog_read_state, found_dialogs, get_unique_filename,
+    global_search_request, import_contact_request, is_unread, message_length, mute_setting,
+    normalize_phone, notify_settings_request, packed_peer_kind, parse_mute_duration, parse_peer_id,
+    parse_telegram_url, peer_dialog_request, reaction_counts, reactions_list_request, reactors,
+    read_history_request, read_inbox_max_id, read_participants, reply_ids_to_resolve,
+    save_draft_request, scheduled_history_request, search_hits, search_messages_request,
+    select_media_messages, send_message_request, sent_message_id, split_message,
+    unavailable_reason, validate_schedule_date, GetMessagesOptions, MediaCandidate, PeerKind,
+    ReadHistoryRequest, SendOptions, MAX_BULK_DOWNLOADS, MAX_BULK_SCAN_MESSAGES,
+    MAX_MESSAGE_LENGTH, MAX_REPLY_CONTEXT_FETCHES,
+};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use grammers_client::{Client, Config, SignInError};
-use grammers_session::Session;
-use grammers_tl_types::enums::{InputPeer, MessageMedia};
-use grammers_tl_types::types::{InputPeerChannel, InputPeerChat, InputPeerUser};
+use futures::stream::{self, StreamExt};
+use grammers_client::types::{Downloadable, LoginToken, PasswordToken};
+use grammers_client::{
+    Client, Config, InitParams, InputMessage, InvocationError, SignInError, Update,
+};
+use grammers_session::{PackedChat, Session};
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
+use std::sync::Arc;
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Number of media files downloaded in parallel by `bulk_download_media`.
+const BULK_DOWNLOAD_CONCURRENCY: usize = 4;
+
+/// Reads updates in the background, starting with the ones missed while
+/// offline, and caches the chats they mention. Runs until the connection drops.
+fn spawn_update_listener(
+    client: Client,
+    cache: Arc<RwLock<HashMap<String, PackedChat>>>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match client.next_update().await {
+                Ok(update) => {
+                    let peers = update_peers(&update);
+                    if !peers.is_empty() {
+                        cache_peers(&mut *cache.write().await, &peers);
+                    }
+                }
+                Err(e) => {
+                    warn!("Stopped processing updates: {}", e);
+                    break;
+                }
+            }
+        }
+    })
+}
+
+/// The chats an update refers to, with their usernames.
+fn update_peers(update: &Update) -> Vec<(PackedChat, Option<String>)> {
+    let chats = match update {
+        Update::NewMessage(message) | Update::MessageEdited(message) => {
+            let mut chats = vec![message.chat()];
+            chats.extend(message.sender());
+            chats
+        }
+        Update::CallbackQuery(query) => vec![query.chat().clone(), query.sender().clone()],
+        _ => Vec::new(),
+    };
+
+    chats
+        .iter()
+        .map(|chat| (chat.pack(), chat.username().map(str::to_string)))
+        .collect()
+}
 
 pub struct TelegramClient {
     client: Option<Client>,
     config: TelegramConfig,
     session_file: std::path::PathBuf,
     downloads_dir: std::path::PathBuf,
-    entities_cache: RwLock<HashMap<String, InputPeer>>,
+    entities_cache: Arc<RwLock<HashMap<String, PackedChat>>>,
+    update_task: Option<JoinHandle<()>>,
+    login_token: Option<LoginToken>,
+    password_token: Option<PasswordToken>,
 }
 
 impl TelegramClient {
@@ -38,7 +106,10 @@ impl TelegramClient {
             config,
             session_file,
             downloads_dir,
-            entities_cache: RwLock::new(HashMap::new()),
+            entities_cache: Arc::new(RwLock::new(HashMap::new())),
+            update_task: None,
+            login_token: None,
+            password_token: None,
         })
     }
 
@@ -48,13 +119,43 @@ impl TelegramClient {
         }
 
         let session = Session::load_file(&self.session_file).unwrap_or_else(|_| Session::new());
+
+        let mut params = InitParams {
+            catch_up: self.config.catch_up,
+            ..InitParams::default()
+        };
+        if let Some(proxy) = &self.config.proxy {
+            if let ProxyConfig::MtProxy { .. } = proxy {
+                return Err(TelegramError::Proxy(
+                    "MTProxy is not supported by the grammers transport; use a SOCKS5 proxy"
+                        .to_string(),
+                ));
+            }
+            params.proxy_url = Some(proxy.to_url());
+        }
+
         let client = Client::connect(Config {
             session,
             api_id: self.config.api_id,
             api_hash: self.config.api_hash.clone(),
-            params: Default::default(),
+            params,
         })
-        .await?;
+        .await
+        .map_err(|e| match &self.config.proxy {
+            Some(proxy) => TelegramError::Proxy(format!(
+                "Failed to connect through proxy {}: {}",
+                proxy.address(),
+                e
+            )),
+            None => TelegramError::Config(format!("Failed to connect to Telegram: {}", e)),
+        })?;
+
+        if self.config.catch_up {
+            self.update_task = Some(spawn_update_listener(
+                client.clone(),
+                Arc::clone(&self.entities_cache),
+            ));
+        }
 
         self.client = Some(client);
         info!("Connected to Telegram");
@@ -62,6 +163,9 @@ impl TelegramClient {
     }
 
     pub async fn disconnect(&mut self) -> Result<(), TelegramError> {
+        if let Some(task) = self.update_task.take() {
+            task.abort();
+        }
         if let Some(client) = self.client.take() {
             client.session().save_to_file(&self.session_file)?;
             info!("Disconnected from Telegram and saved session");
@@ -69,6 +173,25 @@ impl TelegramClient {
         Ok(())
     }
 
+    /// Invalidates the session on Telegram's side via `auth.logOut`.
+    ///
+    /// Connects with the stored session first if needed. Returns `false` when
+    /// the session was not authorized, so there was nothing to revoke. The
+    /// session is not saved afterwards; callers remove the local file.
+    pub async fn log_out(&mut self) -> Result<bool, TelegramError> {
+        self.connect().await?;
+        if !self.is_authorized().await {
+            self.client = None;
+            return Ok(false);
+        }
+
+        if let Some(client) = self.client.take() {
+            client.sign_out().await?;
+            info!("Revoked Telegram session");
+        }
+        Ok(true)
+    }
+
     pub async fn is_authorized(&self) -> bool {
         match &self.client {
             Some(client) => client.is_authorized().await.unwrap_or(false),
@@ -77,21 +200,32 @@ impl TelegramClient {
     }
 
     pub async fn sign_in_with_phone(&mut self, phone: &str) -> Result<(), TelegramError> {
-        let client = self.client.as_mut().ok_or_else(|| {
+        let client = self.client.as_ref().ok_or_else(|| {
             TelegramError::Config("Client not connected. Call connect() first.".to_string())
         })?;
 
-        client.request_login_code(phone).await?;
+        let token = client
+            .request_login_code(phone)
+            .await
+            .map_err(|e| TelegramError::Config(format!("Failed to request login code: {}", e)))?;
+        self.login_token = Some(token);
         Ok(())
     }
 
     pub async fn sign_in_with_code(&mut self, code: &str) -> Result<(), TelegramError> {
-        let client = self.client.as_mut().ok_or_else(|| {
+        let client = self.client.as_ref().ok_or_else(|| {
             TelegramError::Config("Client not connected. Call connect() first.".to_string())
         })?;
+        let token = self.login_token.as_ref().ok_or_else(|| {
+            TelegramError::Config(
+                "No login code requested. Call sign_in_with_phone() first.".to_string(),
+            )
+        })?;
 
-        match client.sign_in_code(code).await {
-            Err(SignInError::PasswordRequired(_)) => {
+        let result = client.sign_in(token, code).await;
+        match result {
+            Err(SignInError::PasswordRequired(password_token)) => {
+                self.password_token = Some(password_token);
                 Err(TelegramError::Config("2FA password required".to_string()))
             }
             Err(e) => Err(TelegramError::Config(format!("Sign in error: {:?}", e))),
@@ -103,109 +237,253 @@ impl TelegramClient {
     }
 
     pub async fn sign_in_with_password(&mut self, password: &str) -> Result<(), TelegramError> {
-        let client = self.client.as_mut().ok_or_else(|| {
+        let client = self.client.as_ref().ok_or_else(|| {
             TelegramError::Config("Client not connected. Call connect() first.".to_string())
         })?;
+        let token = self.password_token.take().ok_or_else(|| {
+            TelegramError::Config(
+                "No 2FA password requested. Call sign_in_with_code() first.".to_string(),
+            )
+        })?;
 
-        client.check_password(password).await?;
+        client
+            .check_password(token, password)
+            .await
+            .map_err(|e| TelegramError::Config(format!("Sign in error: {}", e)))?;
         info!("Successfully signed in with 2FA password");
         Ok(())
     }
 
-    async fn resolve_entity(&self, entity: &str) -> Result<InputPeer, TelegramError> {
-        let client = self.client.as_ref().ok_or_else(|| {
-            TelegramError::Config("Client not connected".to_string())
-        })?;
+    async fn resolve_entity(&self, entity: &str) -> Result<PackedChat, TelegramError> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
 
         // Check cache first
         {
             let cache = self.entities_cache.read().await;
             if let Some(cached) = cache.get(entity) {
-                return Ok(cached.clone());
+                return Ok(*cached);
             }
         }
 
-        // Try to parse as numeric ID first
-        let input_peer = if let Ok(id) = parse_entity(entity) {
-            // For numeric IDs, we need to determine the peer type
-            // This is a limitation - in a real implementation, you'd need to
-            // store peer type information or use different methods
-            InputPeer::User(InputPeerUser {
-                user_id: id,
-                access_hash: 0, // This would need to be resolved properly
-            })
+        // Numeric IDs carry their peer kind; the access hash comes from the dialogs
+        let packed = if let Some((kind, id)) = parse_peer_id(entity) {
+            self.find_dialog_peer(client, kind, id).await?.ok_or_else(|| {
+                TelegramError::InvalidEntity(format!(
+                    "{} is not among the dialogs, so its access hash is unknown; use its username instead",
+                    entity
+                ))
+            })?
         } else if entity == "me" {
             // Special case for self
-            let me = client.get_me().await?;
-            InputPeer::User(InputPeerUser {
-                user_id: me.id,
-                access_hash: me.access_hash.unwrap_or(0),
-            })
+            client.get_me().await?.pack()
         } else {
             // Try to resolve as username
-            match client.resolve_username(entity).await {
-                Ok(resolved) => match resolved {
-                    Peer::User(user) => InputPeer::User(InputPeerUser {
-                        user_id: user.user_id,
-                        access_hash: 0, // Would need proper resolution
-                    }),
-                    Peer::Chat(chat) => InputPeer::Chat(InputPeerChat {
-                        chat_id: chat.chat_id,
-                    }),
-                    Peer::Channel(channel) => InputPeer::Channel(InputPeerChannel {
-                        channel_id: channel.channel_id,
-                        access_hash: 0, // Would need proper resolution
-                    }),
-                },
-                Err(_) => {
+            match client
+                .resolve_username(entity.trim_start_matches('@'))
+                .await
+            {
+                Ok(Some(chat)) => chat.pack(),
+                Ok(None) | Err(InvocationError::Rpc(_)) => {
                     return Err(TelegramError::InvalidEntity(format!(
                         "Could not resolve entity: {}",
                         entity
                     )));
                 }
+                Err(e) => return Err(e.into()),
             }
         };
 
         // Cache the result
         {
             let mut cache = self.entities_cache.write().await;
-            cache.insert(entity.to_string(), input_peer.clone());
+            cache.insert(entity.to_string(), packed);
+        }
+
+        Ok(packed)
+    }
+
+    /// Looks a peer up in the dialog list to recover its access hash. Every
+    /// dialog passed on the way is cached, so later lookups skip the scan.
+    async fn find_dialog_peer(
+        &self,
+        client: &Client,
+        kind: PeerKind,
+        id: i64,
+    ) -> Result<Option<PackedChat>, TelegramError> {
+        let mut dialogs = client.iter_dialogs();
+        let mut scanned = Vec::new();
+        let mut found = None;
+
+        while let Some(dialog) = dialogs.next().await? {
+            let chat = dialog.chat();
+            let packed = chat.pack();
+            scanned.push((packed, chat.username().map(str::to_string)));
+            if packed.id == id && packed_peer_kind(packed.ty) == kind {
+                found = Some(packed);
+                break;
+            }
         }
 
-        Ok(input_peer)
+        cache_peers(&mut *self.entities_cache.write().await, &scanned);
+        Ok(found)
     }
 
+    /// Sends `message`, splitting text over Telegram's length limit into several messages
+    /// unless `no_split` is set, and returns the ids of everything sent in order.
     pub async fn send_message(
         &self,
         entity: &str,
         message: &str,
-        file_paths: Option<&[String]>,
         reply_to: Option<i32>,
-    ) -> Result<(), TelegramError> {
-        let client = self.client.as_ref().ok_or_else(|| {
-            TelegramError::Config("Client not connected".to_string())
-        })?;
+        options: SendOptions,
+        no_split: bool,
+    ) -> Result<Vec<i32>, TelegramError> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
+
+        let length = message_length(message);
+        let parts = if length <= MAX_MESSAGE_LENGTH {
+            vec![message.to_string()]
+        } else if no_split {
+            return Err(TelegramError::InvalidArguments(format!(
+                "Message is {} UTF-16 code units long; Telegram allows at most {}",
+                length, MAX_MESSAGE_LENGTH
+            )));
+        } else {
+            split_message(message, MAX_MESSAGE_LENGTH)
+        };
+
+        let chat = self.resolve_entity(entity).await?;
 
-        // Validate file paths if provided
-        if let Some(paths) = file_paths {
-            for path in paths {
-                let path_obj = Path::new(path);
-                if !path_obj.exists() || !path_obj.is_file() {
-                    return Err(TelegramError::FileNotFound(path.to_string()));
+        let mut message_ids = Vec::with_capacity(parts.len());
+        for (index, part) in parts.iter().enumerate() {
+            // Only the first part answers `reply_to`; the rest follow it in the chat
+            let reply_to = if index == 0 { reply_to } else { None };
+            let random_id = Uuid::new_v4().as_u64_pair().0 as i64;
+            let request =
+                send_message_request(chat.to_input_peer(), part, reply_to, options, random_id);
+            let sent = client
+                .invoke(&request)
+                .await
+                .map_err(TelegramError::from)
+                .and_then(|updates| {
+                    sent_message_id(&updates, random_id).ok_or_else(|| {
+                        TelegramError::Config(
+                            "Telegram did not report the id of the sent message".to_string(),
+                        )
+                    })
+                });
+            match sent {
+                Ok(id) => message_ids.push(id),
+                // Report what was already delivered so a retry doesn't send it twice
+                Err(e) if !message_ids.is_empty() => {
+                    return Err(TelegramError::PartialSend {
+                        sent: message_ids,
+                        total: parts.len(),
+                        source: Box::new(e),
+                    });
                 }
+                Err(e) => return Err(e),
             }
         }
 
-        let input_peer = self.resolve_entity(entity).await?;
+        debug!(
+            "Message sent to {} in {} part(s)",
+            entity,
+            message_ids.len()
+        );
+        Ok(message_ids)
+    }
+
+    /// Queues a text message for delivery at `schedule_date` and returns its scheduled message id.
+    pub async fn schedule_message(
+        &self,
+        entity: &str,
+        message: &str,
+        schedule_date: DateTime<Utc>,
+    ) -> Result<i32, TelegramError> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
+
+        validate_schedule_date(schedule_date, Utc::now())?;
+
+        let chat = self.resolve_entity(entity).await?;
+        let input_message = InputMessage::text(message).schedule_date(Some(schedule_date.into()));
+        let sent = client.send_message(chat, input_message).await?;
+
+        debug!(
+            "Message {} scheduled in {} for {}",
+            sent.id(),
+            entity,
+            schedule_date
+        );
+        Ok(sent.id())
+    }
+
+    /// Returns the messages still queued for delivery in `entity`.
+    pub async fn list_scheduled_messages(
+        &self,
+        entity: &str,
+    ) -> Result<Vec<Message>, TelegramError> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
+
+        let chat = self.resolve_entity(entity).await?;
+        let history = client
+            .invoke(&scheduled_history_request(chat.to_input_peer()))
+            .await?;
+
+        let raw_messages = match history {
+            grammers_tl_types::enums::messages::Messages::Messages(m) => m.messages,
+            grammers_tl_types::enums::messages::Messages::Slice(m) => m.messages,
+            grammers_tl_types::enums::messages::Messages::ChannelMessages(m) => m.messages,
+            grammers_tl_types::enums::messages::Messages::NotModified(_) => Vec::new(),
+        };
 
-        // For now, we'll implement basic text message sending
-        // File sending would require more complex implementation with grammers
+        let messages: Vec<Message> = raw_messages
+            .into_iter()
+            .filter_map(|m| match m {
+                grammers_tl_types::enums::Message::Message(msg) => {
+                    Some(Message::from_grammers_message(&msg, msg.out))
+                }
+                _ => None,
+            })
+            .collect();
+
+        debug!("Found {} scheduled messages in {}", messages.len(), entity);
+        Ok(messages)
+    }
+
+    /// Removes scheduled messages from the queue before they are sent.
+    pub async fn delete_scheduled_messages(
+        &self,
+        entity: &str,
+        message_ids: &[i32],
+    ) -> Result<(), TelegramError> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
+
+        let chat = self.resolve_entity(entity).await?;
         client
-            .send_message(&input_peer, message)
-            .reply_to(reply_to)
+            .invoke(&delete_scheduled_request(chat.to_input_peer(), message_ids))
             .await?;
 
-        debug!("Message sent to {}", entity);
+        debug!(
+            "Deleted {} scheduled messages in {}",
+            message_ids.len(),
+            entity
+        );
         Ok(())
     }
 
@@ -215,14 +493,13 @@ impl TelegramClient {
         message_id: i32,
         new_message: &str,
     ) -> Result<(), TelegramError> {
-        let client = self.client.as_ref().ok_or_else(|| {
-            TelegramError::Config("Client not connected".to_string())
-        })?;
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
 
-        let input_peer = self.resolve_entity(entity).await?;
-        client
-            .edit_message(&input_peer, message_id, new_message)
-            .await?;
+        let chat = self.resolve_entity(entity).await?;
+        client.edit_message(chat, message_id, new_message).await?;
 
         debug!("Message {} edited in {}", message_id, entity);
         Ok(())
@@ -233,12 +510,13 @@ impl TelegramClient {
         entity: &str,
         message_ids: &[i32],
     ) -> Result<(), TelegramError> {
-        let client = self.client.as_ref().ok_or_else(|| {
-            TelegramError::Config("Client not connected".to_string())
-        })?;
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
 
-        let input_peer = self.resolve_entity(entity).await?;
-        client.delete_messages(&input_peer, message_ids).await?;
+        let chat = self.resolve_entity(entity).await?;
+        client.delete_messages(chat, message_ids).await?;
 
         debug!("Deleted {} messages in {}", message_ids.len(), entity);
         Ok(())
@@ -250,70 +528,365 @@ impl TelegramClient {
         limit: usize,
         start_date: Option<DateTime<Utc>>,
         end_date: Option<DateTime<Utc>>,
-        unread_only: bool,
-        mark_as_read: bool,
+        options: GetMessagesOptions,
     ) -> Result<Messages, TelegramError> {
-        let client = self.client.as_ref().ok_or_else(|| {
-            TelegramError::Config("Client not connected".to_string())
-        })?;
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
+
+        let chat = self.resolve_entity(entity).await?;
 
-        let input_peer = self.resolve_entity(entity).await?;
+        let read_up_to = if options.unread_only {
+            let dialogs = client
+                .invoke(&peer_dialog_request(chat.to_input_peer()))
+                .await?;
+            Some(read_inbox_max_id(&dialogs).ok_or_else(|| {
+                TelegramError::InvalidEntity(format!("No dialog found for {}", entity))
+            })?)
+        } else {
+            None
+        };
 
         // Get messages using grammers client
         let mut messages = Vec::new();
-        let mut iter = client.iter_messages(&input_peer);
+        let mut iter = client.iter_messages(chat);
 
         while let Some(message) = iter.next().await? {
             if messages.len() >= limit {
                 break;
             }
 
+            // History runs newest first, so nothing past the read marker is unread
+            if read_up_to.is_some_and(|max_id| message.id() <= max_id) {
+                break;
+            }
+
             // Filter by date if specified
             if let Some(start) = start_date {
-                if message.date() < start.timestamp() as i32 {
+                if message.date() < start {
                     continue;
                 }
             }
 
             if let Some(end) = end_date {
-                if message.date() > end.timestamp() as i32 {
+                if message.date() > end {
                     break;
                 }
             }
 
             // Convert grammers message to our Message type
-            let msg = Message::from_grammers_message(
-                &message.msg,
-                message.outgoing(),
-            );
+            let msg = Message::from_grammers_message(&message.raw, message.outgoing());
 
-            if mark_as_read {
-                // Mark as read if requested
-                // This would require additional implementation
-                warn!("Mark as read not fully implemented");
+            if read_up_to.is_some_and(|max_id| !is_unread(&msg, max_id)) {
+                continue;
             }
 
             messages.push(msg);
         }
 
+        if options.mark_as_read {
+            if let Some(newest) = messages.iter().map(|m| m.message_id).max() {
+                match read_history_request(chat.to_input_peer(), newest) {
+                    ReadHistoryRequest::Messages(request) => {
+                        client.invoke(&request).await?;
+                    }
+                    ReadHistoryRequest::Channel(request) => {
+                        client.invoke(&request).await?;
+                    }
+                }
+                debug!("Marked {} as read up to message {}", entity, newest);
+            }
+        }
+
+        if options.include_reply_context {
+            let reply_ids = reply_ids_to_resolve(&messages, MAX_REPLY_CONTEXT_FETCHES);
+            let fetched: Vec<Message> = if reply_ids.is_empty() {
+                Vec::new()
+            } else {
+                client
+                    .get_messages_by_id(chat, &reply_ids)
+                    .await?
+                    .into_iter()
+                    .flatten()
+                    .map(|m| Message::from_grammers_message(&m.raw, m.outgoing()))
+                    .collect()
+            };
+            debug!(
+                "Resolved {} of {} replied-to messages",
+                fetched.len(),
+                reply_ids.len()
+            );
+            attach_reply_context(&mut messages, &fetched);
+        }
+
         Ok(Messages {
             messages,
             dialog: None, // Would need to fetch dialog info separately
         })
     }
 
+    /// Marks every message in `entity` as read, up to its latest message, and
+    /// reports how many were unread beforehand.
+    pub async fn mark_dialog_read(&self, entity: &str) -> Result<DialogReadResult, TelegramError> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
+
+        let chat = self.resolve_entity(entity).await?;
+        let dialogs = client
+            .invoke(&peer_dialog_request(chat.to_input_peer()))
+            .await?;
+        let (top_message, unread_count) = dialog_read_state(&dialogs).ok_or_else(|| {
+            TelegramError::InvalidEntity(format!("No dialog found for {}", entity))
+        })?;
+
+        match read_history_request(chat.to_input_peer(), top_message) {
+            ReadHistoryRequest::Messages(request) => {
+                client.invoke(&request).await?;
+            }
+            ReadHistoryRequest::Channel(request) => {
+                client.invoke(&request).await?;
+            }
+        }
+
+        debug!(
+            "Marked {} as read up to message {} ({} were unread)",
+            entity, top_message, unread_count
+        );
+        Ok(DialogReadResult {
+            previous_unread_count: unread_count,
+            read_up_to: top_message,
+        })
+    }
+
+    /// Mutes notifications from a chat for `duration` (see `parse_mute_duration`).
+    pub async fn mute_chat(
+        &self,
+        entity: &str,
+        duration: &str,
+    ) -> Result<MuteSetting, TelegramError> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
+
+        let duration = parse_mute_duration(duration)?;
+        let chat = self.resolve_entity(entity).await?;
+        let (setting, mute_until) = mute_setting(duration, Utc::now());
+
+        client
+            .invoke(&notify_settings_request(chat.to_input_peer(), mute_until))
+            .await?;
+
+        debug!("Muted {} until {}", entity, mute_until);
+        Ok(setting)
+    }
+
+    pub async fn unmute_chat(&self, entity: &str) -> Result<MuteSetting, TelegramError> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
+
+        let chat = self.resolve_entity(entity).await?;
+        client
+            .invoke(&notify_settings_request(chat.to_input_peer(), 0))
+            .await?;
+
+        debug!("Unmuted {}", entity);
+        Ok(MuteSetting {
+            muted: false,
+            forever: false,
+            mute_until: None,
+        })
+    }
+
+    /// Fetches the address book as raw users, which carry the access hashes
+    /// needed to act on a contact.
+    async fn contact_users(
+        &self,
+        client: &Client,
+    ) -> Result<Vec<grammers_tl_types::types::User>, TelegramError> {
+        let contacts = client.invoke(&contacts_request()).await?;
+
+        Ok(match contacts {
+            grammers_tl_types::enums::contacts::Contacts::Contacts(c) => c
+                .users
+                .into_iter()
+                .filter_map(|user| match user {
+                    grammers_tl_types::enums::User::User(user) => Some(user),
+                    grammers_tl_types::enums::User::Empty(_) => None,
+                })
+                .collect(),
+            grammers_tl_types::enums::contacts::Contacts::NotModified => Vec::new(),
+        })
+    }
+
+    pub async fn list_contacts(&self) -> Result<Vec<Contact>, TelegramError> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
+
+        let contacts: Vec<Contact> = self
+            .contact_users(client)
+            .await?
+            .iter()
+            .map(Contact::from_user)
+            .collect();
+
+        debug!("Found {} contacts", contacts.len());
+        Ok(contacts)
+    }
+
+    /// Adds a phone number to the address book and returns the Telegram user it belongs to.
+    pub async fn add_contact(
+        &self,
+        phone: &str,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Contact, TelegramError> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
+
+        let phone = normalize_phone(phone)?;
+        let grammers_tl_types::enums::contacts::ImportedContacts::Contacts(imported) = client
+            .invoke(&import_contact_request(&phone, first_name, last_name))
+            .await?;
+
+        if !imported.retry_contacts.is_empty() {
+            return Err(TelegramError::NotAvailable(format!(
+                "Telegram deferred importing {}; try again later",
+                phone
+            )));
+        }
+
+        let user_id = imported
+            .imported
+            .iter()
+            .map(|contact| match contact {
+                grammers_tl_types::enums::ImportedContact::Contact(c) => c.user_id,
+            })
+            .next()
+            .ok_or_else(|| {
+                TelegramError::InvalidArguments(format!("No Telegram account uses {}", phone))
+            })?;
+
+        let contact = imported
+            .users
+            .iter()
+            .find_map(|user| match user {
+                grammers_tl_types::enums::User::User(user) if user.id == user_id => {
+                    Some(Contact::from_user(user))
+                }
+                _ => None,
+            })
+            .ok_or_else(|| {
+                TelegramError::InvalidEntity(format!("User {} missing from import result", user_id))
+            })?;
+
+        debug!("Added contact {} for {}", contact.user_id, phone);
+        Ok(contact)
+    }
+
+    /// Removes a user from the address book and returns the contact as it was.
+    pub async fn delete_contact(&self, user_id: i64) -> Result<Contact, TelegramError> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
+
+        let user = self
+            .contact_users(client)
+            .await?
+            .into_iter()
+            .find(|user| user.id == user_id)
+            .ok_or_else(|| {
+                TelegramError::InvalidEntity(format!("User {} is not a contact", user_id))
+            })?;
+
+        // Telegram rejects a zero hash, so a min user can't be deleted this way
+        let access_hash = user.access_hash.ok_or_else(|| {
+            TelegramError::InvalidEntity(format!("The access hash of user {} is unknown", user_id))
+        })?;
+        client
+            .invoke(&delete_contact_request(user.id, access_hash))
+            .await?;
+
+        debug!("Deleted contact {}", user_id);
+        Ok(Contact::from_user(&user))
+    }
+
+    /// Full-text search within one chat via `messages.search`.
+    pub async fn search_messages(
+        &self,
+        entity: &str,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<SearchHit>, TelegramError> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
+
+        let chat = self.resolve_entity(entity).await?;
+        let found = client
+            .invoke(&search_messages_request(chat.to_input_peer(), query, limit))
+            .await?;
+
+        let raw_messages = match found {
+            grammers_tl_types::enums::messages::Messages::Messages(m) => m.messages,
+            grammers_tl_types::enums::messages::Messages::Slice(m) => m.messages,
+            grammers_tl_types::enums::messages::Messages::ChannelMessages(m) => m.messages,
+            grammers_tl_types::enums::messages::Messages::NotModified(_) => Vec::new(),
+        };
+
+        let messages: Vec<Message> = raw_messages
+            .into_iter()
+            .filter_map(|m| match m {
+                grammers_tl_types::enums::Message::Message(msg) => {
+                    Some(Message::from_grammers_message(&msg, msg.out))
+                }
+                _ => None,
+            })
+            .collect();
+
+        let hits = search_hits(&messages, query);
+        debug!(
+            "Found {} messages matching {:?} in {}",
+            hits.len(),
+            query,
+            entity
+        );
+        Ok(hits)
+    }
+
     pub async fn search_dialogs(
         &self,
         query: &str,
         limit: usize,
         global_search: bool,
     ) -> Result<Vec<Dialog>, TelegramError> {
-        let client = self.client.as_ref().ok_or_else(|| {
-            TelegramError::Config("Client not connected".to_string())
-        })?;
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
+
+        if global_search {
+            let found = client.invoke(&global_search_request(query, limit)).await?;
+            let dialogs = found_dialogs(&found, limit);
+            debug!(
+                "Found {} dialogs matching {:?} globally",
+                dialogs.len(),
+                query
+            );
+            return Ok(dialogs);
+        }
 
-        // This is a simplified implementation
-        // A full implementation would use the contacts.search API
         let mut dialogs = Vec::new();
         let mut iter = client.iter_dialogs();
 
@@ -327,17 +900,20 @@ impl TelegramClient {
 
             // Simple case-insensitive search
             if title.to_lowercase().contains(&query.to_lowercase()) {
-                let dialog_obj = match chat.pack() {
-                    grammers_tl_types::enums::Chat::User(user) => {
-                        Dialog::from_user(&user, true) // Assume can send for now
-                    }
-                    grammers_tl_types::enums::Chat::Chat(chat) => {
-                        Dialog::from_chat(&chat, true)
+                let dialog_obj = match chat {
+                    grammers_client::types::Chat::User(user) => {
+                        Dialog::from_user(&user.raw, true) // Assume can send for now
                     }
-                    grammers_tl_types::enums::Chat::Channel(channel) => {
-                        Dialog::from_channel(&channel, true)
+                    grammers_client::types::Chat::Group(group) => match &group.raw {
+                        grammers_tl_types::enums::Chat::Chat(chat) => Dialog::from_chat(chat, true),
+                        grammers_tl_types::enums::Chat::Channel(channel) => {
+                            Dialog::from_channel(channel, true)
+                        }
+                        _ => continue,
+                    },
+                    grammers_client::types::Chat::Channel(channel) => {
+                        Dialog::from_channel(&channel.raw, true)
                     }
-                    _ => continue,
                 };
 
                 dialogs.push(dialog_obj);
@@ -347,37 +923,195 @@ impl TelegramClient {
         Ok(dialogs)
     }
 
+    /// Returns the unsent draft saved for `entity`, or an empty string when there is none.
     pub async fn get_draft(&self, entity: &str) -> Result<String, TelegramError> {
-        // Draft functionality would need to be implemented with grammers
-        // For now, return empty string
-        warn!("get_draft not fully implemented");
-        Ok(String::new())
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
+
+        let chat = self.resolve_entity(entity).await?;
+        let dialogs = client
+            .invoke(&peer_dialog_request(chat.to_input_peer()))
+            .await?;
+
+        dialog_draft(&dialogs)
+            .ok_or_else(|| TelegramError::InvalidEntity(format!("No dialog found for {}", entity)))
     }
 
+    /// Saves `message` as the draft for `entity`; an empty message clears it.
     pub async fn set_draft(&self, entity: &str, message: &str) -> Result<(), TelegramError> {
-        // Draft functionality would need to be implemented with grammers
-        warn!("set_draft not fully implemented");
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
+
+        let chat = self.resolve_entity(entity).await?;
+        client
+            .invoke(&save_draft_request(chat.to_input_peer(), message))
+            .await?;
+
+        debug!("Draft updated for {}", entity);
         Ok(())
     }
 
+    /// Reaction counts for a message, plus who reacted when the chat allows
+    /// listing reactors (up to `reactor_limit`; 0 skips the listing).
+    pub async fn get_message_reactions(
+        &self,
+        entity: &str,
+        message_id: i32,
+        reactor_limit: usize,
+    ) -> Result<MessageReactions, TelegramError> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
+
+        let chat = self.resolve_entity(entity).await?;
+
+        let message = client
+            .get_messages_by_id(chat, &[message_id])
+            .await?
+            .into_iter()
+            .next()
+            .flatten()
+            .ok_or(TelegramError::InvalidMessageId(message_id))?;
+
+        let Some(raw_reactions) = &message.raw.reactions else {
+            return Ok(MessageReactions {
+                message_id,
+                total: 0,
+                reactions: Vec::new(),
+                reactors: Vec::new(),
+                reactors_unavailable: None,
+            });
+        };
+
+        let reactions = reaction_counts(raw_reactions);
+        let grammers_tl_types::enums::MessageReactions::Reactions(raw) = raw_reactions;
+
+        let (reactors, reactors_unavailable) = if reactor_limit == 0 {
+            (Vec::new(), None)
+        } else if !raw.can_see_list {
+            (
+                Vec::new(),
+                Some("Telegram does not list who reacted in this chat".to_string()),
+            )
+        } else {
+            match client
+                .invoke(&reactions_list_request(
+                    chat.to_input_peer(),
+                    message_id,
+                    reactor_limit,
+                ))
+                .await
+            {
+                Ok(list) => (reactors(&list), None),
+                Err(InvocationError::Rpc(rpc)) if unavailable_reason(&rpc.name).is_some() => (
+                    Vec::new(),
+                    unavailable_reason(&rpc.name).map(str::to_string),
+                ),
+                Err(e) => return Err(e.into()),
+            }
+        };
+
+        Ok(MessageReactions {
+            message_id,
+            total: reactions.iter().map(|r| r.count).sum(),
+            reactions,
+            reactors,
+            reactors_unavailable,
+        })
+    }
+
+    /// Members who have read one of our messages in a small group, via
+    /// `messages.getMessageReadParticipants`.
+    pub async fn get_read_participants(
+        &self,
+        entity: &str,
+        message_id: i32,
+    ) -> Result<Vec<ReadParticipant>, TelegramError> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
+
+        let chat = self.resolve_entity(entity).await?;
+        if chat.is_user() {
+            return Err(TelegramError::NotAvailable(
+                "read receipts are only available in groups".to_string(),
+            ));
+        }
+
+        let request = grammers_tl_types::functions::messages::GetMessageReadParticipants {
+            peer: chat.to_input_peer(),
+            msg_id: message_id,
+        };
+        match client.invoke(&request).await {
+            Ok(dates) => Ok(read_participants(&dates)),
+            Err(InvocationError::Rpc(rpc)) if unavailable_reason(&rpc.name).is_some() => {
+                Err(TelegramError::NotAvailable(
+                    unavailable_reason(&rpc.name)
+                        .unwrap_or_default()
+                        .to_string(),
+                ))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Describes a message's media (type, size, MIME, dimensions, duration)
+    /// without downloading it.
+    pub async fn get_media_info(
+        &self,
+        entity: &str,
+        message_id: i32,
+    ) -> Result<MediaInfo, TelegramError> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
+
+        let chat = self.resolve_entity(entity).await?;
+
+        let message = client
+            .get_messages_by_id(chat, &[message_id])
+            .await?
+            .into_iter()
+            .next()
+            .flatten()
+            .ok_or(TelegramError::InvalidMessageId(message_id))?;
+
+        let media = message.media().ok_or_else(|| {
+            TelegramError::MediaDownload("Message does not contain media".to_string())
+        })?;
+
+        MediaFile::from_client_media(&media)
+            .map(|file| file.info())
+            .ok_or_else(|| TelegramError::MediaDownload("Unsupported media type".to_string()))
+    }
+
     pub async fn download_media(
         &self,
         entity: &str,
         message_id: i32,
         path: Option<&str>,
     ) -> Result<DownloadedMedia, TelegramError> {
-        let client = self.client.as_ref().ok_or_else(|| {
-            TelegramError::Config("Client not connected".to_string())
-        })?;
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
 
-        let input_peer = self.resolve_entity(entity).await?;
+        let chat = self.resolve_entity(entity).await?;
 
         // Get the specific message
         let message = client
-            .get_messages_by_id(&input_peer, &[message_id])
+            .get_messages_by_id(chat, &[message_id])
             .await?
             .into_iter()
             .next()
+            .flatten()
             .ok_or_else(|| TelegramError::InvalidMessageId(message_id))?;
 
         // Check if message has media
@@ -386,15 +1120,9 @@ impl TelegramClient {
         })?;
 
         // Extract media info and create Media object
-        let media_obj = match media {
-            MessageMedia::Document(doc) => Media::from_document(&doc.document),
-            MessageMedia::Photo(photo) => Media::from_photo(&photo.photo),
-            _ => {
-                return Err(TelegramError::MediaDownload(
-                    "Unsupported media type".to_string(),
-                ));
-            }
-        };
+        let media_obj = MediaFile::from_client_media(&media)
+            .map(|file| file.media())
+            .ok_or_else(|| TelegramError::MediaDownload("Unsupported media type".to_string()))?;
 
         // Generate unique filename
         let filename = get_unique_filename(
@@ -411,25 +1139,125 @@ impl TelegramClient {
         };
 
         // Download the media
-        let downloaded_path = client.download_media(&message, &save_path).await?;
+        client
+            .download_media(&Downloadable::Media(media), &save_path)
+            .await?;
 
         Ok(DownloadedMedia {
-            path: downloaded_path.to_string_lossy().to_string(),
+            path: save_path.to_string_lossy().to_string(),
             media: media_obj,
         })
     }
 
+    /// Downloads every media message in `entity` within the date range, up to `limit` files.
+    pub async fn bulk_download_media(
+        &self,
+        entity: &str,
+        start_date: Option<DateTime<Utc>>,
+        end_date: Option<DateTime<Utc>>,
+        limit: usize,
+        path: Option<&str>,
+    ) -> Result<BulkDownloadResult, TelegramError> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
+
+        let chat = self.resolve_entity(entity).await?;
+        let cap = limit.min(MAX_BULK_DOWNLOADS);
+
+        // Messages arrive newest first, so stop once we're past the start of the range
+        // or have seen one more media message than we're allowed to download. Without
+        // a start date the scan is bounded by MAX_BULK_SCAN_MESSAGES instead.
+        let mut candidates = Vec::new();
+        let mut media_seen = 0;
+        let mut iter = client.iter_messages(chat).limit(MAX_BULK_SCAN_MESSAGES);
+        while let Some(message) = iter.next().await? {
+            let date = message.date();
+            if start_date.is_some_and(|start| date < start) {
+                break;
+            }
+
+            let has_media = message
+                .media()
+                .as_ref()
+                .and_then(MediaFile::from_client_media)
+                .is_some();
+            candidates.push(MediaCandidate {
+                message_id: message.id(),
+                date,
+                has_media,
+            });
+
+            let in_range = end_date.is_none() || end_date.is_some_and(|end| date <= end);
+            if has_media && in_range {
+                media_seen += 1;
+                if media_seen > cap {
+                    break;
+                }
+            }
+        }
+
+        let (message_ids, truncated) =
+            select_media_messages(&candidates, start_date, end_date, cap);
+        let truncated = truncated || candidates.len() >= MAX_BULK_SCAN_MESSAGES;
+
+        let results: Vec<(i32, Result<DownloadedMedia, TelegramError>)> = stream::iter(message_ids)
+            .map(|message_id| async move {
+                (
+                    message_id,
+                    self.download_media(entity, message_id, path).await,
+                )
+            })
+            .buffer_unordered(BULK_DOWNLOAD_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut paths = Vec::new();
+        let mut failed_message_ids = Vec::new();
+        for (message_id, result) in results {
+            match result {
+                Ok(downloaded) => paths.push(downloaded.path),
+                Err(e) => {
+                    warn!(
+                        "Failed to download media from message {}: {}",
+                        message_id, e
+                    );
+                    failed_message_ids.push(message_id);
+                }
+            }
+        }
+
+        debug!("Downloaded {} media files from {}", paths.len(), entity);
+        Ok(BulkDownloadResult {
+            count: paths.len(),
+            paths,
+            failed_message_ids,
+            truncated,
+        })
+    }
+
+    /// Fetches the message a `t.me/<username>/<id>` or private `t.me/c/<chat>/<id>` link points at.
     pub async fn message_from_link(&self, link: &str) -> Result<Message, TelegramError> {
         let (entity, message_id) = parse_telegram_url(link)?;
 
-        let client = self.client.as_ref().ok_or_else(|| {
-            TelegramError::Config("Client not connected".to_string())
-        })?;
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| TelegramError::Config("Client not connected".to_string()))?;
 
-        let input_peer = self.resolve_entity(&entity).await?;
+        let chat = self.resolve_entity(&entity).await?;
+        let message = client
+            .get_messages_by_id(chat, &[message_id])
+            .await?
+            .into_iter()
+            .flatten()
+            .next()
+            .ok_or(TelegramError::InvalidMessageId(message_id))?;
 
-        // For now, return a simplified implementation
-        // TODO: Implement proper message retrieval from link
-        return Err(TelegramError::Config("Message from link not implemented yet".to_string()));
+        Ok(Message::from_grammers_message(
+            &message.raw,
+            message.outgoing(),
+        ))
     }
-}
\ No newline at end of file
+}
@@ -1,5 +1,9 @@
 use chrono::{DateTime, Utc};
-use grammers_tl_types::types::{Channel, Chat, User, Message as GrammersMessage, Document, Photo};
+use grammers_tl_types::enums;
+use grammers_tl_types::types::{
+    Channel, Chat, Document, Message as GrammersMessage, MessageMediaDocument, MessageMediaPhoto,
+    Photo, User,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,7 +29,7 @@ pub struct Dialog {
 
 impl Dialog {
     pub fn from_user(user: &User, can_send_message: bool) -> Self {
-        let dialog_type = if user.bot.unwrap_or(false) {
+        let dialog_type = if user.bot {
             DialogType::Bot
         } else {
             DialogType::User
@@ -33,10 +37,13 @@ impl Dialog {
 
         Self {
             id: user.id,
-            title: format!("{} {}", 
+            title: format!(
+                "{} {}",
                 user.first_name.as_deref().unwrap_or(""),
                 user.last_name.as_deref().unwrap_or("")
-            ).trim().to_string(),
+            )
+            .trim()
+            .to_string(),
             username: user.username.clone(),
             phone_number: user.phone.clone(),
             dialog_type,
@@ -58,7 +65,7 @@ impl Dialog {
     }
 
     pub fn from_channel(channel: &Channel, can_send_message: bool) -> Self {
-        let dialog_type = if channel.megagroup.unwrap_or(false) {
+        let dialog_type = if channel.megagroup {
             DialogType::Group
         } else {
             DialogType::Channel
@@ -86,13 +93,14 @@ pub struct Media {
 
 impl Media {
     pub fn from_document(document: &Document) -> Self {
-        let file_name = document.attributes
+        let file_name = document
+            .attributes
             .iter()
-            .filter_map(|attr| {
-                match attr {
-                    grammers_tl_types::enums::DocumentAttribute::Filename(f) => Some(f.file_name.clone()),
-                    _ => None,
+            .filter_map(|attr| match attr {
+                grammers_tl_types::enums::DocumentAttribute::Filename(f) => {
+                    Some(f.file_name.clone())
                 }
+                _ => None,
             })
             .next();
 
@@ -114,12 +122,218 @@ impl Media {
     }
 }
 
+/// The file behind a message's media; other kinds (polls, locations, ...) have none.
+#[derive(Debug, Clone, Copy)]
+pub enum MediaFile<'a> {
+    Document(&'a Document),
+    Photo(&'a Photo),
+}
+
+impl<'a> MediaFile<'a> {
+    pub fn from_raw_media(media: &'a enums::MessageMedia) -> Option<Self> {
+        match media {
+            enums::MessageMedia::Document(document) => Self::from_document_media(document),
+            enums::MessageMedia::Photo(photo) => Self::from_photo_media(photo),
+            _ => None,
+        }
+    }
+
+    /// Like [`from_raw_media`](Self::from_raw_media), for the media of a client message.
+    pub fn from_client_media(media: &'a grammers_client::types::Media) -> Option<Self> {
+        match media {
+            grammers_client::types::Media::Document(document) => {
+                Self::from_document_media(&document.raw)
+            }
+            grammers_client::types::Media::Sticker(sticker) => {
+                Self::from_document_media(&sticker.document.raw)
+            }
+            grammers_client::types::Media::Photo(photo) => Self::from_photo_media(&photo.raw),
+            _ => None,
+        }
+    }
+
+    fn from_document_media(media: &'a MessageMediaDocument) -> Option<Self> {
+        match media.document.as_ref()? {
+            enums::Document::Document(document) => Some(Self::Document(document)),
+            enums::Document::Empty(_) => None,
+        }
+    }
+
+    fn from_photo_media(media: &'a MessageMediaPhoto) -> Option<Self> {
+        match media.photo.as_ref()? {
+            enums::Photo::Photo(photo) => Some(Self::Photo(photo)),
+            enums::Photo::Empty(_) => None,
+        }
+    }
+
+    pub fn media(&self) -> Media {
+        match self {
+            Self::Document(document) => Media::from_document(document),
+            Self::Photo(photo) => Media::from_photo(photo),
+        }
+    }
+
+    pub fn info(&self) -> MediaInfo {
+        match self {
+            Self::Document(document) => MediaInfo::from_document(document),
+            Self::Photo(photo) => MediaInfo::from_photo(photo),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaKind {
+    Photo,
+    Video,
+    Audio,
+    Voice,
+    Animation,
+    Sticker,
+    Document,
+}
+
+/// Media metadata read from the message itself, without downloading the file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaInfo {
+    #[serde(rename = "type")]
+    pub kind: MediaKind,
+    #[serde(flatten)]
+    pub media: Media,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub duration_seconds: Option<f64>,
+}
+
+impl MediaInfo {
+    pub fn from_document(document: &Document) -> Self {
+        let mut info = Self {
+            kind: MediaKind::Document,
+            media: Media::from_document(document),
+            width: None,
+            height: None,
+            duration_seconds: None,
+        };
+
+        for attr in &document.attributes {
+            match attr {
+                grammers_tl_types::enums::DocumentAttribute::Video(video) => {
+                    if info.kind == MediaKind::Document {
+                        info.kind = MediaKind::Video;
+                    }
+                    info.width = Some(video.w);
+                    info.height = Some(video.h);
+                    info.duration_seconds = Some(video.duration);
+                }
+                grammers_tl_types::enums::DocumentAttribute::Audio(audio) => {
+                    info.kind = if audio.voice {
+                        MediaKind::Voice
+                    } else {
+                        MediaKind::Audio
+                    };
+                    info.duration_seconds = Some(f64::from(audio.duration));
+                }
+                grammers_tl_types::enums::DocumentAttribute::ImageSize(size) => {
+                    info.width = info.width.or(Some(size.w));
+                    info.height = info.height.or(Some(size.h));
+                }
+                // GIFs are sent as silent videos tagged as animated
+                grammers_tl_types::enums::DocumentAttribute::Animated => {
+                    info.kind = MediaKind::Animation
+                }
+                grammers_tl_types::enums::DocumentAttribute::Sticker(_) => {
+                    info.kind = MediaKind::Sticker
+                }
+                _ => {}
+            }
+        }
+
+        info
+    }
+
+    /// Photos come in several server-side sizes; the largest one is reported.
+    pub fn from_photo(photo: &Photo) -> Self {
+        let largest = photo
+            .sizes
+            .iter()
+            .filter_map(|size| match size {
+                grammers_tl_types::enums::PhotoSize::Size(s) => {
+                    Some((s.w, s.h, Some(i64::from(s.size))))
+                }
+                grammers_tl_types::enums::PhotoSize::Progressive(s) => {
+                    Some((s.w, s.h, s.sizes.last().map(|&bytes| i64::from(bytes))))
+                }
+                _ => None,
+            })
+            .max_by_key(|&(w, h, _)| i64::from(w) * i64::from(h));
+
+        let mut media = Media::from_photo(photo);
+        media.file_size = largest.and_then(|(_, _, size)| size);
+
+        Self {
+            kind: MediaKind::Photo,
+            media,
+            width: largest.map(|(w, _, _)| w),
+            height: largest.map(|(_, h, _)| h),
+            duration_seconds: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DownloadedMedia {
     pub path: String,
     pub media: Media,
 }
 
+/// An entry in the account's address book.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Contact {
+    pub user_id: i64,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub username: Option<String>,
+    pub phone: Option<String>,
+    pub mutual: bool,
+}
+
+impl Contact {
+    pub fn from_user(user: &User) -> Self {
+        Self {
+            user_id: user.id,
+            first_name: user.first_name.clone(),
+            last_name: user.last_name.clone(),
+            username: user.username.clone(),
+            phone: user.phone.clone(),
+            mutual: user.mutual_contact,
+        }
+    }
+}
+
+/// Notification setting applied to a chat. `mute_until` is `None` when the
+/// chat is unmuted or muted forever.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MuteSetting {
+    pub muted: bool,
+    pub forever: bool,
+    pub mute_until: Option<DateTime<Utc>>,
+}
+
+/// Outcome of marking a whole dialog as read.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DialogReadResult {
+    pub previous_unread_count: i32,
+    pub read_up_to: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkDownloadResult {
+    pub count: usize,
+    pub paths: Vec<String>,
+    pub failed_message_ids: Vec<i32>,
+    pub truncated: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Message {
     pub message_id: i32,
@@ -129,44 +343,88 @@ pub struct Message {
     pub date: Option<DateTime<Utc>>,
     pub media: Option<Media>,
     pub reply_to: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_context: Option<ReplyContext>,
+}
+
+/// The text and sender of the message a reply points at.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReplyContext {
+    pub message_id: i32,
+    pub sender_id: Option<i64>,
+    pub message: Option<String>,
+}
+
+/// A message matched by an in-chat search, trimmed to the text around the query.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SearchHit {
+    pub message_id: i32,
+    pub sender_id: Option<i64>,
+    pub date: Option<DateTime<Utc>>,
+    pub snippet: String,
+}
+
+/// How many times one reaction was left on a message.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReactionCount {
+    pub reaction: String,
+    pub count: i32,
+    /// Whether the current account picked this reaction.
+    pub chosen: bool,
+}
+
+/// A single user's reaction, only listed where the chat permits it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Reactor {
+    pub peer_id: i64,
+    pub reaction: String,
+    pub date: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageReactions {
+    pub message_id: i32,
+    pub total: i32,
+    pub reactions: Vec<ReactionCount>,
+    pub reactors: Vec<Reactor>,
+    /// Why `reactors` is empty when Telegram would not list who reacted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reactors_unavailable: Option<String>,
+}
+
+/// A group member who has read a message, with the time they read it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReadParticipant {
+    pub user_id: i64,
+    pub date: Option<DateTime<Utc>>,
 }
 
 impl Message {
     pub fn from_grammers_message(msg: &GrammersMessage, outgoing: bool) -> Self {
-        let media = msg.media.as_ref().and_then(|m| {
-            match m {
-                grammers_tl_types::enums::MessageMedia::Document(doc) => {
-                    Some(Media::from_document(&doc.document))
-                }
-                grammers_tl_types::enums::MessageMedia::Photo(photo) => {
-                    Some(Media::from_photo(&photo.photo))
-                }
-                _ => None,
-            }
-        });
+        let media = msg
+            .media
+            .as_ref()
+            .and_then(MediaFile::from_raw_media)
+            .map(|file| file.media());
 
-        let reply_to = msg.reply_to.as_ref().and_then(|r| {
-            match r {
-                grammers_tl_types::enums::MessageReplyHeader::Header(h) => {
-                    h.reply_to_msg_id
-                }
-            }
+        let reply_to = msg.reply_to.as_ref().and_then(|r| match r {
+            grammers_tl_types::enums::MessageReplyHeader::Header(h) => h.reply_to_msg_id,
+            grammers_tl_types::enums::MessageReplyHeader::MessageReplyStoryHeader(_) => None,
         });
 
         Self {
             message_id: msg.id,
-            sender_id: msg.from_id.as_ref().map(|peer| {
-                match peer {
-                    grammers_tl_types::enums::Peer::User(u) => u.user_id,
-                    grammers_tl_types::enums::Peer::Chat(c) => c.chat_id,
-                    grammers_tl_types::enums::Peer::Channel(ch) => ch.channel_id,
-                }
+            sender_id: msg.from_id.as_ref().map(|peer| match peer {
+                grammers_tl_types::enums::Peer::User(u) => u.user_id,
+                grammers_tl_types::enums::Peer::Chat(c) => c.chat_id,
+                grammers_tl_types::enums::Peer::Channel(ch) => ch.channel_id,
             }),
-            message: msg.message.clone(),
+            message: Some(msg.message.clone()),
             outgoing,
             date: Some(DateTime::from_timestamp(msg.date as i64, 0).unwrap_or_default()),
             media,
             reply_to,
+            reply_context: None,
         }
     }
 }
@@ -175,4 +433,102 @@ impl Message {
 pub struct Messages {
     pub messages: Vec<Message>,
     pub dialog: Option<Dialog>,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grammers_tl_types::enums::{DocumentAttribute, PhotoSize};
+    use grammers_tl_types::types::{
+        DocumentAttributeAudio, DocumentAttributeFilename, DocumentAttributeVideo,
+        PhotoSizeProgressive,
+    };
+
+    fn document(mime_type: &str, attributes: Vec<DocumentAttribute>) -> Document {
+        Document {
+            id: 1,
+            access_hash: 2,
+            file_reference: Vec::new(),
+            date: 0,
+            mime_type: mime_type.to_string(),
+            size: 5_000_000,
+            thumbs: None,
+            video_thumbs: None,
+            dc_id: 4,
+            attributes,
+        }
+    }
+
+    #[test]
+    fn test_media_info_from_document_and_photo() {
+        let video = MediaInfo::from_document(&document(
+            "video/mp4",
+            vec![
+                DocumentAttribute::Video(DocumentAttributeVideo {
+                    round_message: false,
+                    supports_streaming: true,
+                    nosound: false,
+                    duration: 12.5,
+                    w: 1280,
+                    h: 720,
+                    preload_prefix_size: None,
+                    video_start_ts: None,
+                }),
+                DocumentAttribute::Filename(DocumentAttributeFilename {
+                    file_name: "clip.mp4".to_string(),
+                }),
+            ],
+        ));
+        assert_eq!(video.kind, MediaKind::Video);
+        assert_eq!((video.width, video.height), (Some(1280), Some(720)));
+        assert_eq!(video.duration_seconds, Some(12.5));
+        assert_eq!(video.media.file_name.as_deref(), Some("clip.mp4"));
+        assert_eq!(video.media.file_size, Some(5_000_000));
+
+        let voice = MediaInfo::from_document(&document(
+            "audio/ogg",
+            vec![DocumentAttribute::Audio(DocumentAttributeAudio {
+                voice: true,
+                duration: 7,
+                title: None,
+                performer: None,
+                waveform: None,
+            })],
+        ));
+        assert_eq!(voice.kind, MediaKind::Voice);
+        assert_eq!(voice.duration_seconds, Some(7.0));
+        assert_eq!(voice.width, None);
+
+        let pdf = MediaInfo::from_document(&document("application/pdf", vec![]));
+        assert_eq!(pdf.kind, MediaKind::Document);
+        assert_eq!(pdf.duration_seconds, None);
+
+        let photo = MediaInfo::from_photo(&Photo {
+            has_stickers: false,
+            id: 9,
+            access_hash: 0,
+            file_reference: Vec::new(),
+            date: 0,
+            sizes: vec![
+                PhotoSize::Size(grammers_tl_types::types::PhotoSize {
+                    r#type: "m".to_string(),
+                    w: 320,
+                    h: 240,
+                    size: 20_000,
+                }),
+                PhotoSize::Progressive(PhotoSizeProgressive {
+                    r#type: "y".to_string(),
+                    w: 1280,
+                    h: 960,
+                    sizes: vec![10_000, 60_000, 150_000],
+                }),
+            ],
+            video_sizes: None,
+            dc_id: 4,
+        });
+        assert_eq!(photo.kind, MediaKind::Photo);
+        assert_eq!((photo.width, photo.height), (Some(1280), Some(960)));
+        assert_eq!(photo.media.file_size, Some(150_000));
+        assert_eq!(photo.media.mime_type.as_deref(), Some("image/jpeg"));
+    }
+}
@@ -1,5 +1,20 @@
 use crate::error::TelegramError;
+use crate::types::{
+    Dialog, Message, MuteSetting, ReactionCount, Reactor, ReadParticipant, ReplyContext, SearchHit,
+};
+use chrono::{DateTime, Duration, Utc};
+use grammers_session::{PackedChat, PackedType};
+use grammers_tl_types::enums::{self, InputPeer, MessagesFilter, Peer, Reaction};
+use grammers_tl_types::functions::account::UpdateNotifySettings;
+use grammers_tl_types::functions::channels;
+use grammers_tl_types::functions::contacts::{self, DeleteContacts, GetContacts, ImportContacts};
+use grammers_tl_types::functions::messages::{
+    DeleteScheduledMessages, GetMessageReactionsList, GetPeerDialogs, GetScheduledHistory,
+    ReadHistory, SaveDraft, Search, SendMessage,
+};
+use grammers_tl_types::types;
 use regex::Regex;
+use std::collections::HashMap;
 use std::path::Path;
 use uuid::Uuid;
 
@@ -11,14 +26,96 @@ pub fn parse_entity(entity: &str) -> Result<i64, String> {
     }
 }
 
-pub fn get_unique_filename(original_name: Option<&str>, media_id: i64, mime_type: Option<&str>) -> String {
+/// Kind of peer a numeric entity refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeerKind {
+    User,
+    Chat,
+    Channel,
+}
+
+/// Marked channel ids are `-(1_000_000_000_000 + channel_id)`, i.e. `-100…`.
+const CHANNEL_ID_OFFSET: i64 = 1_000_000_000_000;
+
+/// Splits a numeric entity into its peer kind and bare id. Follows the Bot API
+/// convention: positive ids are users, `-100…` ids are channels and supergroups,
+/// and other negative ids are basic groups. A `user:`, `chat:` or `channel:`
+/// prefix overrides the convention. Returns `None` for non-numeric entities.
+pub fn parse_peer_id(entity: &str) -> Option<(PeerKind, i64)> {
+    let (hint, id) = match entity.split_once(':') {
+        Some(("user", id)) => (Some(PeerKind::User), id),
+        Some(("chat", id)) => (Some(PeerKind::Chat), id),
+        Some(("channel", id)) => (Some(PeerKind::Channel), id),
+        Some(_) => return None,
+        None => (None, entity),
+    };
+    let id = parse_entity(id).ok().filter(|id| *id != 0)?;
+
+    let (kind, bare_id) = if id < -CHANNEL_ID_OFFSET {
+        (PeerKind::Channel, -id - CHANNEL_ID_OFFSET)
+    } else if id < 0 {
+        (PeerKind::Chat, -id)
+    } else {
+        (PeerKind::User, id)
+    };
+
+    Some((hint.unwrap_or(kind), bare_id))
+}
+
+/// The peer kind of a dialog's packed chat; megagroups and gigagroups are channels.
+pub fn packed_peer_kind(ty: PackedType) -> PeerKind {
+    match ty {
+        PackedType::User | PackedType::Bot => PeerKind::User,
+        PackedType::Chat => PeerKind::Chat,
+        PackedType::Megagroup | PackedType::Broadcast | PackedType::Gigagroup => PeerKind::Channel,
+    }
+}
+
+/// Marks a bare id the Bot API way; the inverse of [`parse_peer_id`].
+pub fn marked_peer_id(kind: PeerKind, id: i64) -> i64 {
+    match kind {
+        PeerKind::User => id,
+        PeerKind::Chat => -id,
+        PeerKind::Channel => -(CHANNEL_ID_OFFSET + id),
+    }
+}
+
+/// Caches chats seen in updates or dialog scans under their marked id and
+/// username, so resolving them later needs no dialog scan. Users and channels
+/// that arrive without an access hash are skipped rather than cached with an
+/// unusable one.
+pub fn cache_peers(
+    cache: &mut HashMap<String, PackedChat>,
+    peers: &[(PackedChat, Option<String>)],
+) {
+    for (packed, username) in peers {
+        let kind = packed_peer_kind(packed.ty);
+        if kind != PeerKind::Chat && packed.access_hash.is_none() {
+            continue;
+        }
+
+        cache.insert(marked_peer_id(kind, packed.id).to_string(), *packed);
+        if let Some(username) = username {
+            cache.insert(username.clone(), *packed);
+        }
+    }
+}
+
+pub fn get_unique_filename(
+    original_name: Option<&str>,
+    media_id: i64,
+    mime_type: Option<&str>,
+) -> String {
     let unique_id = Uuid::new_v4().to_string();
-    
+
     if let Some(name) = original_name {
         let path = Path::new(name);
-        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("download");
+        let stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("download");
         let extension = path.extension().and_then(|s| s.to_str()).unwrap_or("");
-        
+
         if extension.is_empty() {
             format!("{}_{}", stem, unique_id)
         } else {
@@ -28,7 +125,7 @@ pub fn get_unique_filename(original_name: Option<&str>, media_id: i64, mime_type
         let extension = mime_type
             .and_then(|mime| mime.split('/').nth(1))
             .unwrap_or("bin");
-        
+
         format!("download_{}_{}.{}", media_id, unique_id, extension)
     }
 }
@@ -36,22 +133,1328 @@ pub fn get_unique_filename(original_name: Option<&str>, media_id: i64, mime_type
 pub fn parse_telegram_url(url: &str) -> Result<(String, i32), TelegramError> {
     let pattern = r"^(?:https?://)?t(?:elegram)?\.me/(?:(?P<username>[A-Za-z0-9_]+)/(?P<message_id>\d+)|c/(?P<chat_id>\d+)/(?P<chat_message_id>\d+))/?$";
     let re = Regex::new(pattern).map_err(|e| TelegramError::UrlParsing(e.to_string()))?;
-    
+
     if let Some(captures) = re.captures(url) {
-        let entity = captures.name("username")
-            .or(captures.name("chat_id"))
+        // Private `t.me/c/` links carry a bare channel id
+        let entity = captures
+            .name("username")
             .map(|m| m.as_str().to_string())
+            .or_else(|| {
+                captures
+                    .name("chat_id")
+                    .map(|m| format!("channel:{}", m.as_str()))
+            })
             .ok_or_else(|| TelegramError::UrlParsing("No entity found in URL".to_string()))?;
-        
-        let message_id = captures.name("message_id")
+
+        let message_id = captures
+            .name("message_id")
             .or(captures.name("chat_message_id"))
             .map(|m| m.as_str())
             .ok_or_else(|| TelegramError::UrlParsing("No message ID found in URL".to_string()))?
             .parse::<i32>()
             .map_err(|e| TelegramError::UrlParsing(format!("Invalid message ID: {}", e)))?;
-        
+
         Ok((entity, message_id))
     } else {
-        Err(TelegramError::UrlParsing("Invalid Telegram URL format".to_string()))
+        Err(TelegramError::UrlParsing(
+            "Invalid Telegram URL format".to_string(),
+        ))
+    }
+}
+
+/// Delivery flags for an outgoing text message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SendOptions {
+    /// Deliver without a notification sound.
+    pub silent: bool,
+    /// Don't render a preview for links in the text.
+    pub disable_preview: bool,
+}
+
+impl Default for SendOptions {
+    /// Messages have always gone out without link previews, so that stays the default.
+    fn default() -> Self {
+        Self {
+            silent: false,
+            disable_preview: true,
+        }
+    }
+}
+
+/// What `get_messages` does beyond fetching history; all off by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GetMessagesOptions {
+    /// Only return messages past the dialog's read marker.
+    pub unread_only: bool,
+    /// Mark the dialog read up to the newest returned message.
+    pub mark_as_read: bool,
+    /// Attach the message each reply answers.
+    pub include_reply_context: bool,
+}
+
+/// Builds `messages.sendMessage` for one plain-text part. `random_id` lets the
+/// sent message be picked out of the updates Telegram answers with.
+pub fn send_message_request(
+    peer: InputPeer,
+    text: &str,
+    reply_to: Option<i32>,
+    options: SendOptions,
+    random_id: i64,
+) -> SendMessage {
+    SendMessage {
+        no_webpage: options.disable_preview,
+        silent: options.silent,
+        background: false,
+        clear_draft: false,
+        noforwards: false,
+        update_stickersets_order: false,
+        invert_media: false,
+        peer,
+        reply_to: reply_to.map(|reply_to_msg_id| {
+            enums::InputReplyTo::Message(types::InputReplyToMessage {
+                reply_to_msg_id,
+                top_msg_id: None,
+                reply_to_peer_id: None,
+                quote_text: None,
+                quote_entities: None,
+                quote_offset: None,
+            })
+        }),
+        message: text.to_string(),
+        random_id,
+        reply_markup: None,
+        entities: None,
+        schedule_date: None,
+        send_as: None,
+        quick_reply_shortcut: None,
+        effect: None,
+    }
+}
+
+/// Finds the id Telegram gave the message sent with `random_id`.
+pub fn sent_message_id(updates: &enums::Updates, random_id: i64) -> Option<i32> {
+    let updates = match updates {
+        enums::Updates::UpdateShortSentMessage(sent) => return Some(sent.id),
+        enums::Updates::Updates(u) => &u.updates,
+        enums::Updates::Combined(u) => &u.updates,
+        _ => return None,
+    };
+    updates.iter().find_map(|update| match update {
+        enums::Update::MessageId(u) if u.random_id == random_id => Some(u.id),
+        _ => None,
+    })
+}
+
+/// Telegram rejects text messages longer than this, counted in UTF-16 code units.
+pub const MAX_MESSAGE_LENGTH: usize = 4096;
+
+/// Length of `text` as Telegram measures it: emoji and other characters outside
+/// the Basic Multilingual Plane count twice.
+pub fn message_length(text: &str) -> usize {
+    text.encode_utf16().count()
+}
+
+/// Byte index where the longest prefix of `text` within `max_len` UTF-16 code units ends.
+fn utf16_prefix_end(text: &str, max_len: usize) -> usize {
+    let mut units = 0;
+    for (index, c) in text.char_indices() {
+        units += c.len_utf16();
+        if units > max_len {
+            return index;
+        }
+    }
+    text.len()
+}
+
+/// Splits `text` into chunks of at most `max_len` UTF-16 code units, breaking after the
+/// last paragraph, line, sentence or word that fits, and only mid-word when nothing else
+/// does. Whitespace at the break is dropped so parts don't start or end with blank lines.
+pub fn split_message(text: &str, max_len: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut rest = text.trim();
+
+    while message_length(rest) > max_len {
+        // Always make progress, even if a single character doesn't fit
+        let first_char = rest.chars().next().map_or(0, char::len_utf8);
+        let window_end = utf16_prefix_end(rest, max_len).max(first_char);
+        let window = &rest[..window_end];
+
+        let split_at = window
+            .rfind("\n\n")
+            .or_else(|| window.rfind('\n'))
+            .or_else(|| {
+                [". ", "! ", "? "]
+                    .iter()
+                    .filter_map(|end| window.rfind(end).map(|i| i + 1))
+                    .max()
+            })
+            .or_else(|| window.rfind(char::is_whitespace))
+            .filter(|&i| !window[..i].trim().is_empty())
+            .unwrap_or(window_end);
+
+        chunks.push(rest[..split_at].trim_end().to_string());
+        rest = rest[split_at..].trim_start();
+    }
+
+    if !rest.is_empty() {
+        chunks.push(rest.to_string());
+    }
+    chunks
+}
+
+/// Telegram rejects scheduled messages more than 365 days ahead.
+pub const MAX_SCHEDULE_DAYS: i64 = 365;
+
+pub fn validate_schedule_date(
+    schedule_date: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<(), TelegramError> {
+    if schedule_date <= now {
+        return Err(TelegramError::InvalidArguments(format!(
+            "schedule_date {} must be in the future",
+            schedule_date.to_rfc3339()
+        )));
+    }
+
+    if schedule_date > now + Duration::days(MAX_SCHEDULE_DAYS) {
+        return Err(TelegramError::InvalidArguments(format!(
+            "schedule_date {} is more than {} days ahead",
+            schedule_date.to_rfc3339(),
+            MAX_SCHEDULE_DAYS
+        )));
+    }
+
+    Ok(())
+}
+
+/// Longest finite mute; anything longer should use `forever`.
+pub const MAX_MUTE_DAYS: i64 = 365;
+
+/// Parses a mute duration: `forever`, whole seconds (`3600`), or a number with
+/// a unit suffix `m`, `h`, `d` or `w` (`30m`, `8h`, `2d`, `1w`). `None` means forever.
+pub fn parse_mute_duration(duration: &str) -> Result<Option<Duration>, TelegramError> {
+    let duration = duration.trim().to_lowercase();
+    if duration == "forever" {
+        return Ok(None);
     }
-}
\ No newline at end of file
+
+    let invalid = || {
+        TelegramError::InvalidArguments(format!(
+            "Invalid mute duration {:?}: use \"forever\", seconds, or a number followed by m, h, d or w",
+            duration
+        ))
+    };
+    let (amount, unit) = match duration.find(|c: char| !c.is_ascii_digit()) {
+        Some(index) => duration.split_at(index),
+        None => (duration.as_str(), "s"),
+    };
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    let unit_seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+
+    if amount <= 0 {
+        return Err(TelegramError::InvalidArguments(
+            "Mute duration must be positive".to_string(),
+        ));
+    }
+    match amount.checked_mul(unit_seconds) {
+        Some(seconds) if seconds <= MAX_MUTE_DAYS * 24 * 60 * 60 => {
+            Ok(Some(Duration::seconds(seconds)))
+        }
+        _ => Err(TelegramError::InvalidArguments(format!(
+            "Mute duration is more than {} days; use \"forever\" instead",
+            MAX_MUTE_DAYS
+        ))),
+    }
+}
+
+/// Maps a mute duration onto the setting Telegram stores: an absolute
+/// `mute_until` timestamp, with `i32::MAX` meaning forever.
+pub fn mute_setting(duration: Option<Duration>, now: DateTime<Utc>) -> (MuteSetting, i32) {
+    match duration {
+        Some(duration) => {
+            let until = now + duration;
+            let setting = MuteSetting {
+                muted: true,
+                forever: false,
+                mute_until: Some(until),
+            };
+            (setting, until.timestamp() as i32)
+        }
+        None => {
+            let setting = MuteSetting {
+                muted: true,
+                forever: true,
+                mute_until: None,
+            };
+            (setting, i32::MAX)
+        }
+    }
+}
+
+/// Builds `account.updateNotifySettings` for one chat; a `mute_until` of 0 unmutes it.
+pub fn notify_settings_request(peer: InputPeer, mute_until: i32) -> UpdateNotifySettings {
+    UpdateNotifySettings {
+        peer: enums::InputNotifyPeer::Peer(types::InputNotifyPeer { peer }),
+        settings: enums::InputPeerNotifySettings::Settings(types::InputPeerNotifySettings {
+            show_previews: None,
+            silent: None,
+            mute_until: Some(mute_until),
+            sound: None,
+            stories_muted: None,
+            stories_hide_sender: None,
+            stories_sound: None,
+        }),
+    }
+}
+
+/// Builds `messages.getScheduledHistory`; a zero hash always returns the full queue.
+pub fn scheduled_history_request(peer: InputPeer) -> GetScheduledHistory {
+    GetScheduledHistory { peer, hash: 0 }
+}
+
+/// Builds `messages.deleteScheduledMessages` for the given scheduled message ids.
+pub fn delete_scheduled_request(peer: InputPeer, message_ids: &[i32]) -> DeleteScheduledMessages {
+    DeleteScheduledMessages {
+        peer,
+        id: message_ids.to_vec(),
+    }
+}
+
+/// Builds `messages.getPeerDialogs` for a single chat.
+pub fn peer_dialog_request(peer: InputPeer) -> GetPeerDialogs {
+    GetPeerDialogs {
+        peers: vec![enums::InputDialogPeer::Peer(types::InputDialogPeer {
+            peer,
+        })],
+    }
+}
+
+/// The chat's dialog in a `messages.getPeerDialogs` reply.
+fn peer_dialog(dialogs: &enums::messages::PeerDialogs) -> Option<&types::Dialog> {
+    let enums::messages::PeerDialogs::Dialogs(dialogs) = dialogs;
+    dialogs.dialogs.iter().find_map(|dialog| match dialog {
+        enums::Dialog::Dialog(d) => Some(d),
+        enums::Dialog::Folder(_) => None,
+    })
+}
+
+/// Latest message id and unread count of the dialog in a `messages.getPeerDialogs` reply.
+pub fn dialog_read_state(dialogs: &enums::messages::PeerDialogs) -> Option<(i32, i32)> {
+    peer_dialog(dialogs).map(|d| (d.top_message, d.unread_count))
+}
+
+/// Id of the newest incoming message already read in the dialog.
+pub fn read_inbox_max_id(dialogs: &enums::messages::PeerDialogs) -> Option<i32> {
+    peer_dialog(dialogs).map(|d| d.read_inbox_max_id)
+}
+
+/// Incoming messages newer than the dialog's read marker are unread.
+pub fn is_unread(message: &Message, read_inbox_max_id: i32) -> bool {
+    !message.outgoing && message.message_id > read_inbox_max_id
+}
+
+/// Text of the draft saved in the dialog, empty when there is none.
+pub fn dialog_draft(dialogs: &enums::messages::PeerDialogs) -> Option<String> {
+    peer_dialog(dialogs).map(|d| match &d.draft {
+        Some(enums::DraftMessage::Message(draft)) => draft.message.clone(),
+        _ => String::new(),
+    })
+}
+
+/// Builds `messages.saveDraft`; an empty `text` clears the draft.
+pub fn save_draft_request(peer: InputPeer, text: &str) -> SaveDraft {
+    SaveDraft {
+        no_webpage: false,
+        invert_media: false,
+        reply_to: None,
+        peer,
+        message: text.to_string(),
+        entities: None,
+        media: None,
+        effect: None,
+    }
+}
+
+/// Channels and supergroups keep their own read state and need
+/// `channels.readHistory`; every other peer uses `messages.readHistory`.
+pub enum ReadHistoryRequest {
+    Messages(ReadHistory),
+    Channel(channels::ReadHistory),
+}
+
+/// Builds the call that marks every message up to and including `max_id` as read.
+pub fn read_history_request(peer: InputPeer, max_id: i32) -> ReadHistoryRequest {
+    match peer {
+        InputPeer::Channel(channel) => ReadHistoryRequest::Channel(channels::ReadHistory {
+            channel: enums::InputChannel::Channel(types::InputChannel {
+                channel_id: channel.channel_id,
+                access_hash: channel.access_hash,
+            }),
+            max_id,
+        }),
+        peer => ReadHistoryRequest::Messages(ReadHistory { peer, max_id }),
+    }
+}
+
+/// Builds `contacts.getContacts`; a zero hash always returns the full list.
+pub fn contacts_request() -> GetContacts {
+    GetContacts { hash: 0 }
+}
+
+/// Normalizes a phone number to `+` followed by digits, dropping common
+/// separators. Anything that is not a plausible international number is rejected.
+pub fn normalize_phone(phone: &str) -> Result<String, TelegramError> {
+    let trimmed = phone.trim();
+    let digits: String = trimmed
+        .strip_prefix('+')
+        .unwrap_or(trimmed)
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '.' | '(' | ')'))
+        .collect();
+
+    if digits.len() < 8
+        || digits.len() > 15
+        || digits.starts_with('0')
+        || !digits.chars().all(|c| c.is_ascii_digit())
+    {
+        return Err(TelegramError::InvalidArguments(format!(
+            "Invalid phone number {:?}: expected an international number such as +14155552671",
+            phone
+        )));
+    }
+
+    Ok(format!("+{}", digits))
+}
+
+/// Builds `contacts.importContacts` for a single phone contact.
+pub fn import_contact_request(phone: &str, first_name: &str, last_name: &str) -> ImportContacts {
+    ImportContacts {
+        contacts: vec![enums::InputContact::InputPhoneContact(
+            types::InputPhoneContact {
+                client_id: 0,
+                phone: phone.to_string(),
+                first_name: first_name.to_string(),
+                last_name: last_name.to_string(),
+            },
+        )],
+    }
+}
+
+/// Builds `contacts.deleteContacts` for a single user.
+pub fn delete_contact_request(user_id: i64, access_hash: i64) -> DeleteContacts {
+    DeleteContacts {
+        id: vec![enums::InputUser::User(types::InputUser {
+            user_id,
+            access_hash,
+        })],
+    }
+}
+
+/// Builds `contacts.search`, which also finds public chats the account hasn't joined.
+pub fn global_search_request(query: &str, limit: usize) -> contacts::Search {
+    contacts::Search {
+        q: query.to_string(),
+        limit: limit.min(MAX_SEARCH_LIMIT) as i32,
+    }
+}
+
+/// Dialogs for the peers in a `contacts.search` reply, chats the account is
+/// already in first. Channels it hasn't joined are reported as not writable.
+pub fn found_dialogs(found: &enums::contacts::Found, limit: usize) -> Vec<Dialog> {
+    let enums::contacts::Found::Found(found) = found;
+    found
+        .my_results
+        .iter()
+        .chain(&found.results)
+        .filter_map(|peer| match peer {
+            Peer::User(p) => found.users.iter().find_map(|user| match user {
+                enums::User::User(user) if user.id == p.user_id => {
+                    Some(Dialog::from_user(user, true))
+                }
+                _ => None,
+            }),
+            Peer::Chat(p) => found.chats.iter().find_map(|chat| match chat {
+                enums::Chat::Chat(chat) if chat.id == p.chat_id => {
+                    Some(Dialog::from_chat(chat, !chat.left))
+                }
+                _ => None,
+            }),
+            Peer::Channel(p) => found.chats.iter().find_map(|chat| match chat {
+                enums::Chat::Channel(channel) if channel.id == p.channel_id => {
+                    Some(Dialog::from_channel(channel, !channel.left))
+                }
+                _ => None,
+            }),
+        })
+        .take(limit)
+        .collect()
+}
+
+/// Largest page `messages.search` will return in one call.
+pub const MAX_SEARCH_LIMIT: usize = 100;
+
+/// Characters of context kept on each side of the match in a search snippet.
+pub const SNIPPET_CONTEXT_CHARS: usize = 40;
+
+/// Builds `messages.search` for text matches within a single chat, newest first.
+pub fn search_messages_request(peer: InputPeer, query: &str, limit: usize) -> Search {
+    Search {
+        peer,
+        q: query.to_string(),
+        from_id: None,
+        saved_peer_id: None,
+        saved_reaction: None,
+        top_msg_id: None,
+        filter: MessagesFilter::InputMessagesFilterEmpty,
+        min_date: 0,
+        max_date: 0,
+        offset_id: 0,
+        add_offset: 0,
+        limit: limit.clamp(1, MAX_SEARCH_LIMIT) as i32,
+        max_id: 0,
+        min_id: 0,
+        hash: 0,
+    }
+}
+
+/// Cuts `text` down to the first case-insensitive match of `query` plus some
+/// surrounding context. Falls back to the start of the text when Telegram matched
+/// on something other than the literal query (stemming, transliteration).
+pub fn message_snippet(text: &str, query: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let needle: Vec<char> = query.to_lowercase().chars().collect();
+    let lowered: Vec<char> = chars.iter().flat_map(|c| c.to_lowercase()).collect();
+
+    // Only trust the match position when lowercasing kept the char count stable.
+    let match_at = if lowered.len() == chars.len() && !needle.is_empty() {
+        lowered
+            .windows(needle.len())
+            .position(|w| w == needle.as_slice())
+    } else {
+        None
+    };
+
+    let (start, end) = match match_at {
+        Some(pos) => (
+            pos.saturating_sub(SNIPPET_CONTEXT_CHARS),
+            (pos + needle.len() + SNIPPET_CONTEXT_CHARS).min(chars.len()),
+        ),
+        None => (0, (2 * SNIPPET_CONTEXT_CHARS).min(chars.len())),
+    };
+
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push('…');
+    }
+    snippet.extend(&chars[start..end]);
+    if end < chars.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+/// Maps search results to hits, dropping service messages that carry no text.
+pub fn search_hits(messages: &[Message], query: &str) -> Vec<SearchHit> {
+    messages
+        .iter()
+        .filter_map(|m| {
+            let text = m.message.as_deref().filter(|t| !t.is_empty())?;
+            Some(SearchHit {
+                message_id: m.message_id,
+                sender_id: m.sender_id,
+                date: m.date,
+                snippet: message_snippet(text, query),
+            })
+        })
+        .collect()
+}
+
+/// Largest page `messages.getMessageReactionsList` will return in one call.
+pub const MAX_REACTORS_LIMIT: usize = 100;
+
+/// Readable name for a reaction: the emoji itself, `custom:<document id>` for
+/// custom emoji, or `paid` for star reactions.
+pub fn reaction_label(reaction: &Reaction) -> Option<String> {
+    match reaction {
+        Reaction::Emoji(emoji) => Some(emoji.emoticon.clone()),
+        Reaction::CustomEmoji(custom) => Some(format!("custom:{}", custom.document_id)),
+        Reaction::Paid => Some("paid".to_string()),
+        Reaction::Empty => None,
+    }
+}
+
+/// Per-reaction counts from a message's `reactions` field, most used first.
+pub fn reaction_counts(reactions: &enums::MessageReactions) -> Vec<ReactionCount> {
+    let enums::MessageReactions::Reactions(reactions) = reactions;
+    let mut counts: Vec<ReactionCount> = reactions
+        .results
+        .iter()
+        .filter_map(|result| {
+            let enums::ReactionCount::Count(result) = result;
+            Some(ReactionCount {
+                reaction: reaction_label(&result.reaction)?,
+                count: result.count,
+                chosen: result.chosen_order.is_some(),
+            })
+        })
+        .collect();
+    counts.sort_by_key(|c| std::cmp::Reverse(c.count));
+    counts
+}
+
+/// Builds `messages.getMessageReactionsList` for every reaction on one message.
+pub fn reactions_list_request(
+    peer: InputPeer,
+    message_id: i32,
+    limit: usize,
+) -> GetMessageReactionsList {
+    GetMessageReactionsList {
+        peer,
+        id: message_id,
+        reaction: None,
+        offset: None,
+        limit: limit.clamp(1, MAX_REACTORS_LIMIT) as i32,
+    }
+}
+
+pub fn peer_id(peer: &Peer) -> i64 {
+    match peer {
+        Peer::User(u) => u.user_id,
+        Peer::Chat(c) => c.chat_id,
+        Peer::Channel(ch) => ch.channel_id,
+    }
+}
+
+pub fn reactors(list: &enums::messages::MessageReactionsList) -> Vec<Reactor> {
+    let enums::messages::MessageReactionsList::List(list) = list;
+    list.reactions
+        .iter()
+        .filter_map(|reaction| {
+            let enums::MessagePeerReaction::Reaction(reaction) = reaction;
+            Some(Reactor {
+                peer_id: peer_id(&reaction.peer_id),
+                reaction: reaction_label(&reaction.reaction)?,
+                date: DateTime::from_timestamp(i64::from(reaction.date), 0),
+            })
+        })
+        .collect()
+}
+
+pub fn read_participants(dates: &[enums::ReadParticipantDate]) -> Vec<ReadParticipant> {
+    dates
+        .iter()
+        .map(|date| {
+            let enums::ReadParticipantDate::Date(date) = date;
+            ReadParticipant {
+                user_id: date.user_id,
+                date: DateTime::from_timestamp(i64::from(date.date), 0),
+            }
+        })
+        .collect()
+}
+
+/// Explains the RPC errors Telegram returns when reactors or read receipts
+/// are withheld for a chat, as opposed to the request itself being wrong.
+pub fn unavailable_reason(rpc_error_name: &str) -> Option<&'static str> {
+    match rpc_error_name {
+        "BROADCAST_FORBIDDEN" => Some("channels do not reveal who reacted to or read a post"),
+        "CHAT_TOO_BIG" => Some("read receipts are only kept in groups of up to 100 members"),
+        "MSG_TOO_OLD" => Some("read receipts are only kept for 7 days"),
+        "CHAT_ADMIN_REQUIRED" => Some("listing is restricted to chat admins"),
+        "PEER_ID_INVALID" => Some("this kind of chat does not support it"),
+        _ => None,
+    }
+}
+
+/// Hard ceiling on files fetched by one bulk download call.
+pub const MAX_BULK_DOWNLOADS: usize = 100;
+
+/// Most messages one bulk download call reads from a chat's history while
+/// looking for media, so a call without a start date can't walk it all.
+pub const MAX_BULK_SCAN_MESSAGES: usize = 2000;
+
+/// A message seen while scanning a chat for media to download.
+#[derive(Debug, Clone, Copy)]
+pub struct MediaCandidate {
+    pub message_id: i32,
+    pub date: DateTime<Utc>,
+    pub has_media: bool,
+}
+
+/// Picks media messages inside the date range, keeping at most `cap` (itself capped by
+/// `MAX_BULK_DOWNLOADS`). Returns the selected ids and whether anything was cut off.
+pub fn select_media_messages(
+    candidates: &[MediaCandidate],
+    start_date: Option<DateTime<Utc>>,
+    end_date: Option<DateTime<Utc>>,
+    cap: usize,
+) -> (Vec<i32>, bool) {
+    let cap = cap.min(MAX_BULK_DOWNLOADS);
+    let mut matching = candidates.iter().filter(|candidate| {
+        let after_start =
+            start_date.is_none() || start_date.is_some_and(|start| candidate.date >= start);
+        let before_end = end_date.is_none() || end_date.is_some_and(|end| candidate.date <= end);
+        candidate.has_media && after_start && before_end
+    });
+
+    let selected: Vec<i32> = matching.by_ref().take(cap).map(|c| c.message_id).collect();
+    let truncated = matching.next().is_some();
+
+    (selected, truncated)
+}
+
+/// Upper bound on replied-to messages fetched for one `get_messages` call.
+pub const MAX_REPLY_CONTEXT_FETCHES: usize = 20;
+
+/// Returns the replied-to ids that are not already in `messages`, deduplicated and capped.
+pub fn reply_ids_to_resolve(messages: &[Message], cap: usize) -> Vec<i32> {
+    let mut ids = Vec::new();
+    for reply_to in messages.iter().filter_map(|m| m.reply_to) {
+        let in_page = messages.iter().any(|m| m.message_id == reply_to);
+        if !in_page && !ids.contains(&reply_to) {
+            ids.push(reply_to);
+        }
+    }
+    ids.truncate(cap.min(MAX_REPLY_CONTEXT_FETCHES));
+    ids
+}
+
+/// Fills `reply_context` for each reply whose target is in `messages` or `fetched`.
+pub fn attach_reply_context(messages: &mut [Message], fetched: &[Message]) {
+    let known: HashMap<i32, ReplyContext> = messages
+        .iter()
+        .chain(fetched)
+        .map(|m| {
+            (
+                m.message_id,
+                ReplyContext {
+                    message_id: m.message_id,
+                    sender_id: m.sender_id,
+                    message: m.message.clone(),
+                },
+            )
+        })
+        .collect();
+
+    for message in messages.iter_mut() {
+        message.reply_context = message.reply_to.and_then(|id| known.get(&id).cloned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_peers_populate_entity_cache() {
+        // Chats carried by a synthetic catch-up message: its sender, a
+        // supergroup, a basic group and a min user sent without a hash
+        let peers = vec![
+            (
+                PackedChat {
+                    ty: PackedType::User,
+                    id: 42,
+                    access_hash: Some(7001),
+                },
+                Some("alice".to_string()),
+            ),
+            (
+                PackedChat {
+                    ty: PackedType::Megagroup,
+                    id: 1234567890,
+                    access_hash: Some(9002),
+                },
+                None,
+            ),
+            (
+                PackedChat {
+                    ty: PackedType::Chat,
+                    id: 555,
+                    access_hash: None,
+                },
+                None,
+            ),
+            (
+                PackedChat {
+                    ty: PackedType::User,
+                    id: 43,
+                    access_hash: None,
+                },
+                Some("bob".to_string()),
+            ),
+        ];
+
+        let mut cache = HashMap::new();
+        cache_peers(&mut cache, &peers);
+
+        for key in ["42", "alice"] {
+            assert_eq!(cache.get(key), Some(&peers[0].0));
+        }
+        assert_eq!(cache.get("-1001234567890"), Some(&peers[1].0));
+        assert_eq!(cache.get("-555"), Some(&peers[2].0));
+        assert!(!cache.contains_key("43"));
+        assert!(!cache.contains_key("bob"));
+        assert_eq!(cache.len(), 4);
+
+        // Cached keys resolve back to the same peer kind
+        assert_eq!(
+            parse_peer_id(&marked_peer_id(PeerKind::Channel, 1234567890).to_string()),
+            Some((PeerKind::Channel, 1234567890))
+        );
+    }
+
+    #[test]
+    fn test_numeric_ids_map_to_peer_kinds() {
+        assert_eq!(parse_peer_id("777000"), Some((PeerKind::User, 777000)));
+        assert_eq!(
+            parse_peer_id("-123456789"),
+            Some((PeerKind::Chat, 123456789))
+        );
+        assert_eq!(
+            parse_peer_id("-1001234567890"),
+            Some((PeerKind::Channel, 1234567890))
+        );
+        assert_eq!(
+            parse_peer_id("-999999999999"),
+            Some((PeerKind::Chat, 999999999999))
+        );
+        assert_eq!(
+            parse_peer_id("channel:1234567890"),
+            Some((PeerKind::Channel, 1234567890))
+        );
+        assert_eq!(
+            parse_peer_id("channel:-1001234567890"),
+            Some((PeerKind::Channel, 1234567890))
+        );
+        assert_eq!(parse_peer_id("chat:42"), Some((PeerKind::Chat, 42)));
+        assert_eq!(parse_peer_id("user:42"), Some((PeerKind::User, 42)));
+        assert_eq!(parse_peer_id("durov"), None);
+        assert_eq!(parse_peer_id("group:42"), None);
+        assert_eq!(parse_peer_id("0"), None);
+
+        assert_eq!(packed_peer_kind(PackedType::Bot), PeerKind::User);
+        assert_eq!(packed_peer_kind(PackedType::Megagroup), PeerKind::Channel);
+        assert_eq!(packed_peer_kind(PackedType::Chat), PeerKind::Chat);
+
+        assert_eq!(
+            marked_peer_id(PeerKind::Channel, 1234567890),
+            -1001234567890
+        );
+        assert_eq!(marked_peer_id(PeerKind::Chat, 42), -42);
+        assert_eq!(marked_peer_id(PeerKind::User, 7), 7);
+    }
+
+    #[test]
+    fn test_reaction_counts_mapping() {
+        use grammers_tl_types::types;
+
+        let count = |reaction: Reaction, count: i32, chosen_order: Option<i32>| {
+            enums::ReactionCount::Count(types::ReactionCount {
+                chosen_order,
+                reaction,
+                count,
+            })
+        };
+        let reactions = enums::MessageReactions::Reactions(types::MessageReactions {
+            min: false,
+            can_see_list: true,
+            reactions_as_tags: false,
+            results: vec![
+                count(
+                    Reaction::Emoji(types::ReactionEmoji {
+                        emoticon: "👍".to_string(),
+                    }),
+                    3,
+                    None,
+                ),
+                count(
+                    Reaction::CustomEmoji(types::ReactionCustomEmoji { document_id: 42 }),
+                    7,
+                    Some(0),
+                ),
+                count(Reaction::Empty, 1, None),
+                count(Reaction::Paid, 2, None),
+            ],
+            recent_reactions: None,
+            top_reactors: None,
+        });
+
+        let counts = reaction_counts(&reactions);
+        let summary: Vec<(&str, i32, bool)> = counts
+            .iter()
+            .map(|c| (c.reaction.as_str(), c.count, c.chosen))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("custom:42", 7, true), ("👍", 3, false), ("paid", 2, false),]
+        );
+    }
+
+    #[test]
+    fn test_send_options_map_to_request_flags() {
+        let peer = InputPeer::PeerSelf;
+
+        let defaults = send_message_request(peer.clone(), "hi", None, SendOptions::default(), 1);
+        assert!(!defaults.silent);
+        assert!(defaults.no_webpage);
+        assert!(defaults.reply_to.is_none());
+
+        let preview = SendOptions {
+            silent: false,
+            disable_preview: false,
+        };
+        let request =
+            send_message_request(peer.clone(), "see https://example.com", None, preview, 2);
+        assert!(!request.silent);
+        assert!(!request.no_webpage);
+
+        let quiet = SendOptions {
+            silent: true,
+            disable_preview: true,
+        };
+        let request = send_message_request(peer, "hi", Some(42), quiet, 3);
+        assert!(request.silent);
+        assert!(request.no_webpage);
+        assert_eq!(request.random_id, 3);
+        match request.reply_to {
+            Some(enums::InputReplyTo::Message(reply)) => assert_eq!(reply.reply_to_msg_id, 42),
+            other => panic!("unexpected reply_to: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_sent_message_id_matches_random_id() {
+        let short = enums::Updates::UpdateShortSentMessage(types::UpdateShortSentMessage {
+            out: true,
+            id: 77,
+            pts: 0,
+            pts_count: 0,
+            date: 0,
+            media: None,
+            entities: None,
+            ttl_period: None,
+        });
+        assert_eq!(sent_message_id(&short, 5), Some(77));
+
+        let full = enums::Updates::Updates(types::Updates {
+            updates: vec![
+                enums::Update::MessageId(types::UpdateMessageId {
+                    id: 10,
+                    random_id: 4,
+                }),
+                enums::Update::MessageId(types::UpdateMessageId {
+                    id: 11,
+                    random_id: 5,
+                }),
+            ],
+            users: vec![],
+            chats: vec![],
+            date: 0,
+            seq: 0,
+        });
+        assert_eq!(sent_message_id(&full, 5), Some(11));
+        assert_eq!(sent_message_id(&full, 6), None);
+    }
+
+    #[test]
+    fn test_split_message_breaks_on_boundaries() {
+        let paragraph = format!("{}\n\n", "word ".repeat(700).trim_end());
+        let sentences = "Short sentence here. ".repeat(100);
+        let text = format!("{}{}", paragraph, sentences);
+        assert!(text.chars().count() > MAX_MESSAGE_LENGTH);
+
+        let chunks = split_message(&text, MAX_MESSAGE_LENGTH);
+        assert_eq!(chunks, vec![paragraph.trim_end(), sentences.trim_end()]);
+
+        let sentences = "Short sentence here. ".repeat(300);
+        let chunks = split_message(&sentences, MAX_MESSAGE_LENGTH);
+        assert!(chunks.len() > 1);
+        assert!(chunks
+            .iter()
+            .all(|c| message_length(c) <= MAX_MESSAGE_LENGTH && c.ends_with("here.")));
+        assert_eq!(chunks.join(" "), sentences.trim_end());
+
+        let unbroken = "é".repeat(MAX_MESSAGE_LENGTH + 10);
+        let sizes: Vec<usize> = split_message(&unbroken, MAX_MESSAGE_LENGTH)
+            .iter()
+            .map(|c| message_length(c))
+            .collect();
+        assert_eq!(sizes, vec![MAX_MESSAGE_LENGTH, 10]);
+
+        // Emoji take two UTF-16 code units, so 3000 of them are over the limit
+        let emoji = "😀".repeat(3000);
+        assert!(emoji.chars().count() <= MAX_MESSAGE_LENGTH);
+        let sizes: Vec<usize> = split_message(&emoji, MAX_MESSAGE_LENGTH)
+            .iter()
+            .map(|c| message_length(c))
+            .collect();
+        assert_eq!(sizes, vec![MAX_MESSAGE_LENGTH, 6000 - MAX_MESSAGE_LENGTH]);
+
+        assert_eq!(split_message("hello", MAX_MESSAGE_LENGTH), vec!["hello"]);
+    }
+
+    #[test]
+    fn test_validate_schedule_date() {
+        let now = Utc::now();
+
+        assert!(validate_schedule_date(now + Duration::hours(1), now).is_ok());
+        assert!(validate_schedule_date(now, now).is_err());
+        assert!(validate_schedule_date(now - Duration::minutes(5), now).is_err());
+        assert!(validate_schedule_date(now + Duration::days(MAX_SCHEDULE_DAYS + 1), now).is_err());
+    }
+
+    #[test]
+    fn test_select_media_messages_filters_and_caps() {
+        let base = Utc::now();
+        let candidate = |message_id: i32, hours_ago: i64, has_media: bool| MediaCandidate {
+            message_id,
+            date: base - Duration::hours(hours_ago),
+            has_media,
+        };
+        let candidates = vec![
+            candidate(10, 1, true),
+            candidate(9, 2, false),
+            candidate(8, 3, true),
+            candidate(7, 4, true),
+            candidate(6, 48, true),
+        ];
+
+        let start = Some(base - Duration::hours(24));
+        let (selected, truncated) = select_media_messages(&candidates, start, None, 10);
+        assert_eq!(selected, vec![10, 8, 7]);
+        assert!(!truncated);
+
+        let (selected, truncated) = select_media_messages(&candidates, start, None, 2);
+        assert_eq!(selected, vec![10, 8]);
+        assert!(truncated);
+
+        let many: Vec<MediaCandidate> = (0..150).map(|i| candidate(i, 0, true)).collect();
+        let (selected, truncated) = select_media_messages(&many, None, None, 1000);
+        assert_eq!(selected.len(), MAX_BULK_DOWNLOADS);
+        assert!(truncated);
+    }
+
+    #[test]
+    fn test_reply_context_resolution() {
+        let message = |message_id: i32, text: &str, reply_to: Option<i32>| Message {
+            message_id,
+            sender_id: Some(i64::from(message_id) * 100),
+            message: Some(text.to_string()),
+            outgoing: false,
+            date: None,
+            media: None,
+            reply_to,
+            reply_context: None,
+        };
+        let mut messages = vec![
+            message(12, "sounds good", Some(10)),
+            message(11, "which one?", Some(3)),
+            message(10, "lunch at noon?", None),
+            message(9, "and this one", Some(3)),
+            message(8, "old thread", Some(1)),
+        ];
+
+        assert_eq!(reply_ids_to_resolve(&messages, 10), vec![3, 1]);
+        assert_eq!(reply_ids_to_resolve(&messages, 1), vec![3]);
+
+        let fetched = vec![message(3, "pick a restaurant", None)];
+        attach_reply_context(&mut messages, &fetched);
+
+        let context = |text: &str, message_id: i32| ReplyContext {
+            message_id,
+            sender_id: Some(i64::from(message_id) * 100),
+            message: Some(text.to_string()),
+        };
+        assert_eq!(
+            messages[0].reply_context,
+            Some(context("lunch at noon?", 10))
+        );
+        assert_eq!(
+            messages[1].reply_context,
+            Some(context("pick a restaurant", 3))
+        );
+        assert_eq!(
+            messages[3].reply_context,
+            Some(context("pick a restaurant", 3))
+        );
+        assert_eq!(messages[2].reply_context, None);
+        assert_eq!(messages[4].reply_context, None);
+    }
+
+    #[test]
+    fn test_scheduled_message_requests() {
+        use grammers_tl_types::types::InputPeerUser;
+
+        let peer = InputPeer::User(InputPeerUser {
+            user_id: 42,
+            access_hash: 7,
+        });
+
+        let list = scheduled_history_request(peer.clone());
+        assert_eq!(list.hash, 0);
+        assert!(
+            matches!(list.peer, InputPeer::User(ref u) if u.user_id == 42 && u.access_hash == 7)
+        );
+
+        let delete = delete_scheduled_request(peer, &[101, 102]);
+        assert_eq!(delete.id, vec![101, 102]);
+        assert!(matches!(delete.peer, InputPeer::User(ref u) if u.user_id == 42));
+    }
+
+    #[test]
+    fn test_read_history_request_targets_max_id() {
+        use grammers_tl_types::types::{InputPeerChannel, InputPeerChat};
+
+        let chat = InputPeer::Chat(InputPeerChat { chat_id: 5 });
+        match read_history_request(chat, 812) {
+            ReadHistoryRequest::Messages(request) => {
+                assert_eq!(request.max_id, 812);
+                assert!(matches!(request.peer, InputPeer::Chat(ref c) if c.chat_id == 5));
+            }
+            ReadHistoryRequest::Channel(_) => panic!("basic groups use messages.readHistory"),
+        }
+
+        let channel = InputPeer::Channel(InputPeerChannel {
+            channel_id: 9,
+            access_hash: 3,
+        });
+        match read_history_request(channel, 4096) {
+            ReadHistoryRequest::Channel(request) => {
+                assert_eq!(request.max_id, 4096);
+                assert!(matches!(
+                    request.channel,
+                    enums::InputChannel::Channel(ref c) if c.channel_id == 9 && c.access_hash == 3
+                ));
+            }
+            ReadHistoryRequest::Messages(_) => panic!("channels use channels.readHistory"),
+        }
+    }
+
+    #[test]
+    fn test_mute_duration_to_settings() {
+        let now = Utc::now();
+
+        assert_eq!(parse_mute_duration("8h").unwrap(), Some(Duration::hours(8)));
+        assert_eq!(
+            parse_mute_duration("30m").unwrap(),
+            Some(Duration::minutes(30))
+        );
+        assert_eq!(parse_mute_duration("2D").unwrap(), Some(Duration::days(2)));
+        assert_eq!(
+            parse_mute_duration("3600").unwrap(),
+            Some(Duration::hours(1))
+        );
+        assert_eq!(parse_mute_duration(" forever ").unwrap(), None);
+        assert!(parse_mute_duration("0h").is_err());
+        assert!(parse_mute_duration("-5m").is_err());
+        assert!(parse_mute_duration("soon").is_err());
+        assert!(parse_mute_duration("5y").is_err());
+        assert!(parse_mute_duration("53w").is_err());
+        assert!(parse_mute_duration("99999999999999999w").is_err());
+
+        let (setting, mute_until) = mute_setting(Some(Duration::hours(8)), now);
+        assert!(setting.muted && !setting.forever);
+        assert_eq!(setting.mute_until, Some(now + Duration::hours(8)));
+        assert_eq!(
+            i64::from(mute_until),
+            (now + Duration::hours(8)).timestamp()
+        );
+
+        let (setting, mute_until) = mute_setting(None, now);
+        assert!(setting.muted && setting.forever);
+        assert_eq!(setting.mute_until, None);
+        assert_eq!(mute_until, i32::MAX);
+
+        let request = notify_settings_request(InputPeer::Empty, 0);
+        let enums::InputPeerNotifySettings::Settings(settings) = request.settings;
+        assert_eq!(settings.mute_until, Some(0));
+        assert!(settings.silent.is_none());
+    }
+
+    #[test]
+    fn test_contact_requests() {
+        assert_eq!(contacts_request().hash, 0);
+
+        let import = import_contact_request("+14155552671", "Ada", "Lovelace");
+        assert_eq!(import.contacts.len(), 1);
+        let enums::InputContact::InputPhoneContact(contact) = &import.contacts[0];
+        assert_eq!(contact.phone, "+14155552671");
+        assert_eq!(contact.first_name, "Ada");
+        assert_eq!(contact.last_name, "Lovelace");
+
+        let delete = delete_contact_request(42, 7);
+        assert!(matches!(
+            delete.id.as_slice(),
+            [enums::InputUser::User(u)] if u.user_id == 42 && u.access_hash == 7
+        ));
+    }
+
+    #[test]
+    fn test_draft_and_unread_helpers() {
+        use grammers_tl_types::types::InputPeerUser;
+
+        let draft = save_draft_request(
+            InputPeer::User(InputPeerUser {
+                user_id: 42,
+                access_hash: 7,
+            }),
+            "see you at 5",
+        );
+        assert_eq!(draft.message, "see you at 5");
+        assert!(draft.reply_to.is_none() && !draft.no_webpage);
+        assert!(matches!(draft.peer, InputPeer::User(ref u) if u.user_id == 42));
+
+        let message = |message_id: i32, outgoing: bool| Message {
+            message_id,
+            sender_id: Some(7),
+            message: None,
+            outgoing,
+            date: None,
+            media: None,
+            reply_to: None,
+            reply_context: None,
+        };
+        assert!(is_unread(&message(11, false), 10));
+        assert!(!is_unread(&message(10, false), 10));
+        assert!(!is_unread(&message(11, true), 10));
+
+        assert_eq!(
+            parse_telegram_url("https://t.me/durov/42").unwrap(),
+            ("durov".to_string(), 42)
+        );
+        let (entity, message_id) = parse_telegram_url("t.me/c/1234567890/7").unwrap();
+        assert_eq!((entity.as_str(), message_id), ("channel:1234567890", 7));
+        assert_eq!(
+            parse_peer_id(&entity),
+            Some((PeerKind::Channel, 1234567890))
+        );
+    }
+
+    #[test]
+    fn test_global_search_request_and_found_dialogs() {
+        use grammers_tl_types::types::{PeerChat, PeerUser};
+
+        let request = global_search_request("rust", 500);
+        assert_eq!(request.q, "rust");
+        assert_eq!(request.limit, MAX_SEARCH_LIMIT as i32);
+
+        let chat = |id: i64, title: &str, left: bool| {
+            enums::Chat::Chat(types::Chat {
+                creator: false,
+                left,
+                deactivated: false,
+                call_active: false,
+                call_not_empty: false,
+                noforwards: false,
+                id,
+                title: title.to_string(),
+                photo: enums::ChatPhoto::Empty,
+                participants_count: 3,
+                date: 0,
+                version: 1,
+                migrated_to: None,
+                admin_rights: None,
+                default_banned_rights: None,
+            })
+        };
+        let found = enums::contacts::Found::Found(types::contacts::Found {
+            my_results: vec![Peer::Chat(PeerChat { chat_id: 1 })],
+            // The user isn't in `users`, so it can't be described and is skipped
+            results: vec![
+                Peer::User(PeerUser { user_id: 99 }),
+                Peer::Chat(PeerChat { chat_id: 2 }),
+            ],
+            chats: vec![chat(2, "Rust Users", true), chat(1, "Rust Team", false)],
+            users: vec![],
+        });
+
+        let dialogs = found_dialogs(&found, 10);
+        let summary: Vec<(i64, &str, bool)> = dialogs
+            .iter()
+            .map(|d| (d.id, d.title.as_str(), d.can_send_message))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![(1, "Rust Team", true), (2, "Rust Users", false)]
+        );
+        assert_eq!(found_dialogs(&found, 1).len(), 1);
+    }
+
+    #[test]
+    fn test_normalize_phone() {
+        assert_eq!(
+            normalize_phone("+1 (415) 555-2671").unwrap(),
+            "+14155552671"
+        );
+        assert_eq!(normalize_phone("44.20.7946.0958").unwrap(), "+442079460958");
+        assert!(normalize_phone("555-2671").is_err());
+        assert!(normalize_phone("+0123456789").is_err());
+        assert!(normalize_phone("+1415555267a").is_err());
+        assert!(normalize_phone("++14155552671").is_err());
+        assert!(normalize_phone("+1234567890123456").is_err());
+    }
+
+    #[test]
+    fn test_search_messages_request_and_hits() {
+        use grammers_tl_types::types::InputPeerChannel;
+
+        let peer = InputPeer::Channel(InputPeerChannel {
+            channel_id: 9,
+            access_hash: 3,
+        });
+        let request = search_messages_request(peer.clone(), "invoice", 20);
+        assert_eq!(request.q, "invoice");
+        assert_eq!(request.limit, 20);
+        assert!(matches!(
+            request.filter,
+            MessagesFilter::InputMessagesFilterEmpty
+        ));
+        assert!(matches!(request.peer, InputPeer::Channel(ref c) if c.channel_id == 9));
+        assert_eq!(
+            search_messages_request(peer, "invoice", 5000).limit,
+            MAX_SEARCH_LIMIT as i32
+        );
+
+        let message = |message_id: i32, text: Option<&str>| Message {
+            message_id,
+            sender_id: Some(7),
+            message: text.map(str::to_string),
+            outgoing: false,
+            date: None,
+            media: None,
+            reply_to: None,
+            reply_context: None,
+        };
+        let long = format!(
+            "{}Invoice #42 is attached{}",
+            "a".repeat(60),
+            "b".repeat(60)
+        );
+        let hits = search_hits(
+            &[
+                message(3, Some("the invoice is paid")),
+                message(2, None),
+                message(1, Some(&long)),
+            ],
+            "invoice",
+        );
+
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].message_id, 3);
+        assert_eq!(hits[0].snippet, "the invoice is paid");
+        assert_eq!(hits[1].message_id, 1);
+        assert!(hits[1].snippet.starts_with('…') && hits[1].snippet.ends_with('…'));
+        assert!(hits[1].snippet.contains("Invoice #42 is attached"));
+        assert_eq!(
+            hits[1].snippet.chars().count(),
+            2 * SNIPPET_CONTEXT_CHARS + "invoice".len() + 2
+        );
+    }
+}